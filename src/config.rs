@@ -0,0 +1,208 @@
+//! Configuration files for redaction jobs.
+//!
+//! A config file describes a whole job, so a team can keep a standard
+//! redaction recipe under version control instead of a long command line.
+//! Files ending in `.json` are read as JSON and anything else as TOML:
+//!
+//! ```toml
+//! input = "statements"
+//! output = "redacted"
+//! targets = ["phones", 'regex:Acct-\d{6}']
+//! fill_color = "white"
+//! strip_metadata = true
+//! pages = "1-3"
+//! ```
+//!
+//! Targets are written as [`RedactionTarget`] specs; see its
+//! [`FromStr`](RedactionTarget#impl-FromStr-for-RedactionTarget) impl.
+
+use crate::error::{RedactorError, RedactorResult};
+use crate::redaction::{PageSelection, RedactionTarget};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A redaction job read from a config file.
+///
+/// Every field is optional, so a config may hold just the targets and take
+/// the input and output from the command line.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedactionConfig {
+    /// Input PDF, or a directory of PDFs to redact in batch
+    pub input: Option<PathBuf>,
+
+    /// Output PDF, or a directory when the input is a directory
+    pub output: Option<PathBuf>,
+
+    /// Targets to redact, as specs such as `phones` or `regex:PATTERN`
+    #[serde(deserialize_with = "parse_each")]
+    pub targets: Vec<RedactionTarget>,
+
+    /// Fill color of redaction boxes: a name (black, white, gray) or hex
+    /// `RRGGBB`
+    pub fill_color: Option<String>,
+
+    /// Remove document metadata (author, title, producer, XMP)
+    pub strip_metadata: bool,
+
+    /// Pages to redact, e.g. `1,3,5-9`
+    #[serde(deserialize_with = "parse_optional")]
+    pub pages: Option<PageSelection>,
+}
+
+impl RedactionConfig {
+    /// Reads a config file, as JSON if its extension is `.json` and as
+    /// TOML otherwise.
+    pub fn load(path: &Path) -> RedactorResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| RedactorError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        }
+    }
+
+    /// Parses a config from TOML.
+    pub fn from_toml(contents: &str) -> RedactorResult<Self> {
+        toml::from_str(contents).map_err(invalid)
+    }
+
+    /// Parses a config from JSON.
+    pub fn from_json(contents: &str) -> RedactorResult<Self> {
+        serde_json::from_str(contents).map_err(invalid)
+    }
+}
+
+/// Wraps a parse failure as an invalid `config`.
+fn invalid(reason: impl Display) -> RedactorError {
+    RedactorError::InvalidInput {
+        parameter: "config".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Deserializes a list of strings, parsing each with [`FromStr`].
+fn parse_each<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|spec| spec.parse().map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// Deserializes an optional string, parsing it with [`FromStr`].
+fn parse_optional<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|spec| spec.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let config = RedactionConfig::from_toml(
+            r#"
+            input = "statements/march.pdf"
+            output = "redacted.pdf"
+            targets = ["phones", 'regex:Acct-\d{6}', "passport:US"]
+            fill_color = "white"
+            strip_metadata = true
+            pages = "1,3-4"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.input, Some(PathBuf::from("statements/march.pdf")));
+        assert_eq!(config.output, Some(PathBuf::from("redacted.pdf")));
+        assert_eq!(
+            config.targets,
+            vec![
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::Regex(r"Acct-\d{6}".to_string()),
+                RedactionTarget::Passport {
+                    country: Some("US".to_string())
+                },
+            ]
+        );
+        assert_eq!(config.fill_color.as_deref(), Some("white"));
+        assert!(config.strip_metadata);
+        assert_eq!(config.pages, Some(PageSelection::parse("1,3-4").unwrap()));
+    }
+
+    #[test]
+    fn test_from_json() {
+        let config =
+            RedactionConfig::from_json(r#"{"targets": ["ssn", "literal:Jane Doe"]}"#).unwrap();
+
+        assert_eq!(
+            config,
+            RedactionConfig {
+                targets: vec![
+                    RedactionTarget::Ssn,
+                    RedactionTarget::Literal("Jane Doe".to_string()),
+                ],
+                ..RedactionConfig::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_config() {
+        for toml in [
+            r#"targets = ["phone"]"#,
+            r#"targets = "phones""#,
+            r#"pages = "0""#,
+            r#"fill-color = "white""#,
+            "targets = [",
+        ] {
+            let err = RedactionConfig::from_toml(toml).unwrap_err();
+            assert!(
+                matches!(err, RedactorError::InvalidInput { ref parameter, .. } if parameter == "config"),
+                "{:?}: {}",
+                toml,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_chooses_format_by_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let json = dir.path().join("job.json");
+        let toml = dir.path().join("job.toml");
+        std::fs::write(&json, r#"{"targets": ["emails"]}"#).unwrap();
+        std::fs::write(&toml, r#"targets = ["emails"]"#).unwrap();
+
+        for path in [json, toml] {
+            let config = RedactionConfig::load(&path).unwrap();
+            assert_eq!(config.targets, vec![RedactionTarget::Email]);
+        }
+
+        let missing = dir.path().join("missing.toml");
+        assert!(matches!(
+            RedactionConfig::load(&missing),
+            Err(RedactorError::Io { .. })
+        ));
+    }
+}
@@ -3,9 +3,10 @@
 //! This module provides business logic for detecting and handling
 //! account numbers, with special support for Verizon's 9-5 format.
 
-use super::PatternMatcher;
+use super::{ParseMatch, PatternMatcher};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 
 /// Verizon account number pattern matcher.
 ///
@@ -14,6 +15,18 @@ use regex::Regex;
 #[derive(Debug, Clone)]
 pub struct VerizonAccountMatcher;
 
+/// Confidence of a 9-5 number after an account label.
+const SCORE_9_5_LABELLED: f32 = 1.0;
+
+/// Confidence of any other 9-5 number.
+const SCORE_9_5: f32 = 0.75;
+
+/// Confidence of 14 consecutive digits after an account label.
+const SCORE_14_LABELLED: f32 = 0.5;
+
+/// Confidence of any other 14 consecutive digits.
+const SCORE_14: f32 = 0.25;
+
 impl VerizonAccountMatcher {
     /// Creates a new Verizon account matcher.
     pub fn new() -> Self {
@@ -28,6 +41,9 @@ impl VerizonAccountMatcher {
     /// 3. 14 consecutive digits with "account" keyword
     /// 4. Any 14 consecutive digits
     /// 5. Generic account numbers (10-15 digits)
+    ///
+    /// Candidates of the same priority are ranked by their byte distance to
+    /// the nearest "account" or "acct" keyword, then by position.
     pub fn find_account_number(text: &str) -> Option<String> {
         let mut candidates = Vec::new();
 
@@ -73,9 +89,15 @@ impl VerizonAccountMatcher {
             }
         }
 
-        // Remove duplicates while preserving priority order
-        candidates.sort_by_key(|(priority, _)| *priority);
-        candidates.dedup_by(|(_, a), (_, b)| a == b);
+        // Rank by priority, then keyword distance; the sort is stable, so
+        // full ties keep their order in the text
+        let distances = Self::keyword_distances(text);
+        let distance = |num: &String| distances.get(num).copied().unwrap_or(usize::MAX);
+        candidates.sort_by_key(|(priority, num)| (*priority, distance(num)));
+
+        // Remove duplicates while preserving rank order
+        let mut seen = HashSet::new();
+        candidates.retain(|(_, num)| seen.insert(num.clone()));
 
         // Prefer 14-digit candidates
         candidates
@@ -85,6 +107,49 @@ impl VerizonAccountMatcher {
             .or_else(|| candidates.first().map(|(_, num)| num.clone()))
     }
 
+    /// Returns the byte distance from each account number in `text` to the
+    /// nearest account keyword, keyed by its digits.
+    ///
+    /// A number found more than once keeps its shortest distance; numbers
+    /// in text without a keyword are left out.
+    fn keyword_distances(text: &str) -> HashMap<String, usize> {
+        static KEYWORD: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?i)account|acct").expect("Valid regex"));
+        let keywords: Vec<_> = KEYWORD.find_iter(text).collect();
+
+        let mut distances = HashMap::new();
+        for (start, end, found) in Self::new().find_with_positions(text) {
+            let nearest = keywords
+                .iter()
+                .map(|keyword| {
+                    if keyword.end() <= start {
+                        start - keyword.end()
+                    } else {
+                        keyword.start().saturating_sub(end)
+                    }
+                })
+                .min();
+            if let Some(distance) = nearest {
+                let digits: String = found.chars().filter(|c| c.is_ascii_digit()).collect();
+                distances
+                    .entry(digits)
+                    .and_modify(|shortest: &mut usize| *shortest = (*shortest).min(distance))
+                    .or_insert(distance);
+            }
+        }
+        distances
+    }
+
+    /// Returns true if an account label ends right before byte offset
+    /// `start` of `text`.
+    fn is_labelled(text: &str, start: usize) -> bool {
+        static LABEL: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?i)(?:account|acct)(?:\s*(?:number|num|no|#))?\s*:?\s*$")
+                .expect("Valid regex")
+        });
+        LABEL.is_match(&text[..start])
+    }
+
     // Pattern helper methods (cached via Lazy)
 
     fn pattern_9_5_with_context() -> &'static Regex {
@@ -179,6 +244,49 @@ impl PatternMatcher for VerizonAccountMatcher {
 
         variants
     }
+
+    /// Reports 9-5 numbers and 14-digit runs, ordered by position.
+    ///
+    /// Each is an account number by itself, so none are checked with
+    /// [`normalize`](PatternMatcher::normalize), which looks for the best
+    /// account number in a whole text.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        self.extract_all_scored(text)
+            .into_iter()
+            .map(|(found, _)| (found.start, found.end, found.text))
+            .collect()
+    }
+
+    /// Scores 9-5 numbers and 14-digit runs by the priority tiers of
+    /// [`find_account_number`](Self::find_account_number): a 9-5 number
+    /// right after an account label scores 1.0, a bare run of 14 digits
+    /// 0.25.
+    fn extract_all_scored<'a>(&self, text: &'a str) -> Vec<(ParseMatch<'a>, f32)> {
+        let tiers = [
+            (Self::pattern_9_5(), SCORE_9_5_LABELLED, SCORE_9_5),
+            (Self::pattern_14(), SCORE_14_LABELLED, SCORE_14),
+        ];
+        let mut scored: Vec<_> = tiers
+            .into_iter()
+            .flat_map(|(pattern, labelled, bare)| {
+                pattern.find_iter(text).map(move |m| {
+                    let score = if Self::is_labelled(text, m.start()) {
+                        labelled
+                    } else {
+                        bare
+                    };
+                    let found = ParseMatch {
+                        start: m.start(),
+                        end: m.end(),
+                        text: m.as_str(),
+                    };
+                    (found, score)
+                })
+            })
+            .collect();
+        scored.sort_by_key(|(found, _)| found.start);
+        scored
+    }
 }
 
 #[cfg(test)]
@@ -200,6 +308,32 @@ mod tests {
         assert_eq!(account, Some("12345678900001".to_string()));
     }
 
+    #[test]
+    fn test_keyword_distance_breaks_ties() {
+        // Neither number directly follows a label, so both rank as bare
+        // 14-digit runs; the one nearer the keyword wins
+        let text = "Printed 20250115093000 on page 1 of your monthly statement.\n\
+                    Acct ref: 12345678900001";
+        let account = VerizonAccountMatcher::find_account_number(text);
+        assert_eq!(account, Some("12345678900001".to_string()));
+
+        // Regardless of which comes first
+        let text = "Acct ref: 12345678900001\n\
+                    Printed 20250115093000 on page 1 of your monthly statement.";
+        let account = VerizonAccountMatcher::find_account_number(text);
+        assert_eq!(account, Some("12345678900001".to_string()));
+    }
+
+    #[test]
+    fn test_find_with_positions_reports_both_formats() {
+        let matcher = VerizonAccountMatcher::new();
+        let text = "Account: 123456789-00001, ref 98765432100002";
+        assert_eq!(
+            matcher.find_with_positions(text),
+            vec![(9, 24, "123456789-00001"), (30, 44, "98765432100002")]
+        );
+    }
+
     #[test]
     fn test_account_variants() {
         let matcher = VerizonAccountMatcher::new();
@@ -210,6 +344,31 @@ mod tests {
         assert_eq!(variants.len(), 3);
     }
 
+    #[test]
+    fn test_labelled_accounts_score_higher() {
+        let matcher = VerizonAccountMatcher::new();
+        let text = "Random: 999999999-99999 Account: 123456789-00001\n\
+                    Ref 12345678900001, Acct # 98765432100002";
+        let scored: Vec<(&str, f32)> = matcher
+            .extract_all_scored(text)
+            .into_iter()
+            .map(|(found, score)| (found.text, score))
+            .collect();
+        assert_eq!(
+            scored,
+            vec![
+                ("999999999-99999", SCORE_9_5),
+                ("123456789-00001", SCORE_9_5_LABELLED),
+                ("12345678900001", SCORE_14),
+                ("98765432100002", SCORE_14_LABELLED),
+            ]
+        );
+
+        // Labels raise the score of both formats
+        assert!(scored[1].1 > scored[0].1);
+        assert!(scored[3].1 > scored[2].1);
+    }
+
     #[test]
     fn test_no_account_found() {
         let text = "This document has no account number";
@@ -0,0 +1,226 @@
+//! Fixed-length checksummed number domain logic.
+//!
+//! This module detects digit sequences of a length and checksum chosen at
+//! runtime, so proprietary ID formats (member numbers, policy numbers) can
+//! be redacted without a dedicated matcher.
+
+use super::PatternMatcher;
+use regex::Regex;
+
+/// Checksum a digit sequence must pass to be redacted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// Luhn (mod 10), as used by payment cards and IMEIs
+    Luhn,
+
+    /// ISO 7064 MOD 97-10: the number mod 97 is 1, as in IBANs
+    Mod97,
+
+    /// No checksum; every sequence of the length matches
+    None,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the algorithm's lowercase name: `luhn`, `mod97` or `none`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Luhn => "luhn",
+            Self::Mod97 => "mod97",
+            Self::None => "none",
+        }
+    }
+
+    /// Returns the algorithm called `name`, as returned by
+    /// [`name`](Self::name), ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Luhn, Self::Mod97, Self::None]
+            .into_iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns true if `digits`, a string of ASCII digits, passes the
+    /// checksum.
+    pub fn validate(self, digits: &str) -> bool {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+
+        match self {
+            Self::Luhn => {
+                let sum: u32 = digits
+                    .bytes()
+                    .rev()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        let d = u32::from(b - b'0');
+                        if i % 2 == 1 {
+                            let doubled = d * 2;
+                            if doubled > 9 {
+                                doubled - 9
+                            } else {
+                                doubled
+                            }
+                        } else {
+                            d
+                        }
+                    })
+                    .sum();
+                sum % 10 == 0
+            }
+            Self::Mod97 => {
+                let remainder = digits
+                    .bytes()
+                    .fold(0u32, |acc, b| (acc * 10 + u32::from(b - b'0')) % 97);
+                remainder == 1
+            }
+            Self::None => true,
+        }
+    }
+}
+
+/// Pattern matcher for fixed-length digit sequences that pass a checksum.
+///
+/// A sequence matches only as a whole number: digits directly before or
+/// after it (or letters, as in `AB123456`) mean it is part of something
+/// longer.
+#[derive(Debug, Clone)]
+pub struct ChecksumMatcher {
+    length: usize,
+    algorithm: ChecksumAlgorithm,
+    regex: Regex,
+}
+
+impl ChecksumMatcher {
+    /// Creates a matcher for `length`-digit sequences validated with
+    /// `algorithm`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is zero; see [`try_new`](Self::try_new).
+    pub fn new(length: usize, algorithm: ChecksumAlgorithm) -> Self {
+        Self::try_new(length, algorithm).expect("Checksum matcher length must be positive")
+    }
+
+    /// Creates a matcher for `length`-digit sequences validated with
+    /// `algorithm`, or returns `None` if `length` is zero.
+    pub fn try_new(length: usize, algorithm: ChecksumAlgorithm) -> Option<Self> {
+        if length == 0 {
+            return None;
+        }
+        let regex = Regex::new(&format!(r"\b\d{{{}}}\b", length)).expect("Valid checksum regex");
+        Some(Self {
+            length,
+            algorithm,
+            regex,
+        })
+    }
+
+    /// Number of digits in a match.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Checksum a match must pass.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+}
+
+impl PatternMatcher for ChecksumMatcher {
+    fn pattern(&self) -> &Regex {
+        &self.regex
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|digits| self.algorithm.validate(digits))
+            .collect()
+    }
+
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.extract_all(text)
+            .first()
+            .map(|digits| digits.to_string())
+    }
+
+    /// Numbers are searched exactly as written.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_names() {
+        for algorithm in [
+            ChecksumAlgorithm::Luhn,
+            ChecksumAlgorithm::Mod97,
+            ChecksumAlgorithm::None,
+        ] {
+            assert_eq!(
+                ChecksumAlgorithm::from_name(algorithm.name()),
+                Some(algorithm)
+            );
+        }
+        assert_eq!(
+            ChecksumAlgorithm::from_name("LUHN"),
+            Some(ChecksumAlgorithm::Luhn)
+        );
+        assert_eq!(ChecksumAlgorithm::from_name("crc32"), None);
+    }
+
+    #[test]
+    fn test_algorithms() {
+        assert!(ChecksumAlgorithm::Luhn.validate("4111111111111111"));
+        assert!(ChecksumAlgorithm::Luhn.validate("79927398713"));
+        assert!(!ChecksumAlgorithm::Luhn.validate("79927398710"));
+
+        // 97 * 1234567 + 1
+        assert!(ChecksumAlgorithm::Mod97.validate("119753000"));
+        assert!(!ChecksumAlgorithm::Mod97.validate("119752999"));
+
+        assert!(ChecksumAlgorithm::None.validate("0000"));
+        assert!(!ChecksumAlgorithm::None.validate(""));
+        assert!(!ChecksumAlgorithm::None.validate("12a4"));
+    }
+
+    #[test]
+    fn test_fixed_length_extraction() {
+        let matcher = ChecksumMatcher::new(11, ChecksumAlgorithm::Luhn);
+        let text = "Member 79927398713, not 79927398710, 799273987130 or ID79927398713";
+        assert_eq!(matcher.extract_all(text), vec!["79927398713"]);
+        assert_eq!(matcher.normalize(text), Some("79927398713".to_string()));
+        assert_eq!(
+            matcher.generate_variants("79927398713"),
+            vec!["79927398713".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_no_checksum() {
+        let matcher = ChecksumMatcher::new(6, ChecksumAlgorithm::None);
+        assert_eq!(matcher.length(), 6);
+        assert_eq!(matcher.algorithm(), ChecksumAlgorithm::None);
+        assert_eq!(
+            matcher.extract_all("Ref 123456 and 1234567"),
+            vec!["123456"]
+        );
+    }
+
+    #[test]
+    fn test_zero_length_is_rejected() {
+        assert!(ChecksumMatcher::try_new(0, ChecksumAlgorithm::Luhn).is_none());
+        assert!(ChecksumMatcher::try_new(1, ChecksumAlgorithm::Luhn).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "length must be positive")]
+    fn test_zero_length_panics() {
+        ChecksumMatcher::new(0, ChecksumAlgorithm::None);
+    }
+}
@@ -0,0 +1,284 @@
+//! Union of several pattern matchers.
+//!
+//! Redacting several categories at once runs every matcher over the same
+//! text, though most find nothing in a given document. A composite matcher
+//! screens the text once with a [`RegexSet`] of its components' patterns
+//! and only runs the components that can match, so the result is the same
+//! as running each component on its own.
+
+use super::{ParseMatch, PatternMatcher};
+use regex::{Regex, RegexSet};
+use std::fmt;
+
+/// Matcher that finds what any of its components finds.
+///
+/// Each match is validated and normalized by the component that found it,
+/// so checksums and context requirements still apply. A component is only
+/// run on text where its [`pattern`](PatternMatcher::pattern) occurs, so it
+/// should not find anything that pattern does not match.
+///
+/// # Example
+///
+/// ```
+/// use redactor::domain::{CompositeMatcher, EmailMatcher, PatternMatcher, SsnMatcher};
+///
+/// let matcher = CompositeMatcher::new(vec![
+///     Box::new(SsnMatcher::new()),
+///     Box::new(EmailMatcher::new()),
+/// ])
+/// .unwrap();
+/// assert_eq!(
+///     matcher.extract_all("SSN 123-45-6789, mail jane@example.com"),
+///     vec!["123-45-6789", "jane@example.com"]
+/// );
+/// ```
+pub struct CompositeMatcher {
+    components: Vec<Box<dyn PatternMatcher>>,
+
+    /// Each component's pattern, to find in one pass which can match
+    screen: RegexSet,
+
+    /// Alternation of the components' patterns
+    pattern: Regex,
+}
+
+impl CompositeMatcher {
+    /// Creates a matcher for the union of `components`.
+    ///
+    /// Fails if the combined pattern exceeds the regex size limit.
+    pub fn new(components: Vec<Box<dyn PatternMatcher>>) -> Result<Self, regex::Error> {
+        let patterns: Vec<&str> = components.iter().map(|c| c.pattern().as_str()).collect();
+        let screen = RegexSet::new(&patterns)?;
+
+        // Components may reuse group names, which one regex cannot
+        let alternation = patterns
+            .iter()
+            .map(|p| format!("(?:{})", strip_group_names(p)))
+            .collect::<Vec<_>>()
+            .join("|");
+        // An empty alternation would match everywhere
+        let pattern = if components.is_empty() {
+            Regex::new(r"[^\s\S]")?
+        } else {
+            Regex::new(&alternation)?
+        };
+
+        Ok(Self {
+            components,
+            screen,
+            pattern,
+        })
+    }
+
+    /// Returns the component matchers, in the order given.
+    pub fn components(&self) -> &[Box<dyn PatternMatcher>] {
+        &self.components
+    }
+
+    /// Returns the components whose pattern occurs in `text`, found in a
+    /// single pass, in the order given.
+    ///
+    /// Only these components can find anything in `text`.
+    pub fn candidates(&self, text: &str) -> Vec<&dyn PatternMatcher> {
+        self.screen
+            .matches(text)
+            .into_iter()
+            .map(|idx| self.components[idx].as_ref())
+            .collect()
+    }
+}
+
+impl fmt::Debug for CompositeMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompositeMatcher")
+            .field("components", &self.components.len())
+            .field("pattern", &self.pattern.as_str())
+            .finish()
+    }
+}
+
+impl PatternMatcher for CompositeMatcher {
+    fn pattern(&self) -> &Regex {
+        &self.pattern
+    }
+
+    /// Returns what each component extracts, component by component.
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.candidates(text)
+            .into_iter()
+            .flat_map(|component| component.extract_all(text))
+            .collect()
+    }
+
+    /// Returns the normalization of the first component that accepts
+    /// `text`.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.components
+            .iter()
+            .find_map(|component| component.normalize(text))
+    }
+
+    /// Returns the variants of the first component for which `normalized`
+    /// is already normal, or `normalized` alone if there is none.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        self.components
+            .iter()
+            .find(|component| component.is_normalized(normalized))
+            .map_or_else(
+                || vec![normalized.to_string()],
+                |component| component.generate_variants(normalized),
+            )
+    }
+
+    /// Returns every component's matches, ordered by position.
+    ///
+    /// Matches of different components may overlap.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        let mut found: Vec<_> = self
+            .candidates(text)
+            .into_iter()
+            .flat_map(|component| component.find_with_positions(text))
+            .collect();
+        found.sort_by_key(|&(start, end, _)| (start, end));
+        found.dedup();
+        found
+    }
+
+    /// Returns every component's scored matches, ordered by position.
+    fn extract_all_scored<'a>(&self, text: &'a str) -> Vec<(ParseMatch<'a>, f32)> {
+        let mut scored: Vec<_> = self
+            .candidates(text)
+            .into_iter()
+            .flat_map(|component| component.extract_all_scored(text))
+            .collect();
+        scored.sort_by_key(|(found, _)| (found.start, found.end));
+        scored
+    }
+}
+
+/// Turns the named groups of a regex pattern into unnamed ones.
+fn strip_group_names(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(idx) = rest.find(['\\', '(']) {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        // Copy escapes whole, so `\(` is not taken for a group
+        if rest.starts_with('\\') {
+            let len = rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        out.push('(');
+        let named = rest.starts_with("(?P<") || rest.starts_with("(?<");
+        rest = match rest.find('>') {
+            Some(end) if named => &rest[end + 1..],
+            _ => &rest[1..],
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        CreditCardMatcher, DateMatcher, PhoneNumberMatcher, PostalCodeMatcher, SsnMatcher,
+        TimestampMatcher, VerizonAccountMatcher,
+    };
+
+    const TEXT: &str = "Call 555-234-5678 on 2025-01-15.\n\
+                        SSN 123-45-6789, card 4111 1111 1111 1111.\n\
+                        Logged 2025-01-15T14:30:00Z\n\
+                        Springfield, IL 62704";
+
+    fn individual() -> Vec<Box<dyn PatternMatcher>> {
+        vec![
+            Box::new(PhoneNumberMatcher::new()),
+            Box::new(DateMatcher::new()),
+            Box::new(SsnMatcher::new()),
+            Box::new(CreditCardMatcher::new()),
+            Box::new(TimestampMatcher::new()),
+            Box::new(PostalCodeMatcher::new()),
+        ]
+    }
+
+    #[test]
+    fn test_matches_union_of_components() {
+        let composite = CompositeMatcher::new(individual()).unwrap();
+
+        let mut expected: Vec<_> = individual()
+            .iter()
+            .flat_map(|matcher| matcher.find_with_positions(TEXT))
+            .collect();
+        expected.sort_by_key(|&(start, end, _)| (start, end));
+        expected.dedup();
+        assert_eq!(composite.find_with_positions(TEXT), expected);
+
+        let expected: Vec<_> = individual()
+            .iter()
+            .flat_map(|matcher| matcher.extract_all(TEXT))
+            .collect();
+        assert_eq!(composite.extract_all(TEXT), expected);
+    }
+
+    #[test]
+    fn test_dispatches_to_component() {
+        let composite = CompositeMatcher::new(individual()).unwrap();
+
+        assert_eq!(
+            composite.normalize("555-234-5678"),
+            PhoneNumberMatcher::new().normalize("555-234-5678")
+        );
+        assert_eq!(
+            composite.generate_variants("5552345678"),
+            PhoneNumberMatcher::new().generate_variants("5552345678")
+        );
+    }
+
+    #[test]
+    fn test_screens_out_components() {
+        let composite = CompositeMatcher::new(individual()).unwrap();
+        assert_eq!(composite.candidates("SSN 123-45-6789").len(), 1);
+        assert!(composite.candidates("nothing sensitive").is_empty());
+
+        // Date and timestamp patterns share group names
+        assert!(composite.pattern().is_match("2025-01-15"));
+    }
+
+    #[test]
+    fn test_keeps_component_scores() {
+        let composite = CompositeMatcher::new(vec![
+            Box::new(SsnMatcher::new()),
+            Box::new(VerizonAccountMatcher::new()),
+        ])
+        .unwrap();
+        let text = "Acct # 98765432100002, SSN 123-45-6789";
+
+        let expected: Vec<_> = VerizonAccountMatcher::new()
+            .extract_all_scored(text)
+            .into_iter()
+            .chain(SsnMatcher::new().extract_all_scored(text))
+            .collect();
+        assert_eq!(composite.extract_all_scored(text), expected);
+    }
+
+    #[test]
+    fn test_empty_composite_matches_nothing() {
+        let composite = CompositeMatcher::new(Vec::new()).unwrap();
+        assert!(composite.extract_all(TEXT).is_empty());
+        assert!(!composite.pattern().is_match(TEXT));
+    }
+
+    #[test]
+    fn test_strip_group_names() {
+        assert_eq!(
+            strip_group_names(r"(?P<year>\d{4})-(?<m>\d{2})\(?P<x>(?:a)"),
+            r"(\d{4})-(\d{2})\(?P<x>(?:a)"
+        );
+    }
+}
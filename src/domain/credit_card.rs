@@ -0,0 +1,129 @@
+//! Payment card number domain logic.
+//!
+//! This module detects card numbers in their common printed groupings and
+//! validates them with the Luhn checksum, so that other long digit runs
+//! (order numbers, tracking numbers) are left alone.
+
+use super::{ChecksumAlgorithm, PatternMatcher};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Payment card number pattern matcher.
+///
+/// Supports:
+/// - 4111 1111 1111 1111 / 4111-1111-1111-1111 (4-4-4-4)
+/// - 3782 822463 10005 / 3782-822463-10005 (4-6-5, American Express)
+/// - 4111111111111111 (13-19 ungrouped digits)
+#[derive(Debug, Clone)]
+pub struct CreditCardMatcher;
+
+impl CreditCardMatcher {
+    /// Creates a new credit card matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for grouped and ungrouped card numbers.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"\b(?:",
+                r"\d{4}-\d{4}-\d{4}-\d{4}|\d{4} \d{4} \d{4} \d{4}",
+                r"|\d{4}-\d{6}-\d{5}|\d{4} \d{6} \d{5}",
+                r"|\d{13,19}",
+                r")\b",
+            ))
+            .expect("Valid credit card regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates a digit string with the Luhn (mod 10) checksum.
+    pub fn luhn_valid(digits: &str) -> bool {
+        (13..=19).contains(&digits.len()) && ChecksumAlgorithm::Luhn.validate(digits)
+    }
+}
+
+impl Default for CreditCardMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for CreditCardMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|card| self.normalize(card).is_some())
+            .collect()
+    }
+
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.pattern().find_iter(text).find_map(|m| {
+            let digits: String = m.as_str().chars().filter(char::is_ascii_digit).collect();
+            Self::luhn_valid(&digits).then_some(digits)
+        })
+    }
+
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        let groups: &[usize] = match normalized.len() {
+            16 => &[4, 4, 4, 4],
+            15 => &[4, 6, 5],
+            _ => return vec![normalized.to_string()],
+        };
+
+        let mut parts = Vec::with_capacity(groups.len());
+        let mut start = 0;
+        for len in groups {
+            parts.push(&normalized[start..start + len]);
+            start += len;
+        }
+
+        vec![
+            normalized.to_string(), // 4111111111111111
+            parts.join(" "),        // 4111 1111 1111 1111
+            parts.join("-"),        // 4111-1111-1111-1111
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luhn() {
+        assert!(CreditCardMatcher::luhn_valid("4111111111111111"));
+        assert!(CreditCardMatcher::luhn_valid("378282246310005"));
+        assert!(!CreditCardMatcher::luhn_valid("4111111111111112"));
+        assert!(!CreditCardMatcher::luhn_valid("411111111111"));
+    }
+
+    #[test]
+    fn test_card_extraction() {
+        let matcher = CreditCardMatcher::new();
+        let text = "Visa 4111-1111-1111-1111, Amex 3782 822463 10005, order 1234567890123";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["4111-1111-1111-1111", "3782 822463 10005"]
+        );
+    }
+
+    #[test]
+    fn test_card_variants() {
+        let matcher = CreditCardMatcher::new();
+        let normalized = matcher.normalize("4111 1111 1111 1111").unwrap();
+        assert_eq!(normalized, "4111111111111111");
+        let variants = matcher.generate_variants(&normalized);
+        assert!(variants.contains(&"4111 1111 1111 1111".to_string()));
+        assert!(variants.contains(&"4111-1111-1111-1111".to_string()));
+
+        let amex = matcher.generate_variants("378282246310005");
+        assert!(amex.contains(&"3782 822463 10005".to_string()));
+    }
+}
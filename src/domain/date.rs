@@ -0,0 +1,168 @@
+//! Calendar date domain logic.
+//!
+//! This module detects dates written in the ISO, US and textual forms found
+//! in statements and correspondence. Unlike a plain digit pattern, it
+//! rejects dates that do not exist, such as 2026-13-40 or February 30.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// Calendar date pattern matcher.
+///
+/// Supports:
+/// - ISO: 2025-01-06
+/// - US: 01/06/2025, 1/6/2025
+/// - Textual: January 6, 2025, Jan. 6 2025
+#[derive(Debug, Clone)]
+pub struct DateMatcher;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+impl DateMatcher {
+    /// Creates a new date matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for ISO, US and textual dates.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                // ISO: YYYY-MM-DD
+                r"\b(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2})\b",
+                r"|",
+                // US: MM/DD/YYYY
+                r"\b(?P<umonth>\d{1,2})/(?P<uday>\d{1,2})/(?P<uyear>\d{4})\b",
+                r"|",
+                // Textual: full or abbreviated month, day, optional comma, year
+                r"\b(?P<tmonth>(?i:jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may",
+                r"|june?|july?|aug(?:ust)?|sep(?:t(?:ember)?)?|oct(?:ober)?|nov(?:ember)?",
+                r"|dec(?:ember)?))\.?\s+(?P<tday>\d{1,2}),?\s+(?P<tyear>\d{4})\b",
+            ))
+            .expect("Valid date regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates that a date exists in the Gregorian calendar.
+    pub fn validate(year: u32, month: u32, day: u32) -> bool {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return false,
+        };
+        (1..=days_in_month).contains(&day)
+    }
+
+    /// Returns true if a match of [`regex`](Self::regex) is a real date.
+    fn is_valid(caps: &Captures<'_>) -> bool {
+        let num = |name: &str| caps.name(name).and_then(|m| m.as_str().parse().ok());
+
+        let fields = if caps.name("year").is_some() {
+            (num("year"), num("month"), num("day"))
+        } else if caps.name("uyear").is_some() {
+            (num("uyear"), num("umonth"), num("uday"))
+        } else {
+            let month = caps.name("tmonth").and_then(|m| {
+                let prefix = m.as_str().get(..3)?.to_ascii_lowercase();
+                MONTHS.iter().position(|name| *name == prefix)
+            });
+            (num("tyear"), month.map(|m| m as u32 + 1), num("tday"))
+        };
+
+        match fields {
+            (Some(year), Some(month), Some(day)) => Self::validate(year, month, day),
+            _ => false,
+        }
+    }
+}
+
+impl Default for DateMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for DateMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .captures_iter(text)
+            .filter(Self::is_valid)
+            .filter_map(|caps| caps.get(0))
+            .map(|m| m.as_str())
+            .collect()
+    }
+
+    /// Returns the first valid date in `text`, as written.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.extract_all(text).first().map(|date| date.to_string())
+    }
+
+    /// Dates are searched exactly as written; the same date in another
+    /// format is detected separately.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(DateMatcher::validate(2026, 1, 15));
+        assert!(DateMatcher::validate(2024, 2, 29));
+        assert!(DateMatcher::validate(2000, 2, 29));
+
+        assert!(!DateMatcher::validate(2026, 13, 40));
+        assert!(!DateMatcher::validate(2026, 4, 31));
+        assert!(!DateMatcher::validate(2026, 2, 29));
+        assert!(!DateMatcher::validate(1900, 2, 29));
+        assert!(!DateMatcher::validate(2026, 0, 10));
+    }
+
+    #[test]
+    fn test_date_extraction() {
+        let matcher = DateMatcher::new();
+        let text = "Meeting: 2026-01-15\nInvalid: 2026-13-40\n\
+                    Due 02/28/2026, not 02/30/2026\nSigned January 6, 2025";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["2026-01-15", "02/28/2026", "January 6, 2025"]
+        );
+    }
+
+    #[test]
+    fn test_textual_forms() {
+        let matcher = DateMatcher::new();
+        let text = "Jan. 6 2025, SEPT 30, 2025 and Feb 30, 2025";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["Jan. 6 2025", "SEPT 30, 2025"]
+        );
+        assert_eq!(
+            matcher.normalize("Paid on March 3, 2025"),
+            Some("March 3, 2025".to_string())
+        );
+    }
+
+    #[test]
+    fn test_variants_are_verbatim() {
+        let matcher = DateMatcher::new();
+        assert_eq!(
+            matcher.generate_variants("1/6/2025"),
+            vec!["1/6/2025".to_string()]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! US driver's license number domain logic.
+//!
+//! Each state issues license numbers in its own format, and most formats
+//! are just a few letters and digits that collide with other identifiers.
+//! Like passport numbers, a state's format is matched anywhere only when
+//! the caller names the state; otherwise only numbers labelled as license
+//! numbers are matched.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Driver's license number pattern matcher.
+///
+/// Supports:
+/// - CA: A1234567 (a letter and 7 digits)
+/// - FL: A123-456-78-901-0 (a letter and 12 digits, hyphens optional)
+/// - IL: A123-4567-8901 (a letter and 11 digits, hyphens optional)
+/// - NY: 123456789 or 123 456 789 (9 digits)
+/// - PA: 12345678 or 12 345 678 (8 digits)
+/// - TX: 12345678 (8 digits)
+/// - Any state: "DL No: A1234567" (5-18 letters, digits and hyphens after
+///   a license keyword)
+#[derive(Debug, Clone)]
+pub struct DriversLicenseMatcher {
+    /// USPS code of the state whose format is expected, or `None` to
+    /// require a license keyword
+    state: Option<&'static str>,
+}
+
+impl DriversLicenseMatcher {
+    /// States with a known license number format.
+    pub const SUPPORTED_STATES: &'static [&'static str] = &["CA", "FL", "IL", "NY", "PA", "TX"];
+
+    /// Creates a matcher for numbers labelled as license numbers, in any
+    /// state's format.
+    pub fn new() -> Self {
+        Self { state: None }
+    }
+
+    /// Creates a matcher for one state's license number format.
+    ///
+    /// `state` is a USPS state code, case-insensitive. Returns `None` for
+    /// unsupported states.
+    pub fn for_state(state: &str) -> Option<Self> {
+        let code = state.to_ascii_uppercase();
+        let code = Self::SUPPORTED_STATES
+            .iter()
+            .copied()
+            .find(|supported| *supported == code)?;
+        Some(Self { state: Some(code) })
+    }
+
+    /// Returns the state whose format this matcher expects, if any.
+    pub fn state(&self) -> Option<&'static str> {
+        self.state
+    }
+
+    /// License numbers after a keyword, e.g. "Driver's License #: A1234567".
+    ///
+    /// Capture group 1 is the number.
+    fn pattern_with_context() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"(?i:\bDL|driver(?:'|’)?s?\s+licen[cs]e|\blicen[cs]e)(?i:\s*(?:number|num|no\.?|#))?\s*:?\s*\b([A-Z0-9][A-Z0-9-]{3,16}[A-Z0-9])\b",
+            )
+            .expect("Valid regex")
+        });
+        &PATTERN
+    }
+
+    /// A number on its own, as captured by
+    /// [`pattern_with_context`](Self::pattern_with_context).
+    fn pattern_number_only() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"^[A-Z0-9][A-Z0-9-]{3,16}[A-Z0-9]$").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_ca() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b[A-Z]\d{7}\b").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_fl() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"\b[A-Z](?:\d{12}|\d{3}-\d{3}-\d{2}-\d{3}-\d)\b").expect("Valid regex")
+        });
+        &PATTERN
+    }
+
+    fn pattern_il() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"\b[A-Z](?:\d{11}|\d{3}-\d{4}-\d{4})\b").expect("Valid regex")
+        });
+        &PATTERN
+    }
+
+    fn pattern_ny() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b(?:\d{9}|\d{3} \d{3} \d{3})\b").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_pa() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b(?:\d{8}|\d{2} \d{3} \d{3})\b").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_tx() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{8}\b").expect("Valid regex"));
+        &PATTERN
+    }
+}
+
+impl Default for DriversLicenseMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for DriversLicenseMatcher {
+    fn pattern(&self) -> &Regex {
+        match self.state {
+            Some("CA") => Self::pattern_ca(),
+            Some("FL") => Self::pattern_fl(),
+            Some("IL") => Self::pattern_il(),
+            Some("NY") => Self::pattern_ny(),
+            Some("PA") => Self::pattern_pa(),
+            // TX, the only other supported state
+            Some(_) => Self::pattern_tx(),
+            None => Self::pattern_with_context(),
+        }
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        if self.state.is_some() {
+            return self.pattern().find_iter(text).map(|m| m.as_str()).collect();
+        }
+
+        // A keyword followed by a word such as "renewal" is not a number
+        Self::pattern_with_context()
+            .captures_iter(text)
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .filter(|number| number.bytes().any(|b| b.is_ascii_digit()))
+            .collect()
+    }
+
+    /// Returns the first license number in `text`.
+    ///
+    /// Without a state, numbers are only extracted after a keyword, so an
+    /// already extracted number also normalizes on its own.
+    fn normalize(&self, text: &str) -> Option<String> {
+        match self.extract_all(text).first() {
+            Some(number) => Some(number.to_string()),
+            None if self.state.is_none()
+                && Self::pattern_number_only().is_match(text)
+                && text.bytes().any(|b| b.is_ascii_digit()) =>
+            {
+                Some(text.to_string())
+            }
+            None => None,
+        }
+    }
+
+    /// License numbers are searched exactly as written.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ca_format() {
+        let matcher = DriversLicenseMatcher::for_state("ca").unwrap();
+        assert_eq!(matcher.state(), Some("CA"));
+        let text = "License D1234567 issued; ref AB1234567, D12345678, 1234567";
+        assert_eq!(matcher.extract_all(text), vec!["D1234567"]);
+    }
+
+    #[test]
+    fn test_fl_format() {
+        let matcher = DriversLicenseMatcher::for_state("FL").unwrap();
+        let text = "DL S530-123-45-678-0 and S530123456780; not S530-123-45-678";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["S530-123-45-678-0", "S530123456780"]
+        );
+    }
+
+    #[test]
+    fn test_digit_formats() {
+        let ny = DriversLicenseMatcher::for_state("NY").unwrap();
+        assert_eq!(
+            ny.extract_all("ID 123 456 789 or 987654321, not 12345678"),
+            vec!["123 456 789", "987654321"]
+        );
+
+        let tx = DriversLicenseMatcher::for_state("TX").unwrap();
+        assert_eq!(
+            tx.extract_all("TX 12345678, not 123456789"),
+            vec!["12345678"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_state() {
+        assert!(DriversLicenseMatcher::for_state("ZZ").is_none());
+        assert!(DriversLicenseMatcher::for_state("").is_none());
+    }
+
+    #[test]
+    fn test_generic_requires_keyword() {
+        let matcher = DriversLicenseMatcher::new();
+        let text = "Driver's License No.: D1234567\n\
+                    DL# S530-123-45-678-0\n\
+                    License renewal due\n\
+                    Invoice D7654321\n\
+                    HANDLE 12345678";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["D1234567", "S530-123-45-678-0"]
+        );
+        assert_eq!(matcher.normalize(text), Some("D1234567".to_string()));
+    }
+
+    #[test]
+    fn test_generic_normalizes_extracted_number() {
+        let matcher = DriversLicenseMatcher::new();
+        assert_eq!(
+            matcher.normalize("S530-123-45-678-0"),
+            Some("S530-123-45-678-0".to_string())
+        );
+        assert_eq!(matcher.normalize("RENEWAL"), None);
+        assert_eq!(matcher.normalize("Invoice D7654321"), None);
+    }
+}
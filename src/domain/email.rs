@@ -0,0 +1,133 @@
+//! Email address domain logic.
+//!
+//! This module detects email addresses following the practical subset of
+//! RFC 5322 that appears in documents: dot-atom or quoted local parts and
+//! dotted domain names.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Email address pattern matcher.
+///
+/// Supports:
+/// - jane.doe@example.com
+/// - jane+billing@mail.example.co.uk
+/// - "jane doe"@example.com
+///
+/// Rejects local parts with leading, trailing, or repeated dots and
+/// addresses without a domain (e.g. `test@`).
+#[derive(Debug, Clone)]
+pub struct EmailMatcher;
+
+impl EmailMatcher {
+    /// Creates a new email matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for email addresses.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                // Local part: dot-atom or quoted string
+                r#"(?:[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[A-Za-z0-9!#$%&'*+/=?^_`{|}~-]+)*"#,
+                r#"|"(?:[^"\\\r\n]|\\.)+")"#,
+                r"@",
+                // Domain: labels that don't start or end with a hyphen, then a TLD
+                r"(?:[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.)+[A-Za-z]{2,63}\b",
+            ))
+            .expect("Valid email regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns true if the character can't continue a dot-atom local part,
+    /// i.e. a match starting after it is not a suffix of a longer address.
+    fn is_boundary(c: char) -> bool {
+        !(c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-.".contains(c))
+    }
+}
+
+impl Default for EmailMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for EmailMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .filter(|m| {
+                // Skip tails of invalid addresses such as "a..b@example.com"
+                text[..m.start()]
+                    .chars()
+                    .next_back()
+                    .map_or(true, Self::is_boundary)
+            })
+            .map(|m| m.as_str())
+            .collect()
+    }
+
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.extract_all(text)
+            .first()
+            .map(|email| email.to_string())
+    }
+
+    /// Emails render verbatim, so the address itself is the only variant.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_extraction() {
+        let matcher = EmailMatcher::new();
+        let text = "Contact jane+billing@mail.example.co.uk or \"jane doe\"@example.com.";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec![
+                "jane+billing@mail.example.co.uk",
+                "\"jane doe\"@example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_punctuation_not_swallowed() {
+        let matcher = EmailMatcher::new();
+        assert_eq!(
+            matcher.extract_all("(write to user@example.com), thanks."),
+            vec!["user@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_addresses_rejected() {
+        let matcher = EmailMatcher::new();
+        assert!(matcher.extract_all("Not an email: test@").is_empty());
+        assert!(matcher.extract_all("user.@example.com").is_empty());
+        assert!(matcher.extract_all("a..b@example.com").is_empty());
+        assert!(matcher.extract_all("user@-example.com").is_empty());
+    }
+
+    #[test]
+    fn test_email_variants() {
+        let matcher = EmailMatcher::new();
+        let normalized = matcher.normalize("Email: user@example.com").unwrap();
+        assert_eq!(
+            matcher.generate_variants(&normalized),
+            vec!["user@example.com"]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Externally detected entity lists.
+//!
+//! This module parses entity lists produced by NER tools (spaCy and similar)
+//! and maps each entity to the search strings needed to redact it, reusing
+//! the domain matchers' variant generation where the category allows.
+
+use super::{PatternMatcher, PhoneNumberMatcher, VerizonAccountMatcher};
+
+/// A single entity detected outside this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entity {
+    /// Entity text as it was detected
+    pub text: String,
+
+    /// Optional category label (e.g. `PERSON`, `PHONE`)
+    pub category: Option<String>,
+}
+
+impl Entity {
+    /// Parses an entity list, one entity per line.
+    ///
+    /// Each line is `TEXT` or `TEXT<TAB>CATEGORY`. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub fn parse_list(contents: &str) -> Vec<Entity> {
+        contents
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|line| match line.split_once('\t') {
+                Some((text, category)) => Entity {
+                    text: text.trim().to_string(),
+                    category: Some(category.trim().to_string()).filter(|c| !c.is_empty()),
+                },
+                None => Entity {
+                    text: line.trim().to_string(),
+                    category: None,
+                },
+            })
+            .filter(|entity| !entity.text.is_empty())
+            .collect()
+    }
+
+    /// Returns the strings to search for when redacting this entity.
+    ///
+    /// Phone and account categories expand to every rendering their matcher
+    /// knows; anything else is searched verbatim.
+    pub fn variants(&self) -> Vec<String> {
+        let category = self.category.as_deref().unwrap_or("").to_ascii_uppercase();
+
+        let expanded = match category.as_str() {
+            "PHONE" | "PHONE_NUMBER" => {
+                let matcher = PhoneNumberMatcher::new();
+                matcher
+                    .normalize(&self.text)
+                    .map(|normalized| matcher.generate_variants(&normalized))
+            }
+            "ACCOUNT" | "ACCOUNT_NUMBER" => {
+                let digits: String = self.text.chars().filter(|c| c.is_ascii_digit()).collect();
+                (digits.len() >= 10)
+                    .then(|| VerizonAccountMatcher::new().generate_variants(&digits))
+            }
+            _ => None,
+        };
+
+        let mut variants = expanded.unwrap_or_default();
+        if !variants.contains(&self.text) {
+            variants.insert(0, self.text.clone());
+        }
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let entities = Entity::parse_list(
+            "# exported from NER\nJane Roe\tPERSON\n\n555-234-5678\tPHONE\nACME\n",
+        );
+        assert_eq!(entities.len(), 3);
+        assert_eq!(entities[0].text, "Jane Roe");
+        assert_eq!(entities[0].category.as_deref(), Some("PERSON"));
+        assert_eq!(entities[2].category, None);
+    }
+
+    #[test]
+    fn test_phone_entity_variants() {
+        let entity = Entity {
+            text: "555-234-5678".to_string(),
+            category: Some("phone".to_string()),
+        };
+        let variants = entity.variants();
+        assert!(variants.contains(&"555-234-5678".to_string()));
+        assert!(variants.contains(&"(555) 234-5678".to_string()));
+    }
+
+    #[test]
+    fn test_uncategorized_entity_is_verbatim() {
+        let entity = Entity {
+            text: "Jane Roe".to_string(),
+            category: None,
+        };
+        assert_eq!(entity.variants(), vec!["Jane Roe".to_string()]);
+    }
+}
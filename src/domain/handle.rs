@@ -0,0 +1,165 @@
+//! Username and handle domain logic.
+//!
+//! This module detects `@mentions` and usernames given after a user or
+//! login label, as found in support tickets and chat exports. The `@` of
+//! an email address is not a mention, so addresses are left to the email
+//! matcher.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Username and handle pattern matcher.
+///
+/// Supports:
+/// - Mentions: @alice, @jane.doe, @ops-team
+/// - Labelled usernames: User: jdoe, Username: jane_doe, Login: j.doe
+///
+/// A handle is letters, digits, `_`, `.` and `-`, not ending with `.` or
+/// `-`, so trailing punctuation is not swallowed. A mention must not follow
+/// a character that can end an email's local part, and neither kind may be
+/// followed by `@`, so no part of `alice@example.com` matches.
+#[derive(Debug, Clone)]
+pub struct HandleMatcher;
+
+impl HandleMatcher {
+    /// Creates a new handle matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for labelled usernames and mentions.
+    ///
+    /// A labelled username is captured as `user`; a mention is the whole
+    /// match.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"(?i:\b(?:user(?:\s*name)?|login)\s*:\s*)",
+                r"(?P<user>[A-Za-z0-9_](?:[A-Za-z0-9_.-]*[A-Za-z0-9_])?)",
+                r"|@[A-Za-z0-9_](?:[A-Za-z0-9_.-]*[A-Za-z0-9_])?",
+            ))
+            .expect("Valid handle regex")
+        });
+        &PATTERN
+    }
+
+    /// Matches a whole handle, with or without its `@`.
+    fn handle_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"^@?[A-Za-z0-9_](?:[A-Za-z0-9_.-]*[A-Za-z0-9_])?$")
+                .expect("Valid handle regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns true if `c` can end the local part of an email address, so
+    /// an `@` after it belongs to the address.
+    fn is_local_part_char(c: char) -> bool {
+        c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-.\"".contains(c)
+    }
+}
+
+impl Default for HandleMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for HandleMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.find_with_positions(text)
+            .into_iter()
+            .map(|(_, _, handle)| handle)
+            .collect()
+    }
+
+    /// Returns `text` if it is a single handle, as written.
+    ///
+    /// Labels and email context are checked by
+    /// [`find_with_positions`](Self::find_with_positions), so an already
+    /// extracted handle normalizes on its own.
+    fn normalize(&self, text: &str) -> Option<String> {
+        Self::handle_regex()
+            .is_match(text)
+            .then(|| text.to_string())
+    }
+
+    /// Handles are searched exactly as written.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+
+    /// Returns each mention and labelled username in `text`, skipping any
+    /// that are part of an email address.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        self.pattern()
+            .captures_iter(text)
+            .filter_map(|caps| {
+                let found = caps.name("user").unwrap_or_else(|| caps.get(0).unwrap());
+                let in_address = text[found.end()..].starts_with('@')
+                    || (caps.name("user").is_none()
+                        && text[..found.start()]
+                            .chars()
+                            .next_back()
+                            .is_some_and(Self::is_local_part_char));
+                (!in_address).then(|| (found.start(), found.end(), found.as_str()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions() {
+        let matcher = HandleMatcher::new();
+        let text = "Escalated by @alice to @ops-team (cc @jane.doe).";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["@alice", "@ops-team", "@jane.doe"]
+        );
+    }
+
+    #[test]
+    fn test_labelled_usernames() {
+        let matcher = HandleMatcher::new();
+        let text = "User: jdoe\nusername: jane_doe\nLOGIN:j.doe, reset requested";
+        assert_eq!(matcher.extract_all(text), vec!["jdoe", "jane_doe", "j.doe"]);
+    }
+
+    #[test]
+    fn test_email_addresses_are_not_handles() {
+        let matcher = HandleMatcher::new();
+        assert!(matcher.extract_all("Contact alice@example.com").is_empty());
+        assert!(matcher.extract_all("Login: alice@example.com").is_empty());
+        assert!(matcher.extract_all("\"jane doe\"@example.com").is_empty());
+
+        let text = "@alice wrote to alice@example.com";
+        let positions = matcher.find_with_positions(text);
+        assert_eq!(positions, vec![(0, 6, "@alice")]);
+    }
+
+    #[test]
+    fn test_label_must_be_a_word() {
+        let matcher = HandleMatcher::new();
+        assert!(matcher.extract_all("superuser: root").is_empty());
+        assert!(matcher.extract_all("The user logged in").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_and_variants() {
+        let matcher = HandleMatcher::new();
+        assert_eq!(matcher.normalize("@alice"), Some("@alice".to_string()));
+        assert_eq!(matcher.normalize("jdoe"), Some("jdoe".to_string()));
+        assert_eq!(matcher.normalize("@alice."), None);
+        assert!(matcher.is_normalized("@jane.doe"));
+        assert_eq!(matcher.generate_variants("@alice"), vec!["@alice"]);
+    }
+}
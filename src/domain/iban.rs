@@ -0,0 +1,172 @@
+//! International Bank Account Number (IBAN) domain logic.
+//!
+//! This module detects IBANs in their continuous and space-grouped print
+//! forms and validates them with the ISO 13616 mod-97 checksum and the
+//! registered length for their country, so that other alphanumeric
+//! references are left alone.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// IBAN pattern matcher.
+///
+/// Supports:
+/// - GB82WEST12345698765432 (continuous)
+/// - GB82 WEST 1234 5698 7654 32 (groups of four)
+#[derive(Debug, Clone)]
+pub struct IbanMatcher;
+
+/// Returns the total IBAN length registered for a country (ISO 13616).
+fn registered_length(country: &str) -> Option<usize> {
+    match country {
+        "NO" => Some(15),
+        "BE" => Some(16),
+        "DK" | "FI" | "FO" | "GL" | "NL" => Some(18),
+        "MK" | "SI" => Some(19),
+        "AT" | "BA" | "EE" | "KZ" | "LT" | "LU" | "XK" => Some(20),
+        "CH" | "HR" | "LI" | "LV" => Some(21),
+        "BG" | "BH" | "CR" | "DE" | "GB" | "GE" | "IE" | "ME" | "RS" | "VA" => Some(22),
+        "AE" | "GI" | "IL" | "IQ" | "TL" => Some(23),
+        "AD" | "CZ" | "ES" | "MD" | "PK" | "RO" | "SA" | "SE" | "SK" | "TN" | "VG" => Some(24),
+        "PT" | "ST" => Some(25),
+        "IS" | "TR" => Some(26),
+        "FR" | "GR" | "IT" | "MC" | "MR" | "SM" => Some(27),
+        "AL" | "AZ" | "BY" | "CY" | "DO" | "GT" | "HU" | "LB" | "PL" | "SV" => Some(28),
+        "BR" | "EG" | "PS" | "QA" | "UA" => Some(29),
+        "JO" | "KW" | "MU" => Some(30),
+        "MT" | "SC" => Some(31),
+        "LC" => Some(32),
+        _ => None,
+    }
+}
+
+impl IbanMatcher {
+    /// Creates a new IBAN matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for continuous and grouped IBANs.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"\b[A-Z]{2}\d{2}(?:",
+                r"[A-Z0-9]{11,30}",                           // continuous
+                r"|(?: [A-Z0-9]{4}){2,7}(?: [A-Z0-9]{1,3})?", // groups of four
+                r")\b",
+            ))
+            .expect("Valid IBAN regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates a compact, uppercase IBAN against its country's length
+    /// and the mod-97 checksum.
+    pub fn validate(iban: &str) -> bool {
+        let Some(country) = iban.get(..2) else {
+            return false;
+        };
+        if registered_length(country) != Some(iban.len())
+            || !iban.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return false;
+        }
+
+        // Move the country code and check digits to the end, read letters
+        // as two-digit numbers (A = 10 ... Z = 35), and reduce mod 97
+        let remainder = iban[4..]
+            .bytes()
+            .chain(iban[..4].bytes())
+            .fold(0u32, |acc, b| match b {
+                b'0'..=b'9' => (acc * 10 + u32::from(b - b'0')) % 97,
+                _ => (acc * 100 + u32::from(b.to_ascii_uppercase() - b'A') + 10) % 97,
+            });
+
+        remainder == 1
+    }
+}
+
+impl Default for IbanMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for IbanMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|iban| self.normalize(iban).is_some())
+            .collect()
+    }
+
+    /// Returns the first valid IBAN in `text` in compact form.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.pattern().find_iter(text).find_map(|m| {
+            let compact: String = m.as_str().chars().filter(|c| *c != ' ').collect();
+            Self::validate(&compact).then_some(compact)
+        })
+    }
+
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        let grouped = normalized
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).expect("IBANs are ASCII"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        vec![
+            normalized.to_string(), // GB82WEST12345698765432
+            grouped,                // GB82 WEST 1234 5698 7654 32
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(IbanMatcher::validate("GB82WEST12345698765432"));
+        assert!(IbanMatcher::validate("DE89370400440532013000"));
+        assert!(IbanMatcher::validate("NO9386011117947"));
+
+        // Corrupted check digit, wrong length for country, unknown country
+        assert!(!IbanMatcher::validate("GB82WEST12345698765433"));
+        assert!(!IbanMatcher::validate("DE8937040044053201300"));
+        assert!(!IbanMatcher::validate("ZZ82WEST12345698765432"));
+    }
+
+    #[test]
+    fn test_iban_extraction() {
+        let matcher = IbanMatcher::new();
+        let text = "Pay to GB82 WEST 1234 5698 7654 32 or DE89370400440532013000. \
+                    Ref GB82WEST12345698765433";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["GB82 WEST 1234 5698 7654 32", "DE89370400440532013000"]
+        );
+    }
+
+    #[test]
+    fn test_iban_variants() {
+        let matcher = IbanMatcher::new();
+        let normalized = matcher.normalize("GB82 WEST 1234 5698 7654 32").unwrap();
+        assert_eq!(normalized, "GB82WEST12345698765432");
+        assert_eq!(
+            matcher.generate_variants(&normalized),
+            vec![
+                "GB82WEST12345698765432".to_string(),
+                "GB82 WEST 1234 5698 7654 32".to_string(),
+            ]
+        );
+    }
+}
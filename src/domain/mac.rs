@@ -0,0 +1,154 @@
+//! MAC address domain logic.
+//!
+//! This module detects 48-bit MAC addresses in the colon-, hyphen- and
+//! dot-separated forms used by network configuration exports and device
+//! listings.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// MAC address pattern matcher.
+///
+/// Supports:
+/// - 00:1A:2B:3C:4D:5E (colon-separated octets)
+/// - 00-1A-2B-3C-4D-5E (hyphen-separated octets)
+/// - 001A.2B3C.4D5E (dot-separated groups of four)
+#[derive(Debug, Clone)]
+pub struct MacAddressMatcher;
+
+impl MacAddressMatcher {
+    /// Creates a new MAC address matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for separated hex runs.
+    ///
+    /// Runs of any length are matched so that [`validate`](Self::validate)
+    /// can reject longer identifiers instead of matching a prefix of them.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"\b(?:",
+                r"[0-9A-Fa-f]{2}(?:[:-][0-9A-Fa-f]{2})+", // 00:1A:2B:3C:4D:5E
+                r"|[0-9A-Fa-f]{4}(?:\.[0-9A-Fa-f]{4})+",  // 001A.2B3C.4D5E
+                r")\b",
+            ))
+            .expect("Valid MAC address regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates that `mac` is 6 octets of hex written with one separator
+    /// throughout.
+    pub fn validate(mac: &str) -> bool {
+        let groups: Vec<&str> = mac.split([':', '-', '.']).collect();
+        let separators: Vec<char> = mac.chars().filter(|c| !c.is_ascii_hexdigit()).collect();
+        let group_len = match groups.len() {
+            6 => 2,
+            3 => 4,
+            _ => return false,
+        };
+
+        separators.windows(2).all(|pair| pair[0] == pair[1])
+            && (group_len == 4) == (separators.first() == Some(&'.'))
+            && groups
+                .iter()
+                .all(|g| g.len() == group_len && g.bytes().all(|b| b.is_ascii_hexdigit()))
+    }
+}
+
+impl Default for MacAddressMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for MacAddressMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|mac| Self::validate(mac))
+            .collect()
+    }
+
+    /// Returns the first valid MAC address in `text` as 12 uppercase hex
+    /// digits.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.extract_all(text).first().map(|mac| {
+            mac.chars()
+                .filter(char::is_ascii_hexdigit)
+                .map(|c| c.to_ascii_uppercase())
+                .collect()
+        })
+    }
+
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        let octets: Vec<&str> = (0..normalized.len())
+            .step_by(2)
+            .map(|i| &normalized[i..i + 2])
+            .collect();
+        let groups: Vec<&str> = (0..normalized.len())
+            .step_by(4)
+            .map(|i| &normalized[i..i + 4])
+            .collect();
+
+        let upper = [octets.join(":"), octets.join("-"), groups.join(".")];
+        let lower = upper.iter().map(|v| v.to_ascii_lowercase());
+
+        upper.iter().cloned().chain(lower).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(MacAddressMatcher::validate("00:1A:2B:3C:4D:5E"));
+        assert!(MacAddressMatcher::validate("00-1a-2b-3c-4d-5e"));
+        assert!(MacAddressMatcher::validate("001A.2B3C.4D5E"));
+
+        // 5 octets, 7 octets, mixed separators, non-hex
+        assert!(!MacAddressMatcher::validate("00:1A:2B:3C:4D"));
+        assert!(!MacAddressMatcher::validate("00:1A:2B:3C:4D:5E:6F"));
+        assert!(!MacAddressMatcher::validate("00:1A-2B:3C:4D:5E"));
+        assert!(!MacAddressMatcher::validate("00:1A:2B:3C:4D:5G"));
+    }
+
+    #[test]
+    fn test_mac_extraction() {
+        let matcher = MacAddressMatcher::new();
+        let text = "eth0 00:1A:2B:3C:4D:5E, switch port 001a.2b3c.4d5e, \
+                    partial 00:1A:2B:3C:4D, at 12:30";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["00:1A:2B:3C:4D:5E", "001a.2b3c.4d5e"]
+        );
+    }
+
+    #[test]
+    fn test_mac_variants() {
+        let matcher = MacAddressMatcher::new();
+        let normalized = matcher.normalize("001a.2b3c.4d5e").unwrap();
+        assert_eq!(normalized, "001A2B3C4D5E");
+        assert_eq!(
+            matcher.generate_variants(&normalized),
+            vec![
+                "00:1A:2B:3C:4D:5E".to_string(),
+                "00-1A-2B-3C-4D-5E".to_string(),
+                "001A.2B3C.4D5E".to_string(),
+                "00:1a:2b:3c:4d:5e".to_string(),
+                "00-1a-2b-3c-4d-5e".to_string(),
+                "001a.2b3c.4d5e".to_string(),
+            ]
+        );
+    }
+}
@@ -5,14 +5,66 @@
 
 pub mod account;
 pub mod call_details;
+pub mod checksum;
+pub mod composite;
+pub mod credit_card;
+pub mod date;
+pub mod drivers_license;
+pub mod email;
+pub mod entity;
+pub mod handle;
+pub mod iban;
+pub mod mac;
+pub mod name;
+pub mod passport;
 pub mod phone;
+pub mod postal;
+pub mod registry;
+pub mod routing;
+pub mod ssn;
+pub mod timestamp;
+pub mod unicode;
+pub mod vin;
 
 pub use account::VerizonAccountMatcher;
 pub use call_details::VerizonCallDetailsMatcher;
-pub use phone::PhoneNumberMatcher;
+pub use checksum::{ChecksumAlgorithm, ChecksumMatcher};
+pub use composite::CompositeMatcher;
+pub use credit_card::CreditCardMatcher;
+pub use date::DateMatcher;
+pub use drivers_license::DriversLicenseMatcher;
+pub use email::EmailMatcher;
+pub use entity::Entity;
+pub use handle::HandleMatcher;
+pub use iban::IbanMatcher;
+pub use mac::MacAddressMatcher;
+pub use name::NameMatcher;
+pub use passport::PassportMatcher;
+pub use phone::{PhoneLocale, PhoneNumberMatcher};
+pub use postal::PostalCodeMatcher;
+pub use registry::{registered_matchers, MatcherInfo};
+pub use routing::RoutingNumberMatcher;
+pub use ssn::SsnMatcher;
+pub use timestamp::TimestampMatcher;
+pub use unicode::FoldedText;
+pub use vin::VinMatcher;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::ops::Range;
+
+/// A match found in text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseMatch<'a> {
+    /// Byte offset of the start of the match
+    pub start: usize,
+
+    /// Byte offset just past the end of the match
+    pub end: usize,
+
+    /// The matched text
+    pub text: &'a str,
+}
 
 /// Trait for pattern matching strategies.
 pub trait PatternMatcher: Send + Sync {
@@ -20,20 +72,100 @@ pub trait PatternMatcher: Send + Sync {
     fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str>;
     fn normalize(&self, text: &str) -> Option<String>;
     fn generate_variants(&self, normalized: &str) -> Vec<String>;
+
+    /// Returns true if `text` is a value [`normalize`](Self::normalize)
+    /// returns, which normalizes to itself.
+    ///
+    /// Normalization must be idempotent: feeding back any value it returns
+    /// yields the same value, so that variants generated from it cover
+    /// every rendering.
+    fn is_normalized(&self, text: &str) -> bool {
+        self.normalize(text).as_deref() == Some(text)
+    }
+
+    /// Returns the start and end byte offsets of each match in `text`,
+    /// along with the matched text.
+    ///
+    /// Matches that do not [`normalize`](Self::normalize) are skipped, so
+    /// checksum and range validation still applies.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        self.pattern()
+            .find_iter(text)
+            .filter(|m| self.normalize(m.as_str()).is_some())
+            .map(|m| (m.start(), m.end(), m.as_str()))
+            .collect()
+    }
+
+    /// Returns each match in `text` with a confidence score from 0.0 to
+    /// 1.0 that it is what the matcher looks for, ordered by position.
+    ///
+    /// Matchers that cannot tell matches apart score each of
+    /// [`find_with_positions`](Self::find_with_positions) as 1.0.
+    fn extract_all_scored<'a>(&self, text: &'a str) -> Vec<(ParseMatch<'a>, f32)> {
+        self.find_with_positions(text)
+            .into_iter()
+            .map(|(start, end, text)| (ParseMatch { start, end, text }, 1.0))
+            .collect()
+    }
 }
 
 /// PDF escape sequences and patterns.
 pub struct PdfEscapes;
 
 impl PdfEscapes {
+    /// Decodes the escape sequences of a PDF literal string.
+    ///
+    /// Octal escapes of one to three digits (`\101`) become the byte they
+    /// encode, read as PDFDocEncoding/Latin-1. Named escapes (`\n`, `\r`,
+    /// `\t`, `\b`, `\f`, `\(`, `\)`, `\\`) are decoded; any other
+    /// backslash is kept as is.
     pub fn unescape(text: &str) -> String {
-        text.replace("\\040", " ")
-            .replace("\\050", "(")
-            .replace("\\051", ")")
-            .replace("\\n", "\n")
-            .replace("\\r", "\r")
-            .replace("\\t", "\t")
-            .replace("\\\\", "\\")
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+
+            match chars.peek().copied() {
+                Some(digit @ '0'..='7') => {
+                    chars.next();
+                    let mut value = digit as u32 - '0' as u32;
+                    for _ in 0..2 {
+                        match chars.peek().copied() {
+                            Some(digit @ '0'..='7') => {
+                                chars.next();
+                                value = value * 8 + (digit as u32 - '0' as u32);
+                            }
+                            _ => break,
+                        }
+                    }
+                    // High-order overflow is ignored, per the PDF spec
+                    out.push(char::from(value as u8));
+                }
+                Some(named) => {
+                    let decoded = match named {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        '(' | ')' | '\\' => named,
+                        _ => {
+                            out.push('\\');
+                            continue;
+                        }
+                    };
+                    chars.next();
+                    out.push(decoded);
+                }
+                None => out.push('\\'),
+            }
+        }
+
+        out
     }
 }
 
@@ -52,3 +184,92 @@ impl PdfPatterns {
         &PATTERN
     }
 }
+
+/// Extracts the contents of every literal string in PDF content.
+///
+/// Unlike [`PdfPatterns::text_string`], balanced parentheses inside a
+/// string are kept, and parentheses escaped with a backslash neither open
+/// nor close one. Contents are returned still escaped; see
+/// [`PdfEscapes::unescape`]. An unterminated final string is dropped.
+pub fn extract_pdf_strings(content: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in content.char_indices() {
+        if depth == 0 {
+            if c == '(' {
+                depth = 1;
+                start = i + 1;
+            }
+            continue;
+        }
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    strings.push(content[start..i].to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    strings
+}
+
+/// The text shown by one `TJ` operator: the strings of its array, joined.
+///
+/// Kerning between the strings of an array can split a value so that no
+/// single string contains it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TjRun {
+    /// The unescaped strings, concatenated
+    pub text: String,
+
+    /// Byte range of each string in `text`
+    pub pieces: Vec<Range<usize>>,
+}
+
+impl TjRun {
+    /// Returns the part of each string that bytes `start..end` of `text`
+    /// span, in order.
+    pub fn pieces_of(&self, start: usize, end: usize) -> Vec<&str> {
+        self.pieces
+            .iter()
+            .filter(|piece| piece.start < end && start < piece.end)
+            .map(|piece| &self.text[piece.start.max(start)..piece.end.min(end)])
+            .collect()
+    }
+}
+
+/// Extracts the run of every `TJ` operator in PDF content.
+///
+/// Hexadecimal strings are skipped, and an array is cut short at the first
+/// `]`, even one inside a string.
+pub fn extract_tj_runs(content: &str) -> Vec<TjRun> {
+    PdfPatterns::tj_array()
+        .captures_iter(content)
+        .map(|caps| {
+            let mut run = TjRun {
+                text: String::new(),
+                pieces: Vec::new(),
+            };
+            for string in extract_pdf_strings(&caps[1]) {
+                let start = run.text.len();
+                run.text.push_str(&PdfEscapes::unescape(&string));
+                if run.text.len() > start {
+                    run.pieces.push(start..run.text.len());
+                }
+            }
+            run
+        })
+        .collect()
+}
@@ -0,0 +1,125 @@
+//! Personal name domain logic.
+//!
+//! This module expands a person's full name into the renderings documents
+//! use for it, so that redacting a named individual also catches the
+//! surname alone, an initial, and the inverted form of indexes and legal
+//! captions.
+
+/// Personal name variant generator.
+///
+/// For `John Q. Public` generates:
+/// - John Q. Public (as given)
+/// - John Public (without middle names)
+/// - J. Public (first initial)
+/// - Public, John and Public, John Q. (surname first)
+/// - Public (surname alone)
+///
+/// Names are split on whitespace into a first name, any middle names, and
+/// a surname; a name given as `Public, John Q.` is read surname first. A
+/// single-word name has no other renderings.
+#[derive(Debug, Clone)]
+pub struct NameMatcher;
+
+impl NameMatcher {
+    /// Creates a new name matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Splits `name` into its first name, middle names and surname, or
+    /// returns `None` for a name of fewer than two words.
+    fn split(name: &str) -> Option<(&str, Vec<&str>, &str)> {
+        let (words, surname) = match name.split_once(',') {
+            Some((surname, given)) => (given.split_whitespace().collect(), surname.trim()),
+            None => {
+                let mut words: Vec<&str> = name.split_whitespace().collect();
+                let surname = words.pop()?;
+                (words, surname)
+            }
+        };
+        let (first, middle) = words.split_first()?;
+        if surname.is_empty() {
+            return None;
+        }
+        Some((first, middle.to_vec(), surname))
+    }
+
+    /// Returns every rendering of `name` to search for, starting with the
+    /// name in first-last order.
+    ///
+    /// Returns nothing for a blank name.
+    pub fn generate_variants(&self, name: &str) -> Vec<String> {
+        let Some((first, middle, surname)) = Self::split(name) else {
+            let name = name.trim();
+            return if name.is_empty() {
+                Vec::new()
+            } else {
+                vec![name.to_string()]
+            };
+        };
+
+        let mut variants = Vec::new();
+        let mut add = |variant: String| {
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        };
+
+        let given = std::iter::once(first)
+            .chain(middle.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        add(format!("{} {}", given, surname));
+        add(format!("{} {}", first, surname));
+        if let Some(initial) = first.chars().next().filter(|c| c.is_alphabetic()) {
+            add(format!("{}. {}", initial, surname));
+        }
+        add(format!("{}, {}", surname, first));
+        add(format!("{}, {}", surname, given));
+        add(surname.to_string());
+        variants
+    }
+}
+
+impl Default for NameMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_name_variants() {
+        let variants = NameMatcher::new().generate_variants("John Doe");
+        assert_eq!(variants, vec!["John Doe", "J. Doe", "Doe, John", "Doe"]);
+    }
+
+    #[test]
+    fn test_middle_names_and_inverted_input() {
+        let matcher = NameMatcher::new();
+        let variants = matcher.generate_variants("John Q. Public");
+        for expected in [
+            "John Q. Public",
+            "John Public",
+            "J. Public",
+            "Public, John",
+            "Public, John Q.",
+            "Public",
+        ] {
+            assert!(variants.contains(&expected.to_string()), "{}", expected);
+        }
+
+        // Surname-first input yields the same renderings
+        assert_eq!(matcher.generate_variants("Public, John Q."), variants);
+    }
+
+    #[test]
+    fn test_single_word_and_blank_names() {
+        let matcher = NameMatcher::new();
+        assert_eq!(matcher.generate_variants(" Cher "), vec!["Cher"]);
+        assert!(matcher.generate_variants("  ").is_empty());
+    }
+}
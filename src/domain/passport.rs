@@ -0,0 +1,191 @@
+//! Passport number domain logic.
+//!
+//! Passport numbers have no checksum and their formats overlap with other
+//! identifiers, so this module matches a country's format only when the
+//! caller names the country, and otherwise only numbers labelled as
+//! passport numbers.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Passport number pattern matcher.
+///
+/// Supports:
+/// - US: 123456789, A12345678 (9 digits, or a letter and 8 digits)
+/// - GB: 123456789 (9 digits)
+/// - CA: AB123456 (2 letters and 6 digits)
+/// - Any country: "Passport No: X1234567" (6-9 letters and digits after a
+///   passport keyword)
+#[derive(Debug, Clone)]
+pub struct PassportMatcher {
+    /// ISO 3166 alpha-2 code of the expected format, or `None` to require
+    /// a passport keyword
+    country: Option<&'static str>,
+}
+
+impl PassportMatcher {
+    /// Countries with a known passport number format.
+    pub const SUPPORTED_COUNTRIES: &'static [&'static str] = &["US", "GB", "CA"];
+
+    /// Creates a matcher for numbers labelled as passport numbers, in any
+    /// country's format.
+    pub fn new() -> Self {
+        Self { country: None }
+    }
+
+    /// Creates a matcher for one country's passport number format.
+    ///
+    /// `country` is an ISO 3166 alpha-2 code, case-insensitive, with `UK`
+    /// accepted for `GB`. Returns `None` for unsupported countries.
+    pub fn for_country(country: &str) -> Option<Self> {
+        let code = match country.to_ascii_uppercase().as_str() {
+            "UK" => "GB",
+            code => Self::SUPPORTED_COUNTRIES
+                .iter()
+                .copied()
+                .find(|supported| *supported == code)?,
+        };
+        Some(Self {
+            country: Some(code),
+        })
+    }
+
+    /// Returns the country whose format this matcher expects, if any.
+    pub fn country(&self) -> Option<&'static str> {
+        self.country
+    }
+
+    /// Passport numbers after a keyword, e.g. "Passport No.: X1234567".
+    ///
+    /// Capture group 1 is the number.
+    fn pattern_with_context() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?i:passport)(?i:\s*(?:number|num|no\.?|#))?\s*:?\s*\b([A-Z0-9]{6,9})\b")
+                .expect("Valid regex")
+        });
+        &PATTERN
+    }
+
+    /// A number on its own, as captured by
+    /// [`pattern_with_context`](Self::pattern_with_context).
+    fn pattern_number_only() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"^[A-Z0-9]{6,9}$").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_us() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b(?:[A-Z]\d{8}|\d{9})\b").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_gb() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{9}\b").expect("Valid regex"));
+        &PATTERN
+    }
+
+    fn pattern_ca() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b[A-Z]{2}\d{6}\b").expect("Valid regex"));
+        &PATTERN
+    }
+}
+
+impl Default for PassportMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for PassportMatcher {
+    fn pattern(&self) -> &Regex {
+        match self.country {
+            Some("US") => Self::pattern_us(),
+            Some("GB") => Self::pattern_gb(),
+            // CA, the only other supported country
+            Some(_) => Self::pattern_ca(),
+            None => Self::pattern_with_context(),
+        }
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        if self.country.is_some() {
+            return self.pattern().find_iter(text).map(|m| m.as_str()).collect();
+        }
+
+        // A keyword followed by a word such as "details" is not a number
+        Self::pattern_with_context()
+            .captures_iter(text)
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .filter(|number| number.bytes().any(|b| b.is_ascii_digit()))
+            .collect()
+    }
+
+    /// Returns the first passport number in `text`.
+    ///
+    /// Without a country, numbers are only extracted after a keyword, so an
+    /// already extracted number also normalizes on its own.
+    fn normalize(&self, text: &str) -> Option<String> {
+        match self.extract_all(text).first() {
+            Some(number) => Some(number.to_string()),
+            None if self.country.is_none()
+                && Self::pattern_number_only().is_match(text)
+                && text.bytes().any(|b| b.is_ascii_digit()) =>
+            {
+                Some(text.to_string())
+            }
+            None => None,
+        }
+    }
+
+    /// Passport numbers are printed as one unbroken string.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_us_formats() {
+        let matcher = PassportMatcher::for_country("us").unwrap();
+        let text = "Passport 123456789, renewed as A12345678; ZIP 94105";
+        assert_eq!(matcher.extract_all(text), vec!["123456789", "A12345678"]);
+    }
+
+    #[test]
+    fn test_uk_format() {
+        let matcher = PassportMatcher::for_country("UK").unwrap();
+        assert_eq!(matcher.country(), Some("GB"));
+        assert_eq!(
+            matcher.extract_all("Document 925076473 issued, ref A12345678"),
+            vec!["925076473"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_country() {
+        assert!(PassportMatcher::for_country("ZZ").is_none());
+    }
+
+    #[test]
+    fn test_generic_requires_keyword() {
+        let matcher = PassportMatcher::new();
+        let text = "Passport No.: X1234567\nPassport details on file\nRef X7654321";
+        assert_eq!(matcher.extract_all(text), vec!["X1234567"]);
+        assert_eq!(matcher.normalize(text), Some("X1234567".to_string()));
+    }
+
+    #[test]
+    fn test_generic_normalizes_extracted_number() {
+        let matcher = PassportMatcher::new();
+        assert_eq!(matcher.normalize("X1234567"), Some("X1234567".to_string()));
+        assert_eq!(matcher.normalize("DETAILS"), None);
+        assert_eq!(matcher.normalize("Ref X1234567"), None);
+    }
+}
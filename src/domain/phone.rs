@@ -1,7 +1,9 @@
 //! Phone number domain logic.
 //!
 //! This module encapsulates all business rules related to phone number
-//! detection, validation, and format generation.
+//! detection, validation, and format generation. North American numbers
+//! are the default; other numbering plans are chosen with a
+//! [`PhoneLocale`].
 
 use super::PatternMatcher;
 use once_cell::sync::Lazy;
@@ -14,13 +16,135 @@ use regex::Regex;
 /// - 555-123-4567
 /// - 555.123.4567
 /// - +1 555 123 4567
+///
+/// By default area and exchange codes must follow NANP rules; see
+/// [`permissive`](Self::permissive) for malformed legacy data. Local
+/// numbers without an area code are only matched when enabled with
+/// [`with_local_numbers`](Self::with_local_numbers). Numbers outside NANP
+/// are matched instead with [`with_locale`](Self::with_locale).
 #[derive(Debug, Clone)]
-pub struct PhoneNumberMatcher;
+pub struct PhoneNumberMatcher {
+    /// Whether area and exchange codes must follow NANP rules
+    strict: bool,
+
+    /// Whether 7-digit numbers without an area code also match
+    local_numbers: bool,
+
+    /// Numbering plan matched
+    locale: PhoneLocale,
+}
+
+/// Numbering plan a [`PhoneNumberMatcher`] matches.
+///
+/// Outside NANP, numbers normalize to E.164 form: `+`, the country code
+/// and the national number without its trunk `0`, e.g. `+442079460958`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhoneLocale {
+    /// North American Numbering Plan (US, Canada, Caribbean)
+    #[default]
+    Nanp,
+
+    /// Any number written in international form, `+` and a country code,
+    /// of 8 to 15 digits
+    International,
+
+    /// United Kingdom: 10-digit national numbers such as 020 7946 0958,
+    /// or with `+44`
+    Uk,
+
+    /// Germany: national numbers of 6 to 11 digits after the trunk `0`,
+    /// such as 030 1234567, or with `+49`
+    De,
+}
+
+impl PhoneLocale {
+    /// Returns the locale's lowercase name: `nanp`, `intl`, `uk` or `de`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Nanp => "nanp",
+            Self::International => "intl",
+            Self::Uk => "uk",
+            Self::De => "de",
+        }
+    }
+
+    /// Returns the locale called `name`, as returned by
+    /// [`name`](Self::name), ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Nanp, Self::International, Self::Uk, Self::De]
+            .into_iter()
+            .find(|locale| locale.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the country calling code of a national locale.
+    fn country_code(self) -> Option<&'static str> {
+        match self {
+            Self::Uk => Some("44"),
+            Self::De => Some("49"),
+            Self::Nanp | Self::International => None,
+        }
+    }
+
+    /// Returns true if `national`, the digits after the country code and
+    /// trunk `0`, form a valid number in this locale.
+    fn validate_national(self, national: &str) -> bool {
+        let first = national.bytes().next().unwrap_or(b'0');
+        match self {
+            // Geographic (1, 2), non-geographic (3, 5, 8) and mobile (7)
+            Self::Uk => national.len() == 10 && b"123578".contains(&first),
+            Self::De => (6..=11).contains(&national.len()) && first != b'0',
+            Self::Nanp | Self::International => false,
+        }
+    }
+}
+
+/// A 7-digit local number, e.g. 234-5678 (capture groups: exchange,
+/// subscriber).
+///
+/// A separator is required so that arbitrary 7-digit runs don't match.
+const LOCAL_NUMBER: &str = r"\b(\d{3})[-.](\d{4})\b";
 
 impl PhoneNumberMatcher {
     /// Creates a new phone number matcher.
     pub fn new() -> Self {
-        Self
+        Self {
+            strict: true,
+            local_numbers: false,
+            locale: PhoneLocale::Nanp,
+        }
+    }
+
+    /// Creates a matcher that accepts any 10-digit number, including area
+    /// and exchange codes starting with 0 or 1 that NANP never assigns.
+    ///
+    /// Useful for legacy data where malformed numbers still need redacting.
+    pub fn permissive() -> Self {
+        Self {
+            strict: false,
+            local_numbers: false,
+            locale: PhoneLocale::Nanp,
+        }
+    }
+
+    /// Sets the numbering plan to match, NANP by default.
+    ///
+    /// Each locale has its own validation and renderings; the NANP options
+    /// ([`permissive`](Self::permissive) and
+    /// [`with_local_numbers`](Self::with_local_numbers)) only apply to
+    /// [`PhoneLocale::Nanp`].
+    pub fn with_locale(mut self, locale: PhoneLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Sets whether 7-digit local numbers such as 234-5678, written without
+    /// an area code, also match. Disabled by default.
+    ///
+    /// Local numbers normalize to their 7 digits. When strict, the
+    /// exchange code must follow NANP rules as in a full number.
+    pub fn with_local_numbers(mut self, local: bool) -> Self {
+        self.local_numbers = local;
+        self
     }
 
     /// Returns the regex pattern for NANP phone numbers.
@@ -34,6 +158,175 @@ impl PhoneNumberMatcher {
         &PATTERN
     }
 
+    /// Returns the regex pattern for any 10-digit phone number.
+    fn permissive_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"(?:\+?\s*1[-.\s]?)?\(?\s*(\d{3})\s*\)?[-.\s]?\s*(\d{3})[-.\s]?\s*(\d{4})\b",
+            )
+            .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns [`regex`](Self::regex) extended to match local numbers.
+    fn local_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(&format!(
+                "{}|{}",
+                PhoneNumberMatcher::regex().as_str(),
+                LOCAL_NUMBER
+            ))
+            .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns [`permissive_regex`](Self::permissive_regex) extended to
+    /// match local numbers.
+    fn permissive_local_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(&format!(
+                "{}|{}",
+                PhoneNumberMatcher::permissive_regex().as_str(),
+                LOCAL_NUMBER
+            ))
+            .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Matches a number with `+` and a country code: the code, then up to
+    /// six groups of digits, where the national number may be preceded by
+    /// a trunk `(0)`.
+    fn international_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"\+[1-9]\d{0,3}(?:[\s.-]?(?:\(0\)[\s.-]?)?\(?\d{1,5}\)?){1,6}")
+                .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Matches a UK number, with `+44` or a trunk `0`, capturing the
+    /// national number as `national`.
+    fn uk_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"(?:\+44[\s-]?(?:\(0\)[\s-]?)?|\(0|\b0)",
+                r"(?P<national>[1-9]\d{1,4}\)?[\s-]?\d{3,4}[\s-]?\d{3,4})\b",
+            ))
+            .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Matches a German number, with `+49` or a trunk `0`, capturing the
+    /// national number as `national`. Area codes may be followed by `/`.
+    fn de_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"(?:\+49[\s-]?(?:\(0\)[\s-]?)?|\(0|\b0)",
+                r"(?P<national>[1-9]\d{1,4}\)?(?:[\s/-]?\d{2,8}){1,3})\b",
+            ))
+            .expect("Valid phone number regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns `text`'s first number in E.164 form if it is valid in a
+    /// locale other than NANP.
+    fn normalize_e164(&self, text: &str) -> Option<String> {
+        let digits = |s: &str| -> String {
+            s.replace("(0)", "")
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect()
+        };
+        match self.locale.country_code() {
+            Some(code) => {
+                let caps = self.pattern().captures(text)?;
+                let national = digits(caps.name("national")?.as_str());
+                self.locale
+                    .validate_national(&national)
+                    .then(|| format!("+{}{}", code, national))
+            }
+            None => {
+                let number = digits(self.pattern().find(text)?.as_str());
+                (8..=15)
+                    .contains(&number.len())
+                    .then(|| format!("+{}", number))
+            }
+        }
+    }
+
+    /// Returns true if `text` is a number in E.164 form that is valid in a
+    /// locale other than NANP.
+    fn is_e164(&self, text: &str) -> bool {
+        let Some(digits) = text.strip_prefix('+') else {
+            return false;
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        match self.locale.country_code() {
+            Some(code) => digits
+                .strip_prefix(code)
+                .is_some_and(|national| self.locale.validate_national(national)),
+            None => (8..=15).contains(&digits.len()) && !digits.starts_with('0'),
+        }
+    }
+
+    /// Returns the renderings of a number in E.164 form.
+    ///
+    /// National numbers are grouped as their locale writes them; as the
+    /// length of an area code can't be told from the digits, each common
+    /// length is tried. International numbers are only written compactly.
+    fn e164_variants(&self, normalized: &str) -> Vec<String> {
+        let mut variants = vec![normalized.to_string()];
+        let Some((code, national)) = self
+            .locale
+            .country_code()
+            .and_then(|code| Some((code, normalized.strip_prefix('+')?.strip_prefix(code)?)))
+        else {
+            variants.push(format!("00{}", &normalized[1..]));
+            return variants;
+        };
+
+        variants.push(format!("0{}", national));
+        let groupings: &[&[usize]] = match self.locale {
+            // 020 7946 0958, 0113 496 0000, 07700 900123
+            PhoneLocale::Uk if national.starts_with('2') => &[&[2, 4, 4]],
+            PhoneLocale::Uk => &[&[3, 3, 4], &[4, 6]],
+            // 030 1234567, 0221 123456, 0151 23456789, 03342 12345
+            _ => &[&[2], &[3], &[4]],
+        };
+        for grouping in groupings {
+            let mut groups = Vec::new();
+            let mut rest = national;
+            for &len in grouping.iter() {
+                if rest.len() <= len {
+                    break;
+                }
+                let (group, tail) = rest.split_at(len);
+                groups.push(group);
+                rest = tail;
+            }
+            groups.push(rest);
+            let (area, subscriber) = (groups[0], groups[1..].join(" "));
+
+            variants.push(format!("0{} {}", area, subscriber));
+            variants.push(format!("(0{}) {}", area, subscriber));
+            variants.push(format!("+{} {} {}", code, area, subscriber));
+            variants.push(format!("+{} (0){} {}", code, area, subscriber));
+            if self.locale == PhoneLocale::De {
+                variants.push(format!("0{}/{}", area, subscriber));
+            } else {
+                variants.push(format!("0{}-{}", area, subscriber.replace(' ', "-")));
+            }
+        }
+        variants
+    }
+
     /// Validates that a phone number follows NANP rules.
     ///
     /// # Rules
@@ -48,6 +341,33 @@ impl PhoneNumberMatcher {
                 .chars()
                 .next()
                 .is_some_and(|c| ('2'..='9').contains(&c))
+            && Self::validate_local(exchange, subscriber)
+    }
+
+    /// Returns true if `text` is a number as [`normalize`] returns it: 10
+    /// bare digits, or 7 when local numbers are enabled, that pass the
+    /// matcher's validation.
+    ///
+    /// [`normalize`]: PatternMatcher::normalize
+    pub fn is_normalized(&self, text: &str) -> bool {
+        if self.locale != PhoneLocale::Nanp {
+            return self.is_e164(text);
+        }
+        if !text.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        match text.len() {
+            10 => !self.strict || Self::validate(&text[..3], &text[3..6], &text[6..]),
+            7 if self.local_numbers => !self.strict || Self::validate_local(&text[..3], &text[3..]),
+            _ => false,
+        }
+    }
+
+    /// Validates that a local number (without area code) follows NANP
+    /// rules: an exchange code starting 2-9 and a 4-digit subscriber number.
+    pub fn validate_local(exchange: &str, subscriber: &str) -> bool {
+        exchange.len() == 3
+            && subscriber.len() == 4
             && exchange
                 .chars()
                 .next()
@@ -63,21 +383,56 @@ impl Default for PhoneNumberMatcher {
 
 impl PatternMatcher for PhoneNumberMatcher {
     fn pattern(&self) -> &Regex {
-        Self::regex()
+        match self.locale {
+            PhoneLocale::Nanp => {}
+            PhoneLocale::International => return Self::international_regex(),
+            PhoneLocale::Uk => return Self::uk_regex(),
+            PhoneLocale::De => return Self::de_regex(),
+        }
+        match (self.strict, self.local_numbers) {
+            (true, false) => Self::regex(),
+            (false, false) => Self::permissive_regex(),
+            (true, true) => Self::local_regex(),
+            (false, true) => Self::permissive_local_regex(),
+        }
     }
 
     fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
-        self.pattern().find_iter(text).map(|m| m.as_str()).collect()
+        let matches = self.pattern().find_iter(text).map(|m| m.as_str());
+        if self.locale == PhoneLocale::Nanp {
+            return matches.collect();
+        }
+        // Other plans' patterns are loose about length, so only numbers
+        // that validate are returned
+        matches
+            .filter(|m| self.normalize_e164(m).is_some())
+            .collect()
     }
 
     fn normalize(&self, text: &str) -> Option<String> {
+        // Already normalized; local numbers need a separator to match the
+        // pattern, so their bare digits would not normalize again
+        if PhoneNumberMatcher::is_normalized(self, text) {
+            return Some(text.to_string());
+        }
+        if self.locale != PhoneLocale::Nanp {
+            return self.normalize_e164(text);
+        }
+
         // Find first match in text (not just from start)
         self.pattern().captures(text).and_then(|caps| {
-            let area_str = caps.get(1)?.as_str();
+            let Some(area) = caps.get(1) else {
+                // Groups 4 and 5 only exist in local-number patterns
+                let exchange_str = caps.get(4)?.as_str();
+                let subscriber_str = caps.get(5)?.as_str();
+                return (!self.strict || Self::validate_local(exchange_str, subscriber_str))
+                    .then(|| format!("{}{}", exchange_str, subscriber_str));
+            };
+            let area_str = area.as_str();
             let exchange_str = caps.get(2)?.as_str();
             let subscriber_str = caps.get(3)?.as_str();
 
-            if Self::validate(area_str, exchange_str, subscriber_str) {
+            if !self.strict || Self::validate(area_str, exchange_str, subscriber_str) {
                 Some(format!("{}{}{}", area_str, exchange_str, subscriber_str))
             } else {
                 None
@@ -85,7 +440,21 @@ impl PatternMatcher for PhoneNumberMatcher {
         })
     }
 
+    fn is_normalized(&self, text: &str) -> bool {
+        PhoneNumberMatcher::is_normalized(self, text)
+    }
+
     fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        if normalized.starts_with('+') {
+            return self.e164_variants(normalized);
+        }
+        if normalized.len() == 7 {
+            let (exchange, subscriber) = normalized.split_at(3);
+            return vec![
+                format!("{}-{}", exchange, subscriber), // 123-4567
+                format!("{}.{}", exchange, subscriber), // 123.4567
+            ];
+        }
         if normalized.len() != 10 {
             return vec![normalized.to_string()];
         }
@@ -135,6 +504,185 @@ mod tests {
         assert!(variants.contains(&"(555) 234-5678".to_string()));
     }
 
+    #[test]
+    fn test_find_with_positions() {
+        let matcher = PhoneNumberMatcher::new();
+        let text = "Home: 555-234-5678 Mobile: 555-345-6789";
+        let mobile: Vec<_> = matcher
+            .find_with_positions(text)
+            .into_iter()
+            .filter(|(start, _, _)| text[..*start].trim_end().ends_with("Mobile:"))
+            .collect();
+        assert_eq!(mobile.len(), 1);
+
+        // The pattern may take leading whitespace, so compare trimmed
+        let (start, end, number) = mobile[0];
+        assert_eq!(&text[start..end], number);
+        assert_eq!(number.trim(), "555-345-6789");
+    }
+
+    #[test]
+    fn test_permissive_accepts_unassigned_codes() {
+        let text = "Legacy contact: (155) 234-5678";
+
+        let strict = PhoneNumberMatcher::new();
+        assert!(strict.extract_all(text).is_empty());
+        assert_eq!(strict.normalize(text), None);
+
+        let permissive = PhoneNumberMatcher::permissive();
+        assert_eq!(permissive.extract_all(text).len(), 1);
+        assert_eq!(permissive.normalize(text), Some("1552345678".to_string()));
+        assert_eq!(
+            permissive.normalize("055-134-5678"),
+            Some("0551345678".to_string())
+        );
+    }
+
+    #[test]
+    fn test_local_numbers_require_opt_in() {
+        let text = "Front desk: 555-1234";
+
+        let matcher = PhoneNumberMatcher::new();
+        assert!(matcher.extract_all(text).is_empty());
+        assert_eq!(matcher.normalize(text), None);
+
+        let local = PhoneNumberMatcher::new().with_local_numbers(true);
+        assert_eq!(local.extract_all(text), vec!["555-1234"]);
+        assert_eq!(local.normalize(text), Some("5551234".to_string()));
+        assert_eq!(
+            local.generate_variants("5551234"),
+            vec!["555-1234".to_string(), "555.1234".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_local_numbers_keep_full_matches() {
+        let local = PhoneNumberMatcher::new().with_local_numbers(true);
+        let text = "Call (555) 234-5678 or 345-6789, not order 1234567";
+        assert_eq!(
+            local
+                .pattern()
+                .find_iter(text)
+                .map(|m| m.as_str())
+                .collect::<Vec<_>>(),
+            local.extract_all(text)
+        );
+        assert_eq!(local.extract_all(text), vec!["(555) 234-5678", "345-6789"]);
+        assert_eq!(local.normalize(text), Some("5552345678".to_string()));
+
+        // Exchange codes still follow NANP rules unless permissive
+        assert_eq!(local.normalize("ext 155-1234"), None);
+        let permissive = PhoneNumberMatcher::permissive().with_local_numbers(true);
+        assert_eq!(
+            permissive.normalize("ext 155-1234"),
+            Some("1551234".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        let cases = [
+            (PhoneNumberMatcher::new(), "(555) 234-5678", "5552345678"),
+            (PhoneNumberMatcher::new(), "+1 555 234 5678", "5552345678"),
+            (
+                PhoneNumberMatcher::permissive(),
+                "(155) 234-5678",
+                "1552345678",
+            ),
+            (
+                PhoneNumberMatcher::new().with_local_numbers(true),
+                "Front desk: 555-1234",
+                "5551234",
+            ),
+        ];
+        for (matcher, text, expected) in cases {
+            let normalized = matcher.normalize(text);
+            assert_eq!(normalized.as_deref(), Some(expected), "{}", text);
+            assert_eq!(matcher.normalize(expected).as_deref(), Some(expected));
+            assert!(matcher.is_normalized(expected), "{}", expected);
+            assert!(!matcher.is_normalized(text), "{}", text);
+        }
+
+        // Bare digits still follow the matcher's rules
+        assert!(!PhoneNumberMatcher::new().is_normalized("1552345678"));
+        assert!(!PhoneNumberMatcher::new().is_normalized("5551234"));
+    }
+
+    #[test]
+    fn test_uk_locale() {
+        let text = "London office: +44 20 7946 0958, mobile 07700 900123";
+
+        // NANP rejects both numbers
+        assert!(PhoneNumberMatcher::new().extract_all(text).is_empty());
+
+        let uk = PhoneNumberMatcher::new().with_locale(PhoneLocale::Uk);
+        assert_eq!(
+            uk.extract_all(text),
+            vec!["+44 20 7946 0958", "07700 900123"]
+        );
+        assert_eq!(uk.normalize(text), Some("+442079460958".to_string()));
+        assert_eq!(
+            uk.normalize("(020) 7946 0958"),
+            Some("+442079460958".to_string())
+        );
+        assert_eq!(
+            uk.normalize("+44 (0)20 7946 0958"),
+            Some("+442079460958".to_string())
+        );
+        assert!(uk.is_normalized("+442079460958"));
+        // Too short, and an unused leading digit
+        assert_eq!(uk.normalize("020 7946 095"), None);
+        assert_eq!(uk.normalize("0400 123 4567"), None);
+
+        let variants = uk.generate_variants("+442079460958");
+        for expected in ["020 7946 0958", "+44 20 7946 0958", "(020) 7946 0958"] {
+            assert!(variants.contains(&expected.to_string()), "{}", expected);
+        }
+        let variants = uk.generate_variants("+447700900123");
+        assert!(variants.contains(&"07700 900123".to_string()));
+    }
+
+    #[test]
+    fn test_de_locale() {
+        let de = PhoneNumberMatcher::new().with_locale(PhoneLocale::De);
+        assert_eq!(
+            de.normalize("Tel. 030/1234567"),
+            Some("+49301234567".to_string())
+        );
+        assert_eq!(
+            de.normalize("+49 151 23456789"),
+            Some("+4915123456789".to_string())
+        );
+        assert_eq!(de.normalize("030 12"), None);
+
+        let variants = de.generate_variants("+49301234567");
+        assert!(variants.contains(&"030 1234567".to_string()));
+        assert!(variants.contains(&"030/1234567".to_string()));
+        assert!(variants.contains(&"+49 30 1234567".to_string()));
+    }
+
+    #[test]
+    fn test_international_locale() {
+        let intl = PhoneNumberMatcher::new().with_locale(PhoneLocale::International);
+        let text = "Paris +33 1 23 45 67 89, Tokyo +81-3-1234-5678, ref +12";
+        assert_eq!(
+            intl.extract_all(text),
+            vec!["+33 1 23 45 67 89", "+81-3-1234-5678"]
+        );
+        assert_eq!(intl.normalize(text), Some("+33123456789".to_string()));
+        assert_eq!(
+            intl.generate_variants("+33123456789"),
+            vec!["+33123456789".to_string(), "0033123456789".to_string()]
+        );
+
+        assert_eq!(PhoneLocale::from_name("UK"), Some(PhoneLocale::Uk));
+        assert_eq!(
+            PhoneLocale::from_name(PhoneLocale::International.name()),
+            Some(PhoneLocale::International)
+        );
+        assert_eq!(PhoneLocale::from_name("fr"), None);
+    }
+
     #[test]
     fn test_invalid_area_code() {
         // Area code cannot start with 0 or 1
@@ -0,0 +1,157 @@
+//! Postal code domain logic.
+//!
+//! This module detects US ZIP and ZIP+4 codes in mailing addresses. Bare
+//! five-digit numbers are everywhere in bills (invoice numbers, amounts,
+//! account fragments), so a code only matches where an address or a label
+//! puts it in context.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// US postal code pattern matcher.
+///
+/// Supports:
+/// - ZIP: Springfield, IL 62704
+/// - ZIP+4: Springfield, IL 62704-1234
+/// - Labelled codes: ZIP: 62704, Zip Code 62704, Postal code 62704
+///
+/// A code must directly follow a ", ST" state or territory abbreviation, as
+/// in "City, ST", or a ZIP or postal code label, on the same line. The comma
+/// keeps labels such as "Customer ID 12345" (ID being Idaho) from matching.
+#[derive(Debug, Clone)]
+pub struct PostalCodeMatcher;
+
+impl PostalCodeMatcher {
+    /// Creates a new postal code matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for ZIP and ZIP+4 codes.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b\d{5}(?:-\d{4})?\b").expect("Valid ZIP code regex"));
+        &PATTERN
+    }
+
+    /// Matches the end of text that puts a postal code in context: a comma
+    /// and state abbreviation, or a ZIP or postal code label.
+    fn context_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                r"(?:,\s*(?:A[AEKLPRSZ]|C[AOT]|D[CE]|FL|G[AU]|HI|I[ADLN]|K[SY]|LA|M[ADEINOPST]",
+                r"|N[CDEHJMVY]|O[HKR]|P[AR]|RI|S[CD]|T[NX]|UT|V[AIT]|W[AIVY])\.?,?",
+                r"|(?i:\b(?:zip(?:\s*code)?|postal\s*code)\b[\s:#.-]*))",
+                r"\s*$",
+            ))
+            .expect("Valid ZIP context regex")
+        });
+        &PATTERN
+    }
+
+    /// Returns true if the line before byte offset `start` of `text` puts a
+    /// postal code there in context.
+    fn has_context(text: &str, start: usize) -> bool {
+        let before = &text[..start];
+        let line = before.rfind('\n').map_or(before, |idx| &before[idx + 1..]);
+        Self::context_regex().is_match(line)
+    }
+}
+
+impl Default for PostalCodeMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for PostalCodeMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.find_with_positions(text)
+            .into_iter()
+            .map(|(_, _, code)| code)
+            .collect()
+    }
+
+    /// Returns the first ZIP or ZIP+4 code in `text`, as written.
+    ///
+    /// Context is checked by [`find_with_positions`](Self::find_with_positions),
+    /// so an already extracted code normalizes on its own.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.pattern().find(text).map(|m| m.as_str().to_string())
+    }
+
+    /// Codes are searched exactly as written.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+
+    /// Returns each code in `text` that follows a state abbreviation or a
+    /// ZIP label.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        self.pattern()
+            .find_iter(text)
+            .filter(|m| Self::has_context(text, m.start()))
+            .map(|m| (m.start(), m.end(), m.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_zip_codes() {
+        let matcher = PostalCodeMatcher::new();
+        let text = "Jane Roe\n123 Main St\nSpringfield, IL 62704\nDallas, TX. 75201-4321";
+        assert_eq!(matcher.extract_all(text), vec!["62704", "75201-4321"]);
+    }
+
+    #[test]
+    fn test_labelled_zip_codes() {
+        let matcher = PostalCodeMatcher::new();
+        let text = "ZIP: 10001\nzip code 94105\nPostal Code #02139";
+        assert_eq!(matcher.extract_all(text), vec!["10001", "94105", "02139"]);
+    }
+
+    #[test]
+    fn test_account_fragment_is_not_a_zip() {
+        let matcher = PostalCodeMatcher::new();
+        let text = "Account: 12345-00001\nInvoice 48213\nTotal 12345\n\
+                    Seattle, WA 98101";
+        assert_eq!(matcher.extract_all(text), vec!["98101"]);
+
+        let positions = matcher.find_with_positions(text);
+        assert_eq!(positions.len(), 1);
+        let (start, end, code) = positions[0];
+        assert_eq!(&text[start..end], code);
+    }
+
+    #[test]
+    fn test_context_must_be_adjacent() {
+        let matcher = PostalCodeMatcher::new();
+        // The state ends the previous line, or is not after a comma
+        assert!(matcher.extract_all("Seattle, WA\n98101").is_empty());
+        assert!(matcher.extract_all("Customer ID 12345").is_empty());
+        assert!(matcher.extract_all("Order, CAB 12345").is_empty());
+        assert!(matcher.extract_all("ZIP was updated, ref 12345").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_and_variants() {
+        let matcher = PostalCodeMatcher::new();
+        assert_eq!(
+            matcher.normalize("62704-1234"),
+            Some("62704-1234".to_string())
+        );
+        assert_eq!(
+            matcher.generate_variants("62704"),
+            vec!["62704".to_string()]
+        );
+    }
+}
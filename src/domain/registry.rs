@@ -0,0 +1,200 @@
+//! Catalog of the built-in matchers.
+//!
+//! This module describes each built-in matcher so callers (such as the
+//! `list-matchers` CLI subcommand) can show what can be detected without
+//! reading source. New matchers are added to [`BUILTIN`].
+
+use super::{
+    CreditCardMatcher, DateMatcher, DriversLicenseMatcher, EmailMatcher, HandleMatcher,
+    IbanMatcher, MacAddressMatcher, PassportMatcher, PatternMatcher, PhoneNumberMatcher,
+    PostalCodeMatcher, RoutingNumberMatcher, SsnMatcher, TimestampMatcher, VerizonAccountMatcher,
+    VerizonCallDetailsMatcher, VinMatcher,
+};
+
+/// Description of a built-in matcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatcherInfo {
+    /// Matcher type name, e.g. `PhoneNumberMatcher`
+    pub name: &'static str,
+
+    /// Name of the `RedactionTarget` variant that uses the matcher
+    pub target: &'static str,
+
+    /// What the matcher detects
+    pub description: &'static str,
+
+    /// Text the matcher detects
+    pub example: &'static str,
+
+    /// The matcher's regex pattern
+    pub pattern: String,
+}
+
+/// A built-in matcher and its description, minus the pattern.
+struct Builtin {
+    matcher: fn() -> Box<dyn PatternMatcher>,
+    name: &'static str,
+    target: &'static str,
+    description: &'static str,
+    example: &'static str,
+}
+
+/// Built-in matchers, in `RedactionTarget` order.
+const BUILTIN: &[Builtin] = &[
+    Builtin {
+        matcher: || Box::new(PhoneNumberMatcher::new()),
+        name: "PhoneNumberMatcher",
+        target: "PhoneNumbers",
+        description: "North American (NANP) phone numbers",
+        example: "(555) 234-5678",
+    },
+    Builtin {
+        matcher: || Box::new(VerizonAccountMatcher::new()),
+        name: "VerizonAccountMatcher",
+        target: "VerizonAccount",
+        description: "Verizon account numbers in 9-5 format",
+        example: "123456789-00001",
+    },
+    Builtin {
+        matcher: || Box::new(VerizonCallDetailsMatcher::new()),
+        name: "VerizonCallDetailsMatcher",
+        target: "VerizonCallDetails",
+        description: "Times and locations in Verizon call detail tables",
+        example: "10:26 PM",
+    },
+    Builtin {
+        matcher: || Box::new(TimestampMatcher::new()),
+        name: "TimestampMatcher",
+        target: "Timestamp",
+        description: "Log timestamps (ISO-8601 and syslog)",
+        example: "2025-01-15T14:30:00Z",
+    },
+    Builtin {
+        matcher: || Box::new(DateMatcher::new()),
+        name: "DateMatcher",
+        target: "Dates",
+        description: "Valid calendar dates in ISO, US or textual form",
+        example: "January 6, 2025",
+    },
+    Builtin {
+        matcher: || Box::new(PostalCodeMatcher::new()),
+        name: "PostalCodeMatcher",
+        target: "PostalCode",
+        description: "US ZIP and ZIP+4 codes after a state or ZIP label",
+        example: "Springfield, IL 62704",
+    },
+    Builtin {
+        matcher: || Box::new(SsnMatcher::new()),
+        name: "SsnMatcher",
+        target: "Ssn",
+        description: "U.S. Social Security Numbers",
+        example: "123-45-6789",
+    },
+    Builtin {
+        matcher: || Box::new(EmailMatcher::new()),
+        name: "EmailMatcher",
+        target: "Email",
+        description: "Email addresses",
+        example: "jane.doe@example.com",
+    },
+    Builtin {
+        matcher: || Box::new(CreditCardMatcher::new()),
+        name: "CreditCardMatcher",
+        target: "CreditCard",
+        description: "Payment card numbers that pass the Luhn checksum",
+        example: "4111 1111 1111 1111",
+    },
+    Builtin {
+        matcher: || Box::new(IbanMatcher::new()),
+        name: "IbanMatcher",
+        target: "Iban",
+        description: "IBANs that pass the mod-97 checksum",
+        example: "GB82 WEST 1234 5698 7654 32",
+    },
+    Builtin {
+        matcher: || Box::new(VinMatcher::new()),
+        name: "VinMatcher",
+        target: "Vin",
+        description: "Vehicle Identification Numbers with a valid check digit",
+        example: "1HGCM82633A004352",
+    },
+    Builtin {
+        matcher: || Box::new(MacAddressMatcher::new()),
+        name: "MacAddressMatcher",
+        target: "MacAddress",
+        description: "MAC addresses in colon, hyphen or dot notation",
+        example: "00:1A:2B:3C:4D:5E",
+    },
+    Builtin {
+        matcher: || Box::new(HandleMatcher::new()),
+        name: "HandleMatcher",
+        target: "Handle",
+        description: "@mentions and usernames after a user or login label",
+        example: "@alice",
+    },
+    Builtin {
+        matcher: || Box::new(PassportMatcher::new()),
+        name: "PassportMatcher",
+        target: "Passport",
+        description: "Passport numbers, labelled or in a given country's format",
+        example: "Passport No.: X1234567",
+    },
+    Builtin {
+        matcher: || Box::new(DriversLicenseMatcher::new()),
+        name: "DriversLicenseMatcher",
+        target: "DriversLicense",
+        description: "US driver's license numbers, labelled or in a given state's format",
+        example: "DL No: D1234567",
+    },
+    Builtin {
+        matcher: || Box::new(RoutingNumberMatcher::new()),
+        name: "RoutingNumberMatcher",
+        target: "RoutingNumber",
+        description: "ABA routing numbers that pass the checksum, after a routing label",
+        example: "Routing No: 021000021",
+    },
+];
+
+/// Returns a description of every built-in matcher, in `RedactionTarget`
+/// order.
+pub fn registered_matchers() -> Vec<MatcherInfo> {
+    BUILTIN
+        .iter()
+        .map(|builtin| MatcherInfo {
+            name: builtin.name,
+            target: builtin.target,
+            description: builtin.description,
+            example: builtin.example,
+            pattern: (builtin.matcher)().pattern().as_str().to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_are_detected() {
+        for builtin in BUILTIN {
+            let matcher = (builtin.matcher)();
+            assert!(
+                !matcher.extract_all(builtin.example).is_empty(),
+                "{} does not detect its example '{}'",
+                builtin.name,
+                builtin.example
+            );
+        }
+    }
+
+    #[test]
+    fn test_registered_matchers() {
+        let matchers = registered_matchers();
+        assert_eq!(matchers.len(), BUILTIN.len());
+
+        let phone = &matchers[0];
+        assert_eq!(phone.name, "PhoneNumberMatcher");
+        assert_eq!(phone.target, "PhoneNumbers");
+        assert_eq!(phone.pattern, PhoneNumberMatcher::new().pattern().as_str());
+    }
+}
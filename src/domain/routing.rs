@@ -0,0 +1,167 @@
+//! ABA routing number domain logic.
+//!
+//! This module detects the 9-digit routing transit numbers that identify US
+//! banks on checks and statements. Any 9-digit number passes the checksum
+//! one time in ten, so a number only matches after a routing label on the
+//! same line, or the transit symbol of a check's MICR line.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// ABA routing number pattern matcher.
+///
+/// Supports:
+/// - Labelled numbers: Routing Number: 021000021, ABA 011000015, RTN 021000021
+/// - MICR lines: ⑆021000021⑆
+///
+/// A number must pass the ABA checksum and start with a Federal Reserve
+/// routing symbol (00-12, 21-32, 61-72 or 80).
+#[derive(Debug, Clone)]
+pub struct RoutingNumberMatcher;
+
+/// Weight of each digit in the checksum.
+const WEIGHTS: [u32; 9] = [3, 7, 1, 3, 7, 1, 3, 7, 1];
+
+/// MICR transit symbol printed around routing numbers on checks.
+const TRANSIT_SYMBOL: char = '\u{2446}';
+
+impl RoutingNumberMatcher {
+    /// Creates a new routing number matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for routing numbers.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b\d{9}\b").expect("Valid routing number regex"));
+        &PATTERN
+    }
+
+    /// Matches a label that puts a routing number in context.
+    fn context_regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?i)\b(?:routing|aba|rtn|r/t|transit)\b")
+                .expect("Valid routing context regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates a routing number: nine digits whose weighted sum (weights
+    /// 3, 7, 1 repeating) is a multiple of 10, with a valid prefix.
+    pub fn validate(number: &str) -> bool {
+        let bytes = number.as_bytes();
+        if bytes.len() != 9 || !bytes.iter().all(u8::is_ascii_digit) {
+            return false;
+        }
+
+        let prefix = u32::from(bytes[0] - b'0') * 10 + u32::from(bytes[1] - b'0');
+        if !matches!(prefix, 0..=12 | 21..=32 | 61..=72 | 80) {
+            return false;
+        }
+
+        let sum: u32 = bytes
+            .iter()
+            .zip(WEIGHTS)
+            .map(|(&b, weight)| u32::from(b - b'0') * weight)
+            .sum();
+        sum % 10 == 0
+    }
+
+    /// Returns true if the line before byte offset `start` of `text` puts a
+    /// routing number there in context.
+    fn has_context(text: &str, start: usize) -> bool {
+        let before = &text[..start];
+        if before.ends_with(TRANSIT_SYMBOL) {
+            return true;
+        }
+        let line = before.rfind('\n').map_or(before, |idx| &before[idx + 1..]);
+        Self::context_regex().is_match(line)
+    }
+}
+
+impl Default for RoutingNumberMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for RoutingNumberMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.find_with_positions(text)
+            .into_iter()
+            .map(|(_, _, number)| number)
+            .collect()
+    }
+
+    /// Returns the first valid routing number in `text`.
+    ///
+    /// Context is checked by [`find_with_positions`](Self::find_with_positions),
+    /// so an already extracted number normalizes on its own.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .find(|number| Self::validate(number))
+            .map(|number| number.to_string())
+    }
+
+    /// Routing numbers are searched exactly as written.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+
+    /// Returns each valid routing number in `text` that follows a routing
+    /// label on its line or a MICR transit symbol.
+    fn find_with_positions<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
+        self.pattern()
+            .find_iter(text)
+            .filter(|m| Self::validate(m.as_str()) && Self::has_context(text, m.start()))
+            .map(|m| (m.start(), m.end(), m.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(RoutingNumberMatcher::validate("021000021"));
+        assert!(RoutingNumberMatcher::validate("011000015"));
+
+        // Wrong checksum, reserved prefix, too short
+        assert!(!RoutingNumberMatcher::validate("021000022"));
+        assert!(!RoutingNumberMatcher::validate("500000005"));
+        assert!(!RoutingNumberMatcher::validate("02100002"));
+    }
+
+    #[test]
+    fn test_labelled_numbers() {
+        let matcher = RoutingNumberMatcher::new();
+        let text = "Routing Number: 021000021\nABA# 011000015\n\
+                    Routing: 021000022\nMICR \u{2446}021000021\u{2446} 123456789";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["021000021", "011000015", "021000021"]
+        );
+        assert_eq!(
+            matcher.normalize("x 021000021"),
+            Some("021000021".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unlabelled_numbers_are_ignored() {
+        let matcher = RoutingNumberMatcher::new();
+        // Checksum-valid, but nothing says it is a routing number
+        let text = "Invoice 021000021\nRouting details below\n021000021";
+        assert!(matcher.extract_all(text).is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! Social Security Number domain logic.
+//!
+//! This module encapsulates the SSA's rules for which numbers can be valid
+//! SSNs, so that arbitrary 3-2-4 digit groups are not redacted.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// U.S. Social Security Number pattern matcher.
+///
+/// Supports:
+/// - 123-45-6789
+/// - 123 45 6789
+#[derive(Debug, Clone)]
+pub struct SsnMatcher;
+
+impl SsnMatcher {
+    /// Creates a new SSN matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for dashed or spaced SSNs.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"\b(\d{3})-(\d{2})-(\d{4})\b|\b(\d{3}) (\d{2}) (\d{4})\b")
+                .expect("Valid SSN regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates an SSN against the SSA's issuance rules.
+    ///
+    /// # Rules
+    /// - Area number: not 000, 666, or 900-999
+    /// - Group number: not 00
+    /// - Serial number: not 0000
+    pub fn validate(area: &str, group: &str, serial: &str) -> bool {
+        let Ok(area_num) = area.parse::<u16>() else {
+            return false;
+        };
+
+        area.len() == 3
+            && group.len() == 2
+            && serial.len() == 4
+            && area_num != 0
+            && area_num != 666
+            && area_num < 900
+            && group != "00"
+            && serial != "0000"
+    }
+
+    /// Returns the (area, group, serial) parts of the first valid SSN.
+    fn parts(text: &str) -> Option<(&str, &str, &str)> {
+        Self::regex().captures_iter(text).find_map(|caps| {
+            let (area, group, serial) = match caps.get(1) {
+                Some(area) => (area, caps.get(2)?, caps.get(3)?),
+                None => (caps.get(4)?, caps.get(5)?, caps.get(6)?),
+            };
+            Self::validate(area.as_str(), group.as_str(), serial.as_str())
+                .then(|| (area.as_str(), group.as_str(), serial.as_str()))
+        })
+    }
+}
+
+impl Default for SsnMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for SsnMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|ssn| Self::parts(ssn).is_some())
+            .collect()
+    }
+
+    fn normalize(&self, text: &str) -> Option<String> {
+        Self::parts(text).map(|(area, group, serial)| format!("{}{}{}", area, group, serial))
+    }
+
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        if normalized.len() != 9 {
+            return vec![normalized.to_string()];
+        }
+
+        let area = &normalized[0..3];
+        let group = &normalized[3..5];
+        let serial = &normalized[5..9];
+
+        vec![
+            format!("{}-{}-{}", area, group, serial), // 123-45-6789
+            format!("{} {} {}", area, group, serial), // 123 45 6789
+            normalized.to_string(),                   // 123456789
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssn_extraction() {
+        let matcher = SsnMatcher::new();
+        let text = "SSN: 123-45-6789, spouse 234 56 7890";
+        assert_eq!(
+            matcher.extract_all(text),
+            vec!["123-45-6789", "234 56 7890"]
+        );
+    }
+
+    #[test]
+    fn test_find_with_positions_skips_invalid() {
+        let matcher = SsnMatcher::new();
+        let text = "Order 666-12-3456, SSN 123-45-6789";
+        assert_eq!(
+            matcher.find_with_positions(text),
+            vec![(23, 34, "123-45-6789")]
+        );
+    }
+
+    #[test]
+    fn test_ssn_normalization_and_variants() {
+        let matcher = SsnMatcher::new();
+        assert_eq!(
+            matcher.normalize("123 45 6789"),
+            Some("123456789".to_string())
+        );
+        let variants = matcher.generate_variants("123456789");
+        assert!(variants.contains(&"123-45-6789".to_string()));
+        assert!(variants.contains(&"123 45 6789".to_string()));
+        assert!(variants.contains(&"123456789".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_ssns_rejected() {
+        assert!(!SsnMatcher::validate("000", "45", "6789"));
+        assert!(!SsnMatcher::validate("666", "45", "6789"));
+        assert!(!SsnMatcher::validate("912", "45", "6789"));
+        assert!(!SsnMatcher::validate("123", "00", "6789"));
+        assert!(!SsnMatcher::validate("123", "45", "0000"));
+        assert!(SsnMatcher::validate("899", "45", "6789"));
+
+        let matcher = SsnMatcher::new();
+        assert!(matcher.extract_all("Ref 666-12-3456").is_empty());
+    }
+
+    #[test]
+    fn test_mixed_separators_not_matched() {
+        let matcher = SsnMatcher::new();
+        assert!(matcher.extract_all("123-45 6789").is_empty());
+    }
+}
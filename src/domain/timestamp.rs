@@ -0,0 +1,162 @@
+//! Log timestamp domain logic.
+//!
+//! This module detects the timestamps found in IT and security logs. Unlike
+//! a plain date, a timestamp always carries a time of day.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Log timestamp pattern matcher.
+///
+/// Supports:
+/// - ISO-8601 instants: 2025-01-15T14:30:00Z, 2025-01-15 14:30:00.123+02:00
+/// - Syslog (RFC 3164) timestamps: Jan 15 14:30:00
+#[derive(Debug, Clone)]
+pub struct TimestampMatcher;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl TimestampMatcher {
+    /// Creates a new timestamp matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for ISO-8601 and syslog timestamps.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(concat!(
+                // ISO-8601: date, T or space, time, optional fraction and zone
+                r"\b(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2})[T ]",
+                r"(?P<hour>\d{2}):(?P<minute>\d{2})(?::(?P<second>\d{2})(?:[.,]\d+)?)?",
+                r"(?:Z|[+-]\d{2}:?\d{2})?",
+                r"|",
+                // Syslog: abbreviated month, space-padded day, time
+                r"\b(?P<smonth>Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)\s+",
+                r"(?P<sday>\d{1,2})\s+(?P<shour>\d{2}):(?P<sminute>\d{2}):(?P<ssecond>\d{2})\b",
+            ))
+            .expect("Valid timestamp regex")
+        });
+        &PATTERN
+    }
+
+    /// Validates the calendar and clock fields of a timestamp.
+    ///
+    /// Seconds may be 60 to allow for leap seconds.
+    pub fn validate(month: u32, day: u32, hour: u32, minute: u32, second: u32) -> bool {
+        (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second <= 60
+    }
+}
+
+impl Default for TimestampMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for TimestampMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|ts| self.normalize(ts).is_some())
+            .collect()
+    }
+
+    /// Returns the first valid timestamp in `text`, with an ISO-8601
+    /// separator normalized to `T`.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.pattern().captures_iter(text).find_map(|caps| {
+            let num = |name: &str| -> Option<u32> {
+                caps.name(name).map_or(Some(0), |m| m.as_str().parse().ok())
+            };
+
+            let valid = if caps.name("year").is_some() {
+                Self::validate(
+                    num("month")?,
+                    num("day")?,
+                    num("hour")?,
+                    num("minute")?,
+                    num("second")?,
+                )
+            } else {
+                let month = MONTHS.iter().position(|m| *m == &caps["smonth"])? as u32 + 1;
+                Self::validate(
+                    month,
+                    num("sday")?,
+                    num("shour")?,
+                    num("sminute")?,
+                    num("ssecond")?,
+                )
+            };
+
+            let matched = caps.get(0)?.as_str();
+            valid.then(|| match caps.name("year") {
+                Some(_) => matched.replacen(' ', "T", 1),
+                None => matched.to_string(),
+            })
+        })
+    }
+
+    /// ISO-8601 timestamps are written with either `T` or a space between
+    /// date and time; syslog timestamps are searched verbatim.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        let mut variants = vec![normalized.to_string()];
+        if normalized.as_bytes().get(10) == Some(&b'T') {
+            variants.push(normalized.replacen('T', " ", 1));
+        }
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_8601_instant() {
+        let matcher = TimestampMatcher::new();
+        let text = "login at 2025-01-15T14:30:00Z from 10.0.0.1";
+        assert_eq!(matcher.extract_all(text), vec!["2025-01-15T14:30:00Z"]);
+        assert_eq!(
+            matcher.normalize("2025-01-15 14:30:00.123+02:00"),
+            Some("2025-01-15T14:30:00.123+02:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_syslog_timestamp() {
+        let matcher = TimestampMatcher::new();
+        let text = "Jan 15 14:30:00 host sshd[42]: Accepted publickey";
+        assert_eq!(matcher.extract_all(text), vec!["Jan 15 14:30:00"]);
+        assert_eq!(
+            matcher.generate_variants("Jan 15 14:30:00"),
+            vec!["Jan 15 14:30:00".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_date_without_time_is_ignored() {
+        let matcher = TimestampMatcher::new();
+        assert!(matcher.extract_all("Due 2025-01-15").is_empty());
+        assert!(matcher.extract_all("2025-13-15T14:30:00Z").is_empty());
+    }
+
+    #[test]
+    fn test_iso_variants() {
+        let matcher = TimestampMatcher::new();
+        let variants = matcher.generate_variants("2025-01-15T14:30:00Z");
+        assert!(variants.contains(&"2025-01-15 14:30:00Z".to_string()));
+    }
+}
@@ -0,0 +1,268 @@
+//! Unicode folding for pattern matching.
+//!
+//! Extracted text can hold the same value in several encodings: accents as
+//! separate combining marks (NFD), full-width forms such as `５５５` from
+//! CJK fonts, digits wrapped in bidi control marks in right-to-left
+//! documents, or numbers broken across lines with a hyphen in narrow
+//! columns. Matchers only know the ASCII and composed forms of unbroken
+//! values, so this module folds text before they run and maps each match
+//! back to the text as written, which is what a PDF search has to look for.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+/// Offset between a full-width ASCII form (U+FF01 to U+FF5E) and its ASCII
+/// character.
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+/// Soft hyphen, an invisible hint where a word may be broken.
+const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Maps a full-width ASCII form or the ideographic space to ASCII, leaving
+/// other characters unchanged.
+pub fn fold_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - FULL_WIDTH_OFFSET).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+/// Returns true for the invisible bidi control characters: the directional
+/// marks (U+200E, U+200F, U+061C), embeddings and overrides (U+202A to
+/// U+202E) and isolates (U+2066 to U+2069).
+///
+/// Mixed-direction text places them around and inside numbers, where they
+/// would break up a value no matcher then recognizes.
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Text in NFC with full-width ASCII folded, bidi controls and soft hyphens
+/// removed, and words broken across lines rejoined, able to map matches back
+/// to the original.
+///
+/// A hyphen (or soft hyphen) ending a line between two letters or digits is
+/// taken to break a single word, so it is removed along with the line break:
+/// `123456-\n789-00001` folds to `123456789-00001`.
+///
+/// # Example
+///
+/// ```
+/// use redactor::domain::FoldedText;
+///
+/// let folded = FoldedText::new("Call ５５５-２３４-５６７８");
+/// assert_eq!(folded.as_str(), "Call 555-234-5678");
+/// assert_eq!(folded.originals("555-234-5678"), vec!["５５５-２３４-５６７８"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FoldedText<'a> {
+    original: &'a str,
+    folded: String,
+
+    /// Byte offsets in the folded and original text where each segment (a
+    /// starter character and its combining marks) begins
+    segments: Vec<(usize, usize)>,
+}
+
+impl<'a> FoldedText<'a> {
+    /// Folds `original`.
+    pub fn new(original: &'a str) -> Self {
+        let mut folded = String::with_capacity(original.len());
+        let mut segments = Vec::new();
+        let joins = line_break_joins(original);
+        if original.is_ascii() && joins.is_empty() {
+            folded.push_str(original);
+            return Self {
+                original,
+                folded,
+                segments,
+            };
+        }
+
+        // Composition never crosses a starter, so each segment is
+        // normalized on its own and its offsets stay known. A line-break
+        // join is a single segment that folds to nothing.
+        let mut starts = Vec::new();
+        let mut pending = joins.iter().peekable();
+        for (idx, c) in original.char_indices() {
+            while pending.next_if(|join| join.end <= idx).is_some() {}
+            let inside_join = pending.peek().is_some_and(|join| join.start < idx);
+            if idx == 0 || (!inside_join && canonical_combining_class(c) == 0) {
+                starts.push(idx);
+            }
+        }
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(original.len());
+            segments.push((folded.len(), start));
+            if !joins.iter().any(|join| join.start == start) {
+                folded.extend(fold(&original[start..end]));
+            }
+        }
+
+        Self {
+            original,
+            folded,
+            segments,
+        }
+    }
+
+    /// Returns the folded text.
+    pub fn as_str(&self) -> &str {
+        &self.folded
+    }
+
+    /// Returns how each occurrence of `value` in the folded text is written
+    /// in the original, omitting renderings identical to `value`.
+    pub fn originals(&self, value: &str) -> Vec<&'a str> {
+        let mut originals: Vec<&'a str> = Vec::new();
+        if value.is_empty() || self.folded == self.original {
+            return originals;
+        }
+
+        for (start, _) in self.folded.match_indices(value) {
+            let end = start + value.len();
+            let segment = self
+                .segments
+                .partition_point(|&(folded, _)| folded <= start);
+            let original_start = self.segments[segment.saturating_sub(1)].1;
+            let original_end = self
+                .segments
+                .iter()
+                .find(|&&(folded, _)| folded >= end)
+                .map_or(self.original.len(), |&(_, original)| original);
+
+            let written = &self.original[original_start..original_end];
+            if written != value && !originals.contains(&written) {
+                originals.push(written);
+            }
+        }
+
+        originals
+    }
+}
+
+/// Folds one segment of text.
+fn fold(segment: &str) -> impl Iterator<Item = char> + '_ {
+    segment
+        .nfc()
+        .filter(|&c| c != SOFT_HYPHEN && !is_bidi_control(c))
+        .map(fold_width)
+}
+
+/// Returns the byte ranges of the hyphens ending a line inside a word or
+/// number, each with the line break and surrounding blanks after it.
+fn line_break_joins(text: &str) -> Vec<Range<usize>> {
+    static JOIN: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"[-\u{AD}\u{2010}][ \t]*\r?\n[ \t]*").expect("Valid regex"));
+    JOIN.find_iter(text)
+        .filter(|m| {
+            text[..m.start()]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+                && text[m.end()..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric)
+        })
+        .map(|m| m.range())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_width() {
+        assert_eq!(fold_width('５'), '5');
+        assert_eq!(fold_width('（'), '(');
+        assert_eq!(fold_width('Ａ'), 'A');
+        assert_eq!(fold_width('\u{3000}'), ' ');
+        assert_eq!(fold_width('世'), '世');
+        assert_eq!(fold_width('5'), '5');
+    }
+
+    #[test]
+    fn test_full_width_digits_map_back() {
+        let folded = FoldedText::new("Tel （５５５） ２３４-５６７８, 555-234-5678");
+        assert_eq!(folded.as_str(), "Tel (555) 234-5678, 555-234-5678");
+        assert_eq!(
+            folded.originals("(555) 234-5678"),
+            vec!["（５５５） ２３４-５６７８"]
+        );
+        // ASCII occurrences need no extra rendering
+        assert!(folded.originals("555-234-5678").is_empty());
+    }
+
+    #[test]
+    fn test_decomposed_accents_compose() {
+        // Combining acute accent and tilde
+        let folded = FoldedText::new("Name: Jose\u{301} Pen\u{303}a");
+        assert_eq!(folded.as_str(), "Name: Jos\u{e9} Pe\u{f1}a");
+        assert_eq!(folded.originals("Jos\u{e9}"), vec!["Jose\u{301}"]);
+        assert_eq!(folded.originals("Pe\u{f1}a"), vec!["Pen\u{303}a"]);
+    }
+
+    #[test]
+    fn test_ascii_text_is_unchanged() {
+        let folded = FoldedText::new("Account 123456789-00001");
+        assert_eq!(folded.as_str(), "Account 123456789-00001");
+        assert!(folded.originals("123456789-00001").is_empty());
+    }
+
+    #[test]
+    fn test_soft_hyphens_are_removed() {
+        let folded = FoldedText::new("Account 123456\u{AD}789-00001");
+        assert_eq!(folded.as_str(), "Account 123456789-00001");
+        assert_eq!(
+            folded.originals("123456789-00001"),
+            vec!["123456\u{AD}789-00001"]
+        );
+    }
+
+    #[test]
+    fn test_words_broken_across_lines_are_rejoined() {
+        let folded = FoldedText::new("Account 123456-\n789-00001\nAcct 98765\u{AD} \r\n43210");
+        assert_eq!(folded.as_str(), "Account 123456789-00001\nAcct 9876543210");
+        assert_eq!(
+            folded.originals("123456789-00001"),
+            vec!["123456-\n789-00001"]
+        );
+        assert_eq!(
+            folded.originals("9876543210"),
+            vec!["98765\u{AD} \r\n43210"]
+        );
+        // Matches ending or starting at the break leave the hyphen out
+        assert!(folded.originals("123456").is_empty());
+        assert!(folded.originals("789-00001").is_empty());
+
+        // Hyphens not between letters or digits are kept
+        let folded = FoldedText::new("Total -\n 42, items:\n- one");
+        assert_eq!(folded.as_str(), "Total -\n 42, items:\n- one");
+    }
+
+    #[test]
+    fn test_bidi_controls_are_removed() {
+        // Left-to-right embedding around a number in Hebrew text
+        let folded = FoldedText::new("טלפון \u{202A}555-234-5678\u{202C}");
+        assert_eq!(folded.as_str(), "טלפון 555-234-5678");
+        // The surrounding marks are not part of the match as written
+        assert!(folded.originals("555-234-5678").is_empty());
+
+        // Marks between the digits stay in the rendering to search for
+        let folded = FoldedText::new("Tel 555\u{200E}-234\u{200F}-5678");
+        assert_eq!(folded.as_str(), "Tel 555-234-5678");
+        assert_eq!(
+            folded.originals("555-234-5678"),
+            vec!["555\u{200E}-234\u{200F}-5678"]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Vehicle Identification Number (VIN) domain logic.
+//!
+//! This module detects 17-character VINs and validates the check digit in
+//! the 9th position, so that other 17-character codes are left alone.
+
+use super::PatternMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// VIN pattern matcher.
+///
+/// Supports:
+/// - 1HGCM82633A004352 (17 characters, no I, O or Q)
+#[derive(Debug, Clone)]
+pub struct VinMatcher;
+
+/// Weight of each position in the check digit sum.
+const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Returns the numeric value a VIN character contributes to the check sum.
+fn transliterate(c: u8) -> Option<u32> {
+    match c {
+        b'0'..=b'9' => Some(u32::from(c - b'0')),
+        b'A'..=b'H' => Some(u32::from(c - b'A') + 1),
+        b'J'..=b'N' => Some(u32::from(c - b'J') + 1),
+        b'P' => Some(7),
+        b'R' => Some(9),
+        b'S'..=b'Z' => Some(u32::from(c - b'S') + 2),
+        _ => None,
+    }
+}
+
+impl VinMatcher {
+    /// Creates a new VIN matcher.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the regex pattern for VINs.
+    fn regex() -> &'static Regex {
+        static PATTERN: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b[A-HJ-NPR-Z0-9]{17}\b").expect("Valid VIN regex"));
+        &PATTERN
+    }
+
+    /// Validates a VIN's check digit (position 9), which is the weighted
+    /// sum of the other characters mod 11, with 10 written as `X`.
+    pub fn validate(vin: &str) -> bool {
+        let bytes = vin.as_bytes();
+        if bytes.len() != 17 {
+            return false;
+        }
+
+        let mut sum = 0;
+        for (&c, weight) in bytes.iter().zip(WEIGHTS) {
+            match transliterate(c) {
+                Some(value) => sum += value * weight,
+                None => return false,
+            }
+        }
+
+        let expected = match sum % 11 {
+            10 => b'X',
+            digit => b'0' + digit as u8,
+        };
+        bytes[8] == expected
+    }
+}
+
+impl Default for VinMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternMatcher for VinMatcher {
+    fn pattern(&self) -> &Regex {
+        Self::regex()
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.pattern()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|vin| Self::validate(vin))
+            .collect()
+    }
+
+    /// Returns the first valid VIN in `text`.
+    fn normalize(&self, text: &str) -> Option<String> {
+        self.extract_all(text).first().map(|vin| vin.to_string())
+    }
+
+    /// VINs render as one continuous string, so the VIN itself is the only
+    /// variant.
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(VinMatcher::validate("1HGCM82633A004352"));
+        assert!(VinMatcher::validate("1M8GDM9AXKP042788"));
+
+        // Wrong check digit, letter O, too short
+        assert!(!VinMatcher::validate("1HGCM82643A004352"));
+        assert!(!VinMatcher::validate("1HGCM82633AO04352"));
+        assert!(!VinMatcher::validate("1HGCM82633A00435"));
+    }
+
+    #[test]
+    fn test_vin_extraction() {
+        let matcher = VinMatcher::new();
+        let text = "VIN: 1HGCM82633A004352, quote ref 1HGCM82643A004352";
+        assert_eq!(matcher.extract_all(text), vec!["1HGCM82633A004352"]);
+        assert_eq!(
+            matcher.generate_variants(&matcher.normalize(text).unwrap()),
+            vec!["1HGCM82633A004352".to_string()]
+        );
+    }
+}
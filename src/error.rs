@@ -6,6 +6,7 @@
 use std::fmt;
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Result type alias for redaction operations.
 pub type RedactorResult<T> = Result<T, RedactorError>;
@@ -38,6 +39,22 @@ pub enum RedactorError {
     /// Invalid configuration or parameters
     InvalidInput { parameter: String, reason: String },
 
+    /// PDF is encrypted and no password was supplied
+    EncryptedPdf { path: PathBuf },
+
+    /// PDF uses a feature the backend cannot open, such as certificate
+    /// encryption
+    UnsupportedFeature { feature: String },
+
+    /// Redacted text is still present in the output
+    VerificationFailed { pattern: String },
+
+    /// Redaction did not finish within the configured time limit
+    Timeout { path: PathBuf, limit: Duration },
+
+    /// Output file already exists and overwriting is disabled
+    OutputExists { path: PathBuf },
+
     /// Backend-specific error (MuPDF, LoPDF, etc.)
     BackendError {
         backend: String,
@@ -76,6 +93,39 @@ impl fmt::Display for RedactorError {
             Self::InvalidInput { parameter, reason } => {
                 write!(f, "Invalid input for '{}': {}", parameter, reason)
             }
+            Self::EncryptedPdf { path } => {
+                write!(
+                    f,
+                    "'{}' is password-protected; remove the password or supply it \
+                     with RedactionService::redact_encrypted",
+                    path.display()
+                )
+            }
+            Self::UnsupportedFeature { feature } => {
+                write!(f, "PDF uses an unsupported feature: {}", feature)
+            }
+            Self::VerificationFailed { pattern } => {
+                write!(
+                    f,
+                    "Verification failed: '{}' is still present in the output",
+                    pattern
+                )
+            }
+            Self::Timeout { path, limit } => {
+                write!(
+                    f,
+                    "Redacting '{}' did not finish within {:?}",
+                    path.display(),
+                    limit
+                )
+            }
+            Self::OutputExists { path } => {
+                write!(
+                    f,
+                    "Output '{}' already exists; remove it or allow overwriting",
+                    path.display()
+                )
+            }
             Self::BackendError {
                 backend, message, ..
             } => {
@@ -138,5 +188,13 @@ mod tests {
             context: "document".to_string(),
         };
         assert_eq!(err.to_string(), "Pattern 'test' not found: document");
+
+        let err = RedactorError::UnsupportedFeature {
+            feature: "PDF version 3.0".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "PDF uses an unsupported feature: PDF version 3.0"
+        );
     }
 }
@@ -14,6 +14,7 @@
 //!
 //! # Architecture
 //!
+//! - [`config`]: Config files describing a redaction job
 //! - [`domain`]: Business logic for pattern matching (phone numbers, accounts)
 //! - [`redaction`]: Redaction strategies and service layer
 //! - [`error`]: Comprehensive error handling
@@ -68,17 +69,22 @@
 //! ```
 
 // Public API
+pub mod config;
 pub mod domain;
 pub mod error;
 pub mod redaction;
 
 // Re-exports for convenient access
+pub use config::RedactionConfig;
 pub use domain::{
     PatternMatcher, PhoneNumberMatcher, VerizonAccountMatcher, VerizonCallDetailsMatcher,
 };
 pub use error::{RedactorError, RedactorResult};
 pub use redaction::{
-    RedactionResult, RedactionService, RedactionStrategy, RedactionTarget, SecureRedactionStrategy,
+    DiffLine, DocumentAnalysis, LayerInfo, LineDiff, MaskingRedactionStrategy, MupdfWorker,
+    OverlayRedactionStrategy, PageRedactionStats, PageSelection, RedactionHit, RedactionOptions,
+    RedactionPhase, RedactionProgress, RedactionResult, RedactionService, RedactionStrategy,
+    RedactionTarget, SecureRedactionStrategy, TextCache, TextDiff, WorkerStrategy,
 };
 
 // Re-export as a module for test backwards compatibility
@@ -4,10 +4,17 @@
 //! supporting various redaction modes with proper error handling and user feedback.
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
-use redactor::{RedactionService, RedactionTarget, SecureRedactionStrategy};
+use redactor::domain::{registered_matchers, PhoneLocale};
+use redactor::{
+    DiffLine, PageSelection, RedactionConfig, RedactionPhase, RedactionProgress, RedactionResult,
+    RedactionService, RedactionTarget, SecureRedactionStrategy,
+};
 
 /// PDF Redaction Tool
 ///
@@ -17,11 +24,17 @@ use redactor::{RedactionService, RedactionTarget, SecureRedactionStrategy};
 #[command(name = "redactor")]
 #[command(version, about, long_about = None)]
 struct Cli {
-    /// Input PDF file path
+    /// Config file (TOML, or JSON with a .json extension) describing the
+    /// job; flags given on the command line take precedence
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Input PDF file path, or a directory of PDFs to redact in batch
     #[arg(short, long, value_name = "FILE")]
     input: Option<PathBuf>,
 
-    /// Output PDF file path
+    /// Output PDF file path (a directory when the input is a directory), or
+    /// - to write the redacted PDF to stdout
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
 
@@ -29,18 +42,169 @@ struct Cli {
     #[arg(short, long, value_name = "PATTERN")]
     pattern: Vec<String>,
 
+    /// File of literal patterns to redact, one per line; blank lines and
+    /// lines starting with # are ignored (can be specified multiple times)
+    #[arg(long, value_name = "FILE")]
+    pattern_file: Vec<PathBuf>,
+
+    /// Only redact patterns where they stand alone as words
+    #[arg(long)]
+    whole_word: bool,
+
+    /// Full name of a person to redact, also as "Last, First", "F. Last"
+    /// and the surname alone (can be specified multiple times)
+    #[arg(long, value_name = "NAME")]
+    name: Vec<String>,
+
     /// Redact American phone numbers
     #[arg(long)]
     phones: bool,
 
+    /// Numbering plan --phones matches: nanp (default), intl for E.164
+    /// numbers, uk or de
+    #[arg(long, value_name = "LOCALE", value_parser = parse_phone_locale)]
+    phone_locale: Option<PhoneLocale>,
+
     /// Redact Verizon account number (automatically includes phone numbers and call details)
     #[arg(long)]
     verizon: bool,
 
+    /// Redact U.S. Social Security Numbers
+    #[arg(long)]
+    ssn: bool,
+
+    /// Redact email addresses
+    #[arg(long)]
+    emails: bool,
+
+    /// Redact payment card numbers (Luhn-validated)
+    #[arg(long)]
+    credit_cards: bool,
+
+    /// Redact International Bank Account Numbers (mod-97 validated)
+    #[arg(long)]
+    iban: bool,
+
+    /// Redact Vehicle Identification Numbers (check-digit validated)
+    #[arg(long)]
+    vin: bool,
+
+    /// Redact MAC addresses (colon, hyphen and dot notation)
+    #[arg(long)]
+    mac: bool,
+
+    /// Redact @mentions and usernames after a "user:" or "login:" label
+    #[arg(long)]
+    handles: bool,
+
+    /// Redact passport numbers labelled as such (see --passport-country)
+    #[arg(long)]
+    passport: bool,
+
+    /// Redact passport numbers in this country's format: US, GB (UK) or CA
+    #[arg(long, value_name = "COUNTRY")]
+    passport_country: Option<String>,
+
+    /// Redact driver's license numbers labelled as such (see --dl-state)
+    #[arg(long)]
+    dl: bool,
+
+    /// Redact driver's license numbers in this state's format: CA, FL, IL, NY, PA or TX
+    #[arg(long, value_name = "STATE")]
+    dl_state: Option<String>,
+
+    /// Redact ABA bank routing numbers labelled as such (checksum validated)
+    #[arg(long)]
+    routing: bool,
+
+    /// Redact log timestamps (ISO-8601 and syslog formats)
+    #[arg(long)]
+    timestamps: bool,
+
+    /// Redact valid calendar dates (2025-01-06, 01/06/2025, January 6, 2025)
+    #[arg(long)]
+    dates: bool,
+
+    /// Redact US ZIP and ZIP+4 codes in addresses or after a ZIP label
+    #[arg(long)]
+    zip: bool,
+
+    /// Delete every hyperlink, leaving its visible text in place
+    #[arg(long)]
+    links: bool,
+
+    /// Only redact these pages, e.g. 1,3,5-9 (default: all pages)
+    #[arg(long, value_name = "PAGES")]
+    pages: Option<PageSelection>,
+
+    /// Fill color of redaction boxes: a name (black, white, gray) or hex RRGGBB
+    #[arg(long, value_name = "COLOR", value_parser = parse_color)]
+    fill_color: Option<(f32, f32, f32)>,
+
+    /// Label drawn inside each redaction box (e.g. REDACTED)
+    #[arg(long, value_name = "TEXT")]
+    label: Option<String>,
+
+    /// Label each redaction box with the category removed, e.g. [PHONE]
+    /// (overrides --label)
+    #[arg(long)]
+    category_labels: bool,
+
+    /// Write a JSON audit report of the redaction to this file
+    #[arg(long, value_name = "FILE")]
+    audit_report: Option<PathBuf>,
+
+    /// Remove document metadata (author, title, producer, XMP)
+    #[arg(long)]
+    strip_metadata: bool,
+
+    /// Delete pages left without text or images by redaction, such as
+    /// after --pattern ".+"; pages that were already blank are kept
+    #[arg(long)]
+    drop_empty_pages: bool,
+
+    /// Print the SHA-256 digest of the output file, as sha256sum does
+    #[arg(long)]
+    hash: bool,
+
+    /// Fail instead of overwriting an existing output file
+    #[arg(long)]
+    no_clobber: bool,
+
+    /// Give the output the input's modification time
+    #[arg(long)]
+    preserve_timestamps: bool,
+
+    /// Fail if any requested pattern matches nothing, instead of writing an
+    /// unredacted copy
+    #[arg(long)]
+    require_matches: bool,
+
+    /// In batch mode, report failed files without exiting with an error
+    #[arg(long)]
+    continue_on_error: bool,
+
+    /// Skip the confirmation prompt for full-page (`.+`) redaction
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Print the result in this format: text, or json for scripts
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
     /// Enable verbose output
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Print nothing but errors and warnings
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log diagnostics to stderr at this level: off, error, warn, info,
+    /// debug or trace (overrides RUST_LOG)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<log::LevelFilter>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -57,26 +221,73 @@ enum Commands {
         #[arg(short, long, value_name = "FILE")]
         output: Option<PathBuf>,
     },
+
+    /// Compare the text of a PDF with its redacted copy, showing the
+    /// removed spans of each changed line as [-removed-]
+    Diff {
+        /// Original PDF file path
+        #[arg(short, long, value_name = "FILE")]
+        input: PathBuf,
+
+        /// Redacted PDF file path
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+    },
+
+    /// List the built-in matchers and what they detect (patterns with --verbose)
+    ListMatchers,
+}
+
+/// How the result of a redaction is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable messages
+    Text,
+
+    /// The redaction result as a JSON object on stdout
+    Json,
 }
 
 /// Redaction command handler with dependency injection.
 struct RedactionHandler {
     service: RedactionService,
     verbose: bool,
+    quiet: bool,
+    format: OutputFormat,
 }
 
 impl RedactionHandler {
-    /// Creates a new handler with the secure redaction strategy.
-    fn new(verbose: bool) -> Self {
-        let strategy = SecureRedactionStrategy::new();
+    /// Creates a new handler with the given redaction service.
+    fn new(verbose: bool, service: RedactionService) -> Self {
         Self {
-            service: RedactionService::new(Box::new(strategy)),
+            service,
             verbose,
+            quiet: false,
+            format: OutputFormat::Text,
         }
     }
 
+    /// Suppresses everything but errors and warnings when set.
+    fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Sets how redaction results are printed.
+    fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Executes a redaction operation.
-    fn redact(&self, input: &Path, output: &Path, targets: Vec<RedactionTarget>) -> Result<()> {
+    fn redact(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: Vec<RedactionTarget>,
+        pages: Option<&PageSelection>,
+        audit_report: Option<&Path>,
+    ) -> Result<()> {
         // Validate inputs
         if !input.exists() {
             anyhow::bail!("Input file does not exist: {}", input.display());
@@ -86,24 +297,55 @@ impl RedactionHandler {
             anyhow::bail!("No redaction targets specified. Use --pattern, --phones, or --verizon.");
         }
 
-        if self.verbose {
+        // JSON replaces the text output on stdout, so leave it clean
+        let verbose = self.verbose && self.format == OutputFormat::Text;
+        if verbose {
             println!("Input:  {}", input.display());
             println!("Output: {}", output.display());
             println!("Targets: {} redaction target(s)", targets.len());
+            if let Some(pages) = pages {
+                println!("Pages:  {}", pages);
+            }
         }
 
+        self.warn_pages_without_text(input, pages);
+
         // Perform redaction
-        let result = self
-            .service
-            .redact(input, output, &targets)
-            .with_context(|| "Redaction failed")?;
+        let result = match pages {
+            Some(pages) => self.service.redact_pages(input, output, &targets, pages),
+            None if self.verbose => {
+                self.service
+                    .redact_with_progress(input, output, &targets, &mut render_progress)
+            }
+            None => self.service.redact(input, output, &targets),
+        }
+        .with_context(|| "Redaction failed")?;
+
+        if let Some(report) = audit_report {
+            std::fs::write(report, result.to_audit_json())
+                .with_context(|| format!("Failed to write audit report {}", report.display()))?;
+        }
+
+        if self.format == OutputFormat::Json {
+            println!("{}", json_report(&result));
+            return Ok(());
+        }
 
         // Report results
-        if self.verbose {
+        if verbose {
             println!("\nRedaction Summary:");
             println!("  Pages processed: {}", result.pages_processed);
             println!("  Pages modified:  {}", result.pages_modified);
+            if result.pages_removed > 0 {
+                println!("  Pages removed:   {}", result.pages_removed);
+            }
             println!("  Instances redacted: {}", result.instances_redacted);
+            for page in result.per_page.iter().filter(|p| p.instances > 0) {
+                println!(
+                    "    Page {}: {} instance(s), {} pattern(s)",
+                    page.page_number, page.instances, page.patterns_matched
+                );
+            }
             println!(
                 "  Secure: {}",
                 if result.secure {
@@ -114,19 +356,249 @@ impl RedactionHandler {
             );
         }
 
-        if result.instances_redacted > 0 {
+        // Counts only: the values themselves must not end up in logs
+        for warning in incomplete_warnings(&result) {
+            eprintln!("⚠ WARNING: {}", warning);
+        }
+
+        if !self.quiet {
+            if result.instances_redacted > 0 {
+                println!(
+                    "✓ Successfully redacted {} instance(s) → {}",
+                    result.instances_redacted,
+                    output.display()
+                );
+            } else {
+                println!("⚠ No instances found to redact");
+            }
+        }
+
+        // Printed even when quiet, since it was asked for
+        if let Some(digest) = &result.output_sha256 {
+            println!("{}  {}", digest, output.display());
+        }
+
+        Ok(())
+    }
+
+    /// Warns on stderr about selected pages with no extractable text, which
+    /// no target can match even if they visibly hold sensitive content.
+    ///
+    /// Only runs with `--verbose`, since the analysis parses the document a
+    /// second time. Analysis failures are left for the redaction itself to
+    /// report.
+    fn warn_pages_without_text(&self, input: &Path, pages: Option<&PageSelection>) {
+        if !self.verbose {
+            return;
+        }
+        let Ok(analysis) = self.service.analyze(input) else {
+            return;
+        };
+        let describe = |list: Vec<usize>| {
+            list.into_iter()
+                .filter(|page| pages.map_or(true, |pages| pages.contains(*page)))
+                .map(|page| page.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let without_text = describe(analysis.pages_without_text());
+        if without_text.is_empty() {
+            return;
+        }
+        eprintln!(
+            "⚠ WARNING: no extractable text on page(s) {}; their content cannot be redacted",
+            without_text
+        );
+        let vector_text = describe(analysis.vector_text_suspected);
+        if !vector_text.is_empty() {
+            eprintln!(
+                "⚠ Page(s) {} appear to hold text drawn as outlines; check them by eye",
+                vector_text
+            );
+        }
+    }
+
+    /// Redacts `input` and writes the redacted PDF to stdout.
+    ///
+    /// Status messages go to stderr so they don't corrupt the PDF bytes.
+    fn redact_to_stdout(&self, input: &Path, targets: Vec<RedactionTarget>) -> Result<()> {
+        if !input.exists() {
+            anyhow::bail!("Input file does not exist: {}", input.display());
+        }
+
+        if targets.is_empty() {
+            anyhow::bail!("No redaction targets specified. Use --pattern, --phones, or --verizon.");
+        }
+
+        if self.verbose {
+            eprintln!("Input:  {}", input.display());
+            eprintln!("Output: <stdout>");
+            eprintln!("Targets: {} redaction target(s)", targets.len());
+        }
+
+        self.warn_pages_without_text(input, None);
+
+        let bytes =
+            std::fs::read(input).with_context(|| format!("Failed to read {}", input.display()))?;
+        let redacted = self
+            .service
+            .redact_bytes(&bytes, &targets)
+            .with_context(|| "Redaction failed")?;
+
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(&redacted)
+            .and_then(|()| stdout.flush())
+            .with_context(|| "Failed to write to stdout")?;
+
+        if self.verbose {
+            eprintln!(
+                "✓ Wrote {} byte(s) of redacted PDF to stdout",
+                redacted.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Redacts every PDF in `input_dir` into `output_dir`, printing a
+    /// per-file summary table.
+    fn redact_batch(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+        targets: Vec<RedactionTarget>,
+        continue_on_error: bool,
+    ) -> Result<()> {
+        if targets.is_empty() {
+            anyhow::bail!("No redaction targets specified. Use --pattern, --phones, or --verizon.");
+        }
+
+        let inputs = pdf_files_in(input_dir)?;
+        if inputs.is_empty() {
+            anyhow::bail!("No PDF files found in {}", input_dir.display());
+        }
+
+        if self.verbose {
+            println!("Input:  {} ({} file(s))", input_dir.display(), inputs.len());
+            println!("Output: {}", output_dir.display());
+            println!("Targets: {} redaction target(s)", targets.len());
+        }
+
+        let results = self
+            .service
+            .redact_batch(&inputs, output_dir, &targets)
+            .with_context(|| "Batch redaction failed")?;
+
+        if !self.quiet {
+            println!("{:<40} {:>10}  Status", "File", "Redacted");
+        }
+        let mut failed = 0;
+        let mut total = RedactionResult::none();
+        for (input, result) in &results {
+            let name = input.file_name().unwrap_or_default().to_string_lossy();
+            match result {
+                Ok(result) => {
+                    total.merge(result);
+                    let status = if result.is_complete() {
+                        "ok"
+                    } else {
+                        "incomplete"
+                    };
+                    if !self.quiet {
+                        println!("{:<40} {:>10}  {}", name, result.instances_redacted, status);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    if self.quiet {
+                        eprintln!("{}: failed: {}", name, e);
+                    } else {
+                        println!("{:<40} {:>10}  failed: {}", name, "-", e);
+                    }
+                }
+            }
+        }
+
+        if !self.quiet {
+            println!("{:<40} {:>10}", "Total", total.instances_redacted);
+
             println!(
-                "✓ Successfully redacted {} instance(s) → {}",
-                result.instances_redacted,
-                output.display()
+                "\n✓ Redacted {} of {} file(s) → {}",
+                results.len() - failed,
+                results.len(),
+                output_dir.display()
             );
-        } else {
-            println!("⚠ No instances found to redact");
+            if self.verbose {
+                println!(
+                    "  {} of {} page(s) modified",
+                    total.pages_modified, total.pages_processed
+                );
+                for (category, count) in &total.by_category {
+                    println!("  {}: {}", category, count);
+                }
+            }
+        }
+
+        if failed > 0 && !continue_on_error {
+            anyhow::bail!(
+                "{} file(s) failed to redact (pass --continue-on-error to ignore)",
+                failed
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Prints the lines redaction changed, marking removed spans, and a
+    /// count of the spans removed.
+    ///
+    /// Unchanged lines are printed too with --verbose.
+    fn diff(&self, input: &Path, output: &Path) -> Result<()> {
+        let diff = self
+            .service
+            .compare(input, output)
+            .with_context(|| "Text comparison failed")?;
+
+        for line in &diff.lines {
+            match line {
+                DiffLine::Unchanged(line) if self.verbose => println!("  {}", line),
+                DiffLine::Unchanged(_) => {}
+                DiffLine::Removed { line, spans } => {
+                    println!("- {}", mark_spans(line, spans));
+                }
+                DiffLine::Added(line) => println!("+ {}", line),
+            }
+        }
+
+        if !self.quiet {
+            let removed = diff.removed();
+            if removed.is_empty() {
+                println!("No text removed");
+            } else {
+                println!("{} span(s) removed", removed.len());
+            }
         }
 
         Ok(())
     }
 
+    /// Prints each built-in matcher with its target and an example.
+    fn list_matchers(&self) {
+        for (i, info) in registered_matchers().iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!("{} (RedactionTarget::{})", info.name, info.target);
+            println!("  {}", info.description);
+            println!("  Example: {}", info.example);
+            if self.verbose {
+                println!("  Pattern: {}", info.pattern);
+            }
+        }
+    }
+
     /// Extracts text from a PDF.
     fn extract(&self, input: &Path, output: Option<&Path>) -> Result<()> {
         if !input.exists() {
@@ -141,6 +613,9 @@ impl RedactionHandler {
         if let Some(output_path) = output {
             std::fs::write(output_path, &text)
                 .with_context(|| format!("Failed to write to {}", output_path.display()))?;
+            if self.quiet {
+                return Ok(());
+            }
             println!(
                 "✓ Extracted {} characters → {}",
                 text.len(),
@@ -154,12 +629,81 @@ impl RedactionHandler {
     }
 }
 
-/// Parses command-line arguments and builds redaction targets.
-fn build_targets(patterns: &[String], phones: bool, verizon: bool) -> Vec<RedactionTarget> {
+/// Renders `result` as the JSON object printed by `--format json`, with a
+/// `warnings` list.
+///
+/// Like the audit report, it holds no redacted text, so warnings give
+/// counts rather than the values left in the output.
+fn json_report(result: &RedactionResult) -> String {
+    #[derive(Serialize)]
+    struct Report<'a> {
+        #[serde(flatten)]
+        result: &'a RedactionResult,
+        warnings: Vec<String>,
+    }
+
+    let warnings = incomplete_warnings(result);
+    let report = Report { result, warnings };
+    serde_json::to_string_pretty(&report).expect("Redaction result is always serializable")
+}
+
+/// Describes what `result` may have left in the output, by count and
+/// category rather than by value.
+fn incomplete_warnings(result: &RedactionResult) -> Vec<String> {
+    let categories = |categories: &BTreeSet<String>| {
+        if categories.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " ({})",
+                categories
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    };
+
+    let mut warnings = Vec::new();
+    if !result.detected_not_redacted.is_empty() {
+        warnings.push(format!(
+            "{} detected value(s) not redacted (still present in output){}",
+            result.detected_not_redacted.len(),
+            categories(&result.unredacted_categories)
+        ));
+    }
+    if !result.truncated_patterns.is_empty() {
+        warnings.push(format!(
+            "hit limit reached, more matches may remain in the output for {} pattern(s){}",
+            result.truncated_patterns.len(),
+            categories(&result.truncated_categories)
+        ));
+    }
+    warnings
+}
+
+/// Parses the contents of a pattern file into literal patterns.
+///
+/// Each line is one pattern with surrounding whitespace trimmed; blank
+/// lines and lines starting with `#` are skipped.
+fn parse_pattern_file(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Builds redaction targets from parsed command-line arguments.
+///
+/// Fails if a `--pattern-file` cannot be read.
+fn build_targets(cli: &Cli) -> Result<Vec<RedactionTarget>> {
     let mut targets = Vec::new();
 
     // Add Verizon account if requested
-    if verizon {
+    if cli.verizon {
         targets.push(RedactionTarget::VerizonAccount);
         // Verizon bills contain phone numbers, so automatically redact them too
         targets.push(RedactionTarget::PhoneNumbers);
@@ -168,25 +712,307 @@ fn build_targets(patterns: &[String], phones: bool, verizon: bool) -> Vec<Redact
     }
 
     // Add phone numbers if requested (and not already added by verizon flag)
-    if phones && !verizon {
+    if cli.phones && !cli.verizon {
         targets.push(RedactionTarget::PhoneNumbers);
     }
 
-    // Add literal patterns if specified
-    targets.extend(patterns.iter().map(|p| RedactionTarget::Literal(p.clone())));
+    if cli.ssn {
+        targets.push(RedactionTarget::Ssn);
+    }
+
+    if cli.emails {
+        targets.push(RedactionTarget::Email);
+    }
+
+    if cli.credit_cards {
+        targets.push(RedactionTarget::CreditCard);
+    }
+
+    if cli.iban {
+        targets.push(RedactionTarget::Iban);
+    }
+
+    if cli.vin {
+        targets.push(RedactionTarget::Vin);
+    }
+
+    if cli.mac {
+        targets.push(RedactionTarget::MacAddress);
+    }
+
+    if cli.handles {
+        targets.push(RedactionTarget::Handle);
+    }
+
+    if cli.passport || cli.passport_country.is_some() {
+        targets.push(RedactionTarget::Passport {
+            country: cli.passport_country.clone(),
+        });
+    }
+
+    if cli.dl || cli.dl_state.is_some() {
+        targets.push(RedactionTarget::DriversLicense {
+            state: cli.dl_state.clone(),
+        });
+    }
+
+    if cli.routing {
+        targets.push(RedactionTarget::RoutingNumber);
+    }
+
+    if cli.timestamps {
+        targets.push(RedactionTarget::Timestamp);
+    }
+
+    if cli.dates {
+        targets.push(RedactionTarget::Dates);
+    }
+
+    if cli.zip {
+        targets.push(RedactionTarget::PostalCode);
+    }
+
+    if cli.links {
+        targets.push(RedactionTarget::AllLinks);
+    }
 
+    if !cli.name.is_empty() {
+        targets.push(RedactionTarget::NamedEntity(cli.name.clone()));
+    }
+
+    // Add literal patterns if specified, then those from pattern files
+    let mut patterns = cli.pattern.clone();
+    for path in &cli.pattern_file {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read pattern file {}", path.display()))?;
+        patterns.extend(parse_pattern_file(&contents));
+    }
+    targets.extend(patterns.into_iter().map(|p| {
+        if cli.whole_word {
+            RedactionTarget::LiteralWholeWord(p)
+        } else {
+            RedactionTarget::Literal(p)
+        }
+    }));
+
+    Ok(targets)
+}
+
+/// Fills in the options not given on the command line from `config`,
+/// returning the config's targets.
+///
+/// Targets from flags are added to the config's rather than replacing
+/// them, and `--strip-metadata` can only turn stripping on.
+fn apply_config(cli: &mut Cli, config: RedactionConfig) -> Result<Vec<RedactionTarget>> {
+    if cli.input.is_none() {
+        cli.input = config.input;
+    }
+    if cli.output.is_none() {
+        cli.output = config.output;
+    }
+    if cli.pages.is_none() {
+        cli.pages = config.pages;
+    }
+    if cli.fill_color.is_none() {
+        cli.fill_color = config
+            .fill_color
+            .as_deref()
+            .map(parse_color)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid fill_color in config: {}", e))?;
+    }
+    cli.strip_metadata |= config.strip_metadata;
+
+    Ok(config.targets)
+}
+
+/// Builds the redaction strategy from parsed command-line arguments.
+fn build_strategy(cli: &Cli) -> SecureRedactionStrategy {
+    let mut strategy = SecureRedactionStrategy::new()
+        .with_strip_metadata(cli.strip_metadata)
+        .with_remove_emptied_pages(cli.drop_empty_pages)
+        .with_hash_output(cli.hash)
+        .with_category_labels(cli.category_labels);
+
+    if let Some((r, g, b)) = cli.fill_color {
+        strategy = strategy.with_fill_color(r, g, b);
+    }
+    if let Some(label) = &cli.label {
+        strategy = strategy.with_overlay_text(label.clone());
+    }
+    if let Some(locale) = cli.phone_locale {
+        strategy = strategy.with_phone_locale(locale);
+    }
+
+    strategy
+}
+
+/// Wraps each span of `line` in `[-` and `-]`, as `git diff --word-diff`
+/// marks removed words.
+fn mark_spans(line: &str, spans: &[std::ops::Range<usize>]) -> String {
+    let mut marked = String::with_capacity(line.len() + spans.len() * 4);
+    let mut last = 0;
+    for span in spans {
+        marked.push_str(&line[last..span.start]);
+        marked.push_str("[-");
+        marked.push_str(&line[span.clone()]);
+        marked.push_str("-]");
+        last = span.end;
+    }
+    marked.push_str(&line[last..]);
+    marked
+}
+
+/// Parses a phone numbering plan name (nanp, intl, uk, de).
+fn parse_phone_locale(value: &str) -> Result<PhoneLocale, String> {
+    PhoneLocale::from_name(value).ok_or_else(|| {
+        format!(
+            "invalid phone locale '{}': expected nanp, intl, uk or de",
+            value
+        )
+    })
+}
+
+/// Parses a color name or `RRGGBB` hex value (with optional `#`) into RGB
+/// components in `0.0..=1.0`.
+fn parse_color(value: &str) -> Result<(f32, f32, f32), String> {
+    let hex = match value.to_ascii_lowercase().as_str() {
+        "black" => "000000".to_string(),
+        "white" => "ffffff".to_string(),
+        "gray" | "grey" => "808080".to_string(),
+        other => other.trim_start_matches('#').to_string(),
+    };
+
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "invalid color '{}': expected black, white, gray, or hex RRGGBB",
+            value
+        ));
+    }
+
+    let channel = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16).expect("Validated hex digits") as f32 / 255.0
+    };
+    Ok((channel(0), channel(2), channel(4)))
+}
+
+/// Lists the PDF files directly inside `dir`, sorted by name.
+fn pdf_files_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_pdf = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+        if is_pdf && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns true if the targets remove every piece of text on each page.
+fn is_full_page(targets: &[RedactionTarget]) -> bool {
     targets
+        .iter()
+        .any(|t| matches!(t, RedactionTarget::Literal(p) if p == ".+"))
+}
+
+/// Asks the user to confirm full-page redaction on stdin.
+///
+/// Anything but an explicit yes, including end of input, declines.
+fn confirm_full_page(input: &Path) -> Result<bool> {
+    let pages = lopdf::Document::load(input)
+        .map(|doc| format!("{} page(s)", doc.get_pages().len()))
+        .unwrap_or_else(|_| "every page".to_string());
+
+    eprint!("This will remove ALL text from {}. Continue? [y/N] ", pages);
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    if !std::io::stdin().is_terminal() {
+        eprintln!();
+    }
+
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// Redraws a one-line progress bar on stderr, ending the line once the
+/// output is being saved.
+///
+/// Nothing is drawn when stderr is not a terminal.
+fn render_progress(progress: RedactionProgress) {
+    const WIDTH: usize = 30;
+
+    let mut stderr = std::io::stderr();
+    if !stderr.is_terminal() {
+        return;
+    }
+
+    let phase = match progress.phase {
+        RedactionPhase::Resolving => "Resolving",
+        RedactionPhase::Searching => "Searching",
+        RedactionPhase::Applying => "Applying",
+        RedactionPhase::Saving => "Saving",
+    };
+    let filled = match progress.total_pages {
+        0 => 0,
+        total => WIDTH * progress.current_page / total,
+    };
+    let _ = write!(
+        stderr,
+        "\r[{}{}] {:<9} {}/{}",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        phase,
+        progress.current_page,
+        progress.total_pages
+    );
+    if progress.phase == RedactionPhase::Saving {
+        let _ = writeln!(stderr);
+    }
+}
+
+/// Initializes the logger from `RUST_LOG`, with `level` taking precedence.
+fn init_logging(level: Option<log::LevelFilter>) {
+    let mut builder = env_logger::Builder::from_default_env();
+    if let Some(level) = level {
+        builder.filter_level(level);
+    }
+    builder.init();
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let handler = RedactionHandler::new(cli.verbose);
+    let mut cli = Cli::parse();
+    init_logging(cli.log_level);
+    let config = match &cli.config {
+        Some(path) => RedactionConfig::load(path)
+            .with_context(|| format!("Failed to load config {}", path.display()))?,
+        None => RedactionConfig::default(),
+    };
+    let mut targets = apply_config(&mut cli, config)?;
+    let service = RedactionService::new(Box::new(build_strategy(&cli)))
+        .with_no_clobber(cli.no_clobber)
+        .with_preserve_mtime(cli.preserve_timestamps)
+        .with_require_matches(cli.require_matches);
+    let handler = RedactionHandler::new(cli.verbose, service)
+        .with_quiet(cli.quiet)
+        .with_format(cli.format);
 
     match &cli.command {
         Some(Commands::Extract { input, output }) => {
             // Extract subcommand
             handler.extract(input, output.as_deref())?;
         }
+        Some(Commands::Diff { input, output }) => handler.diff(input, output)?,
+        Some(Commands::ListMatchers) => handler.list_matchers(),
         None => {
             // Default: redaction mode
             let input = cli
@@ -198,8 +1024,55 @@ fn main() -> Result<()> {
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("--output is required"))?;
 
-            let targets = build_targets(&cli.pattern, cli.phones, cli.verizon);
-            handler.redact(input, output, targets)?;
+            targets.extend(build_targets(&cli)?);
+            if is_full_page(&targets) && !cli.yes && !confirm_full_page(input)? {
+                anyhow::bail!(
+                    "Aborted: full-page redaction not confirmed (pass --yes to skip the prompt)"
+                );
+            }
+            if output.as_os_str() == "-" {
+                if input.is_dir() {
+                    anyhow::bail!("--output - cannot be used with a directory input");
+                }
+                if cli.pages.is_some() {
+                    anyhow::bail!("--pages cannot be used with --output -");
+                }
+                if cli.audit_report.is_some() {
+                    anyhow::bail!("--audit-report cannot be used with --output -");
+                }
+                if cli.format == OutputFormat::Json {
+                    anyhow::bail!("--format json cannot be used with --output -");
+                }
+                if cli.hash {
+                    anyhow::bail!("--hash cannot be used with --output -");
+                }
+                if cli.preserve_timestamps {
+                    anyhow::bail!("--preserve-timestamps cannot be used with --output -");
+                }
+                handler.redact_to_stdout(input, targets)?;
+            } else if input.is_dir() {
+                if cli.pages.is_some() {
+                    anyhow::bail!("--pages cannot be used with a directory input");
+                }
+                if cli.audit_report.is_some() {
+                    anyhow::bail!("--audit-report cannot be used with a directory input");
+                }
+                if cli.format == OutputFormat::Json {
+                    anyhow::bail!("--format json cannot be used with a directory input");
+                }
+                if cli.hash {
+                    anyhow::bail!("--hash cannot be used with a directory input");
+                }
+                handler.redact_batch(input, output, targets, cli.continue_on_error)?;
+            } else {
+                handler.redact(
+                    input,
+                    output,
+                    targets,
+                    cli.pages.as_ref(),
+                    cli.audit_report.as_deref(),
+                )?;
+            }
         }
     }
 
@@ -210,20 +1083,251 @@ fn main() -> Result<()> {
 mod tests {
     use super::*;
 
+    fn targets_for(args: &[&str]) -> Vec<RedactionTarget> {
+        let cli = Cli::parse_from(std::iter::once("redactor").chain(args.iter().copied()));
+        build_targets(&cli).unwrap()
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("white"), Ok((1.0, 1.0, 1.0)));
+        assert_eq!(parse_color("#000000"), Ok((0.0, 0.0, 0.0)));
+        assert_eq!(parse_color("FF0000"), Ok((1.0, 0.0, 0.0)));
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("purple").is_err());
+    }
+
+    #[test]
+    fn test_mark_spans() {
+        assert_eq!(
+            mark_spans("Call 555-234-5678 or 555-987-6543", &[5..17, 21..33]),
+            "Call [-555-234-5678-] or [-555-987-6543-]"
+        );
+        assert_eq!(mark_spans("Unchanged", &[]), "Unchanged");
+    }
+
+    #[test]
+    fn test_incomplete_warnings_hold_no_values() {
+        let result = RedactionResult {
+            detected_not_redacted: vec!["555-234-5678".to_string()],
+            unredacted_categories: BTreeSet::from(["phone".to_string()]),
+            truncated_patterns: vec!["123-45-6789".to_string()],
+            truncated_categories: BTreeSet::from(["ssn".to_string()]),
+            ..Default::default()
+        };
+
+        let warnings = incomplete_warnings(&result);
+        assert_eq!(
+            warnings,
+            vec![
+                "1 detected value(s) not redacted (still present in output) (phone)",
+                "hit limit reached, more matches may remain in the output for 1 pattern(s) (ssn)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_phone_locale() {
+        assert_eq!(parse_phone_locale("uk"), Ok(PhoneLocale::Uk));
+        assert_eq!(parse_phone_locale("INTL"), Ok(PhoneLocale::International));
+        assert!(parse_phone_locale("fr").is_err());
+
+        let cli = Cli::parse_from(["redactor", "--phones", "--phone-locale", "de"]);
+        assert_eq!(cli.phone_locale, Some(PhoneLocale::De));
+        assert!(cli.phones);
+    }
+
+    #[test]
+    fn test_parse_pattern_file() {
+        let contents =
+            "# Customers\nJane Doe\n\n  John Smith  \r\n#ACME\nAcct 42 # not a comment\n";
+        assert_eq!(
+            parse_pattern_file(contents),
+            vec!["Jane Doe", "John Smith", "Acct 42 # not a comment"]
+        );
+        assert!(parse_pattern_file("\n# only comments\n").is_empty());
+    }
+
+    #[test]
+    fn test_pages_argument() {
+        let cli = Cli::parse_from(["redactor", "--pages", "1,3,5-9"]);
+        assert_eq!(
+            cli.pages.map(|p| p.to_string()),
+            Some("1,3,5-9".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["redactor", "--pages", "5-2"]).is_err());
+        assert!(Cli::try_parse_from(["redactor", "--pages", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_log_level_argument() {
+        let cli = Cli::parse_from(["redactor", "--log-level", "debug"]);
+        assert_eq!(cli.log_level, Some(log::LevelFilter::Debug));
+
+        let cli = Cli::parse_from(["redactor", "extract", "-i", "in.pdf", "--log-level", "off"]);
+        assert_eq!(cli.log_level, Some(log::LevelFilter::Off));
+
+        assert!(Cli::try_parse_from(["redactor", "--log-level", "loud"]).is_err());
+    }
+
+    #[test]
+    fn test_output_arguments() {
+        let cli = Cli::parse_from(["redactor", "--format", "json", "-q"]);
+        assert_eq!(cli.format, OutputFormat::Json);
+        assert!(cli.quiet);
+        assert_eq!(Cli::parse_from(["redactor"]).format, OutputFormat::Text);
+
+        assert!(Cli::try_parse_from(["redactor", "--quiet", "--verbose"]).is_err());
+        assert!(Cli::try_parse_from(["redactor", "--format", "yaml"]).is_err());
+
+        assert!(Cli::parse_from(["redactor", "--hash"]).hash);
+        assert!(Cli::parse_from(["redactor", "--category-labels"]).category_labels);
+        assert!(Cli::parse_from(["redactor", "--preserve-timestamps"]).preserve_timestamps);
+        assert!(!Cli::parse_from(["redactor"]).hash);
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let config = RedactionConfig::from_toml(
+            r#"
+            input = "config.pdf"
+            output = "config-out.pdf"
+            targets = ["phones"]
+            fill_color = "white"
+            strip_metadata = true
+            pages = "1-2"
+            "#,
+        )
+        .unwrap();
+
+        let mut cli = Cli::parse_from(["redactor", "-o", "cli-out.pdf", "--pages", "3", "--ssn"]);
+        let mut targets = apply_config(&mut cli, config.clone()).unwrap();
+        targets.extend(build_targets(&cli).unwrap());
+
+        assert_eq!(cli.input, Some(PathBuf::from("config.pdf")));
+        assert_eq!(cli.output, Some(PathBuf::from("cli-out.pdf")));
+        assert_eq!(cli.pages, Some(PageSelection::parse("3").unwrap()));
+        assert_eq!(cli.fill_color, Some((1.0, 1.0, 1.0)));
+        assert!(cli.strip_metadata);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::PhoneNumbers, RedactionTarget::Ssn]
+        );
+
+        let mut cli = Cli::parse_from(["redactor", "--fill-color", "black"]);
+        apply_config(&mut cli, config).unwrap();
+        assert_eq!(cli.fill_color, Some((0.0, 0.0, 0.0)));
+
+        let bad = RedactionConfig {
+            fill_color: Some("purple".to_string()),
+            ..RedactionConfig::default()
+        };
+        assert!(apply_config(&mut Cli::parse_from(["redactor"]), bad).is_err());
+    }
+
     #[test]
     fn test_target_building() {
         // Test verizon flag (should include phones and call details automatically)
-        let targets = build_targets(&[], false, true);
+        let targets = targets_for(&["--verizon"]);
         assert_eq!(targets.len(), 3); // VerizonAccount + PhoneNumbers + VerizonCallDetails
 
         // Test literal pattern
-        let targets = build_targets(&[String::from("test")], false, false);
+        let targets = targets_for(&["--pattern", "test"]);
         assert_eq!(targets.len(), 1);
         assert!(matches!(targets[0], RedactionTarget::Literal(_)));
 
+        let targets = targets_for(&["--pattern", "SECRET", "--whole-word"]);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::LiteralWholeWord("SECRET".to_string())]
+        );
+
+        let targets = targets_for(&["--name", "John Doe", "--name", "Jane Roe"]);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::NamedEntity(vec![
+                "John Doe".to_string(),
+                "Jane Roe".to_string()
+            ])]
+        );
+
         // Test phones flag
-        let targets = build_targets(&[], true, false);
+        let targets = targets_for(&["--phones"]);
         assert_eq!(targets.len(), 1);
         assert!(matches!(targets[0], RedactionTarget::PhoneNumbers));
+
+        // Test timestamps flag
+        let targets = targets_for(&["--timestamps"]);
+        assert_eq!(targets, vec![RedactionTarget::Timestamp]);
+
+        // Test dates flag
+        let targets = targets_for(&["--dates"]);
+        assert_eq!(targets, vec![RedactionTarget::Dates]);
+
+        // Test ZIP code flag
+        let targets = targets_for(&["--zip"]);
+        assert_eq!(targets, vec![RedactionTarget::PostalCode]);
+
+        // Test links flag
+        let targets = targets_for(&["--links"]);
+        assert_eq!(targets, vec![RedactionTarget::AllLinks]);
+
+        // Test ssn flag
+        let targets = targets_for(&["--ssn"]);
+        assert_eq!(targets, vec![RedactionTarget::Ssn]);
+
+        // Test emails flag
+        let targets = targets_for(&["--emails"]);
+        assert_eq!(targets, vec![RedactionTarget::Email]);
+
+        // Test credit cards flag
+        let targets = targets_for(&["--credit-cards"]);
+        assert_eq!(targets, vec![RedactionTarget::CreditCard]);
+
+        // Test IBAN flag
+        let targets = targets_for(&["--iban"]);
+        assert_eq!(targets, vec![RedactionTarget::Iban]);
+
+        // Test VIN flag
+        let targets = targets_for(&["--vin"]);
+        assert_eq!(targets, vec![RedactionTarget::Vin]);
+
+        // Test MAC address flag
+        let targets = targets_for(&["--mac"]);
+        assert_eq!(targets, vec![RedactionTarget::MacAddress]);
+
+        // Test handle flag
+        let targets = targets_for(&["--handles"]);
+        assert_eq!(targets, vec![RedactionTarget::Handle]);
+
+        // Test passport flags
+        let targets = targets_for(&["--passport"]);
+        assert_eq!(targets, vec![RedactionTarget::Passport { country: None }]);
+        let targets = targets_for(&["--passport-country", "US"]);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::Passport {
+                country: Some("US".to_string())
+            }]
+        );
+
+        // Test driver's license flags
+        let targets = targets_for(&["--dl"]);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::DriversLicense { state: None }]
+        );
+        let targets = targets_for(&["--dl", "--dl-state", "FL"]);
+        assert_eq!(
+            targets,
+            vec![RedactionTarget::DriversLicense {
+                state: Some("FL".to_string())
+            }]
+        );
+
+        // Test routing number flag
+        let targets = targets_for(&["--routing"]);
+        assert_eq!(targets, vec![RedactionTarget::RoutingNumber]);
     }
 }
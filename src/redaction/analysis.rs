@@ -0,0 +1,275 @@
+//! Document content analysis.
+//!
+//! Redaction can only find text a PDF actually encodes as text. Scanned
+//! pages are images, and some generators convert text to outlines, drawing
+//! each glyph as a filled path. Either way the words are visible but no
+//! search will match them, so a redaction can report success while leaving
+//! them untouched. These helpers classify each page by what its content
+//! streams draw so callers can warn about such pages.
+
+use super::xobjects::{deref, lookup, page_resources};
+use crate::error::{RedactorError, RedactorResult};
+use lopdf::content::Content;
+use lopdf::{Dictionary, Document, Object};
+use std::path::Path;
+
+/// Deepest form nesting followed, guarding against cyclic resources.
+const MAX_DEPTH: usize = 16;
+
+/// Curve segments a page without text must draw before its paths are
+/// taken for outlined text. A single line of outlined text uses hundreds,
+/// while charts and logos rarely come close.
+const VECTOR_TEXT_MIN_CURVES: usize = 200;
+
+/// What the pages of a PDF are made of.
+///
+/// Page numbers are 1-based.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentAnalysis {
+    /// Number of pages in the document
+    pub page_count: usize,
+
+    /// Pages that show text with a font, which search can find
+    pub text_pages: Vec<usize>,
+
+    /// Pages that draw at least one image
+    pub image_pages: Vec<usize>,
+
+    /// Pages without text that draw enough curves to look like text
+    /// converted to outlines
+    pub vector_text_suspected: Vec<usize>,
+}
+
+impl DocumentAnalysis {
+    /// Returns the pages with no extractable text, whose content cannot be
+    /// found by any text target.
+    pub fn pages_without_text(&self) -> Vec<usize> {
+        (1..=self.page_count)
+            .filter(|page| !self.text_pages.contains(page))
+            .collect()
+    }
+}
+
+/// Classifies each page of a PDF by the content it draws, following the
+/// Form XObjects it uses.
+pub fn analyze(input: &Path) -> RedactorResult<DocumentAnalysis> {
+    let bytes = std::fs::read(input).map_err(|e| RedactorError::Io {
+        path: input.to_path_buf(),
+        source: e,
+    })?;
+    let doc = Document::load_mem(&bytes).map_err(|e| RedactorError::BackendError {
+        backend: "LoPDF".to_string(),
+        message: "Failed to read page content".to_string(),
+        source: Some(Box::new(e)),
+    })?;
+
+    let pages = doc.get_pages();
+    let mut analysis = DocumentAnalysis {
+        page_count: pages.len(),
+        ..DocumentAnalysis::default()
+    };
+    for (page_idx, page_id) in pages.values().enumerate() {
+        let page = page_idx + 1;
+        let mut summary = ContentSummary::default();
+        if let Ok(content) = doc.get_page_content(*page_id) {
+            summary.scan(&doc, &content, &page_resources(&doc, *page_id), 0);
+        }
+
+        if summary.text {
+            analysis.text_pages.push(page);
+        }
+        if summary.images {
+            analysis.image_pages.push(page);
+        }
+        if !summary.text && summary.curves >= VECTOR_TEXT_MIN_CURVES {
+            analysis.vector_text_suspected.push(page);
+        }
+    }
+
+    Ok(analysis)
+}
+
+/// What a page's content streams draw.
+#[derive(Debug, Default)]
+struct ContentSummary {
+    text: bool,
+    images: bool,
+    curves: usize,
+}
+
+impl ContentSummary {
+    /// Adds what one content stream draws, including the forms it uses.
+    fn scan(&mut self, doc: &Document, content: &[u8], resources: &[&Dictionary], depth: usize) {
+        let Ok(content) = Content::decode(content) else {
+            return;
+        };
+
+        for op in &content.operations {
+            match op.operator.as_str() {
+                "Tj" | "TJ" | "'" | "\"" => self.text |= shows_text(&op.operands),
+                "c" | "v" | "y" => self.curves += 1,
+                "Do" => {
+                    if let Some(name) = op.operands.first().and_then(|n| n.as_name().ok()) {
+                        self.scan_xobject(doc, name, resources, depth);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds the image or form named `name`.
+    fn scan_xobject(
+        &mut self,
+        doc: &Document,
+        name: &[u8],
+        resources: &[&Dictionary],
+        depth: usize,
+    ) {
+        let Some(Object::Stream(stream)) = lookup(doc, resources, b"XObject", name) else {
+            return;
+        };
+        let subtype = stream.dict.get(b"Subtype").and_then(Object::as_name);
+        match subtype {
+            Ok(b"Image") => self.images = true,
+            Ok(b"Form") if depth < MAX_DEPTH => {
+                // Forms without their own resources use those of their parent
+                let own = stream
+                    .dict
+                    .get(b"Resources")
+                    .ok()
+                    .and_then(|r| deref(doc, r).as_dict().ok());
+                let form_resources: Vec<&Dictionary> = match own {
+                    Some(own) => vec![own],
+                    None => resources.to_vec(),
+                };
+                let content = stream
+                    .decompressed_content()
+                    .unwrap_or_else(|_| stream.content.clone());
+                self.scan(doc, &content, &form_resources, depth + 1);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if a text operator's operands hold a non-empty string.
+fn shows_text(operands: &[Object]) -> bool {
+    operands.iter().any(|operand| match operand {
+        Object::String(bytes, _) => !bytes.is_empty(),
+        Object::Array(parts) => shows_text(parts),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Stream};
+
+    /// Writes a PDF with one page per entry of `pages`, each drawing its
+    /// content stream with a font `F1` and an image `Im1` available.
+    fn document(pages: &[String]) -> tempfile::NamedTempFile {
+        let mut doc = Document::with_version("1.5");
+        let font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let image = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => 1,
+                "Height" => 1,
+                "ColorSpace" => "DeviceGray",
+                "BitsPerComponent" => 8,
+            },
+            vec![0],
+        ));
+
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|content| {
+                let content =
+                    doc.add_object(Stream::new(Dictionary::new(), content.clone().into_bytes()));
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+                    "Contents" => content,
+                    "Resources" => dictionary! {
+                        "Font" => dictionary! { "F1" => font },
+                        "XObject" => dictionary! { "Im1" => image },
+                    },
+                })
+                .into()
+            })
+            .collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => kids.len() as i64,
+                "Kids" => kids,
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        doc.save_to(&mut file).unwrap();
+        file
+    }
+
+    /// Draws `count` curve segments, as outlined glyphs do.
+    fn curves(count: usize) -> String {
+        let mut content = String::from("0 0 m");
+        for _ in 0..count {
+            content.push_str(" 1 2 3 4 5 6 c");
+        }
+        content.push_str(" f");
+        content
+    }
+
+    #[test]
+    fn test_classifies_pages() {
+        let pdf = document(&[
+            "BT /F1 12 Tf 72 720 Td (Account 12345) Tj ET".to_string(),
+            "q 612 0 0 792 0 0 cm /Im1 Do Q".to_string(),
+            curves(VECTOR_TEXT_MIN_CURVES),
+            curves(10),
+            format!("BT /F1 12 Tf [(Caption)] TJ ET {}", curves(500)),
+        ]);
+
+        let analysis = analyze(pdf.path()).unwrap();
+
+        assert_eq!(
+            analysis,
+            DocumentAnalysis {
+                page_count: 5,
+                text_pages: vec![1, 5],
+                image_pages: vec![2],
+                vector_text_suspected: vec![3],
+            }
+        );
+        assert_eq!(analysis.pages_without_text(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_empty_strings_are_not_text() {
+        let pdf = document(&["BT /F1 12 Tf () Tj [()] TJ ET".to_string()]);
+
+        let analysis = analyze(pdf.path()).unwrap();
+
+        assert!(analysis.text_pages.is_empty());
+        assert_eq!(analysis.pages_without_text(), vec![1]);
+    }
+
+    #[test]
+    fn test_missing_file_is_io_error() {
+        let result = analyze(Path::new("/nonexistent/input.pdf"));
+        assert!(matches!(result, Err(RedactorError::Io { .. })));
+    }
+}
@@ -0,0 +1,132 @@
+//! Comment annotations (sticky notes, free text and highlights).
+//!
+//! A comment's text is stored in its annotation dictionary rather than in
+//! the page content stream, so redacting page content leaves it in place.
+//! These helpers read comment text and delete comments that contain
+//! redacted text.
+
+use super::forms::{decode_text_string, matching_needles};
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfObject, PdfPage};
+
+/// Annotation subtypes whose `/Contents` is free text entered by a person.
+const COMMENT_SUBTYPES: [&[u8]; 3] = [b"Text", b"FreeText", b"Highlight"];
+
+/// Returns the text of every comment in the document.
+pub(super) fn comment_texts(pdf_doc: &PdfDocument) -> RedactorResult<Vec<String>> {
+    let mut texts = Vec::new();
+    for page_idx in 0..pdf_doc.page_count().map_err(annotation_error)? {
+        let page = pdf_doc.find_page(page_idx).map_err(annotation_error)?;
+        page_comment_texts(&page, &mut texts).map_err(annotation_error)?;
+    }
+    Ok(texts)
+}
+
+/// Deletes every comment on `page` whose text contains one of `needles`,
+/// along with its pop-up window.
+///
+/// The text and appearance of each deleted comment are cleared too, so
+/// nothing is left in the file if the unlinked object is written out.
+///
+/// Returns, for each deleted comment, the indices into `needles` of every
+/// needle its text contained.
+pub(super) fn delete_matching_comments(
+    page: &PdfPage,
+    needles: &[&str],
+) -> RedactorResult<Vec<Vec<usize>>> {
+    delete_comments(&page.object(), needles).map_err(annotation_error)
+}
+
+fn page_comment_texts(page: &PdfObject, texts: &mut Vec<String>) -> Result<(), mupdf::Error> {
+    let Some(annots) = page.get_dict("Annots")? else {
+        return Ok(());
+    };
+    for idx in 0..annots.len()? {
+        if let Some(annot) = annots.get_array(idx as i32)? {
+            texts.extend(comment_text(&annot)?);
+        }
+    }
+    Ok(())
+}
+
+fn delete_comments(page: &PdfObject, needles: &[&str]) -> Result<Vec<Vec<usize>>, mupdf::Error> {
+    let Some(mut annots) = page.get_dict("Annots")? else {
+        return Ok(Vec::new());
+    };
+
+    let mut deleted = Vec::new();
+    let mut popups = Vec::new();
+
+    // Walk backwards so deleting an entry doesn't shift unvisited ones
+    for idx in (0..annots.len()? as i32).rev() {
+        let Some(mut annot) = annots.get_array(idx)? else {
+            continue;
+        };
+        let Some(text) = comment_text(&annot)? else {
+            continue;
+        };
+        let found = matching_needles(&text, needles);
+        if found.is_empty() {
+            continue;
+        }
+
+        if let Some(popup) = annot.get_dict("Popup")? {
+            popups.extend(object_number(&popup)?);
+        }
+        for key in ["Contents", "RC", "AP"] {
+            annot.dict_delete(key)?;
+        }
+        annots.array_delete(idx)?;
+        deleted.push(found);
+    }
+
+    if !popups.is_empty() {
+        for idx in (0..annots.len()? as i32).rev() {
+            let Some(annot) = annots.get_array(idx)? else {
+                continue;
+            };
+            if object_number(&annot)?.is_some_and(|num| popups.contains(&num)) {
+                annots.array_delete(idx)?;
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Returns a comment annotation's text, or `None` for other annotations
+/// and comments without text.
+fn comment_text(annot: &PdfObject) -> Result<Option<String>, mupdf::Error> {
+    let is_comment = match annot.get_dict("Subtype")? {
+        Some(subtype) if subtype.is_name()? => COMMENT_SUBTYPES.contains(&subtype.as_name()?),
+        _ => false,
+    };
+    if !is_comment {
+        return Ok(None);
+    }
+
+    match annot.get_dict("Contents")? {
+        Some(contents) if contents.is_string()? => {
+            let text = decode_text_string(contents.as_bytes()?);
+            Ok((!text.is_empty()).then_some(text))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the object number of an indirect reference.
+fn object_number(obj: &PdfObject) -> Result<Option<i32>, mupdf::Error> {
+    if obj.is_indirect()? {
+        obj.as_indirect().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn annotation_error(e: mupdf::Error) -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "Failed to process annotations".to_string(),
+        page: None,
+        source: Some(Box::new(e)),
+    }
+}
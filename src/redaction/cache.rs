@@ -0,0 +1,189 @@
+//! Extracted-text cache shared across operations on the same document.
+//!
+//! Extraction is the most expensive step of pattern resolution, and
+//! workflows that inspect a document before redacting it would otherwise
+//! extract the same text several times.
+
+use super::mapped::read_input;
+use crate::error::RedactorResult;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Cache key derived from the document bytes, so renamed or copied files
+/// still hit and modified files never return stale text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ContentKey {
+    len: u64,
+    hash: u64,
+}
+
+impl ContentKey {
+    fn of(bytes: &[u8]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Self {
+            len: bytes.len() as u64,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Most recently used entries first.
+    entries: VecDeque<(ContentKey, Arc<String>)>,
+    hits: usize,
+    misses: usize,
+}
+
+/// Least-recently-used cache of extracted text keyed by file content hash.
+///
+/// The cache is opt-in (see [`RedactionService::with_text_cache`]) because
+/// it keeps the full text of up to `capacity` documents in memory.
+///
+/// [`RedactionService::with_text_cache`]: super::RedactionService::with_text_cache
+#[derive(Debug)]
+pub struct TextCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl TextCache {
+    /// Creates a cache holding the text of at most `capacity` documents.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Returns the cached text for `input`, calling `extract` on a miss.
+    pub fn get_or_extract<F>(&self, input: &Path, extract: F) -> RedactorResult<Arc<String>>
+    where
+        F: FnOnce(&Path) -> RedactorResult<String>,
+    {
+        let key = ContentKey::of(&read_input(input)?);
+
+        {
+            let mut state = self.lock();
+            if let Some(pos) = state.entries.iter().position(|(k, _)| *k == key) {
+                let entry = state.entries.remove(pos).expect("Position is in bounds");
+                let text = Arc::clone(&entry.1);
+                state.entries.push_front(entry);
+                state.hits += 1;
+                return Ok(text);
+            }
+            state.misses += 1;
+        }
+
+        // Extract without holding the lock so other documents aren't blocked
+        let text = Arc::new(extract(input)?);
+
+        let mut state = self.lock();
+        state.entries.retain(|(k, _)| *k != key);
+        state.entries.push_front((key, Arc::clone(&text)));
+        state.entries.truncate(self.capacity);
+
+        Ok(text)
+    }
+
+    /// Number of lookups served from the cache.
+    pub fn hits(&self) -> usize {
+        self.lock().hits
+    }
+
+    /// Number of lookups that required a fresh extraction.
+    pub fn misses(&self) -> usize {
+        self.lock().misses
+    }
+
+    /// Number of documents currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns true if no documents are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all cached text.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // A panic in another thread can't leave the state inconsistent
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, contents: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_cache_hit_on_same_content() {
+        let dir = TempDir::new().unwrap();
+        let a = write(&dir, "a.pdf", "same bytes");
+        let b = write(&dir, "b.pdf", "same bytes");
+        let cache = TextCache::new(4);
+
+        let first = cache
+            .get_or_extract(&a, |_| Ok("text".to_string()))
+            .unwrap();
+        let second = cache
+            .get_or_extract(&b, |_| panic!("should not re-extract"))
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_cache_miss_after_modification() {
+        let dir = TempDir::new().unwrap();
+        let path = write(&dir, "a.pdf", "version 1");
+        let cache = TextCache::new(4);
+
+        cache
+            .get_or_extract(&path, |_| Ok("one".to_string()))
+            .unwrap();
+        std::fs::write(&path, "version 2").unwrap();
+        let text = cache
+            .get_or_extract(&path, |_| Ok("two".to_string()))
+            .unwrap();
+
+        assert_eq!(text.as_str(), "two");
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let dir = TempDir::new().unwrap();
+        let a = write(&dir, "a.pdf", "a");
+        let b = write(&dir, "b.pdf", "b");
+        let c = write(&dir, "c.pdf", "c");
+        let cache = TextCache::new(2);
+
+        cache.get_or_extract(&a, |_| Ok("a".to_string())).unwrap();
+        cache.get_or_extract(&b, |_| Ok("b".to_string())).unwrap();
+        cache.get_or_extract(&a, |_| Ok("a".to_string())).unwrap();
+        cache.get_or_extract(&c, |_| Ok("c".to_string())).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        cache.get_or_extract(&a, |_| Ok("a".to_string())).unwrap();
+        assert_eq!(cache.misses(), 3, "'a' should have survived eviction");
+    }
+}
@@ -0,0 +1,99 @@
+//! Fill colors for redaction boxes.
+//!
+//! A redaction annotation's interior color (`/IC`) may have one, three or
+//! four components, for gray, RGB or CMYK. Fills are configured as RGB, so
+//! on pages composited in another color space they are converted first;
+//! print-ready bills often use a gray or CMYK transparency group, and an
+//! RGB fill there can fail in MuPDF.
+
+use mupdf::pdf::PdfObject;
+
+/// Device color space a fill is given in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorSpace {
+    Gray,
+    Rgb,
+    Cmyk,
+}
+
+impl ColorSpace {
+    /// Parses a color space name, device or calibrated.
+    ///
+    /// Returns `None` for spaces without a fixed component count, such as
+    /// `ICCBased` or `Separation`.
+    pub(crate) fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"DeviceGray" | b"CalGray" | b"G" => Some(Self::Gray),
+            b"DeviceRGB" | b"CalRGB" | b"RGB" => Some(Self::Rgb),
+            b"DeviceCMYK" | b"CMYK" => Some(Self::Cmyk),
+            _ => None,
+        }
+    }
+
+    /// Converts an RGB color with components in `0.0..=1.0` to this space.
+    pub(crate) fn components(self, [r, g, b]: [f32; 3]) -> Vec<f32> {
+        match self {
+            // ITU-R BT.601 luma, as used by MuPDF
+            Self::Gray => vec![0.299 * r + 0.587 * g + 0.114 * b],
+            Self::Rgb => vec![r, g, b],
+            Self::Cmyk => {
+                let k = 1.0 - r.max(g).max(b);
+                if k >= 1.0 {
+                    return vec![0.0, 0.0, 0.0, 1.0];
+                }
+                let ink = |c: f32| (1.0 - c - k) / (1.0 - k);
+                vec![ink(r), ink(g), ink(b), k]
+            }
+        }
+    }
+}
+
+/// Returns the components of `rgb` in the color space of `page`'s
+/// transparency group.
+///
+/// Pages without a group are RGB. Returns `None` when the group's space
+/// has no fixed component count, in which case MuPDF's default black is
+/// the safe choice.
+pub(crate) fn page_fill(page: &PdfObject, rgb: [f32; 3]) -> Result<Option<Vec<f32>>, mupdf::Error> {
+    let Some(space) = page
+        .get_dict("Group")?
+        .map(|group| group.get_dict("CS"))
+        .transpose()?
+        .flatten()
+    else {
+        return Ok(Some(ColorSpace::Rgb.components(rgb)));
+    };
+
+    if !space.is_name()? {
+        return Ok(None);
+    }
+    Ok(ColorSpace::from_name(space.as_name()?).map(|space| space.components(rgb)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(ColorSpace::from_name(b"DeviceGray"), Some(ColorSpace::Gray));
+        assert_eq!(ColorSpace::from_name(b"CalRGB"), Some(ColorSpace::Rgb));
+        assert_eq!(ColorSpace::from_name(b"DeviceCMYK"), Some(ColorSpace::Cmyk));
+        assert_eq!(ColorSpace::from_name(b"ICCBased"), None);
+    }
+
+    #[test]
+    fn test_components() {
+        let white = [1.0, 1.0, 1.0];
+        let black = [0.0, 0.0, 0.0];
+        let red = [1.0, 0.0, 0.0];
+
+        assert_eq!(ColorSpace::Gray.components(white), vec![1.0]);
+        assert_eq!(ColorSpace::Gray.components(black), vec![0.0]);
+        assert_eq!(ColorSpace::Rgb.components(red), red.to_vec());
+
+        assert_eq!(ColorSpace::Cmyk.components(white), vec![0.0; 4]);
+        assert_eq!(ColorSpace::Cmyk.components(black), vec![0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(ColorSpace::Cmyk.components(red), vec![0.0, 1.0, 1.0, 0.0]);
+    }
+}
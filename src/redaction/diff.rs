@@ -0,0 +1,240 @@
+//! Before/after text comparison for redaction previews.
+//!
+//! A [`TextDiff`] shows the extracted text of a document next to the same
+//! text with every span that would be redacted masked out. It is a cheap
+//! way to check targets before writing a PDF.
+//!
+//! A [`LineDiff`] compares the text of a document with that of its
+//! redacted copy, to check afterwards that only the intended content
+//! disappeared.
+
+use similar::{ChangeTag, DiffTag};
+use std::ops::Range;
+
+/// Character used to mask redacted spans.
+pub const MASK_CHAR: char = '█';
+
+/// Extracted text before and after redaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextDiff {
+    /// Text as extracted from the input
+    pub before: String,
+
+    /// Text with redacted spans replaced by [`MASK_CHAR`]
+    pub after: String,
+}
+
+impl TextDiff {
+    /// Builds a diff by masking every occurrence of `redacted` in `before`.
+    ///
+    /// The full-page sentinel `.+` masks all non-whitespace text.
+    pub fn new<'a>(before: String, redacted: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut masked = vec![false; before.len()];
+
+        for pattern in redacted {
+            if pattern == ".+" {
+                for (idx, ch) in before.char_indices() {
+                    if !ch.is_whitespace() {
+                        masked[idx..idx + ch.len_utf8()].fill(true);
+                    }
+                }
+            } else if !pattern.is_empty() {
+                for (start, found) in before.match_indices(pattern) {
+                    masked[start..start + found.len()].fill(true);
+                }
+            }
+        }
+
+        let after = before
+            .char_indices()
+            .map(|(idx, ch)| if masked[idx] { MASK_CHAR } else { ch })
+            .collect();
+
+        Self { before, after }
+    }
+
+    /// Returns true if redaction would change the text.
+    pub fn has_changes(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+/// A line of a [`LineDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A line found in both texts
+    Unchanged(String),
+
+    /// A line of the first text that changed or disappeared
+    Removed {
+        /// The line as it was
+        line: String,
+
+        /// Byte ranges of `line` missing from the second text
+        spans: Vec<Range<usize>>,
+    },
+
+    /// A line of the second text that replaced one of the first, or is new
+    Added(String),
+}
+
+/// Line-oriented comparison of two extracted texts, such as a document's
+/// text before and after redaction.
+///
+/// Changed lines are paired up in order, and each removed line records the
+/// spans of characters its counterpart lost; a line with no counterpart
+/// lost all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LineDiff {
+    /// Every line of both texts, in order
+    pub lines: Vec<DiffLine>,
+}
+
+impl LineDiff {
+    /// Compares `before` with `after`.
+    pub fn new(before: &str, after: &str) -> Self {
+        let diff = similar::TextDiff::from_lines(before, after);
+        let mut lines = Vec::new();
+
+        for op in diff.ops() {
+            let (tag, old, new) = op.as_tag_tuple();
+            let old_lines = &diff.old_slices()[old];
+            let new_lines = &diff.new_slices()[new];
+
+            if tag == DiffTag::Equal {
+                lines.extend(
+                    old_lines
+                        .iter()
+                        .map(|line| DiffLine::Unchanged(trim_newline(line).to_string())),
+                );
+                continue;
+            }
+
+            for (idx, line) in old_lines.iter().enumerate() {
+                let line = trim_newline(line);
+                let spans = match new_lines.get(idx) {
+                    Some(replacement) => removed_spans(line, trim_newline(replacement)),
+                    None if line.is_empty() => Vec::new(),
+                    None => vec![0..line.len()],
+                };
+                lines.push(DiffLine::Removed {
+                    line: line.to_string(),
+                    spans,
+                });
+            }
+            lines.extend(
+                new_lines
+                    .iter()
+                    .map(|line| DiffLine::Added(trim_newline(line).to_string())),
+            );
+        }
+
+        Self { lines }
+    }
+
+    /// Returns the text of every removed span, trimmed, skipping spans
+    /// that are only whitespace.
+    pub fn removed(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .flat_map(|line| match line {
+                DiffLine::Removed { line, spans } => spans
+                    .iter()
+                    .map(|span| line[span.clone()].trim())
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            })
+            .filter(|span| !span.is_empty())
+            .collect()
+    }
+
+    /// Returns true if the texts differ.
+    pub fn has_changes(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| !matches!(line, DiffLine::Unchanged(_)))
+    }
+}
+
+/// Strips the line ending `from_lines` leaves on each line.
+fn trim_newline(line: &str) -> &str {
+    line.trim_end_matches(['\r', '\n'])
+}
+
+/// Returns the byte ranges of `old` that are missing from `new`, merging
+/// adjacent characters into one span.
+fn removed_spans(old: &str, new: &str) -> Vec<Range<usize>> {
+    let diff = similar::TextDiff::from_chars(old, new);
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    let mut offset = 0;
+
+    for change in diff.iter_all_changes() {
+        let len = change.value().len();
+        match change.tag() {
+            ChangeTag::Delete => {
+                match spans.last_mut() {
+                    Some(span) if span.end == offset => span.end += len,
+                    _ => spans.push(offset..offset + len),
+                }
+                offset += len;
+            }
+            ChangeTag::Equal => offset += len,
+            ChangeTag::Insert => {}
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_every_occurrence() {
+        let diff = TextDiff::new("call 555-1234 or 555-1234".to_string(), ["555-1234"]);
+        assert_eq!(diff.after, "call ████████ or ████████");
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn test_overlapping_patterns_and_full_page() {
+        let diff = TextDiff::new("ab cd".to_string(), ["abc", "b c", "a"]);
+        assert_eq!(diff.after, "████d");
+
+        let diff = TextDiff::new("ab cd".to_string(), [".+"]);
+        assert_eq!(diff.after, "██ ██");
+    }
+
+    #[test]
+    fn test_line_diff_marks_removed_spans() {
+        let diff = LineDiff::new(
+            "Statement\nCall 555-234-5678 today\nThanks\n",
+            "Statement\nCall  today\nThanks\n",
+        );
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.lines,
+            vec![
+                DiffLine::Unchanged("Statement".to_string()),
+                DiffLine::Removed {
+                    line: "Call 555-234-5678 today".to_string(),
+                    spans: vec![5..17],
+                },
+                DiffLine::Added("Call  today".to_string()),
+                DiffLine::Unchanged("Thanks".to_string()),
+            ]
+        );
+        assert_eq!(diff.removed(), vec!["555-234-5678"]);
+    }
+
+    #[test]
+    fn test_line_diff_of_dropped_lines_and_identical_text() {
+        let diff = LineDiff::new("Name\nSSN 123-45-6789\n\nEnd\n", "Name\nEnd\n");
+        assert_eq!(diff.removed(), vec!["SSN 123-45-6789"]);
+
+        let diff = LineDiff::new("Same\ntext\n", "Same\ntext\n");
+        assert!(!diff.has_changes());
+        assert!(diff.removed().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Recognizing PDF features MuPDF cannot open.
+//!
+//! When MuPDF fails to open a document, its error rarely says why in terms
+//! a user can act on. These helpers look at the raw file for the usual
+//! causes: encryption by a security handler other than the standard
+//! password one (certificate or DRM protection), a PDF version newer than
+//! MuPDF knows, and a linearized file whose cross-reference table is
+//! damaged beyond repair.
+
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+/// How far into the file the header and linearization dictionary are
+/// looked for.
+const HEAD: usize = 1024;
+
+/// Names the feature of `bytes` that keeps MuPDF from opening them, if it
+/// is one of the recognized causes.
+///
+/// Only meaningful once opening has failed: a linearized file or a recent
+/// header alone is no reason to reject a document.
+pub(super) fn unsupported_feature(bytes: &[u8]) -> Option<String> {
+    if let Some(handler) = security_handler(bytes).filter(|handler| handler != "Standard") {
+        return Some(format!(
+            "encryption with the {} security handler (only password encryption is \
+             supported; remove the protection with the software that applied it)",
+            handler
+        ));
+    }
+
+    let head = &bytes[..bytes.len().min(HEAD)];
+    if let Some((major, minor)) = header_version(head).filter(|&(major, _)| major > 2) {
+        return Some(format!(
+            "PDF version {}.{} (versions up to 2.0 are supported)",
+            major, minor
+        ));
+    }
+
+    if head.windows(11).any(|w| w == b"/Linearized") {
+        return Some(
+            "linearized PDF with a damaged cross-reference table (re-save or repair the \
+             file, e.g. with qpdf, and try again)"
+                .to_string(),
+        );
+    }
+
+    None
+}
+
+/// Returns the version in the `%PDF-x.y` header.
+fn header_version(head: &[u8]) -> Option<(u32, u32)> {
+    static HEADER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?-u)%PDF-(\d{1,2})\.(\d{1,2})").expect("Valid regex"));
+    let caps = HEADER.captures(head)?;
+    let number = |idx: usize| -> Option<u32> { std::str::from_utf8(&caps[idx]).ok()?.parse().ok() };
+    Some((number(1)?, number(2)?))
+}
+
+/// Returns the `/Filter` of the encryption dictionary the trailer refers
+/// to, if the document is encrypted.
+///
+/// The encryption dictionary is never itself encrypted or stored in an
+/// object stream, so it can be read from the raw bytes.
+fn security_handler(bytes: &[u8]) -> Option<String> {
+    static ENCRYPT: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?-u)/Encrypt\s+(\d+)\s+(\d+)\s+R").expect("Valid regex"));
+    static FILTER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?-u)/Filter\s*/([^\s/<>\[\]()]+)").expect("Valid regex"));
+
+    // The last trailer is the current one
+    let caps = ENCRYPT.captures_iter(bytes).last()?;
+    let number = std::str::from_utf8(&caps[1]).ok()?;
+    let generation = std::str::from_utf8(&caps[2]).ok()?;
+
+    let object = Regex::new(&format!(
+        r"(?s-u)(?:^|[^0-9]){}\s+{}\s+obj(.*?)endobj",
+        number, generation
+    ))
+    .ok()?;
+    let dict = object.captures_iter(bytes).last()?;
+    let filter = FILTER.captures(&dict[1])?;
+    Some(String::from_utf8_lossy(&filter[1]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encrypted_with(filter: &str) -> Vec<u8> {
+        format!(
+            "%PDF-1.7\n1 0 obj\n<< /Type /Catalog >>\nendobj\n\
+             11 0 obj\n<< /Filter /{} /V 4 /CF << /StdCF << /CFM /AESV2 >> >> >>\nendobj\n\
+             trailer\n<< /Root 1 0 R /Encrypt 11 0 R >>\n%%EOF\n",
+            filter
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_unknown_security_handler() {
+        let feature = unsupported_feature(&encrypted_with("Adobe.PubSec")).unwrap();
+        assert!(
+            feature.contains("Adobe.PubSec security handler"),
+            "{}",
+            feature
+        );
+
+        // Password encryption is handled by unlocking, not rejected here
+        assert_eq!(
+            security_handler(&encrypted_with("Standard")).as_deref(),
+            Some("Standard")
+        );
+        assert_eq!(unsupported_feature(&encrypted_with("Standard")), None);
+    }
+
+    #[test]
+    fn test_newer_pdf_version() {
+        let feature = unsupported_feature(b"%PDF-3.0\n%%EOF\n").unwrap();
+        assert!(feature.starts_with("PDF version 3.0"), "{}", feature);
+        assert_eq!(unsupported_feature(b"%PDF-2.0\n%%EOF\n"), None);
+    }
+
+    #[test]
+    fn test_linearized_file() {
+        let bytes = b"%PDF-1.5\n1 0 obj\n<< /Linearized 1 /L 1234 >>\nendobj\nxref\nbroken";
+        let feature = unsupported_feature(bytes).unwrap();
+        assert!(feature.starts_with("linearized PDF"), "{}", feature);
+        assert_eq!(unsupported_feature(b"%PDF-1.5\ngarbage"), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Interactive form (AcroForm) field values.
+//!
+//! Field values are stored in the document's form dictionary rather than
+//! in page content streams, so neither text extraction nor MuPDF search
+//! sees them. These helpers read the values and clear fields that contain
+//! redacted text.
+
+use super::pages::PageSelection;
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfObject};
+use std::collections::HashMap;
+
+/// Nesting depth beyond which a field tree is assumed to be cyclic.
+const MAX_FIELD_DEPTH: usize = 32;
+
+/// Returns the text value of every form field that has one.
+pub(super) fn field_values(pdf_doc: &PdfDocument) -> RedactorResult<Vec<String>> {
+    let mut values = Vec::new();
+    for field in fields(pdf_doc).map_err(form_error)? {
+        if let Some(value) = text_value(&field).map_err(form_error)? {
+            values.push(value);
+        }
+    }
+    Ok(values)
+}
+
+/// Clears the value and appearance of every field whose value contains one
+/// of `needles`.
+///
+/// With `pages`, a field is only cleared if one of its widgets is on a
+/// selected page. A field with no widget on any page is shown nowhere, so
+/// it is cleared whatever the selection.
+///
+/// Returns, for each cleared field, the indices into `needles` of every
+/// needle its value contained.
+pub(super) fn clear_matching_fields(
+    pdf_doc: &PdfDocument,
+    needles: &[&str],
+    pages: Option<&PageSelection>,
+) -> RedactorResult<Vec<Vec<usize>>> {
+    let widget_pages = match pages {
+        Some(_) => widget_pages(pdf_doc).map_err(form_error)?,
+        None => HashMap::new(),
+    };
+
+    let mut cleared = Vec::new();
+    for mut field in fields(pdf_doc).map_err(form_error)? {
+        let Some(value) = text_value(&field).map_err(form_error)? else {
+            continue;
+        };
+        let found = matching_needles(&value, needles);
+        if found.is_empty() {
+            continue;
+        }
+        if let Some(pages) = pages {
+            let mut shown_on = Vec::new();
+            field_pages(&field, &widget_pages, 0, &mut shown_on).map_err(form_error)?;
+            if !shown_on.is_empty() && !shown_on.iter().any(|&page| pages.contains(page)) {
+                continue;
+            }
+        }
+
+        clear_field(&mut field, 0).map_err(form_error)?;
+        cleared.push(found);
+    }
+    Ok(cleared)
+}
+
+/// Returns the indices into `needles` of every non-empty needle that
+/// `value` contains.
+pub(super) fn matching_needles(value: &str, needles: &[&str]) -> Vec<usize> {
+    needles
+        .iter()
+        .enumerate()
+        .filter(|(_, needle)| !needle.is_empty() && value.contains(**needle))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Collects every field dictionary in the form's field tree.
+fn fields(pdf_doc: &PdfDocument) -> Result<Vec<PdfObject>, mupdf::Error> {
+    let mut fields = Vec::new();
+    let Some(form) = pdf_doc.catalog()?.get_dict("AcroForm")? else {
+        return Ok(fields);
+    };
+    if let Some(roots) = form.get_dict("Fields")? {
+        collect_fields(&roots, 0, &mut fields)?;
+    }
+    Ok(fields)
+}
+
+fn collect_fields(
+    kids: &PdfObject,
+    depth: usize,
+    fields: &mut Vec<PdfObject>,
+) -> Result<(), mupdf::Error> {
+    if depth > MAX_FIELD_DEPTH {
+        return Ok(());
+    }
+    for idx in 0..kids.len()? {
+        let Some(field) = kids.get_array(idx as i32)? else {
+            continue;
+        };
+        if let Some(children) = field.get_dict("Kids")? {
+            collect_fields(&children, depth + 1, fields)?;
+        }
+        fields.push(field);
+    }
+    Ok(())
+}
+
+/// Maps the object number of every annotation to the 1-based page it is
+/// on.
+fn widget_pages(pdf_doc: &PdfDocument) -> Result<HashMap<i32, usize>, mupdf::Error> {
+    let mut pages = HashMap::new();
+    for page_idx in 0..pdf_doc.page_count()? {
+        let page = pdf_doc.find_page(page_idx)?;
+        let Some(annots) = page.get_dict("Annots")? else {
+            continue;
+        };
+        for idx in 0..annots.len()? {
+            if let Some(annot) = annots.get_array(idx as i32)? {
+                if annot.is_indirect()? {
+                    pages.insert(annot.as_indirect()?, page_idx as usize + 1);
+                }
+            }
+        }
+    }
+    Ok(pages)
+}
+
+/// Adds the page of each widget of `field` to `pages`: the field itself if
+/// it is a widget, and its kids.
+fn field_pages(
+    field: &PdfObject,
+    widget_pages: &HashMap<i32, usize>,
+    depth: usize,
+    pages: &mut Vec<usize>,
+) -> Result<(), mupdf::Error> {
+    if field.is_indirect()? {
+        pages.extend(widget_pages.get(&field.as_indirect()?));
+    }
+    if depth < MAX_FIELD_DEPTH {
+        if let Some(kids) = field.get_dict("Kids")? {
+            for idx in 0..kids.len()? {
+                if let Some(kid) = kids.get_array(idx as i32)? {
+                    field_pages(&kid, widget_pages, depth + 1, pages)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns a field's own text value, if it has one.
+fn text_value(field: &PdfObject) -> Result<Option<String>, mupdf::Error> {
+    match field.get_dict("V")? {
+        Some(value) if value.is_string()? => {
+            let text = decode_text_string(value.as_bytes()?);
+            Ok((!text.is_empty()).then_some(text))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Removes a field's value and the appearance streams of the field and its
+/// widgets, so viewers have nothing left to render.
+fn clear_field(field: &mut PdfObject, depth: usize) -> Result<(), mupdf::Error> {
+    field.dict_delete("V")?;
+    field.dict_delete("DV")?;
+    field.dict_delete("AP")?;
+
+    if depth < MAX_FIELD_DEPTH {
+        if let Some(kids) = field.get_dict("Kids")? {
+            for idx in 0..kids.len()? {
+                if let Some(mut kid) = kids.get_array(idx as i32)? {
+                    clear_field(&mut kid, depth + 1)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a PDF text string, which is UTF-16BE when it starts with a byte
+/// order mark and PDFDocEncoding (close to Latin-1) otherwise.
+pub(super) fn decode_text_string(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => bytes.iter().map(|&b| char::from(b)).collect(),
+    }
+}
+
+fn form_error(e: mupdf::Error) -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "Failed to process form fields".to_string(),
+        page: None,
+        source: Some(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text_string() {
+        assert_eq!(decode_text_string(b"555-234-5678"), "555-234-5678");
+        assert_eq!(
+            decode_text_string(&[0xFE, 0xFF, 0x00, b'J', 0x00, b'o']),
+            "Jo"
+        );
+        assert_eq!(decode_text_string(b"R\xE9sum\xE9"), "Résumé");
+    }
+}
@@ -0,0 +1,177 @@
+//! Page coordinate conversion.
+//!
+//! MuPDF reports positions with the origin at the top-left of the visible
+//! page box, y pointing down and the page's `/Rotate` applied, while
+//! content streams draw in PDF user space. These helpers convert between
+//! the two for code that edits pages through lopdf.
+
+use lopdf::{Document, Object, ObjectId};
+
+/// Page box assumed when a page declares none (US Letter).
+const DEFAULT_MEDIA_BOX: [f32; 4] = [0.0, 0.0, 612.0, 792.0];
+
+/// Maps between MuPDF page coordinates and PDF user space.
+///
+/// MuPDF puts the origin at the top-left of the visible page box, with y
+/// pointing down and the page's `/Rotate` applied.
+pub(crate) struct PageTransform {
+    /// Quarter turns of the page's `/Rotate`, clockwise
+    quarter_turns: u8,
+    /// Visible page box in user space as `[x0, y0, x1, y1]`
+    page_box: [f32; 4],
+}
+
+impl PageTransform {
+    /// Reads the transform from a page's boxes and `/Rotate`.
+    pub(crate) fn for_page(doc: &Document, page_id: ObjectId) -> Self {
+        let media = inherited(doc, page_id, b"MediaBox")
+            .and_then(|obj| as_box(doc, obj))
+            .unwrap_or(DEFAULT_MEDIA_BOX);
+        let page_box = match inherited(doc, page_id, b"CropBox").and_then(|obj| as_box(doc, obj)) {
+            Some(crop) => [
+                crop[0].max(media[0]),
+                crop[1].max(media[1]),
+                crop[2].min(media[2]),
+                crop[3].min(media[3]),
+            ],
+            None => media,
+        };
+        let rotate = inherited(doc, page_id, b"Rotate")
+            .and_then(|obj| obj.as_i64().ok())
+            .unwrap_or(0);
+
+        Self {
+            quarter_turns: (rotate.rem_euclid(360) / 90) as u8,
+            page_box,
+        }
+    }
+
+    /// Rotates a user-space point the way MuPDF does, by `-Rotate`.
+    fn rotate(&self, x: f32, y: f32, inverse: bool) -> (f32, f32) {
+        let turns = if inverse {
+            (4 - self.quarter_turns) % 4
+        } else {
+            self.quarter_turns
+        };
+        match turns {
+            1 => (y, -x),
+            2 => (-x, -y),
+            3 => (-y, x),
+            _ => (x, y),
+        }
+    }
+
+    /// Returns the offset MuPDF subtracts to put the page's top-left
+    /// corner at the origin.
+    fn origin(&self) -> (f32, f32) {
+        let [bx0, by0, bx1, by1] = self.page_box;
+        let corners = [(bx0, by0), (bx0, by1), (bx1, by0), (bx1, by1)].map(|(x, y)| {
+            let (rx, ry) = self.rotate(x, y, false);
+            (rx, -ry)
+        });
+        let tx = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min);
+        let ty = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min);
+        (tx, ty)
+    }
+
+    /// Converts a MuPDF rectangle to a user-space `(x0, y0, x1, y1)`.
+    pub(crate) fn to_user_space(&self, rect: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (tx, ty) = self.origin();
+        let (x0, y0, x1, y1) = rect;
+        bounds([(x0, y0), (x1, y1)].map(|(x, y)| self.rotate(x + tx, -(y + ty), true)))
+    }
+
+    /// Converts a user-space rectangle to MuPDF page coordinates.
+    pub(crate) fn to_page_space(&self, rect: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        let (tx, ty) = self.origin();
+        let (x0, y0, x1, y1) = rect;
+        bounds([(x0, y0), (x1, y1)].map(|(x, y)| {
+            let (rx, ry) = self.rotate(x, y, false);
+            (rx - tx, -ry - ty)
+        }))
+    }
+}
+
+/// Returns the rectangle spanned by two corners.
+fn bounds(points: [(f32, f32); 2]) -> (f32, f32, f32, f32) {
+    (
+        points[0].0.min(points[1].0),
+        points[0].1.min(points[1].1),
+        points[0].0.max(points[1].0),
+        points[0].1.max(points[1].1),
+    )
+}
+
+/// Looks up a page attribute, following `/Parent` for inheritable ones.
+fn inherited<'a>(doc: &'a Document, page_id: ObjectId, key: &[u8]) -> Option<&'a Object> {
+    let mut node = doc.get_dictionary(page_id).ok()?;
+    // Bound the walk in case of a cyclic page tree
+    for _ in 0..64 {
+        if let Ok(value) = node.get(key) {
+            return doc.dereference(value).ok().map(|(_, obj)| obj);
+        }
+        let parent = node.get(b"Parent").and_then(Object::as_reference).ok()?;
+        node = doc.get_dictionary(parent).ok()?;
+    }
+    None
+}
+
+fn as_box(doc: &Document, obj: &Object) -> Option<[f32; 4]> {
+    let values = obj.as_array().ok()?;
+    let mut coords = [0.0; 4];
+    if values.len() != 4 {
+        return None;
+    }
+    for (coord, value) in coords.iter_mut().zip(values) {
+        *coord = doc.dereference(value).ok()?.1.as_float().ok()?;
+    }
+    Some([
+        coords[0].min(coords[2]),
+        coords[1].min(coords[3]),
+        coords[0].max(coords[2]),
+        coords[1].max(coords[3]),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(quarter_turns: u8) -> PageTransform {
+        PageTransform {
+            quarter_turns,
+            page_box: [0.0, 0.0, 612.0, 792.0],
+        }
+    }
+
+    #[test]
+    fn test_unrotated_page_flips_y() {
+        let rect = transform(0).to_user_space((72.0, 100.0, 172.0, 112.0));
+        assert_eq!(rect, (72.0, 680.0, 172.0, 692.0));
+    }
+
+    #[test]
+    fn test_rotated_page_round_trips() {
+        // Turned clockwise, the page's bottom-left corner is at the top-left
+        let rect = transform(1).to_user_space((0.0, 0.0, 10.0, 20.0));
+        assert_eq!(rect, (0.0, 0.0, 20.0, 10.0));
+
+        // Upside down, the bottom-right corner is at the top-left
+        let rect = transform(2).to_user_space((0.0, 0.0, 10.0, 20.0));
+        assert_eq!(rect, (602.0, 0.0, 612.0, 20.0));
+    }
+
+    #[test]
+    fn test_page_space_inverts_user_space() {
+        for quarter_turns in 0..4 {
+            let transform = transform(quarter_turns);
+            let rect = (72.0, 100.0, 172.0, 112.0);
+            assert_eq!(
+                transform.to_page_space(transform.to_user_space(rect)),
+                rect,
+                "{} quarter turn(s)",
+                quarter_turns
+            );
+        }
+    }
+}
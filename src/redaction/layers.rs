@@ -0,0 +1,297 @@
+//! Optional content group (layer) support.
+//!
+//! MuPDF only searches content that is visible under a document's default
+//! layer configuration, so text in a hidden layer would survive redaction
+//! even though it is still present in the file. These helpers inspect the
+//! layer configuration and temporarily switch every layer on.
+
+use crate::error::{RedactorError, RedactorResult};
+use lopdf::{Dictionary, Document, Object, ObjectId};
+use std::path::Path;
+
+/// An optional content group declared by a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerInfo {
+    /// Layer name as shown by PDF viewers
+    pub name: String,
+
+    /// Whether the layer is shown under the default configuration
+    pub visible: bool,
+}
+
+/// Lists the layers declared in a PDF's `/OCProperties`.
+///
+/// Documents without layers return an empty list.
+pub fn list_layers(input: &Path) -> RedactorResult<Vec<LayerInfo>> {
+    let bytes = std::fs::read(input).map_err(|e| RedactorError::Io {
+        path: input.to_path_buf(),
+        source: e,
+    })?;
+    let doc = load(&bytes)?;
+
+    let Some(props) = oc_properties(&doc) else {
+        return Ok(Vec::new());
+    };
+    let config = props
+        .get(b"D")
+        .and_then(|d| doc.dereference(d))
+        .and_then(|(_, d)| d.as_dict())
+        .ok();
+
+    let ids = |key: &[u8], dict: Option<&Dictionary>| -> Vec<ObjectId> {
+        dict.and_then(|d| d.get(key).ok())
+            .and_then(|a| a.as_array().ok())
+            .map(|a| a.iter().filter_map(|o| o.as_reference().ok()).collect())
+            .unwrap_or_default()
+    };
+    let base_off = config
+        .and_then(|d| d.get(b"BaseState").ok())
+        .and_then(|s| s.as_name().ok())
+        == Some(b"OFF".as_slice());
+    let on = ids(b"ON", config);
+    let off = ids(b"OFF", config);
+
+    let layers = ids(b"OCGs", Some(props))
+        .into_iter()
+        .map(|id| {
+            let name = doc
+                .get_dictionary(id)
+                .and_then(|ocg| ocg.get(b"Name"))
+                .and_then(Object::as_str)
+                .map(decode_text)
+                .unwrap_or_default();
+            let visible = if base_off {
+                on.contains(&id)
+            } else {
+                !off.contains(&id)
+            };
+            LayerInfo { name, visible }
+        })
+        .collect();
+
+    Ok(layers)
+}
+
+/// Returns a copy of the document with every layer switched on, together
+/// with the original default configuration so it can be restored.
+///
+/// Returns `None` if no layer is hidden by default. Documents that cannot
+/// declare layers are not parsed.
+pub(crate) fn show_all_layers(bytes: &[u8]) -> RedactorResult<Option<(Vec<u8>, Dictionary)>> {
+    if !may_have_layers(bytes) {
+        return Ok(None);
+    }
+    let mut doc = load(bytes)?;
+
+    let Some(config) = default_config_mut(&mut doc) else {
+        return Ok(None);
+    };
+    let original = config.clone();
+
+    let base_off = config
+        .get(b"BaseState")
+        .and_then(Object::as_name)
+        .is_ok_and(|s| s == b"OFF");
+    let has_off = config
+        .get(b"OFF")
+        .and_then(Object::as_array)
+        .is_ok_and(|a| !a.is_empty());
+    if !base_off && !has_off {
+        return Ok(None);
+    }
+
+    config.set("BaseState", Object::Name(b"ON".to_vec()));
+    config.remove(b"OFF");
+
+    Ok(Some((save(&mut doc)?, original)))
+}
+
+/// Puts back the default layer configuration captured by
+/// [`show_all_layers`] in a written output file.
+pub(crate) fn restore_layer_config(output: &Path, original: &Dictionary) -> RedactorResult<()> {
+    let bytes = std::fs::read(output).map_err(|e| RedactorError::Io {
+        path: output.to_path_buf(),
+        source: e,
+    })?;
+
+    if let Some(bytes) = with_layer_config(&bytes, original)? {
+        std::fs::write(output, bytes).map_err(|e| RedactorError::Io {
+            path: output.to_path_buf(),
+            source: e,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Returns a copy of a written document with the default layer
+/// configuration captured by [`show_all_layers`] put back.
+///
+/// Returns `None` if the document has no layer configuration.
+pub(crate) fn with_layer_config(
+    bytes: &[u8],
+    original: &Dictionary,
+) -> RedactorResult<Option<Vec<u8>>> {
+    let mut doc = load(bytes)?;
+
+    let Some(config) = default_config_mut(&mut doc) else {
+        return Ok(None);
+    };
+    *config = original.clone();
+    save(&mut doc).map(Some)
+}
+
+/// Returns false if `bytes` cannot declare layers, without parsing them.
+///
+/// Layers are declared under the catalog's `/OCProperties` key, which can
+/// only be hidden from a byte search by storing the catalog in a
+/// compressed object stream.
+fn may_have_layers(bytes: &[u8]) -> bool {
+    let contains = |needle: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+    contains(b"/OCProperties") || contains(b"/ObjStm")
+}
+
+fn load(bytes: &[u8]) -> RedactorResult<Document> {
+    Document::load_mem(bytes).map_err(|e| RedactorError::BackendError {
+        backend: "LoPDF".to_string(),
+        message: "Failed to read layer configuration".to_string(),
+        source: Some(Box::new(e)),
+    })
+}
+
+fn save(doc: &mut Document) -> RedactorResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    doc.save_to(&mut bytes)
+        .map_err(|e| RedactorError::BackendError {
+            backend: "LoPDF".to_string(),
+            message: "Failed to write layer configuration".to_string(),
+            source: Some(Box::new(e)),
+        })?;
+    Ok(bytes)
+}
+
+fn oc_properties(doc: &Document) -> Option<&Dictionary> {
+    let props = doc.catalog().ok()?.get(b"OCProperties").ok()?;
+    doc.dereference(props).ok()?.1.as_dict().ok()
+}
+
+/// Finds the default configuration dictionary (`/OCProperties /D`), which
+/// may be stored directly or behind references at either level.
+fn default_config_mut(doc: &mut Document) -> Option<&mut Dictionary> {
+    let props = doc.catalog().ok()?.get(b"OCProperties").ok()?;
+    let props_id = props.as_reference().ok();
+    let config_id = oc_properties(doc)?.get(b"D").ok()?.as_reference().ok();
+
+    if let Some(id) = config_id {
+        return doc.get_dictionary_mut(id).ok();
+    }
+    let props = match props_id {
+        Some(id) => doc.get_dictionary_mut(id).ok()?,
+        None => doc
+            .catalog_mut()
+            .ok()?
+            .get_mut(b"OCProperties")
+            .ok()?
+            .as_dict_mut()
+            .ok()?,
+    };
+    props.get_mut(b"D").ok()?.as_dict_mut().ok()
+}
+
+/// Decodes a PDF text string (UTF-16BE with BOM, or PDFDocEncoding treated
+/// as Latin-1).
+fn decode_text(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    /// Builds a minimal document with a visible and a hidden layer.
+    fn layered_document() -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let shown = doc.add_object(dictionary! {
+            "Type" => "OCG",
+            "Name" => Object::string_literal("Shown"),
+        });
+        let hidden = doc.add_object(dictionary! {
+            "Type" => "OCG",
+            "Name" => Object::string_literal("Hidden"),
+        });
+        let pages = doc.add_object(dictionary! {
+            "Type" => "Pages",
+            "Kids" => Vec::<Object>::new(),
+            "Count" => 0,
+        });
+        let catalog = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages,
+            "OCProperties" => dictionary! {
+                "OCGs" => vec![shown.into(), hidden.into()],
+                "D" => dictionary! { "OFF" => vec![hidden.into()] },
+            },
+        });
+        doc.trailer.set("Root", catalog);
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_list_layers_reports_visibility() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("layers.pdf");
+        std::fs::write(&path, layered_document()).unwrap();
+
+        let layers = list_layers(&path).unwrap();
+        assert_eq!(
+            layers,
+            vec![
+                LayerInfo {
+                    name: "Shown".to_string(),
+                    visible: true
+                },
+                LayerInfo {
+                    name: "Hidden".to_string(),
+                    visible: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_show_all_layers_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("shown.pdf");
+
+        let (shown, original) = show_all_layers(&layered_document()).unwrap().unwrap();
+        std::fs::write(&path, shown).unwrap();
+        assert!(list_layers(&path).unwrap().iter().all(|l| l.visible));
+        assert!(show_all_layers(&std::fs::read(&path).unwrap())
+            .unwrap()
+            .is_none());
+
+        restore_layer_config(&path, &original).unwrap();
+        assert!(!list_layers(&path).unwrap()[1].visible);
+    }
+
+    #[test]
+    fn test_document_without_layers_is_not_parsed() {
+        assert!(may_have_layers(&layered_document()));
+        // Not a PDF lopdf can read, but nothing in it can declare a layer
+        assert!(show_all_layers(b"%PDF-1.4 not parsed").unwrap().is_none());
+        assert!(show_all_layers(b"%PDF-1.5 /OCProperties").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Hyperlinks (link annotations with URI actions).
+//!
+//! A link's target is stored in its annotation's action dictionary rather
+//! than in the page content stream, so redacting the visible text of a
+//! link leaves the URI it points to, such as an internal host name, in the
+//! file. These helpers read link URIs and delete links whose URI contains
+//! redacted text.
+
+use super::forms::matching_needles;
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfObject, PdfPage};
+
+/// Returns the URI of every link in the document.
+pub(super) fn link_uris(pdf_doc: &PdfDocument) -> RedactorResult<Vec<String>> {
+    let mut uris = Vec::new();
+    for page_idx in 0..pdf_doc.page_count().map_err(link_error)? {
+        let page = pdf_doc.find_page(page_idx).map_err(link_error)?;
+        page_link_uris(&page, &mut uris).map_err(link_error)?;
+    }
+    Ok(uris)
+}
+
+/// Deletes every link on `page` whose URI contains one of `needles`, or
+/// every link with a URI if `all` is set.
+///
+/// The action of each deleted link is cleared too, so the URI is not left
+/// in the file if the unlinked annotation is written out.
+///
+/// Returns, for each deleted link, the indices into `needles` of every
+/// needle its URI contained; empty for links deleted only because of `all`.
+pub(super) fn delete_matching_links(
+    page: &PdfPage,
+    needles: &[&str],
+    all: bool,
+) -> RedactorResult<Vec<Vec<usize>>> {
+    delete_links(&page.object(), needles, all).map_err(link_error)
+}
+
+fn page_link_uris(page: &PdfObject, uris: &mut Vec<String>) -> Result<(), mupdf::Error> {
+    let Some(annots) = page.get_dict("Annots")? else {
+        return Ok(());
+    };
+    for idx in 0..annots.len()? {
+        if let Some(annot) = annots.get_array(idx as i32)? {
+            uris.extend(link_uri(&annot)?);
+        }
+    }
+    Ok(())
+}
+
+fn delete_links(
+    page: &PdfObject,
+    needles: &[&str],
+    all: bool,
+) -> Result<Vec<Vec<usize>>, mupdf::Error> {
+    let Some(mut annots) = page.get_dict("Annots")? else {
+        return Ok(Vec::new());
+    };
+
+    let mut deleted = Vec::new();
+
+    // Walk backwards so deleting an entry doesn't shift unvisited ones
+    for idx in (0..annots.len()? as i32).rev() {
+        let Some(mut annot) = annots.get_array(idx)? else {
+            continue;
+        };
+        let Some(uri) = link_uri(&annot)? else {
+            continue;
+        };
+        let found = matching_needles(&uri, needles);
+        if found.is_empty() && !all {
+            continue;
+        }
+
+        if let Some(mut action) = annot.get_dict("A")? {
+            action.dict_delete("URI")?;
+        }
+        for key in ["A", "Contents"] {
+            annot.dict_delete(key)?;
+        }
+        annots.array_delete(idx)?;
+        deleted.push(found);
+    }
+
+    Ok(deleted)
+}
+
+/// Returns the URI a link annotation opens, or `None` for other
+/// annotations and links to other destinations.
+fn link_uri(annot: &PdfObject) -> Result<Option<String>, mupdf::Error> {
+    let is_link = match annot.get_dict("Subtype")? {
+        Some(subtype) if subtype.is_name()? => subtype.as_name()? == b"Link",
+        _ => false,
+    };
+    if !is_link {
+        return Ok(None);
+    }
+    let Some(action) = annot.get_dict("A")? else {
+        return Ok(None);
+    };
+
+    match action.get_dict("URI")? {
+        // URIs are 7-bit ASCII, but be lenient with what generators write
+        Some(uri) if uri.is_string()? => {
+            let uri = String::from_utf8_lossy(uri.as_bytes()?).into_owned();
+            Ok((!uri.is_empty()).then_some(uri))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn link_error(e: mupdf::Error) -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "Failed to process links".to_string(),
+        page: None,
+        source: Some(Box::new(e)),
+    }
+}
@@ -0,0 +1,142 @@
+//! Memory-mapped input reading.
+//!
+//! Reading a PDF into a `Vec<u8>` before parsing it holds a private copy of
+//! the whole file, which for multi-hundred-megabyte uploads doubles the
+//! memory a redaction needs. Mapping the file instead lets the kernel page
+//! it in from the page cache on demand and drop those pages under pressure.
+
+use crate::error::{RedactorError, RedactorResult};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Deref;
+use std::path::Path;
+
+/// The contents of an input file, mapped where possible.
+pub(crate) enum InputBytes {
+    /// The file mapped read-only into memory
+    Mapped(Mmap),
+
+    /// The file read into memory, where it could not be mapped
+    Read(Vec<u8>),
+}
+
+impl Deref for InputBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(map) => map,
+            Self::Read(bytes) => bytes,
+        }
+    }
+}
+
+/// Maps `input` into memory, falling back to reading it where mapping
+/// fails, as for empty files or filesystems without mmap support.
+///
+/// The file must not be truncated while the mapping is in use: on most
+/// platforms reading a page past the new end of the file kills the
+/// process. Inputs are only read, and the redaction tools never modify
+/// them in place.
+pub(crate) fn read_input(input: &Path) -> RedactorResult<InputBytes> {
+    let io_error = |e| RedactorError::Io {
+        path: input.to_path_buf(),
+        source: e,
+    };
+    let file = File::open(input).map_err(io_error)?;
+
+    // SAFETY: the map is read-only and dropped with the returned value;
+    // see above for the requirement on other writers.
+    match unsafe { Mmap::map(&file) } {
+        Ok(map) => Ok(InputBytes::Mapped(map)),
+        Err(e) => {
+            log::debug!("Reading {} instead of mapping it: {}", input.display(), e);
+            std::fs::read(input).map(InputBytes::Read).map_err(io_error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_input_maps_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"%PDF-1.7\n").unwrap();
+
+        let bytes = read_input(file.path()).unwrap();
+        assert!(matches!(bytes, InputBytes::Mapped(_)));
+        assert_eq!(&*bytes, b"%PDF-1.7\n");
+    }
+
+    #[test]
+    fn test_read_input_reads_empty_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        let bytes = read_input(file.path()).unwrap();
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_read_input_missing_file_is_io_error() {
+        let result = read_input(Path::new("/nonexistent/input.pdf"));
+        assert!(matches!(result, Err(RedactorError::Io { .. })));
+    }
+
+    /// Returns this process's resident anonymous memory, in KiB.
+    #[cfg(target_os = "linux")]
+    fn rss_anon_kib() -> usize {
+        std::fs::read_to_string("/proc/self/status")
+            .unwrap()
+            .lines()
+            .find_map(|line| line.strip_prefix("RssAnon:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+            .unwrap()
+    }
+
+    /// Compares the private memory held while scanning a 256 MiB input
+    /// mapped and read. Run with `cargo test --release -- --ignored
+    /// test_mapping_avoids_private_copy --nocapture`.
+    #[test]
+    #[ignore = "benchmark: writes a 256 MiB file"]
+    #[cfg(target_os = "linux")]
+    fn test_mapping_avoids_private_copy() {
+        const SIZE: usize = 256 << 20;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let chunk = vec![b'x'; 1 << 20];
+        for _ in 0..SIZE / chunk.len() {
+            file.write_all(&chunk).unwrap();
+        }
+        file.flush().unwrap();
+        drop(chunk);
+
+        // Touch every byte, as a parser does
+        let scan = |bytes: &[u8]| bytes.iter().map(|&b| b as usize).sum::<usize>();
+
+        let before = rss_anon_kib();
+        let mapped = read_input(file.path()).unwrap();
+        assert!(matches!(mapped, InputBytes::Mapped(_)));
+        assert_eq!(scan(&mapped), SIZE * b'x' as usize);
+        let mapped_kib = rss_anon_kib().saturating_sub(before);
+        drop(mapped);
+
+        let before = rss_anon_kib();
+        let read = std::fs::read(file.path()).unwrap();
+        assert_eq!(scan(&read), SIZE * b'x' as usize);
+        let read_kib = rss_anon_kib().saturating_sub(before);
+        drop(read);
+
+        println!(
+            "private memory for a {} MiB input: mapped {} KiB, read {} KiB",
+            SIZE >> 20,
+            mapped_kib,
+            read_kib
+        );
+        assert!(
+            mapped_kib * 4 < read_kib,
+            "mapping should not copy the file into private memory"
+        );
+    }
+}
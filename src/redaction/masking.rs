@@ -0,0 +1,99 @@
+//! Masking redaction strategy.
+//!
+//! Removes the leading characters of each numeric match and leaves the last
+//! few readable, so a redacted account number can still be referenced
+//! (`**********00001`).
+
+use super::secure::SecureRedactionStrategy;
+use super::strategy::{
+    RedactionHit, RedactionOptions, RedactionResult, RedactionStrategy, RedactionTarget,
+};
+use crate::error::RedactorResult;
+use std::path::Path;
+
+/// Secure redaction that keeps the trailing characters of numeric matches.
+///
+/// Matches are located as by [`SecureRedactionStrategy`]. Each hit is split
+/// horizontally in proportion to its character count, and only the masked
+/// prefix is physically removed and overdrawn with `*`. Matches without
+/// digits are removed whole.
+///
+/// Character positions are estimated assuming evenly spaced glyphs, so
+/// with proportional fonts part of a neighbouring character may be
+/// removed or left visible at the split.
+#[derive(Debug, Clone)]
+pub struct MaskingRedactionStrategy {
+    inner: SecureRedactionStrategy,
+}
+
+impl MaskingRedactionStrategy {
+    /// Creates a masking strategy that keeps the last 4 characters.
+    pub fn new() -> Self {
+        Self {
+            inner: SecureRedactionStrategy::new().with_keep_trailing(4),
+        }
+    }
+
+    /// Sets how many trailing characters of each match stay visible.
+    pub fn with_keep_trailing(mut self, keep: usize) -> Self {
+        self.inner = self.inner.with_keep_trailing(keep);
+        self
+    }
+}
+
+impl Default for MaskingRedactionStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedactionStrategy for MaskingRedactionStrategy {
+    fn redact(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        self.inner.redact(input, output, targets)
+    }
+
+    fn redact_with_options(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<RedactionResult> {
+        self.inner
+            .redact_with_options(input, output, targets, options)
+    }
+
+    fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        self.inner.preview(input, targets)
+    }
+
+    fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        self.inner.apply_hits(input, output, hits)
+    }
+
+    fn extract_text(&self, input: &Path) -> RedactorResult<String> {
+        self.inner.extract_text(input)
+    }
+
+    fn name(&self) -> &str {
+        "MaskingRedaction"
+    }
+
+    fn is_secure(&self) -> bool {
+        true
+    }
+}
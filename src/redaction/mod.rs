@@ -3,14 +3,53 @@
 //! This module provides a strategy pattern for different redaction approaches,
 //! allowing for flexible and testable redaction implementations.
 
+pub mod analysis;
+mod annotations;
+pub mod cache;
+mod colors;
+pub mod diff;
+mod features;
+mod forms;
+mod geometry;
+pub mod layers;
+mod links;
+mod mapped;
+pub mod masking;
+mod outline;
+pub mod overlay;
+pub mod pages;
+mod raster;
+mod runs;
 pub mod secure;
 pub mod strategy;
+mod styles;
+pub mod worker;
+mod xobjects;
 
+pub use analysis::DocumentAnalysis;
+pub use cache::TextCache;
+pub use diff::{DiffLine, LineDiff, TextDiff};
+pub use layers::LayerInfo;
+pub use masking::MaskingRedactionStrategy;
+pub use overlay::OverlayRedactionStrategy;
+pub use pages::PageSelection;
 pub use secure::SecureRedactionStrategy;
-pub use strategy::{RedactionResult, RedactionStrategy, RedactionTarget};
+pub use strategy::{
+    PageRedactionStats, RedactionHit, RedactionOptions, RedactionPhase, RedactionProgress,
+    RedactionResult, RedactionStrategy, RedactionTarget,
+};
+pub use worker::{MupdfWorker, WorkerJob, WorkerStrategy};
 
+use crate::domain::PatternMatcher;
 use crate::error::{RedactorError, RedactorResult};
-use std::path::Path;
+use filetime::FileTime;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
 
 /// Redaction service coordinating strategy execution.
 ///
@@ -19,12 +58,28 @@ use std::path::Path;
 /// progress reporting and error handling.
 pub struct RedactionService {
     strategy: Box<dyn RedactionStrategy>,
+    text_cache: Option<TextCache>,
+    custom_matchers: HashMap<String, Box<dyn PatternMatcher>>,
+    no_clobber: bool,
+    require_matches: bool,
+    min_confidence: Option<f32>,
+    preserve_mtime: bool,
+    concurrent: bool,
 }
 
 impl RedactionService {
     /// Creates a new redaction service with the specified strategy.
     pub fn new(strategy: Box<dyn RedactionStrategy>) -> Self {
-        Self { strategy }
+        Self {
+            strategy,
+            text_cache: None,
+            custom_matchers: HashMap::new(),
+            no_clobber: false,
+            require_matches: false,
+            min_confidence: None,
+            preserve_mtime: false,
+            concurrent: false,
+        }
     }
 
     /// Creates a service with secure (physical removal) redaction.
@@ -32,8 +87,101 @@ impl RedactionService {
         Self::new(Box::new(SecureRedactionStrategy::default()))
     }
 
+    /// Enables reuse of extracted text across operations on the same file.
+    ///
+    /// With a cache, extracting text and then redacting the same document
+    /// extracts only once. Disabled by default to avoid holding document
+    /// text in memory.
+    pub fn with_text_cache(mut self, cache: TextCache) -> Self {
+        self.text_cache = Some(cache);
+        self
+    }
+
+    /// Registers `matcher` under `name`, to be used by
+    /// [`RedactionTarget::Custom`] targets with that name.
+    ///
+    /// Registering a name again replaces its matcher.
+    pub fn with_custom_matcher(mut self, name: &str, matcher: Box<dyn PatternMatcher>) -> Self {
+        self.custom_matchers.insert(name.to_string(), matcher);
+        self
+    }
+
+    /// Refuses to overwrite existing output files when set.
+    ///
+    /// Redacting to an existing path then fails with
+    /// [`RedactorError::OutputExists`] before the input is read, including
+    /// redacting a file in place. Outputs are overwritten by default.
+    pub fn with_no_clobber(mut self, no_clobber: bool) -> Self {
+        self.no_clobber = no_clobber;
+        self
+    }
+
+    /// Gives each output the modification time of its input when set, so a
+    /// redacted copy keeps the original's place in a chronology.
+    ///
+    /// The input's time is read before redacting, so it is kept when
+    /// redacting a file in place too. Outputs get the current time by
+    /// default.
+    pub fn with_preserve_mtime(mut self, preserve: bool) -> Self {
+        self.preserve_mtime = preserve;
+        self
+    }
+
+    /// Fails with [`RedactorError::PatternNotFound`] when a target matches
+    /// nothing, instead of writing an unredacted copy.
+    ///
+    /// Each target is [previewed](Self::preview) on its own before anything
+    /// is written, which costs an extra search of the document per target.
+    /// Text found only in form fields, comments or link URIs does not count
+    /// as a match, and [`RedactionTarget::AllLinks`] is not checked.
+    /// Encrypted documents cannot be previewed, so redacting one
+    /// with this set is an error.
+    pub fn with_require_matches(mut self, require: bool) -> Self {
+        self.require_matches = require;
+        self
+    }
+
+    /// Skips matches that custom matchers score below `min_confidence`
+    /// (see [`PatternMatcher::extract_all_scored`]).
+    ///
+    /// This trades recall for precision on noisy documents. Built-in
+    /// targets are resolved by the strategy and are not filtered; to
+    /// filter one, register its matcher (such as
+    /// [`VerizonAccountMatcher`](crate::domain::VerizonAccountMatcher)) as
+    /// a custom matcher instead.
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = Some(min_confidence);
+        self
+    }
+
+    /// Runs all of the strategy's MuPDF work on `worker`.
+    ///
+    /// The service can then be shared between threads without a lock of
+    /// its own, and [`redact_batch`](Self::redact_batch) processes files
+    /// concurrently: reading, matching custom patterns and writing overlap
+    /// while MuPDF itself runs one job at a time. Use
+    /// [`MupdfWorker::global`] unless documents should be kept apart from
+    /// the rest of the process. A strategy's
+    /// [timeout](SecureRedactionStrategy::with_timeout) still applies, but
+    /// a timed-out redaction keeps the worker busy until MuPDF returns.
+    pub fn with_mupdf_worker(self, worker: Arc<MupdfWorker>) -> Self {
+        Self {
+            strategy: Box::new(WorkerStrategy::new(self.strategy, worker)),
+            concurrent: true,
+            ..self
+        }
+    }
+
+    /// Returns the text cache, if enabled.
+    pub fn text_cache(&self) -> Option<&TextCache> {
+        self.text_cache.as_ref()
+    }
+
     /// Redacts patterns from a PDF document.
     ///
+    /// Identical targets are applied once, so repeating a target does not
+    /// change `instances_redacted`.
+    ///
     /// # Arguments
     /// * `input` - Path to input PDF
     /// * `output` - Path for output PDF
@@ -46,41 +194,641 @@ impl RedactionService {
         input: &Path,
         output: &Path,
         targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        self.redact_selected(input, output, targets, RedactionOptions::default())
+    }
+
+    /// Redacts patterns from a PDF document, calling `progress` as the
+    /// strategy moves through each phase and page.
+    ///
+    /// Strategies that do not report progress never call it.
+    pub fn redact_with_progress(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        progress: &mut dyn FnMut(RedactionProgress),
+    ) -> RedactorResult<RedactionResult> {
+        let progress = RefCell::new(progress);
+        let report = |update: RedactionProgress| (*progress.borrow_mut())(update);
+        let options = RedactionOptions {
+            progress: Some(&report),
+            ..Default::default()
+        };
+        self.redact_selected(input, output, targets, options)
+    }
+
+    /// Redacts patterns from a PDF document, then re-extracts the output's
+    /// text and checks that none of the redacted text remains.
+    ///
+    /// Every string the strategy locates is checked, along with values it
+    /// detected but could not locate. This catches text that was matched but
+    /// not physically removed, e.g. because of its encoding. Strings located
+    /// as whole words only fail verification where they remain as whole
+    /// words, and areas, styles and links, which stand for regions rather
+    /// than text, are not checked.
+    ///
+    /// # Errors
+    /// Returns [`RedactorError::VerificationFailed`] with the first string
+    /// still present in the output; the output is left in place for
+    /// inspection. Strategies that do not support
+    /// [`preview`](Self::preview) cannot be verified.
+    pub fn redact_and_verify(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        let located: Vec<(String, bool)> = self
+            .preview(input, targets)?
+            .into_iter()
+            .filter(|hit| !matches!(hit.category.as_str(), "area" | "link" | "style"))
+            .map(|hit| (hit.text, hit.whole_word))
+            .collect();
+        let result = self.redact(input, output, targets)?;
+
+        let text = self.extract_text(output)?;
+        let remains = |pattern: &str, whole_word: bool| {
+            if pattern.is_empty() {
+                false
+            } else if whole_word {
+                secure::whole_word_occurrences(&text, pattern)
+                    .map_or_else(|| text.contains(pattern), |found| found.contains(&true))
+            } else {
+                text.contains(pattern)
+            }
+        };
+        if let Some(pattern) = located
+            .iter()
+            .map(|(pattern, whole_word)| (pattern, *whole_word))
+            .chain(
+                result
+                    .detected_not_redacted
+                    .iter()
+                    .map(|pattern| (pattern, false)),
+            )
+            .find_map(|(pattern, whole_word)| remains(pattern, whole_word).then_some(pattern))
+        {
+            return Err(RedactorError::VerificationFailed {
+                pattern: pattern.clone(),
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Redacts patterns from a PDF held in memory, returning the redacted
+    /// PDF.
+    ///
+    /// [`SecureRedactionStrategy`] never writes the document to disk;
+    /// other strategies may go through a temporary file.
+    pub fn redact_bytes(
+        &self,
+        input: &[u8],
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<u8>> {
+        check_targets(targets)?;
+        let targets = self.resolve_custom(&distinct_targets(targets), || {
+            self.strategy.extract_text_bytes(input)
+        })?;
+        self.strategy.redact_bytes(input, &targets)
+    }
+
+    /// Redacts patterns from an encrypted PDF, opening it with `password`.
+    ///
+    /// Text is extracted with MuPDF after authenticating, and hidden layers
+    /// are not switched on. The output keeps the input's encryption.
+    pub fn redact_encrypted(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        password: &str,
+    ) -> RedactorResult<RedactionResult> {
+        let options = RedactionOptions {
+            password: Some(password),
+            ..Default::default()
+        };
+        self.redact_selected(input, output, targets, options)
+    }
+
+    /// Redacts patterns from the selected pages only.
+    ///
+    /// Other pages are copied unchanged. Selecting a page beyond the end of
+    /// the document is an error.
+    pub fn redact_pages(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        pages: &PageSelection,
+    ) -> RedactorResult<RedactionResult> {
+        let options = RedactionOptions {
+            pages: Some(pages),
+            ..Default::default()
+        };
+        self.redact_selected(input, output, targets, options)
+    }
+
+    /// Redacts each input into `output_dir`, keeping its file name.
+    ///
+    /// Files are processed one at a time because MuPDF font loading is not
+    /// thread-safe, unless the service runs on a
+    /// [worker](Self::with_mupdf_worker), in which case they are processed
+    /// concurrently. A failure on one file is recorded in its entry and does
+    /// not stop the batch.
+    ///
+    /// # Returns
+    /// Each input paired with the outcome of redacting it, in input order
+    pub fn redact_batch(
+        &self,
+        inputs: &[PathBuf],
+        output_dir: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<(PathBuf, RedactorResult<RedactionResult>)>> {
+        check_targets(targets)?;
+        std::fs::create_dir_all(output_dir).map_err(|e| RedactorError::Io {
+            path: output_dir.to_path_buf(),
+            source: e,
+        })?;
+
+        let redact_one = |input: &PathBuf| {
+            let result = match input.file_name() {
+                Some(name) => self.redact(input, &output_dir.join(name), targets),
+                None => Err(RedactorError::InvalidInput {
+                    parameter: "inputs".to_string(),
+                    reason: format!("'{}' has no file name", input.display()),
+                }),
+            };
+            (input.clone(), result)
+        };
+
+        if !self.concurrent || inputs.len() < 2 {
+            return Ok(inputs.iter().map(redact_one).collect());
+        }
+
+        // Threads take the next unclaimed input until none are left
+        let next = AtomicUsize::new(0);
+        let threads = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(inputs.len());
+        let mut results: Vec<_> = inputs.iter().map(|_| None).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(input) = inputs.get(idx) else {
+                                break;
+                            };
+                            done.push((idx, redact_one(input)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let done = handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                for (idx, result) in done {
+                    results[idx] = Some(result);
+                }
+            }
+        });
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn redact_selected(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: RedactionOptions<'_>,
     ) -> RedactorResult<RedactionResult> {
         // Validate inputs
-        if !input.exists() {
-            return Err(RedactorError::Io {
-                path: input.to_path_buf(),
-                source: std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Input file does not exist",
-                ),
+        check_input_exists(input)?;
+        check_targets(targets)?;
+        self.check_output(output)?;
+
+        let distinct = distinct_targets(targets);
+        if self.require_matches {
+            self.check_matches(input, &distinct, &options)?;
+        }
+        let targets = &self.resolve_custom(&distinct, || self.extract_text(input))?;
+
+        // Execute redaction strategy, reusing cached text when available.
+        // Encrypted inputs are extracted by the strategy once unlocked.
+        let text = match &self.text_cache {
+            Some(cache)
+                if options.password.is_none()
+                    && targets.iter().any(RedactionTarget::requires_text) =>
+            {
+                Some(cache.get_or_extract(input, |path| self.strategy.extract_text(path))?)
+            }
+            _ => None,
+        };
+        let options = RedactionOptions {
+            text: text.as_ref().map(|t| t.as_str()),
+            ..options
+        };
+        let mtime = self.input_mtime(input)?;
+        let mut result = self
+            .strategy
+            .redact_with_options(input, output, targets, &options)?;
+        restore_mtime(output, mtime)?;
+
+        result.input = Some(input.to_path_buf());
+        result.output = Some(output.to_path_buf());
+        result.targets = distinct;
+        Ok(result)
+    }
+
+    /// Locates the regions that `targets` would redact, without writing.
+    ///
+    /// The returned hits can be reviewed or edited and then passed to
+    /// [`apply_hits`](Self::apply_hits).
+    pub fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        check_input_exists(input)?;
+        check_targets(targets)?;
+
+        let targets =
+            self.resolve_custom(&distinct_targets(targets), || self.extract_text(input))?;
+        self.strategy.preview(input, &targets)
+    }
+
+    /// Compares the document text before and after redacting `targets`,
+    /// without writing a PDF.
+    ///
+    /// Only spans the strategy would actually locate are masked, so text the
+    /// backend cannot find shows up unmasked in [`TextDiff::after`].
+    pub fn diff(&self, input: &Path, targets: &[RedactionTarget]) -> RedactorResult<TextDiff> {
+        let hits = self.preview(input, targets)?;
+        let before = self.extract_text(input)?;
+
+        Ok(TextDiff::new(
+            before,
+            hits.iter().map(|hit| hit.text.as_str()),
+        ))
+    }
+
+    /// Compares the text of `input` with that of `output`, its redacted
+    /// copy, line by line.
+    ///
+    /// The removed spans show what redaction took out, so anything removed
+    /// besides the intended content stands out.
+    pub fn compare(&self, input: &Path, output: &Path) -> RedactorResult<LineDiff> {
+        check_input_exists(input)?;
+        check_input_exists(output)?;
+
+        let before = self.extract_text(input)?;
+        let after = self.extract_text(output)?;
+        Ok(LineDiff::new(&before, &after))
+    }
+
+    /// Redacts exactly the supplied regions, bypassing detection entirely.
+    pub fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        check_input_exists(input)?;
+        self.check_output(output)?;
+
+        let mtime = self.input_mtime(input)?;
+        let mut result = self.strategy.apply_hits(input, output, hits)?;
+        restore_mtime(output, mtime)?;
+        result.input = Some(input.to_path_buf());
+        result.output = Some(output.to_path_buf());
+        Ok(result)
+    }
+
+    /// Lists the optional content groups (layers) declared by a PDF.
+    pub fn list_layers(&self, input: &Path) -> RedactorResult<Vec<LayerInfo>> {
+        check_input_exists(input)?;
+
+        layers::list_layers(input)
+    }
+
+    /// Reports which pages of a PDF hold extractable text, images, or paths
+    /// that look like text converted to outlines.
+    ///
+    /// Text targets cannot match anything on a page without extractable
+    /// text, so callers can use this to warn before trusting a redaction.
+    pub fn analyze(&self, input: &Path) -> RedactorResult<DocumentAnalysis> {
+        check_input_exists(input)?;
+
+        analysis::analyze(input)
+    }
+
+    /// Extracts text from a PDF for analysis.
+    pub fn extract_text(&self, input: &Path) -> RedactorResult<String> {
+        match &self.text_cache {
+            Some(cache) => cache
+                .get_or_extract(input, |path| self.strategy.extract_text(path))
+                .map(|text| text.as_str().to_string()),
+            None => self.strategy.extract_text(input),
+        }
+    }
+
+    /// Returns the input's modification time if outputs should keep it.
+    fn input_mtime(&self, input: &Path) -> RedactorResult<Option<FileTime>> {
+        if !self.preserve_mtime {
+            return Ok(None);
+        }
+        let metadata = std::fs::metadata(input).map_err(|e| RedactorError::Io {
+            path: input.to_path_buf(),
+            source: e,
+        })?;
+        Ok(Some(FileTime::from_last_modification_time(&metadata)))
+    }
+
+    /// Fails if `output` exists and overwriting is disabled.
+    fn check_output(&self, output: &Path) -> RedactorResult<()> {
+        if self.no_clobber && output.exists() {
+            return Err(RedactorError::OutputExists {
+                path: output.to_path_buf(),
             });
         }
+        Ok(())
+    }
 
-        if targets.is_empty() {
+    /// Fails if any of `targets` has no hit on the selected pages.
+    fn check_matches(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<()> {
+        if options.password.is_some() {
             return Err(RedactorError::InvalidInput {
-                parameter: "targets".to_string(),
-                reason: "No redaction targets specified".to_string(),
+                parameter: "require_matches".to_string(),
+                reason: "Matches cannot be checked in an encrypted document".to_string(),
             });
         }
 
-        // Execute redaction strategy
-        self.strategy.redact(input, output, targets)
+        let selected = |page| options.pages.map_or(true, |pages| pages.contains(page));
+        // Links are not page content, so preview never finds them
+        for target in targets.iter().filter(|t| **t != RedactionTarget::AllLinks) {
+            let hits = self.preview(input, std::slice::from_ref(target))?;
+            if !hits.iter().any(|hit| selected(hit.page)) {
+                return Err(RedactorError::PatternNotFound {
+                    pattern: target.to_string(),
+                    context: format!("no matches in {}", input.display()),
+                });
+            }
+        }
+        Ok(())
     }
 
-    /// Extracts text from a PDF for analysis.
-    pub fn extract_text(&self, input: &Path) -> RedactorResult<String> {
-        self.strategy.extract_text(input)
+    /// Replaces each [`RedactionTarget::Custom`] target with a literal
+    /// target for each variant of each value its matcher finds in the
+    /// document text, which `text` is called for only if needed.
+    fn resolve_custom(
+        &self,
+        targets: &[RedactionTarget],
+        text: impl FnOnce() -> RedactorResult<String>,
+    ) -> RedactorResult<Vec<RedactionTarget>> {
+        let custom_names = targets.iter().filter_map(|target| match target {
+            RedactionTarget::Custom(name) => Some(name),
+            _ => None,
+        });
+        let mut any_custom = false;
+        for name in custom_names {
+            if !self.custom_matchers.contains_key(name) {
+                return Err(RedactorError::InvalidInput {
+                    parameter: "targets".to_string(),
+                    reason: format!("No custom matcher registered as '{}'", name),
+                });
+            }
+            any_custom = true;
+        }
+        if !any_custom {
+            return Ok(targets.to_vec());
+        }
+
+        let text = text()?;
+        let mut resolved = Vec::with_capacity(targets.len());
+        for target in targets {
+            let RedactionTarget::Custom(name) = target else {
+                resolved.push(target.clone());
+                continue;
+            };
+            let matcher = &self.custom_matchers[name];
+            let values = match self.min_confidence {
+                Some(min) => matcher
+                    .extract_all_scored(&text)
+                    .into_iter()
+                    .filter(|(_, score)| *score >= min)
+                    .map(|(found, _)| found.text)
+                    .collect(),
+                None => matcher.extract_all(&text),
+            };
+            for value in values {
+                if let Some(normalized) = matcher.normalize(value) {
+                    resolved.extend(
+                        matcher
+                            .generate_variants(&normalized)
+                            .into_iter()
+                            .filter(|variant| !variant.is_empty())
+                            .map(RedactionTarget::Literal),
+                    );
+                }
+            }
+        }
+        Ok(distinct_targets(&resolved))
     }
 }
 
+fn check_input_exists(input: &Path) -> RedactorResult<()> {
+    if !input.exists() {
+        return Err(RedactorError::Io {
+            path: input.to_path_buf(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "Input file does not exist"),
+        });
+    }
+    Ok(())
+}
+
+fn check_targets(targets: &[RedactionTarget]) -> RedactorResult<()> {
+    if targets.is_empty() {
+        return Err(RedactorError::InvalidInput {
+            parameter: "targets".to_string(),
+            reason: "No redaction targets specified".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Sets the output's modification time to `mtime`, if given.
+fn restore_mtime(output: &Path, mtime: Option<FileTime>) -> RedactorResult<()> {
+    match mtime {
+        Some(mtime) => filetime::set_file_mtime(output, mtime).map_err(|e| RedactorError::Io {
+            path: output.to_path_buf(),
+            source: e,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Removes repeated targets, keeping the first occurrence of each.
+fn distinct_targets(targets: &[RedactionTarget]) -> Vec<RedactionTarget> {
+    let mut distinct: Vec<RedactionTarget> = Vec::with_capacity(targets.len());
+    for target in targets {
+        if !distinct.contains(target) {
+            distinct.push(target.clone());
+        }
+    }
+    distinct
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use regex::Regex;
+
+    /// Matches the word "FOO" in any case, redacting it as written.
+    struct FooMatcher(Regex);
+
+    impl PatternMatcher for FooMatcher {
+        fn pattern(&self) -> &Regex {
+            &self.0
+        }
+
+        fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+            self.0.find_iter(text).map(|m| m.as_str()).collect()
+        }
+
+        fn normalize(&self, text: &str) -> Option<String> {
+            Some(text.to_string())
+        }
+
+        fn generate_variants(&self, normalized: &str) -> Vec<String> {
+            vec![normalized.to_string()]
+        }
+    }
+
+    #[test]
+    fn test_preserve_mtime() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = dir.path().join("input.pdf");
+        let output = dir.path().join("output.pdf");
+        std::fs::write(&input, b"input").unwrap();
+        std::fs::write(&output, b"output").unwrap();
+        let archived = FileTime::from_unix_time(1_262_304_000, 0);
+        filetime::set_file_mtime(&input, archived).unwrap();
+
+        let service = RedactionService::with_secure_strategy();
+        assert_eq!(service.input_mtime(&input).unwrap(), None);
+
+        let service = service.with_preserve_mtime(true);
+        let mtime = service.input_mtime(&input).unwrap();
+        assert_eq!(mtime, Some(archived));
+        restore_mtime(&output, mtime).unwrap();
+        let metadata = std::fs::metadata(&output).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&metadata), archived);
+
+        let missing = dir.path().join("missing.pdf");
+        assert!(matches!(
+            service.input_mtime(&missing),
+            Err(RedactorError::Io { .. })
+        ));
+    }
+
+    fn foo_service() -> RedactionService {
+        RedactionService::with_secure_strategy().with_custom_matcher(
+            "foo",
+            Box::new(FooMatcher(Regex::new(r"(?i)\bfoo\b").unwrap())),
+        )
+    }
+
+    #[test]
+    fn test_resolve_custom_targets() {
+        let targets = [
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::Custom("foo".to_string()),
+        ];
+        let resolved = foo_service()
+            .resolve_custom(&targets, || Ok("FOO, food and Foo, then FOO".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            resolved,
+            vec![
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::Literal("FOO".to_string()),
+                RedactionTarget::Literal("Foo".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_custom_without_custom_targets_skips_text() {
+        let targets = [RedactionTarget::Ssn];
+        let resolved = foo_service()
+            .resolve_custom(&targets, || panic!("Text is not needed"))
+            .unwrap();
+        assert_eq!(resolved, targets);
+    }
+
+    #[test]
+    fn test_resolve_custom_min_confidence() {
+        let service = RedactionService::with_secure_strategy()
+            .with_custom_matcher("account", Box::new(crate::VerizonAccountMatcher::new()))
+            .with_min_confidence(0.9);
+        let targets = [RedactionTarget::Custom("account".to_string())];
+        let resolved = service
+            .resolve_custom(&targets, || {
+                Ok("Random: 999999999-99999 Account: 123456789-00001".to_string())
+            })
+            .unwrap();
+
+        // Only the labelled number scores high enough
+        assert!(resolved.contains(&RedactionTarget::Literal("123456789-00001".to_string())));
+        assert!(!resolved
+            .iter()
+            .any(|t| matches!(t, RedactionTarget::Literal(s) if s.starts_with("999999999"))));
+    }
+
+    #[test]
+    fn test_resolve_custom_unknown_name() {
+        let targets = [RedactionTarget::Custom("bar".to_string())];
+        let result = foo_service().resolve_custom(&targets, || panic!("Text is not needed"));
+        assert!(matches!(
+            result,
+            Err(RedactorError::InvalidInput { reason, .. }) if reason.contains("'bar'")
+        ));
+    }
 
     #[test]
     fn test_service_creation() {
         let _service = RedactionService::with_secure_strategy();
     }
+
+    #[test]
+    fn test_distinct_targets_preserves_order() {
+        let targets = [
+            RedactionTarget::Literal("b".to_string()),
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::Literal("b".to_string()),
+            RedactionTarget::Literal("a".to_string()),
+            RedactionTarget::PhoneNumbers,
+        ];
+        assert_eq!(
+            distinct_targets(&targets),
+            vec![
+                RedactionTarget::Literal("b".to_string()),
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::Literal("a".to_string()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,148 @@
+//! Document outline (bookmarks).
+//!
+//! Bookmark titles are stored in the outline tree under the document
+//! catalog rather than in page content, so redacting the pages leaves a
+//! bookmark such as "Account 123456789-00001" readable in any viewer's
+//! sidebar. These helpers read the titles and scrub redacted text from
+//! them, keeping the bookmarks themselves so navigation still works.
+
+use super::forms::{decode_text_string, matching_needles};
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfObject};
+use std::collections::HashSet;
+
+/// Text that replaces redacted text in a title.
+const SCRUBBED: &str = "[REDACTED]";
+
+/// Number of outline items beyond which the tree is assumed to be cyclic.
+const MAX_OUTLINE_ITEMS: usize = 100_000;
+
+/// Returns the title of every outline item.
+pub(super) fn outline_titles(pdf_doc: &PdfDocument) -> RedactorResult<Vec<String>> {
+    let mut titles = Vec::new();
+    for item in items(pdf_doc).map_err(outline_error)? {
+        titles.extend(title(&item).map_err(outline_error)?);
+    }
+    Ok(titles)
+}
+
+/// Replaces each of `needles` in every outline title that contains one.
+///
+/// Returns, for each scrubbed title, the indices into `needles` of every
+/// needle it contained.
+pub(super) fn scrub_matching_titles(
+    pdf_doc: &PdfDocument,
+    needles: &[&str],
+) -> RedactorResult<Vec<Vec<usize>>> {
+    let mut scrubbed = Vec::new();
+    for mut item in items(pdf_doc).map_err(outline_error)? {
+        let Some(text) = title(&item).map_err(outline_error)? else {
+            continue;
+        };
+        let found = matching_needles(&text, needles);
+        if found.is_empty() {
+            continue;
+        }
+
+        let new_title = scrub(&text, needles, &found);
+        let value = if new_title.is_ascii() {
+            pdf_doc.new_string(&new_title)
+        } else {
+            pdf_doc.new_object_from_str(&encode_utf16(&new_title))
+        };
+        value
+            .and_then(|value| item.dict_put("Title", value))
+            .map_err(outline_error)?;
+        scrubbed.push(found);
+    }
+    Ok(scrubbed)
+}
+
+/// Collects every item of the outline tree, parents before children.
+fn items(pdf_doc: &PdfDocument) -> Result<Vec<PdfObject>, mupdf::Error> {
+    let mut items = Vec::new();
+    let Some(outlines) = pdf_doc.catalog()?.get_dict("Outlines")? else {
+        return Ok(items);
+    };
+
+    let mut seen = HashSet::new();
+    let mut pending: Vec<PdfObject> = outlines.get_dict("First")?.into_iter().collect();
+    while let Some(first) = pending.pop() {
+        let mut next = Some(first);
+        while let Some(item) = next {
+            if items.len() >= MAX_OUTLINE_ITEMS {
+                return Ok(items);
+            }
+            // Items are indirect objects; a repeat means a cycle
+            if item.is_indirect()? && !seen.insert(item.as_indirect()?) {
+                break;
+            }
+            pending.extend(item.get_dict("First")?);
+            next = item.get_dict("Next")?;
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Returns an outline item's title, or `None` if it has none.
+fn title(item: &PdfObject) -> Result<Option<String>, mupdf::Error> {
+    match item.get_dict("Title")? {
+        Some(title) if title.is_string()? => {
+            let text = decode_text_string(title.as_bytes()?);
+            Ok((!text.is_empty()).then_some(text))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Replaces every occurrence of the `found` needles in `title`.
+///
+/// Needles are replaced in order, so a longer needle listed first is
+/// replaced whole.
+fn scrub(title: &str, needles: &[&str], found: &[usize]) -> String {
+    found.iter().fold(title.to_string(), |text, &idx| {
+        text.replace(needles[idx], SCRUBBED)
+    })
+}
+
+/// Encodes `text` as a PDF hex string in UTF-16BE with a byte order mark.
+fn encode_utf16(text: &str) -> String {
+    let hex: String = text
+        .encode_utf16()
+        .map(|unit| format!("{:04X}", unit))
+        .collect();
+    format!("<FEFF{}>", hex)
+}
+
+fn outline_error(e: mupdf::Error) -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "Failed to process the document outline".to_string(),
+        page: None,
+        source: Some(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub() {
+        let needles = ["123456789-00001", "555-234-5678", "00001"];
+        assert_eq!(
+            scrub("Account 123456789-00001", &needles, &[0, 2]),
+            "Account [REDACTED]"
+        );
+        assert_eq!(
+            scrub("Call 555-234-5678 or 555-234-5678", &needles, &[1]),
+            "Call [REDACTED] or [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_encode_utf16() {
+        assert_eq!(encode_utf16("Jo"), "<FEFF004A006F>");
+        assert_eq!(encode_utf16("Ré"), "<FEFF005200E9>");
+    }
+}
@@ -0,0 +1,574 @@
+//! Visual overlay redaction strategy.
+//!
+//! Draws opaque boxes over matches by appending operators to each page's
+//! content stream. Nothing is removed: the covered text stays in the file
+//! and can still be selected, copied and extracted. Use it only where the
+//! output is printed or rasterized, or to mock up redactions for review.
+
+use super::diff::MASK_CHAR;
+use super::geometry::PageTransform;
+use super::secure::SecureRedactionStrategy;
+use super::strategy::{
+    PageRedactionStats, RedactionHit, RedactionResult, RedactionStrategy, RedactionTarget,
+};
+use crate::error::{RedactorError, RedactorResult};
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+/// Non-destructive redaction that paints black boxes over matches.
+///
+/// Matches are located as by [`SecureRedactionStrategy`], then each page
+/// gets an extra content stream filling the matched regions. The original
+/// content is wrapped in `q`/`Q` so its graphics state cannot move the
+/// boxes.
+///
+/// Matches of a [`RegexWithReplacement`](RedactionTarget::RegexWithReplacement)
+/// target are blanked in white and overdrawn with one replacement
+/// character per matched character instead. The replacement must be
+/// printable ASCII, drawn in Courier, or [`MASK_CHAR`], drawn as a solid
+/// cell.
+///
+/// **This is not secure.** The text under each box is still in the file
+/// and is returned by any text extractor, so results report
+/// [`secure`](RedactionResult::secure) as `false`. Use
+/// [`SecureRedactionStrategy`] for anything that leaves your hands.
+#[derive(Debug, Clone, Default)]
+pub struct OverlayRedactionStrategy {
+    inner: SecureRedactionStrategy,
+}
+
+impl OverlayRedactionStrategy {
+    /// Creates an overlay strategy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RedactionStrategy for OverlayRedactionStrategy {
+    fn redact(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        for target in targets {
+            if let RedactionTarget::RegexWithReplacement { replacement, .. } = target {
+                check_replacement(*replacement)?;
+            }
+        }
+
+        let mut hits: Vec<RedactionHit> = Vec::new();
+        let mut masks = Vec::new();
+        let mut by_category = Vec::new();
+        for target in targets {
+            let mask = match target {
+                RedactionTarget::RegexWithReplacement { replacement, .. } => Some(*replacement),
+                _ => None,
+            };
+            for hit in self.inner.preview(input, std::slice::from_ref(target))? {
+                let duplicate = hits
+                    .iter()
+                    .any(|seen| seen.page == hit.page && seen.rect == hit.rect);
+                if !duplicate {
+                    by_category.push(target.category());
+                    masks.push(mask);
+                    hits.push(hit);
+                }
+            }
+        }
+
+        let mut result = self.overlay(input, output, &hits, &masks)?;
+        for category in by_category {
+            *result.by_category.entry(category.to_string()).or_insert(0) += 1;
+        }
+        Ok(result)
+    }
+
+    fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        self.inner.preview(input, targets)
+    }
+
+    fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        self.overlay(input, output, hits, &[])
+    }
+
+    fn extract_text(&self, input: &Path) -> RedactorResult<String> {
+        self.inner.extract_text(input)
+    }
+
+    fn name(&self) -> &str {
+        "OverlayRedaction"
+    }
+
+    fn is_secure(&self) -> bool {
+        false
+    }
+}
+
+impl OverlayRedactionStrategy {
+    /// Covers each hit with a box, or with the row of characters at the
+    /// same index in `masks`. Hits without an entry get a box.
+    fn overlay(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+        masks: &[Option<char>],
+    ) -> RedactorResult<RedactionResult> {
+        let bytes = std::fs::read(input).map_err(|e| RedactorError::Io {
+            path: input.to_path_buf(),
+            source: e,
+        })?;
+        let mut doc = Document::load_mem(&bytes).map_err(|e| lopdf_error("read", e))?;
+        let pages = doc.get_pages();
+
+        if let Some(hit) = hits
+            .iter()
+            .find(|hit| hit.page == 0 || hit.page > pages.len())
+        {
+            return Err(RedactorError::InvalidInput {
+                parameter: "hits".to_string(),
+                reason: format!(
+                    "Hit on page {} is outside the document's {} page(s)",
+                    hit.page,
+                    pages.len()
+                ),
+            });
+        }
+
+        let mut result = RedactionResult {
+            pages_processed: pages.len(),
+            secure: false,
+            ..Default::default()
+        };
+
+        let mut fonts = MaskFonts::default();
+        for (page_idx, page_id) in pages.values().enumerate() {
+            let page_hits: Vec<(&RedactionHit, Option<char>)> = hits
+                .iter()
+                .enumerate()
+                .filter(|(_, hit)| hit.page == page_idx + 1)
+                .map(|(i, hit)| (hit, masks.get(i).copied().flatten()))
+                .collect();
+            let mut stats = PageRedactionStats {
+                page_number: page_idx + 1,
+                patterns_matched: page_hits
+                    .iter()
+                    .map(|(hit, _)| hit.text.as_str())
+                    .collect::<HashSet<_>>()
+                    .len(),
+                ..Default::default()
+            };
+
+            if !page_hits.is_empty() {
+                let transform = PageTransform::for_page(&doc, *page_id);
+                let mut overlay = b"Q\nq\n".to_vec();
+                let boxes: Vec<_> = page_hits
+                    .iter()
+                    .filter(|(_, mask)| mask.is_none())
+                    .collect();
+                if !boxes.is_empty() {
+                    overlay.extend_from_slice(b"0 0 0 rg\n");
+                    for (hit, _) in boxes {
+                        let (x0, y0, x1, y1) = transform.to_user_space(hit.rect);
+                        writeln!(overlay, "{} {} {} {} re", x0, y0, x1 - x0, y1 - y0)
+                            .expect("Writing to a Vec cannot fail");
+                    }
+                    overlay.extend_from_slice(b"f\n");
+                }
+                for (hit, mask) in &page_hits {
+                    if let Some(mask) = mask {
+                        let font = fonts.font_for(&mut doc, *mask);
+                        add_page_font(&mut doc, *page_id, font)?;
+                        let count = hit.text.chars().count().max(1);
+                        draw_mask(
+                            &mut overlay,
+                            transform.to_user_space(hit.rect),
+                            *mask,
+                            count,
+                        );
+                    }
+                }
+                overlay.extend_from_slice(b"Q\n");
+                wrap_page_contents(&mut doc, *page_id, overlay)?;
+
+                stats.instances = page_hits.len();
+                result.instances_redacted += page_hits.len();
+                result.pages_modified += 1;
+            }
+            result.per_page.push(stats);
+        }
+
+        let mut written = Vec::new();
+        if result.has_redactions() {
+            doc.save_to(&mut written)
+                .map_err(|e| lopdf_error("write", e))?;
+        } else {
+            written = bytes;
+        }
+        result.output_bytes = written.len() as u64;
+        std::fs::write(output, written).map_err(|e| RedactorError::Io {
+            path: output.to_path_buf(),
+            source: e,
+        })?;
+        log::info!(
+            "Overlaid {} box(es) without removing text to {}",
+            result.instances_redacted,
+            output.display()
+        );
+
+        Ok(result)
+    }
+}
+
+fn lopdf_error<E>(action: &str, e: E) -> RedactorError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    RedactorError::BackendError {
+        backend: "LoPDF".to_string(),
+        message: format!("Failed to {} PDF for overlay", action),
+        source: Some(Box::new(e)),
+    }
+}
+
+/// Courier glyphs, and the mask font's cell, are 600 units of a 1000-unit
+/// em wide.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// Fraction of the font size below the baseline, so a cell spans the hit's
+/// full height.
+const DESCENT: f32 = 0.2;
+
+/// Deepest page tree searched for inherited resources.
+const MAX_TREE_DEPTH: usize = 64;
+
+/// Font resource names used for mask characters.
+const COURIER_FONT: &str = "RedactorCourier";
+const MASK_FONT: &str = "RedactorMask";
+
+/// Checks that a replacement character can be drawn.
+fn check_replacement(replacement: char) -> RedactorResult<()> {
+    if replacement.is_ascii_graphic() || replacement == MASK_CHAR {
+        return Ok(());
+    }
+    Err(RedactorError::InvalidInput {
+        parameter: "replacement".to_string(),
+        reason: format!(
+            "'{}' cannot be drawn; use printable ASCII or '{}'",
+            replacement.escape_default(),
+            MASK_CHAR
+        ),
+    })
+}
+
+/// Fonts for mask characters, added to the document when first needed.
+#[derive(Default)]
+struct MaskFonts {
+    courier: Option<ObjectId>,
+    mask: Option<ObjectId>,
+}
+
+impl MaskFonts {
+    /// Returns the resource name and object of the font that draws `mask`.
+    fn font_for(&mut self, doc: &mut Document, mask: char) -> (&'static str, ObjectId) {
+        if mask == MASK_CHAR {
+            let id = *self.mask.get_or_insert_with(|| add_mask_font(doc));
+            (MASK_FONT, id)
+        } else {
+            let id = *self.courier.get_or_insert_with(|| {
+                doc.add_object(dictionary! {
+                    "Type" => "Font",
+                    "Subtype" => "Type1",
+                    "BaseFont" => "Courier",
+                    "Encoding" => "WinAnsiEncoding",
+                })
+            });
+            (COURIER_FONT, id)
+        }
+    }
+}
+
+/// Adds a Type 3 font whose only glyph, code 1, is a solid cell that
+/// extracts as [`MASK_CHAR`].
+fn add_mask_font(doc: &mut Document) -> ObjectId {
+    let cell = doc.add_object(Stream::new(
+        Dictionary::new(),
+        b"600 0 0 -200 600 800 d1\n0 -200 600 1000 re\nf\n".to_vec(),
+    ));
+    let to_unicode = doc.add_object(Stream::new(
+        Dictionary::new(),
+        format!(
+            "/CIDInit /ProcSet findresource begin\n\
+             12 dict begin\n\
+             begincmap\n\
+             /CMapName /RedactorMask def\n\
+             /CMapType 2 def\n\
+             1 begincodespacerange\n<01> <01>\nendcodespacerange\n\
+             1 beginbfchar\n<01> <{:04X}>\nendbfchar\n\
+             endcmap\n\
+             CMapName currentdict /CMap defineresource pop\n\
+             end\n\
+             end\n",
+            MASK_CHAR as u32
+        )
+        .into_bytes(),
+    ));
+    doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type3",
+        "FontBBox" => vec![0.into(), (-200).into(), 600.into(), 800.into()],
+        "FontMatrix" => vec![0.001.into(), 0.into(), 0.into(), 0.001.into(), 0.into(), 0.into()],
+        "CharProcs" => dictionary! { "cell" => cell },
+        "Encoding" => dictionary! {
+            "Type" => "Encoding",
+            "Differences" => vec![1.into(), Object::Name(b"cell".to_vec())],
+        },
+        "FirstChar" => 1,
+        "LastChar" => 1,
+        "Widths" => vec![600.into()],
+        "Resources" => Dictionary::new(),
+        "ToUnicode" => to_unicode,
+    })
+}
+
+/// Makes a font available to a page's content under the resource `name`.
+///
+/// The page's resources, its own or the nearest inherited, are copied onto
+/// the page first, so adding the font changes no other page.
+fn add_page_font(
+    doc: &mut Document,
+    page_id: ObjectId,
+    (name, font_id): (&str, ObjectId),
+) -> RedactorResult<()> {
+    let as_dict = |obj: &Object| match obj {
+        Object::Reference(id) => doc.get_dictionary(*id).ok().cloned(),
+        obj => obj.as_dict().ok().cloned(),
+    };
+    // The depth bound guards against a cyclic page tree
+    let mut resources = std::iter::successors(doc.get_dictionary(page_id).ok(), |node| {
+        node.get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .ok()
+    })
+    .take(MAX_TREE_DEPTH)
+    .find_map(|node| node.get(b"Resources").ok())
+    .and_then(as_dict)
+    .unwrap_or_default();
+    let mut fonts = resources
+        .get(b"Font")
+        .ok()
+        .and_then(as_dict)
+        .unwrap_or_default();
+    fonts.set(name, font_id);
+    resources.set("Font", fonts);
+
+    doc.get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|e| lopdf_error("update", e))?
+        .set("Resources", resources);
+    Ok(())
+}
+
+/// Blanks `rect` in white and fills it with `count` copies of `mask`,
+/// stretched horizontally to its width.
+fn draw_mask(
+    overlay: &mut Vec<u8>,
+    (x0, y0, x1, y1): (f32, f32, f32, f32),
+    mask: char,
+    count: usize,
+) {
+    let (x0, x1) = (x0.min(x1), x0.max(x1));
+    let (y0, y1) = (y0.min(y1), y0.max(y1));
+    let (width, height) = (x1 - x0, y1 - y0);
+    if width <= 0.0 || height <= 0.0 {
+        return;
+    }
+    let (font, code) = if mask == MASK_CHAR {
+        (MASK_FONT, 1)
+    } else {
+        (COURIER_FONT, mask as u8)
+    };
+    let scale = 100.0 * width / (count as f32 * GLYPH_WIDTH * height);
+    let codes = format!("{:02X}", code).repeat(count);
+
+    writeln!(
+        overlay,
+        "1 1 1 rg\n{x0} {y0} {width} {height} re\nf\n0 0 0 rg\n\
+         BT\n/{font} {height} Tf\n{scale} Tz\n{x0} {baseline} Td\n<{codes}> Tj\nET",
+        x0 = x0,
+        y0 = y0,
+        width = width,
+        height = height,
+        font = font,
+        scale = scale,
+        baseline = y0 + DESCENT * height,
+        codes = codes,
+    )
+    .expect("Writing to a Vec cannot fail");
+}
+
+/// Saves the graphics state before a page's existing content and appends
+/// `overlay`, which must start by restoring it.
+fn wrap_page_contents(
+    doc: &mut Document,
+    page_id: ObjectId,
+    overlay: Vec<u8>,
+) -> RedactorResult<()> {
+    let mut contents = match doc
+        .get_dictionary(page_id)
+        .and_then(|page| page.get(b"Contents"))
+    {
+        Ok(Object::Reference(id)) => match doc.get_object(*id) {
+            Ok(Object::Array(streams)) => streams.clone(),
+            _ => vec![Object::Reference(*id)],
+        },
+        Ok(Object::Array(streams)) => streams.clone(),
+        _ => Vec::new(),
+    };
+
+    let save = doc.add_object(Stream::new(Dictionary::new(), b"q\n".to_vec()));
+    let boxes = doc.add_object(Stream::new(Dictionary::new(), overlay));
+    contents.insert(0, Object::Reference(save));
+    contents.push(Object::Reference(boxes));
+
+    doc.get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|e| lopdf_error("update", e))?
+        .set("Contents", contents);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_is_not_secure() {
+        let strategy = OverlayRedactionStrategy::new();
+        assert!(!strategy.is_secure());
+        assert_eq!(strategy.name(), "OverlayRedaction");
+    }
+
+    #[test]
+    fn test_check_replacement() {
+        assert!(check_replacement(MASK_CHAR).is_ok());
+        assert!(check_replacement('*').is_ok());
+        assert!(check_replacement('X').is_ok());
+        for replacement in [' ', '\n', 'é', '■'] {
+            assert!(matches!(
+                check_replacement(replacement),
+                Err(RedactorError::InvalidInput { ref parameter, .. }) if parameter == "replacement"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_draw_mask_stretches_one_glyph_per_character() {
+        let mut overlay = Vec::new();
+        draw_mask(&mut overlay, (72.0, 112.0, 120.0, 100.0), '*', 4);
+
+        let ops = String::from_utf8(overlay).unwrap();
+        assert!(ops.starts_with("1 1 1 rg\n72 100 48 12 re\nf\n0 0 0 rg\n"));
+        assert!(ops.contains("/RedactorCourier 12 Tf\n"));
+        assert!(ops.contains("<2A2A2A2A> Tj"));
+
+        // Four 0.6 em glyphs at 12pt span 28.8pt, so stretch to 48pt
+        let scale: f32 = ops
+            .lines()
+            .find_map(|line| line.strip_suffix(" Tz"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((scale - 100.0 * 48.0 / 28.8).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_overlay_draws_mask_fonts_on_their_page() {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = (0..2)
+            .map(|_| {
+                let content = doc.add_object(Stream::new(
+                    Dictionary::new(),
+                    b"BT /F1 12 Tf 72 700 Td (Acct 1234) Tj ET".to_vec(),
+                ));
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+                    "Contents" => content,
+                })
+                .into()
+            })
+            .collect();
+        let font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => 2,
+                "Resources" => dictionary! { "Font" => dictionary! { "F1" => font } },
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let input = dir.path().join("input.pdf");
+        let output = dir.path().join("output.pdf");
+        doc.save(&input).unwrap();
+
+        let hit = |page| RedactionHit {
+            page,
+            rect: (100.0, 80.0, 130.0, 94.0),
+            text: "1234".to_string(),
+            category: "regex".to_string(),
+            whole_word: false,
+        };
+        let result = OverlayRedactionStrategy::new()
+            .overlay(&input, &output, &[hit(1), hit(2)], &[Some(MASK_CHAR), None])
+            .unwrap();
+        assert_eq!(result.instances_redacted, 2);
+
+        let doc = Document::load(&output).unwrap();
+        let pages = doc.get_pages();
+        let fonts = |page| doc.get_page_fonts(pages[&page]);
+
+        let masked = String::from_utf8(doc.get_page_content(pages[&1]).unwrap()).unwrap();
+        assert!(masked.contains("/RedactorMask 14 Tf"), "{}", masked);
+        assert!(masked.contains("<01010101> Tj"), "{}", masked);
+        let mask_font = fonts(1)[b"RedactorMask".as_slice()];
+        assert_eq!(
+            mask_font.get(b"Subtype").unwrap().as_name().unwrap(),
+            b"Type3"
+        );
+        assert!(mask_font.has(b"ToUnicode"));
+        assert!(
+            fonts(1).contains_key(b"F1".as_slice()),
+            "Inherited fonts should stay available"
+        );
+
+        let boxed = String::from_utf8(doc.get_page_content(pages[&2]).unwrap()).unwrap();
+        assert!(!boxed.contains("Tj\nET\nQ"), "{}", boxed);
+        assert!(boxed.trim_end().ends_with("re\nf\nQ"), "{}", boxed);
+        assert!(!fonts(2).contains_key(b"RedactorMask".as_slice()));
+    }
+}
@@ -0,0 +1,127 @@
+//! Page selection for limiting redaction to part of a document.
+
+use crate::error::{RedactorError, RedactorResult};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A set of 1-based page numbers, written as individual pages and
+/// inclusive ranges separated by commas (e.g. `1,3,5-9`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSelection {
+    ranges: Vec<RangeInclusive<usize>>,
+}
+
+impl PageSelection {
+    /// Parses a selection such as `1,3,5-9`.
+    pub fn parse(spec: &str) -> RedactorResult<Self> {
+        let invalid = |reason: String| RedactorError::InvalidInput {
+            parameter: "pages".to_string(),
+            reason,
+        };
+        let page = |s: &str| -> RedactorResult<usize> {
+            match s.trim().parse::<usize>() {
+                Ok(0) => Err(invalid("page numbers start at 1".to_string())),
+                Ok(n) => Ok(n),
+                Err(_) => Err(invalid(format!("'{}' is not a page number", s.trim()))),
+            }
+        };
+
+        let mut ranges = Vec::new();
+        for part in spec.split(',') {
+            let range = match part.split_once('-') {
+                Some((start, end)) => page(start)?..=page(end)?,
+                None => page(part)?..=page(part)?,
+            };
+            if range.is_empty() {
+                return Err(invalid(format!(
+                    "range '{}' ends before it starts",
+                    part.trim()
+                )));
+            }
+            ranges.push(range);
+        }
+
+        Ok(Self { ranges })
+    }
+
+    /// Returns true if the 1-based `page` is selected.
+    pub fn contains(&self, page: usize) -> bool {
+        self.ranges.iter().any(|r| r.contains(&page))
+    }
+
+    /// Returns the highest selected page number.
+    pub fn last_page(&self) -> usize {
+        self.ranges.iter().map(|r| *r.end()).max().unwrap_or(0)
+    }
+
+    /// Checks that every selected page exists in a document of
+    /// `page_count` pages.
+    pub fn check_within(&self, page_count: usize) -> RedactorResult<()> {
+        let last = self.last_page();
+        if last > page_count {
+            return Err(RedactorError::InvalidInput {
+                parameter: "pages".to_string(),
+                reason: format!(
+                    "page {} is out of range (document has {} pages)",
+                    last, page_count
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PageSelection {
+    type Err = RedactorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PageSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            if range.start() == range.end() {
+                write!(f, "{}", range.start())?;
+            } else {
+                write!(f, "{}-{}", range.start(), range.end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pages_and_ranges() {
+        let pages = PageSelection::parse("1, 3,5-9").unwrap();
+        assert!(pages.contains(1));
+        assert!(!pages.contains(2));
+        assert!(pages.contains(7));
+        assert!(!pages.contains(10));
+        assert_eq!(pages.last_page(), 9);
+        assert_eq!(pages.to_string(), "1,3,5-9");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        for spec in ["", "0", "a", "3-1", "1,,2", "1-"] {
+            assert!(PageSelection::parse(spec).is_err(), "{:?}", spec);
+        }
+    }
+
+    #[test]
+    fn test_check_within() {
+        let pages = PageSelection::parse("2-4").unwrap();
+        assert!(pages.check_within(4).is_ok());
+        assert!(pages.check_within(3).is_err());
+    }
+}
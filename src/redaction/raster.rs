@@ -0,0 +1,156 @@
+//! Page rasterization.
+//!
+//! Redaction removes the matched text, but the rest of the page is left as
+//! it was: its remaining text, fonts and vector drawings are all still in
+//! the file. Rasterizing replaces a page's content with a single image of
+//! how the page looks, so nothing on it can be selected, searched or
+//! extracted.
+
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfPage};
+use mupdf::{Colorspace, Image, Matrix, Rect};
+
+/// Name of the page image in the page's resources.
+const IMAGE_NAME: &str = "Im0";
+
+/// Replaces the content of a page with an image of it rendered at `dpi`.
+///
+/// The page's annotations, including form fields, are drawn into the image
+/// and removed, along with its resources. Objects the page no longer uses
+/// stay in the file unless it is written with garbage collection.
+pub(super) fn rasterize_page(
+    pdf_doc: &mut PdfDocument,
+    page_idx: i32,
+    dpi: u32,
+) -> RedactorResult<()> {
+    let to_error = |e: mupdf::Error| RedactorError::PdfProcessing {
+        message: format!("Failed to rasterize page {}", page_idx + 1),
+        page: Some(page_idx as usize + 1),
+        source: Some(Box::new(e)),
+    };
+
+    let page = pdf_doc.load_page(page_idx).map_err(to_error)?;
+    let pdf_page = PdfPage::try_from(page).map_err(to_error)?;
+    let bounds = pdf_page.bounds().map_err(to_error)?;
+    let placement = image_matrix(bounds, &pdf_page.ctm().map_err(to_error)?).ok_or_else(|| {
+        RedactorError::PdfProcessing {
+            message: format!("Page {} has a degenerate transform", page_idx + 1),
+            page: Some(page_idx as usize + 1),
+            source: None,
+        }
+    })?;
+
+    let scale = dpi as f32 / 72.0;
+    let pixmap = pdf_page
+        .to_pixmap(
+            &Matrix::new_scale(scale, scale),
+            &Colorspace::device_rgb(),
+            false,
+            false,
+        )
+        .map_err(to_error)?;
+    let image = Image::from_pixmap(&pixmap).map_err(to_error)?;
+    let image_ref = pdf_doc.add_image(&image).map_err(to_error)?;
+
+    let [a, b, c, d, e, f] = placement;
+    let content = format!(
+        "q {} {} {} {} {} {} cm /{} Do Q",
+        a, b, c, d, e, f, IMAGE_NAME
+    );
+    let mut content_ref = pdf_doc
+        .new_dict()
+        .and_then(|dict| pdf_doc.add_object(&dict))
+        .map_err(to_error)?;
+    content_ref
+        .write_stream_string(&content)
+        .map_err(to_error)?;
+
+    let mut xobjects = pdf_doc.new_dict().map_err(to_error)?;
+    xobjects.dict_put(IMAGE_NAME, image_ref).map_err(to_error)?;
+    let mut resources = pdf_doc.new_dict().map_err(to_error)?;
+    resources.dict_put("XObject", xobjects).map_err(to_error)?;
+
+    let mut page_obj = pdf_doc.find_page(page_idx).map_err(to_error)?;
+    page_obj
+        .dict_put("Resources", resources)
+        .map_err(to_error)?;
+    page_obj
+        .dict_put("Contents", content_ref)
+        .map_err(to_error)?;
+    page_obj.dict_delete("Annots").map_err(to_error)?;
+    Ok(())
+}
+
+/// Returns the matrix, in the page's own coordinate space, that draws an
+/// image (the unit square) over `bounds`, the page's bounds once
+/// transformed by `ctm`.
+///
+/// Returns `None` if `ctm` cannot be inverted.
+fn image_matrix(bounds: Rect, ctm: &Matrix) -> Option<[f32; 6]> {
+    // Image space to rendered page space; images are drawn top row first
+    let (w, h) = (bounds.x1 - bounds.x0, bounds.y1 - bounds.y0);
+    let to_rendered = [w, 0.0, 0.0, -h, bounds.x0, bounds.y0 + h];
+
+    // Rendered page space back to the page's own space
+    let det = ctm.a * ctm.d - ctm.b * ctm.c;
+    if det == 0.0 {
+        return None;
+    }
+    let inverse = [
+        ctm.d / det,
+        -ctm.b / det,
+        -ctm.c / det,
+        ctm.a / det,
+        (ctm.c * ctm.f - ctm.d * ctm.e) / det,
+        (ctm.b * ctm.e - ctm.a * ctm.f) / det,
+    ];
+
+    Some(concat(to_rendered, inverse))
+}
+
+/// Returns the matrix that applies `first` and then `then`.
+fn concat(first: [f32; 6], then: [f32; 6]) -> [f32; 6] {
+    let [a, b, c, d, e, f] = first;
+    let [qa, qb, qc, qd, qe, qf] = then;
+    [
+        a * qa + b * qc,
+        a * qb + b * qd,
+        c * qa + d * qc,
+        c * qb + d * qd,
+        e * qa + f * qc + qe,
+        e * qb + f * qd + qf,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_matrix_unrotated_page() {
+        // MuPDF flips an unrotated US Letter page to a top-left origin
+        let ctm = Matrix::new(1.0, 0.0, 0.0, -1.0, 0.0, 792.0);
+        let bounds = Rect::new(0.0, 0.0, 612.0, 792.0);
+        assert_eq!(
+            image_matrix(bounds, &ctm),
+            Some([612.0, 0.0, 0.0, 792.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn test_image_matrix_rotated_page() {
+        // Axes swapped, as for a US Letter page shown landscape
+        let ctm = Matrix::new(0.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+        let bounds = Rect::new(0.0, 0.0, 792.0, 612.0);
+        assert_eq!(
+            image_matrix(bounds, &ctm),
+            Some([0.0, 792.0, -612.0, 0.0, 612.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn test_image_matrix_singular_transform() {
+        let ctm = Matrix::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(image_matrix(Rect::new(0.0, 0.0, 1.0, 1.0), &ctm), None);
+    }
+}
@@ -0,0 +1,54 @@
+//! Text split across the strings of a `TJ` array.
+//!
+//! A `TJ` operator shows an array of strings with kerning between them, and
+//! a value can be split so that no string contains it, as in
+//! `[(555)-100(234)-100(5678)] TJ`. Depending on the gaps, extracted text
+//! can show such a value with spaces inside it, and a search for it fails.
+//! These helpers read each page's content streams and return the text of
+//! every such array, joined, with where each string starts.
+
+use crate::domain::{extract_tj_runs, TjRun};
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::pdf::{PdfDocument, PdfObject};
+
+/// Returns every run in the document that joins more than one string.
+pub(super) fn split_runs(pdf_doc: &PdfDocument) -> RedactorResult<Vec<TjRun>> {
+    let mut runs = Vec::new();
+    for page_idx in 0..pdf_doc.page_count().map_err(content_error)? {
+        let page = pdf_doc.find_page(page_idx).map_err(content_error)?;
+        let content = page_content(&page).map_err(content_error)?;
+        runs.extend(
+            extract_tj_runs(&String::from_utf8_lossy(&content))
+                .into_iter()
+                .filter(|run| run.pieces.len() > 1),
+        );
+    }
+    Ok(runs)
+}
+
+/// Returns the decoded content of a page, its streams joined by newlines.
+fn page_content(page: &PdfObject) -> Result<Vec<u8>, mupdf::Error> {
+    let Some(contents) = page.get_dict("Contents")? else {
+        return Ok(Vec::new());
+    };
+    if !contents.is_array()? {
+        return contents.read_stream();
+    }
+
+    let mut content = Vec::new();
+    for idx in 0..contents.len()? {
+        if let Some(stream) = contents.get_array(idx as i32)? {
+            content.extend(stream.read_stream()?);
+            content.push(b'\n');
+        }
+    }
+    Ok(content)
+}
+
+fn content_error(e: mupdf::Error) -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "Failed to read page content".to_string(),
+        page: None,
+        source: Some(Box::new(e)),
+    }
+}
@@ -3,15 +3,45 @@
 //! This strategy physically removes text from PDF documents using MuPDF's
 //! redaction API, ensuring that redacted content cannot be recovered.
 
-use super::strategy::{RedactionResult, RedactionStrategy, RedactionTarget};
+use super::annotations;
+use super::colors;
+use super::features;
+use super::forms;
+use super::layers;
+use super::links;
+use super::mapped::read_input;
+use super::outline;
+use super::pages::PageSelection;
+use super::raster;
+use super::runs;
+use super::strategy::{
+    self, PageRedactionStats, RedactionHit, RedactionOptions, RedactionPhase, RedactionProgress,
+    RedactionResult, RedactionStrategy, RedactionTarget,
+};
+use super::styles::{self, TextStyle};
+use super::worker::{self, MupdfWorker};
+use super::xobjects;
 use crate::domain::{
-    PatternMatcher, PhoneNumberMatcher, VerizonAccountMatcher, VerizonCallDetailsMatcher,
+    ChecksumMatcher, CreditCardMatcher, DateMatcher, DriversLicenseMatcher, EmailMatcher, Entity,
+    FoldedText, HandleMatcher, IbanMatcher, MacAddressMatcher, NameMatcher, PassportMatcher,
+    PatternMatcher, PhoneLocale, PhoneNumberMatcher, PostalCodeMatcher, RoutingNumberMatcher,
+    SsnMatcher, TimestampMatcher, TjRun, VerizonAccountMatcher, VerizonCallDetailsMatcher,
+    VinMatcher,
 };
 use crate::error::{RedactorError, RedactorResult};
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
 
-use mupdf::pdf::{PdfAnnotationType, PdfDocument, PdfPage};
-use mupdf::Rect as MuRect;
+use lopdf::Dictionary;
+use mupdf::pdf::{PdfAnnotationType, PdfDocument, PdfPage, PdfWriteOptions};
+use mupdf::text_page::TextBlockType;
+use mupdf::{Page, Quad, Rect as MuRect, TextPageOptions};
+use sha2::{Digest, Sha256};
 
 /// Secure redaction strategy that physically removes text using MuPDF.
 ///
@@ -25,12 +55,76 @@ use mupdf::Rect as MuRect;
 pub struct SecureRedactionStrategy {
     /// Maximum search hits per pattern (prevents performance issues)
     max_hits: u32,
+
+    /// Per-target overrides of `max_hits`
+    target_max_hits: Vec<(RedactionTarget, u32)>,
+
+    /// Top and bottom bands (in points) left intact by full-page redaction
+    alltext_margins: (f32, f32),
+
+    /// Leave content in layers hidden by default unsearched
+    skip_hidden_layers: bool,
+
+    /// Directory for temporary output files (defaults to the output's parent)
+    temp_dir: Option<PathBuf>,
+
+    /// RGB fill color (0.0-1.0) of redaction boxes (MuPDF default: black)
+    fill_color: Option<[f32; 3]>,
+
+    /// Text drawn inside each redaction box
+    overlay_text: Option<String>,
+
+    /// Label each redaction box with its match's category
+    category_labels: bool,
+
+    /// Remove the Info dictionary and XMP metadata from the output
+    strip_metadata: bool,
+
+    /// Record the text of each redacted instance in the result
+    match_text: bool,
+
+    /// Characters left visible at the end of each numeric match
+    keep_trailing: Option<usize>,
+
+    /// Leave comment and link annotations in place even when they contain
+    /// redacted text
+    keep_annotations: bool,
+
+    /// Leave bookmark titles as they are even when they contain redacted
+    /// text
+    keep_outline: bool,
+
+    /// Resolution (dots per inch) to rasterize modified pages at
+    rasterize_dpi: Option<u32>,
+
+    /// Report the SHA-256 digest of the output in the result
+    hash_output: bool,
+
+    /// Longest a redaction may run before failing
+    timeout: Option<Duration>,
+
+    /// Resolve targets from each page's text as the page is redacted,
+    /// instead of from the whole document's text up front
+    streaming: bool,
+
+    /// Append the redactions to the input as a new revision instead of
+    /// rewriting the file
+    incremental: bool,
+
+    /// Delete pages that redaction left without text or images
+    remove_emptied_pages: bool,
+
+    /// Numbering plan phone number targets match
+    phone_locale: PhoneLocale,
 }
 
 impl SecureRedactionStrategy {
     /// Creates a new secure redaction strategy with default settings.
     pub fn new() -> Self {
-        Self { max_hits: 100 }
+        Self {
+            max_hits: 100,
+            ..Default::default()
+        }
     }
 
     /// Sets the maximum number of search hits per pattern.
@@ -39,33 +133,485 @@ impl SecureRedactionStrategy {
         self
     }
 
+    /// Overrides the maximum number of search hits for one target.
+    ///
+    /// Each pattern resolved from `target` is searched with this cap instead
+    /// of the global one, so a broad target can be given room without
+    /// raising the limit for every other target.
+    pub fn with_target_max_hits(mut self, target: RedactionTarget, max_hits: u32) -> Self {
+        self.target_max_hits.retain(|(t, _)| *t != target);
+        self.target_max_hits.push((target, max_hits));
+        self
+    }
+
+    /// Returns the search hit cap that applies to `target`.
+    fn max_hits_for(&self, target: &RedactionTarget) -> u32 {
+        self.target_max_hits
+            .iter()
+            .find(|(t, _)| t == target)
+            .map_or(self.max_hits, |(_, max_hits)| *max_hits)
+    }
+
+    /// Preserves header and footer bands during full-page (`.+`) redaction.
+    ///
+    /// The full-page redaction box is inset by `top` and `bottom` points,
+    /// so content such as Bates or page numbers in those bands survives.
+    pub fn with_alltext_margins(mut self, top: f32, bottom: f32) -> Self {
+        self.alltext_margins = (top.max(0.0), bottom.max(0.0));
+        self
+    }
+
+    /// Sets whether optional content groups (layers) that are hidden by
+    /// default are searched and redacted too. Enabled by default.
+    ///
+    /// MuPDF only sees visible content, so without this a hidden layer's
+    /// text is left in the output, where any text extractor can read it.
+    /// Either way, the output keeps the input's original layer visibility.
+    pub fn with_include_hidden_layers(mut self, include: bool) -> Self {
+        self.skip_hidden_layers = !include;
+        self
+    }
+
+    /// Sets the fill color of redaction boxes, as RGB components in
+    /// `0.0..=1.0`.
+    pub fn with_fill_color(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.fill_color = Some([r, g, b].map(|c| c.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Sets a label (e.g. "REDACTED") drawn inside each redaction box.
+    pub fn with_overlay_text(mut self, text: String) -> Self {
+        // Interior NULs can't cross the C API
+        self.overlay_text = Some(text.replace('\0', ""));
+        self
+    }
+
+    /// Labels each redaction box with the category of the target that
+    /// matched it, e.g. `[PHONE]` or `[SSN]`, instead of the overlay text.
+    ///
+    /// Matches masked by [`with_keep_trailing`](Self::with_keep_trailing)
+    /// keep their mask.
+    pub fn with_category_labels(mut self, labels: bool) -> Self {
+        self.category_labels = labels;
+        self
+    }
+
+    /// Removes document metadata (the Info dictionary with author, title,
+    /// producer, etc., and the catalog's XMP stream) from the output.
+    ///
+    /// Metadata is not page content, so redaction alone leaves it intact.
+    /// When enabled the output is always rewritten, even if nothing on the
+    /// pages matched.
+    pub fn with_strip_metadata(mut self, strip: bool) -> Self {
+        self.strip_metadata = strip;
+        self
+    }
+
+    /// Records the matched pattern of every redacted instance in
+    /// [`RedactionResult::matched_text`].
+    ///
+    /// This puts sensitive text into the result and any audit report made
+    /// from it, so it is meant for debugging only.
+    pub fn with_match_text(mut self, record: bool) -> Self {
+        self.match_text = record;
+        self
+    }
+
+    /// Sets whether comments (sticky notes, free text and highlights) that
+    /// contain redacted text, and links whose URI contains it, are
+    /// deleted. Enabled by default.
+    ///
+    /// A comment's text is not page content, so redacting the page leaves
+    /// it readable in any PDF viewer's comment list; likewise a link's URI.
+    /// [`RedactionTarget::AllLinks`] deletes links either way.
+    pub fn with_redact_annotations(mut self, redact: bool) -> Self {
+        self.keep_annotations = !redact;
+        self
+    }
+
+    /// Sets whether redacted text is scrubbed from bookmark (outline)
+    /// titles. Enabled by default.
+    ///
+    /// The outline is not page content, so redacting the pages leaves its
+    /// titles readable in any PDF viewer's sidebar. Each occurrence in a
+    /// title is replaced with `[REDACTED]`, and the bookmark is kept.
+    pub fn with_redact_outline(mut self, redact: bool) -> Self {
+        self.keep_outline = !redact;
+        self
+    }
+
+    /// Replaces each page that had something redacted with an image of it
+    /// rendered at `dpi` dots per inch, after the redactions are applied.
+    ///
+    /// Redaction removes only the matched text; rasterizing also leaves
+    /// nothing else on the page that can be selected, searched or
+    /// extracted, including text a pattern missed. The cost is that the
+    /// page's text is no longer selectable or accessible to screen
+    /// readers, and the file grows with the page images: 150 DPI is
+    /// readable on screen, 300 DPI for print. Annotations and form fields
+    /// on the page are flattened into the image. A `dpi` of 0 fails the
+    /// redaction with [`RedactorError::InvalidInput`].
+    pub fn with_rasterize(mut self, dpi: u32) -> Self {
+        self.rasterize_dpi = Some(dpi);
+        self
+    }
+
+    /// Reports the SHA-256 digest of the written output in
+    /// [`RedactionResult::output_sha256`], for callers that store a
+    /// checksum alongside the audit report.
+    ///
+    /// The output is read back once it has been written. Disabled by
+    /// default; [`RedactionResult::output_bytes`] is reported either way.
+    pub fn with_hash_output(mut self, hash: bool) -> Self {
+        self.hash_output = hash;
+        self
+    }
+
+    /// Fails a redaction with [`RedactorError::Timeout`] if it has not
+    /// finished within `limit`, so a pathological PDF cannot hang the
+    /// caller.
+    ///
+    /// Each redaction then runs on [`MupdfWorker::global`]. MuPDF cannot
+    /// be interrupted, so a timed-out redaction keeps the worker busy until
+    /// MuPDF returns, and then discards its output. Other MuPDF work must go
+    /// through the global worker too, e.g. with
+    /// [`RedactionService::with_mupdf_worker`](super::RedactionService::with_mupdf_worker),
+    /// or it can run alongside an abandoned redaction. On a service's
+    /// worker the [`WorkerStrategy`](super::WorkerStrategy) enforces the
+    /// limit instead.
+    pub fn with_timeout(mut self, limit: Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
+
+    /// Resolves targets one page at a time, searching each page as soon as
+    /// its text has been matched, instead of extracting the whole
+    /// document's text first.
+    ///
+    /// This bounds memory by the largest page rather than the document, and
+    /// redaction of the first page starts without waiting for the last. A
+    /// value first found on a later page is still searched for on the
+    /// earlier ones and in form fields, but a match split across a page
+    /// break is missed. A target found on no page, such as
+    /// [`RedactionTarget::VerizonAccount`], redacts nothing rather than
+    /// failing with [`RedactorError::PatternNotFound`]. Ignored when text
+    /// is supplied through
+    /// [`RedactionOptions::text`](super::RedactionOptions::text), e.g. from
+    /// a [`TextCache`](super::TextCache).
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Saves the output as an incremental update: the input's bytes are
+    /// kept as they are and the redactions are appended as a new revision,
+    /// preserving every existing object number for diffing or signing
+    /// workflows. Disabled by default.
+    ///
+    /// **Warning**: this defeats secure redaction. The original revision,
+    /// with all of its text, stays in the file and any PDF tool can recover
+    /// it by truncating the file at the previous `%%EOF`. Metadata removal
+    /// and rasterization are likewise only applied to the new revision.
+    /// Use this only when the output is not leaving your hands, e.g. to
+    /// review a redaction before applying it for real. Documents MuPDF had
+    /// to repair, or opened with hidden layers shown (see
+    /// [`with_include_hidden_layers`](Self::with_include_hidden_layers)),
+    /// cannot be updated incrementally and are rewritten in full, as are
+    /// documents redacted in memory.
+    pub fn with_incremental_save(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Deletes pages that redaction left with no text or images, such as
+    /// the pages of a full-page (`.+`) redaction. Disabled by default.
+    ///
+    /// Only pages that had text or images before redacting and none after
+    /// are deleted, so pages that were blank to begin with are kept.
+    /// Drawings and redaction boxes don't count as content, while an
+    /// overlay label does, so labelled pages are never emptied. The first
+    /// page is kept when every page is emptied, as a PDF needs at least
+    /// one. [`RedactionResult::pages_removed`] counts the deleted pages,
+    /// which no longer count towards
+    /// [`RedactionResult::pages_modified`].
+    pub fn with_remove_emptied_pages(mut self, remove: bool) -> Self {
+        self.remove_emptied_pages = remove;
+        self
+    }
+
+    /// Sets the numbering plan [`RedactionTarget::PhoneNumbers`] matches,
+    /// NANP by default.
+    ///
+    /// Besides the renderings the locale knows, each number is searched
+    /// for as it is written in the document.
+    pub fn with_phone_locale(mut self, locale: PhoneLocale) -> Self {
+        self.phone_locale = locale;
+        self
+    }
+
+    /// Covers only the leading characters of numeric matches, leaving the
+    /// last `keep` visible. Used by
+    /// [`MaskingRedactionStrategy`](super::MaskingRedactionStrategy).
+    pub(super) fn with_keep_trailing(mut self, keep: usize) -> Self {
+        self.keep_trailing = Some(keep);
+        self
+    }
+
+    /// Returns how many leading characters of a match to cover when
+    /// trailing characters are kept, or `None` to cover the whole match.
+    ///
+    /// Matches without digits, or no longer than the kept suffix, are
+    /// covered whole.
+    fn masked_prefix_len(&self, text: &str) -> Option<usize> {
+        let keep = self.keep_trailing?;
+        let chars = text.chars().count();
+        (chars > keep && text.chars().any(|c| c.is_ascii_digit())).then(|| chars - keep)
+    }
+
+    /// Returns the label drawn over a match: one `*` per covered character
+    /// when masking, otherwise the [`overlay_for`](Self::overlay_for) its
+    /// category.
+    fn label_for(&self, text: &str, category: &str) -> Option<String> {
+        self.masked_prefix_len(text)
+            .map(|masked| "*".repeat(masked))
+            .or_else(|| self.overlay_for(category))
+    }
+
+    /// Returns the label drawn over a region of `category`: the category in
+    /// brackets with category labels, otherwise the configured overlay
+    /// text.
+    fn overlay_for(&self, category: &str) -> Option<String> {
+        if self.category_labels && !category.is_empty() {
+            // Entity categories come from files and can't cross the C API
+            // with interior NULs
+            let category = category.replace('\0', "").to_ascii_uppercase();
+            return Some(format!("[{}]", category));
+        }
+        self.overlay_text.clone()
+    }
+
+    /// Sets the directory for the temporary file each output is written to
+    /// before being renamed into place.
+    ///
+    /// Renaming is only atomic within one filesystem, so by default the
+    /// temporary file is created next to the output. Override this only
+    /// with a directory on the same filesystem as the output.
+    pub fn with_temp_dir(mut self, dir: PathBuf) -> Self {
+        self.temp_dir = Some(dir);
+        self
+    }
+
     /// Resolves patterns from redaction targets.
+    ///
+    /// `text` is the already-extracted document text, if the caller has it;
+    /// otherwise text is extracted at most once, and only when a target
+    /// needs it. `hidden_values` (form field values and comment text) are
+    /// searched along with the text.
     fn resolve_patterns(
         &self,
         input: &Path,
         targets: &[RedactionTarget],
-    ) -> RedactorResult<Vec<String>> {
+        text: Option<&str>,
+        hidden_values: &[String],
+    ) -> RedactorResult<Vec<SearchPattern>> {
+        let mut text: Cow<'_, str> = match text {
+            Some(text) => Cow::Borrowed(text),
+            None if targets.iter().any(RedactionTarget::requires_text) => {
+                Cow::Owned(self.extract_text(input)?)
+            }
+            None => Cow::Borrowed(""),
+        };
+        for value in hidden_values {
+            let text = text.to_mut();
+            text.push('\n');
+            text.push_str(value);
+        }
+        // Matchers see full-width forms and decomposed accents folded and
+        // words broken across lines rejoined; matches are also searched
+        // for as written
+        let folded = FoldedText::new(text.as_ref());
+        let text = folded.as_str();
+
         let mut patterns = Vec::new();
 
         for target in targets {
+            let mut resolved = Vec::new();
+
             match target {
                 RedactionTarget::Literal(text) => {
-                    patterns.push(text.clone());
+                    resolved.push(SearchPattern::given(text.clone()));
+                }
+                RedactionTarget::LiteralWholeWord(text) => {
+                    resolved.push(SearchPattern::given(text.clone()).whole_word());
+                }
+                RedactionTarget::LiteralCaseInsensitive(literal) => {
+                    let re = regex::RegexBuilder::new(&regex::escape(literal))
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| RedactorError::PatternError {
+                            pattern: literal.clone(),
+                            reason: e.to_string(),
+                        })?;
+
+                    // Renderings found in the text, then common casings in
+                    // case extraction missed some
+                    let mut seen = HashSet::new();
+                    for m in re.find_iter(text).filter(|m| !m.is_empty()) {
+                        if seen.insert(m.as_str().to_string()) {
+                            resolved
+                                .push(SearchPattern::detected(m.as_str().to_string(), m.as_str()));
+                        }
+                    }
+                    for casing in case_renderings(literal) {
+                        if !casing.is_empty() && seen.insert(casing.clone()) {
+                            resolved.push(SearchPattern::given(casing));
+                        }
+                    }
                 }
                 RedactionTarget::PhoneNumbers => {
-                    let text = self.extract_text(input)?;
-                    let matcher = PhoneNumberMatcher::new();
-                    for phone_str in matcher.extract_all(&text) {
+                    let matcher = PhoneNumberMatcher::new().with_locale(self.phone_locale);
+                    for phone_str in matcher.extract_all(text) {
                         if let Some(normalized) = matcher.normalize(phone_str) {
-                            patterns.extend(matcher.generate_variants(&normalized));
+                            let mut variants = matcher.generate_variants(&normalized);
+                            // Groupings vary by country, so also search for
+                            // the number as written
+                            let written = phone_str.trim();
+                            if !variants.iter().any(|variant| variant == written) {
+                                variants.push(written.to_string());
+                            }
+                            resolved.extend(
+                                variants
+                                    .into_iter()
+                                    .map(|variant| SearchPattern::detected(variant, phone_str)),
+                            );
+                        }
+                    }
+                }
+                RedactionTarget::Ssn => {
+                    resolved.extend(detected_patterns(&SsnMatcher::new(), text));
+                }
+                RedactionTarget::Email => {
+                    resolved.extend(detected_patterns(&EmailMatcher::new(), text));
+                }
+                RedactionTarget::CreditCard => {
+                    resolved.extend(detected_patterns(&CreditCardMatcher::new(), text));
+                }
+                RedactionTarget::Iban => {
+                    resolved.extend(detected_patterns(&IbanMatcher::new(), text));
+                }
+                RedactionTarget::Vin => {
+                    resolved.extend(detected_patterns(&VinMatcher::new(), text));
+                }
+                RedactionTarget::MacAddress => {
+                    resolved.extend(detected_patterns(&MacAddressMatcher::new(), text));
+                }
+                RedactionTarget::Handle => {
+                    resolved.extend(detected_patterns(&HandleMatcher::new(), text));
+                }
+                RedactionTarget::Passport { country } => {
+                    let matcher = match country {
+                        Some(country) => {
+                            PassportMatcher::for_country(country).ok_or_else(|| {
+                                RedactorError::InvalidInput {
+                                    parameter: "country".to_string(),
+                                    reason: format!(
+                                        "No passport format for '{}' (supported: {})",
+                                        country,
+                                        PassportMatcher::SUPPORTED_COUNTRIES.join(", ")
+                                    ),
+                                }
+                            })?
+                        }
+                        None => PassportMatcher::new(),
+                    };
+                    resolved.extend(detected_patterns(&matcher, text));
+                }
+                RedactionTarget::DriversLicense { state } => {
+                    let matcher = match state {
+                        Some(state) => {
+                            DriversLicenseMatcher::for_state(state).ok_or_else(|| {
+                                RedactorError::InvalidInput {
+                                    parameter: "state".to_string(),
+                                    reason: format!(
+                                        "No driver's license format for '{}' (supported: {})",
+                                        state,
+                                        DriversLicenseMatcher::SUPPORTED_STATES.join(", ")
+                                    ),
+                                }
+                            })?
                         }
+                        None => DriversLicenseMatcher::new(),
+                    };
+                    resolved.extend(detected_patterns(&matcher, text));
+                }
+                RedactionTarget::RoutingNumber => {
+                    resolved.extend(detected_patterns(&RoutingNumberMatcher::new(), text));
+                }
+                RedactionTarget::Checksum { length, algorithm } => {
+                    let matcher =
+                        ChecksumMatcher::try_new(*length, *algorithm).ok_or_else(|| {
+                            RedactorError::InvalidInput {
+                                parameter: "length".to_string(),
+                                reason: "Checksum length must be at least one digit".to_string(),
+                            }
+                        })?;
+                    resolved.extend(detected_patterns(&matcher, text));
+                }
+                RedactionTarget::Custom(name) => {
+                    return Err(RedactorError::InvalidInput {
+                        parameter: "targets".to_string(),
+                        reason: format!(
+                            "Custom matcher '{}' can only be resolved by a RedactionService",
+                            name
+                        ),
+                    });
+                }
+                RedactionTarget::AllTextInRect { page, rect } => {
+                    let (x0, y0, x1, y1) = *rect;
+                    if page == &Some(0) {
+                        return Err(RedactorError::InvalidInput {
+                            parameter: "page".to_string(),
+                            reason: "Page numbers start at 1".to_string(),
+                        });
+                    }
+                    if !(x0 < x1 && y0 < y1) {
+                        return Err(RedactorError::InvalidInput {
+                            parameter: "rect".to_string(),
+                            reason: format!(
+                                "Rectangle ({}, {}, {}, {}) is empty or inverted",
+                                x0, y0, x1, y1
+                            ),
+                        });
                     }
+                    resolved.push(SearchPattern::area(*page, MuRect::new(x0, y0, x1, y1)));
+                }
+                RedactionTarget::AllLinks => {
+                    resolved.push(SearchPattern::all_links());
+                }
+                RedactionTarget::TextWithStyle {
+                    color,
+                    font_contains,
+                } => {
+                    strategy::check_style(*color, font_contains.as_deref()).map_err(|reason| {
+                        RedactorError::InvalidInput {
+                            parameter: "style".to_string(),
+                            reason,
+                        }
+                    })?;
+                    resolved.push(SearchPattern::styled(
+                        TextStyle::new(*color, font_contains.as_deref()),
+                        &target.to_string(),
+                    ));
                 }
                 RedactionTarget::VerizonAccount => {
-                    let text = self.extract_text(input)?;
-                    if let Some(account) = VerizonAccountMatcher::find_account_number(&text) {
+                    if let Some(account) = VerizonAccountMatcher::find_account_number(text) {
                         let matcher = VerizonAccountMatcher::new();
-                        patterns.extend(matcher.generate_variants(&account));
+                        resolved.extend(
+                            matcher
+                                .generate_variants(&account)
+                                .into_iter()
+                                .map(|variant| SearchPattern::detected(variant, &account)),
+                        );
                     } else {
                         return Err(RedactorError::PatternNotFound {
                             pattern: "Verizon account number".to_string(),
@@ -74,234 +620,2130 @@ impl SecureRedactionStrategy {
                     }
                 }
                 RedactionTarget::VerizonCallDetails => {
-                    let text = self.extract_text(input)?;
                     let matcher = VerizonCallDetailsMatcher::new();
 
                     // Check if document contains call detail table
-                    if VerizonCallDetailsMatcher::has_call_detail_table(&text) {
+                    if VerizonCallDetailsMatcher::has_call_detail_table(text) {
                         // Extract all call detail column values (time, origination, destination)
-                        let details = matcher.extract_all_call_details(&text);
-                        patterns.extend(details);
+                        let details = matcher.extract_all_call_details(text);
+                        resolved.extend(
+                            details
+                                .into_iter()
+                                .map(|d| SearchPattern::detected(d.clone(), &d)),
+                        );
                     }
                     // Note: If no call detail table found, we simply don't add patterns
                     // This is not an error - the document may not have call details
                 }
-                RedactionTarget::Regex(pattern) => {
-                    // Extract text from PDF
-                    let text = self.extract_text(input)?;
-
+                RedactionTarget::Timestamp => {
+                    resolved.extend(detected_patterns(&TimestampMatcher::new(), text));
+                }
+                RedactionTarget::Dates => {
+                    resolved.extend(detected_patterns(&DateMatcher::new(), text));
+                }
+                RedactionTarget::PostalCode => {
+                    resolved.extend(detected_patterns(&PostalCodeMatcher::new(), text));
+                }
+                RedactionTarget::EntityList { path } => {
+                    let contents =
+                        std::fs::read_to_string(path).map_err(|e| RedactorError::Io {
+                            path: path.clone(),
+                            source: e,
+                        })?;
+                    for entity in Entity::parse_list(&contents) {
+                        let category = entity.category.as_deref().unwrap_or("entity");
+                        resolved.extend(entity.variants().into_iter().map(|v| {
+                            SearchPattern::given(v).in_category(&category.to_ascii_lowercase())
+                        }));
+                    }
+                }
+                RedactionTarget::NamedEntity(names) => {
+                    // Surnames and initials alone must not hit inside
+                    // longer words
+                    let matcher = NameMatcher::new();
+                    for name in names {
+                        resolved.extend(
+                            matcher
+                                .generate_variants(name)
+                                .into_iter()
+                                .map(|variant| SearchPattern::given(variant).whole_word()),
+                        );
+                    }
+                }
+                RedactionTarget::Regex(pattern)
+                | RedactionTarget::RegexWithReplacement { pattern, .. } => {
                     // Compile regex pattern
                     let re =
-                        regex::Regex::new(pattern).map_err(|e| RedactorError::InvalidInput {
-                            parameter: "regex_pattern".to_string(),
+                        regex::Regex::new(pattern).map_err(|e| RedactorError::PatternError {
+                            pattern: pattern.clone(),
                             reason: format!("Invalid regex pattern: {}", e),
                         })?;
 
                     // Find all matches and add them as literal patterns
-                    for capture in re.find_iter(&text) {
+                    for capture in re.find_iter(text) {
                         let matched_text = capture.as_str().to_string();
                         if !matched_text.is_empty() {
-                            patterns.push(matched_text);
+                            resolved
+                                .push(SearchPattern::detected(matched_text.clone(), &matched_text));
                         }
                     }
                 }
-            }
-        }
-
-        // Return empty patterns vector if none found - this will result in
-        // zero redactions but is not an error condition
-        Ok(patterns)
-    }
-
-    /// Applies redactions to a PDF using MuPDF.
-    fn apply_mupdf_redactions(
-        &self,
-        pdf_doc: &PdfDocument,
-        patterns: &[String],
-    ) -> RedactorResult<RedactionResult> {
-        let page_count = pdf_doc
-            .page_count()
-            .map_err(|e| RedactorError::BackendError {
-                backend: "MuPDF".to_string(),
-                message: format!("Failed to get page count: {}", e),
-                source: Some(Box::new(e)),
-            })?;
-
-        let mut result = RedactionResult {
-            pages_processed: page_count as usize,
-            secure: true,
-            ..Default::default()
-        };
-
-        // Check if this is a full-page redaction (single pattern matching everything: ".+")
-        let redact_all = patterns.len() == 1 && patterns[0] == ".+";
-
-        // Process each page
-        for page_idx in 0..page_count {
-            let page = pdf_doc
-                .load_page(page_idx)
-                .map_err(|e| RedactorError::PdfProcessing {
-                    message: format!("Failed to load page {}", page_idx + 1),
-                    page: Some(page_idx as usize + 1),
-                    source: Some(Box::new(e)),
-                })?;
-
-            // Convert to PDF page for annotation support
-            let mut pdf_page = match PdfPage::try_from(page.clone()) {
-                Ok(p) => p,
-                Err(_) => continue, // Skip non-PDF pages
-            };
-
-            let mut page_redactions = 0;
-
-            // If redacting all, create a single annotation covering the entire page
-            if redact_all {
-                let bounds = page.bounds().map_err(|e| RedactorError::BackendError {
-                    backend: "MuPDF".to_string(),
-                    message: format!("Failed to get bounds for page {}", page_idx + 1),
-                    source: Some(Box::new(e)),
-                })?;
-
-                let annot = pdf_page
-                    .create_annotation(PdfAnnotationType::Redact)
-                    .map_err(|e| RedactorError::PdfProcessing {
-                        message: "Failed to create redaction annotation".to_string(),
-                        page: Some(page_idx as usize + 1),
-                        source: Some(Box::new(e)),
-                    })?;
+                RedactionTarget::RegexWholeWord(pattern) => {
+                    let re =
+                        regex::Regex::new(pattern).map_err(|e| RedactorError::PatternError {
+                            pattern: pattern.clone(),
+                            reason: format!("Invalid regex pattern: {}", e),
+                        })?;
 
-                unsafe {
-                    ffi::set_annotation_rect(&annot, bounds);
+                    // Only matches that are whole words in the text; hits
+                    // inside longer words are filtered out when searching
+                    for m in re.find_iter(text) {
+                        if !m.is_empty() && is_whole_word(text, m.start(), m.end()) {
+                            resolved.push(
+                                SearchPattern::detected(m.as_str().to_string(), m.as_str())
+                                    .whole_word(),
+                            );
+                        }
+                    }
                 }
+                RedactionTarget::RegexCaptureGroup { pattern, group } => {
+                    let re =
+                        regex::Regex::new(pattern).map_err(|e| RedactorError::PatternError {
+                            pattern: pattern.clone(),
+                            reason: e.to_string(),
+                        })?;
+                    if *group >= re.captures_len() {
+                        return Err(RedactorError::PatternError {
+                            pattern: pattern.clone(),
+                            reason: format!(
+                                "Capture group {} does not exist (pattern has {})",
+                                group,
+                                re.captures_len() - 1
+                            ),
+                        });
+                    }
 
-                page_redactions += 1;
-            } else {
-                // Search for each pattern
-                for pattern in patterns {
-                    let hits = page.search(pattern, self.max_hits).map_err(|e| {
-                        RedactorError::BackendError {
-                            backend: "MuPDF".to_string(),
-                            message: format!("Search failed for pattern: {}", pattern),
-                            source: Some(Box::new(e)),
+                    // Redact only the group's span of each match
+                    for caps in re.captures_iter(text) {
+                        if let Some(m) = caps.get(*group).filter(|m| !m.is_empty()) {
+                            resolved
+                                .push(SearchPattern::detected(m.as_str().to_string(), m.as_str()));
                         }
-                    })?;
+                    }
+                }
+                RedactionTarget::NamedGroups { pattern } => {
+                    let re =
+                        regex::Regex::new(pattern).map_err(|e| RedactorError::PatternError {
+                            pattern: pattern.clone(),
+                            reason: e.to_string(),
+                        })?;
+                    if re.capture_names().flatten().next().is_none() {
+                        return Err(RedactorError::PatternError {
+                            pattern: pattern.clone(),
+                            reason: "Pattern has no named capture groups".to_string(),
+                        });
+                    }
 
-                    // Create redaction annotation for each hit
-                    for quad in hits {
-                        let annot = pdf_page
-                            .create_annotation(PdfAnnotationType::Redact)
-                            .map_err(|e| RedactorError::PdfProcessing {
-                                message: "Failed to create redaction annotation".to_string(),
-                                page: Some(page_idx as usize + 1),
-                                source: Some(Box::new(e)),
-                            })?;
-
-                        // Calculate bounding rectangle
-                        let rect = MuRect {
-                            x0: quad.ul.x.min(quad.ll.x).min(quad.ur.x).min(quad.lr.x),
-                            y0: quad.ul.y.min(quad.ll.y).min(quad.ur.y).min(quad.lr.y),
-                            x1: quad.ul.x.max(quad.ll.x).max(quad.ur.x).max(quad.lr.x),
-                            y1: quad.ul.y.max(quad.ll.y).max(quad.ur.y).max(quad.lr.y),
-                        };
-
-                        // Set annotation rectangle
-                        unsafe {
-                            ffi::set_annotation_rect(&annot, rect);
+                    // Redact each named group's span, labeled with the group name
+                    for caps in re.captures_iter(text) {
+                        for name in re.capture_names().flatten() {
+                            if let Some(m) = caps.name(name).filter(|m| !m.is_empty()) {
+                                resolved.push(
+                                    SearchPattern::detected(m.as_str().to_string(), m.as_str())
+                                        .in_category(name),
+                                );
+                            }
                         }
-
-                        page_redactions += 1;
                     }
                 }
-            } // end else block for pattern-based redaction
+            }
 
-            // Apply redactions if any were created
-            if page_redactions > 0 {
-                pdf_page
-                    .redact()
-                    .map_err(|e| RedactorError::PdfProcessing {
-                        message: format!("Failed to apply redactions on page {}", page_idx + 1),
-                        page: Some(page_idx as usize + 1),
-                        source: Some(Box::new(e)),
-                    })?;
+            let mut written = Vec::new();
+            for pattern in &resolved {
+                if let Some(value) = pattern.detected.as_deref() {
+                    // A normalized value may not appear in the text while
+                    // one of its renderings does
+                    let mut found = folded.originals(value);
+                    if pattern.text != value {
+                        found.extend(folded.originals(&pattern.text));
+                    }
+                    written.extend(found.into_iter().map(|original| {
+                        SearchPattern {
+                            whole_word: pattern.whole_word,
+                            ..SearchPattern::detected(original.to_string(), value)
+                        }
+                        .in_category(&pattern.category)
+                    }));
+                }
+            }
+            resolved.extend(written);
 
-                result.instances_redacted += page_redactions;
-                result.pages_modified += 1;
+            log::debug!("Resolved {} pattern(s) for {:?}", resolved.len(), target);
+            let max_hits = self.max_hits_for(target);
+            for mut pattern in resolved {
+                pattern.max_hits = max_hits;
+                if pattern.category.is_empty() {
+                    pattern.category = target.category().to_string();
+                }
+                patterns.push(pattern);
             }
         }
 
-        Ok(result)
+        // Return empty patterns vector if none found - this will result in
+        // zero redactions but is not an error condition
+        let patterns = dedup_patterns(patterns);
+        log::debug!("Searching for {} distinct pattern(s)", patterns.len());
+        Ok(patterns)
     }
-}
 
-impl RedactionStrategy for SecureRedactionStrategy {
-    fn redact(
+    /// Resolves `targets` against the text of one page while streaming, or
+    /// against the empty text to validate them up front.
+    ///
+    /// A target that finds nothing to redact in `text`, such as an account
+    /// number printed on another page, resolves to no patterns rather than
+    /// failing the redaction.
+    fn resolve_streamed(
         &self,
         input: &Path,
-        output: &Path,
         targets: &[RedactionTarget],
-    ) -> RedactorResult<RedactionResult> {
-        // Resolve patterns from targets
-        let patterns = self.resolve_patterns(input, targets)?;
-
-        // If no patterns found, just copy the file
-        if patterns.is_empty() {
-            std::fs::copy(input, output).map_err(|e| RedactorError::Io {
-                path: output.to_path_buf(),
-                source: e,
-            })?;
-            return Ok(RedactionResult::none());
+        text: &str,
+        hidden_values: &[String],
+    ) -> RedactorResult<Vec<SearchPattern>> {
+        let mut patterns = Vec::new();
+        for target in targets {
+            match self.resolve_patterns(
+                input,
+                std::slice::from_ref(target),
+                Some(text),
+                hidden_values,
+            ) {
+                Ok(resolved) => patterns.extend(resolved),
+                Err(RedactorError::PatternNotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
         }
+        Ok(dedup_patterns(patterns))
+    }
+
+    /// Opens a PDF with MuPDF, authenticating with `password` if the
+    /// document is encrypted.
+    fn open_document(&self, input: &Path, password: Option<&str>) -> RedactorResult<PdfDocument> {
+        ensure_not_empty(input)?;
 
-        // Open PDF with MuPDF
         let input_str = input.to_str().ok_or_else(|| RedactorError::InvalidInput {
             parameter: "input".to_string(),
             reason: "Path contains invalid UTF-8".to_string(),
         })?;
 
-        let pdf_doc = PdfDocument::open(input_str).map_err(|e| RedactorError::PdfProcessing {
-            message: "Failed to open PDF with MuPDF".to_string(),
+        let mut pdf_doc = PdfDocument::open(input_str).map_err(|e| {
+            let bytes = std::fs::read(input).unwrap_or_default();
+            open_error(&bytes, damage_hint(input), e)
+        })?;
+        Self::unlock(&mut pdf_doc, input, password)?;
+        Ok(pdf_doc)
+    }
+
+    /// Authenticates an encrypted document, which MuPDF otherwise cannot
+    /// read.
+    fn unlock(
+        pdf_doc: &mut PdfDocument,
+        input: &Path,
+        password: Option<&str>,
+    ) -> RedactorResult<()> {
+        let to_error = |e: mupdf::Error| RedactorError::PdfProcessing {
+            message: "Failed to check PDF encryption".to_string(),
             page: None,
             source: Some(Box::new(e)),
-        })?;
+        };
 
-        // Apply redactions
-        let result = self.apply_mupdf_redactions(&pdf_doc, &patterns)?;
+        if !pdf_doc.needs_password().map_err(to_error)? {
+            return Ok(());
+        }
+        match password {
+            None => Err(RedactorError::EncryptedPdf {
+                path: input.to_path_buf(),
+            }),
+            Some(password) if pdf_doc.authenticate(password).map_err(to_error)? => Ok(()),
+            Some(_) => Err(RedactorError::InvalidInput {
+                parameter: "password".to_string(),
+                reason: format!("Incorrect password for '{}'", input.display()),
+            }),
+        }
+    }
 
-        // Save if redactions were applied
-        if result.has_redactions() {
-            let output_str = output.to_str().ok_or_else(|| RedactorError::InvalidInput {
-                parameter: "output".to_string(),
+    /// Opens a PDF for searching and redaction.
+    ///
+    /// With hidden layers included, the document is opened with every layer
+    /// switched on and the original layer configuration is returned so
+    /// [`save_document`](Self::save_document) can restore it. Layers are
+    /// not switched on in documents opened with a password. The file is
+    /// only parsed for its layer configuration if a byte search finds it
+    /// may have one.
+    fn open_searchable(
+        &self,
+        input: &Path,
+        password: Option<&str>,
+    ) -> RedactorResult<(PdfDocument, Option<Dictionary>)> {
+        if self.skip_hidden_layers || password.is_some() {
+            return Ok((self.open_document(input, password)?, None));
+        }
+
+        ensure_not_empty(input)?;
+        let bytes = read_input(input)?;
+        let Some((shown, original)) = layers::show_all_layers(&bytes)? else {
+            return Ok((self.open_document(input, None)?, None));
+        };
+
+        let mut pdf_doc = PdfDocument::from_bytes(&shown).map_err(|e| open_error(&bytes, "", e))?;
+        Self::unlock(&mut pdf_doc, input, None)?;
+        Ok((pdf_doc, Some(original)))
+    }
+
+    /// Opens a PDF held in memory for searching and redaction, as
+    /// [`open_searchable`](Self::open_searchable) opens a file.
+    fn open_bytes(&self, input: &[u8]) -> RedactorResult<(PdfDocument, Option<Dictionary>)> {
+        if input.is_empty() {
+            return Err(RedactorError::InvalidInput {
+                parameter: "input".to_string(),
+                reason: "PDF data is empty (0 bytes)".to_string(),
+            });
+        }
+
+        let shown = if !self.skip_hidden_layers {
+            layers::show_all_layers(input)?
+        } else {
+            None
+        };
+        let open =
+            |bytes: &[u8]| PdfDocument::from_bytes(bytes).map_err(|e| open_error(input, "", e));
+        let (mut pdf_doc, layer_config) = match shown {
+            Some((bytes, config)) => (open(&bytes)?, Some(config)),
+            None => (open(input)?, None),
+        };
+        Self::unlock(&mut pdf_doc, Path::new(IN_MEMORY), None)?;
+        Ok((pdf_doc, layer_config))
+    }
+
+    /// Writes the redacted document, or copies the input if nothing changed.
+    ///
+    /// `layer_config` is the default layer configuration to restore, as
+    /// returned by [`open_searchable`](Self::open_searchable).
+    fn save_document(
+        &self,
+        pdf_doc: &PdfDocument,
+        input: &Path,
+        output: &Path,
+        result: &RedactionResult,
+        layer_config: Option<&Dictionary>,
+    ) -> RedactorResult<()> {
+        if !result.has_redactions() && !self.strip_metadata {
+            // No redactions - just copy the file
+            self.copy_document(input, output)?;
+            log::info!("Nothing redacted; copied input to {}", output.display());
+            return Ok(());
+        }
+
+        let incremental = self.saves_incrementally(pdf_doc, layer_config.is_some());
+        let options = self.write_options(pdf_doc, incremental)?;
+        self.write_atomically(output, |temp| {
+            let temp_str = temp.to_str().ok_or_else(|| RedactorError::InvalidInput {
+                parameter: "output".to_string(),
                 reason: "Path contains invalid UTF-8".to_string(),
             })?;
 
-            pdf_doc
-                .save(output_str)
-                .map_err(|e| RedactorError::PdfProcessing {
-                    message: "Failed to save redacted PDF".to_string(),
-                    page: None,
+            if incremental {
+                // MuPDF appends the new revision to the file it is given
+                std::fs::copy(input, temp).map_err(|e| RedactorError::Io {
+                    path: output.to_path_buf(),
+                    source: e,
+                })?;
+            }
+            pdf_doc.save_with_options(temp_str, options).map_err(|e| {
+                RedactorError::PdfProcessing {
+                    message: "Failed to save redacted PDF".to_string(),
+                    page: None,
+                    source: Some(Box::new(e)),
+                }
+            })?;
+
+            if let Some(config) = layer_config {
+                layers::restore_layer_config(temp, config)?;
+            }
+            Ok(())
+        })?;
+        log::info!(
+            "Saved {} redaction(s) to {}",
+            result.instances_redacted,
+            output.display()
+        );
+        Ok(())
+    }
+
+    /// Returns whether the redacted document is appended to its input as
+    /// an incremental update, warning either way when one was requested.
+    ///
+    /// `shown_layers` is whether the document was opened from a copy with
+    /// hidden layers shown, whose object offsets don't match the input.
+    fn saves_incrementally(&self, pdf_doc: &PdfDocument, shown_layers: bool) -> bool {
+        if !self.incremental {
+            return false;
+        }
+        if shown_layers || !pdf_doc.can_be_saved_incrementally() {
+            log::warn!("Document cannot be updated incrementally; rewriting it in full");
+            return false;
+        }
+        log::warn!(
+            "Saving incrementally: the original text remains recoverable from the \
+             previous revision of the output"
+        );
+        true
+    }
+
+    /// Returns the options to write the redacted document with, first
+    /// removing its metadata if configured to.
+    fn write_options(
+        &self,
+        pdf_doc: &PdfDocument,
+        incremental: bool,
+    ) -> RedactorResult<PdfWriteOptions> {
+        let mut options = PdfWriteOptions::default();
+        if self.strip_metadata {
+            Self::remove_metadata(pdf_doc)?;
+            // Drop the now-unreferenced metadata objects from the file
+            options.set_garbage(true);
+        }
+        if self.rasterize_dpi.is_some() {
+            // Drop the replaced content streams and fonts of rasterized
+            // pages, which still hold their text
+            options.set_garbage(true);
+        }
+        if incremental {
+            // Garbage collection renumbers objects, which an update can't
+            options.set_garbage(false).set_incremental(true);
+        }
+        Ok(options)
+    }
+
+    /// Returns the 1-based numbers of the pages with text or images, if
+    /// emptied pages are to be removed, to tell the pages redaction empties
+    /// from those already blank.
+    fn pages_with_content(&self, pdf_doc: &PdfDocument) -> RedactorResult<Option<BTreeSet<usize>>> {
+        if !self.remove_emptied_pages {
+            return Ok(None);
+        }
+        let mut pages = BTreeSet::new();
+        for page_idx in 0..Self::page_count(pdf_doc)? {
+            if page_has_content(&Self::load_page(pdf_doc, page_idx)?, page_idx)? {
+                pages.insert(page_idx as usize + 1);
+            }
+        }
+        Ok(Some(pages))
+    }
+
+    /// Finishes the pages with redactions in `result`: deletes those left
+    /// empty and rasterizes the rest, as configured.
+    ///
+    /// `content` holds the pages that had content before redacting, as
+    /// returned by [`pages_with_content`](Self::pages_with_content).
+    fn finish_modified_pages(
+        &self,
+        pdf_doc: &mut PdfDocument,
+        result: &mut RedactionResult,
+        content: Option<&BTreeSet<usize>>,
+    ) -> RedactorResult<()> {
+        let mut emptied = BTreeSet::new();
+        if let Some(content) = content {
+            for page_number in modified_pages(result).intersection(content) {
+                let page_idx = *page_number as i32 - 1;
+                if !page_has_content(&Self::load_page(pdf_doc, page_idx)?, page_idx)? {
+                    emptied.insert(*page_number);
+                }
+            }
+            if emptied.len() == Self::page_count(pdf_doc)? as usize {
+                emptied.pop_first();
+            }
+        }
+
+        self.rasterize_modified_pages(pdf_doc, result, &emptied)?;
+
+        // Last first, so the numbers of the pages before stay valid
+        for &page_number in emptied.iter().rev() {
+            pdf_doc.delete_page(page_number as i32 - 1).map_err(|e| {
+                RedactorError::PdfProcessing {
+                    message: format!("Failed to remove emptied page {}", page_number),
+                    page: Some(page_number),
+                    source: Some(Box::new(e)),
+                }
+            })?;
+        }
+        if !emptied.is_empty() {
+            log::info!("Removed {} page(s) emptied by redaction", emptied.len());
+        }
+        result.pages_modified -= emptied.len();
+        result.pages_removed += emptied.len();
+        Ok(())
+    }
+
+    /// Rasterizes every page with redactions in `result` except those in
+    /// `skip`, if configured to.
+    fn rasterize_modified_pages(
+        &self,
+        pdf_doc: &mut PdfDocument,
+        result: &RedactionResult,
+        skip: &BTreeSet<usize>,
+    ) -> RedactorResult<()> {
+        let Some(dpi) = self.rasterize_dpi else {
+            return Ok(());
+        };
+        if dpi == 0 {
+            return Err(RedactorError::InvalidInput {
+                parameter: "dpi".to_string(),
+                reason: "Rasterization resolution must be at least 1 DPI".to_string(),
+            });
+        }
+
+        for page_number in modified_pages(result).difference(skip) {
+            raster::rasterize_page(pdf_doc, *page_number as i32 - 1, dpi)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the text of form field values and, unless comments are
+    /// kept, comments, none of which is page content.
+    fn hidden_values(&self, pdf_doc: &PdfDocument) -> RedactorResult<Vec<String>> {
+        let mut values = forms::field_values(pdf_doc)?;
+        if !self.keep_annotations {
+            values.extend(annotations::comment_texts(pdf_doc)?);
+            values.extend(links::link_uris(pdf_doc)?);
+        }
+        if !self.keep_outline {
+            values.extend(outline::outline_titles(pdf_doc)?);
+        }
+        Ok(values)
+    }
+
+    /// Unlinks the Info dictionary from the trailer and the XMP metadata
+    /// stream from the catalog.
+    fn remove_metadata(pdf_doc: &PdfDocument) -> RedactorResult<()> {
+        let to_error = |e: mupdf::Error| RedactorError::PdfProcessing {
+            message: "Failed to strip document metadata".to_string(),
+            page: None,
+            source: Some(Box::new(e)),
+        };
+
+        pdf_doc
+            .trailer()
+            .and_then(|mut trailer| trailer.dict_delete("Info"))
+            .map_err(to_error)?;
+        pdf_doc
+            .catalog()
+            .and_then(|mut catalog| catalog.dict_delete("Metadata"))
+            .map_err(to_error)?;
+
+        Ok(())
+    }
+
+    /// Records the size of the written output in `result` and, if
+    /// configured to, its SHA-256 digest.
+    fn describe_output(&self, output: &Path, result: &mut RedactionResult) -> RedactorResult<()> {
+        let io_error = |e| RedactorError::Io {
+            path: output.to_path_buf(),
+            source: e,
+        };
+        result.output_bytes = std::fs::metadata(output).map_err(io_error)?.len();
+        if self.hash_output {
+            let mut file = std::fs::File::open(output).map_err(io_error)?;
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher).map_err(io_error)?;
+            result.output_sha256 = Some(format!("{:x}", hasher.finalize()));
+        }
+        Ok(())
+    }
+
+    /// Copies the input unchanged to the output.
+    fn copy_document(&self, input: &Path, output: &Path) -> RedactorResult<()> {
+        self.write_atomically(output, |temp| {
+            std::fs::copy(input, temp)
+                .map(|_| ())
+                .map_err(|e| RedactorError::Io {
+                    path: output.to_path_buf(),
+                    source: e,
+                })
+        })
+    }
+
+    /// Runs `write` against a temporary file and renames it over `output`,
+    /// so readers never observe a partially written file and in-place
+    /// redaction never truncates its own input.
+    fn write_atomically<F>(&self, output: &Path, write: F) -> RedactorResult<()>
+    where
+        F: FnOnce(&Path) -> RedactorResult<()>,
+    {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = match &self.temp_dir {
+            Some(dir) => dir.clone(),
+            None => match output.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            },
+        };
+        let name = output
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "output".to_string());
+        let temp = dir.join(format!(
+            ".{}.{}-{}.tmp",
+            name,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let written = write(&temp).and_then(|()| {
+            std::fs::rename(&temp, output).map_err(|e| RedactorError::Io {
+                path: output.to_path_buf(),
+                source: e,
+            })
+        });
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        written
+    }
+
+    /// Runs [`redact_with_options`](RedactionStrategy::redact_with_options)
+    /// on [`MupdfWorker::global`], failing if it has not finished within
+    /// `limit`.
+    ///
+    /// The job writes to a temporary file that is only moved into place if
+    /// it finishes in time. Progress is reported on the caller's thread.
+    ///
+    /// MuPDF cannot be interrupted, so a timed-out job keeps running until
+    /// MuPDF returns. Running it on the shared worker rather than a thread
+    /// of its own keeps the invariant that only the worker is inside MuPDF
+    /// while it does: later timed redactions, and anything else sent to the
+    /// global worker, queue behind it instead of racing it.
+    fn redact_with_timeout(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+        limit: Duration,
+    ) -> RedactorResult<RedactionResult> {
+        enum Message {
+            Progress(RedactionProgress),
+            Done(Box<RedactorResult<RedactionResult>>),
+        }
+
+        let deadline = Instant::now() + limit;
+        let untimed = Self {
+            timeout: None,
+            ..self.clone()
+        };
+        let input_owned = input.to_path_buf();
+        let targets = targets.to_vec();
+        let text = options.text.map(str::to_string);
+        let pages = options.pages.cloned();
+        let password = options.password.map(str::to_string);
+
+        let mut result = None;
+        self.write_atomically(output, |temp| {
+            let temp = temp.to_path_buf();
+            let (sender, receiver) = mpsc::channel();
+
+            MupdfWorker::global()?.submit(move || {
+                let progress = |update| {
+                    let _ = sender.send(Message::Progress(update));
+                };
+                let options = RedactionOptions {
+                    text: text.as_deref(),
+                    pages: pages.as_ref(),
+                    password: password.as_deref(),
+                    progress: Some(&progress),
+                };
+                let redacted = untimed.redact_with_options(&input_owned, &temp, &targets, &options);
+
+                // Nobody is waiting after a timeout, so drop the output
+                if sender.send(Message::Done(Box::new(redacted))).is_err() {
+                    let _ = std::fs::remove_file(&temp);
+                }
+            })?;
+
+            loop {
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(Message::Progress(update)) => {
+                        options.report(update.phase, update.current_page, update.total_pages)
+                    }
+                    Ok(Message::Done(redacted)) => {
+                        result = Some((*redacted)?);
+                        return Ok(());
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        return Err(RedactorError::Timeout {
+                            path: input.to_path_buf(),
+                            limit,
+                        })
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return Err(RedactorError::PdfProcessing {
+                            message: "Redaction worker job panicked".to_string(),
+                            page: None,
+                            source: None,
+                        })
+                    }
+                }
+            }
+        })?;
+
+        Ok(result.expect("Result is set when the worker finishes"))
+    }
+
+    /// Returns the number of pages in the document.
+    fn page_count(pdf_doc: &PdfDocument) -> RedactorResult<i32> {
+        pdf_doc
+            .page_count()
+            .map_err(|e| RedactorError::BackendError {
+                backend: "MuPDF".to_string(),
+                message: format!("Failed to get page count: {}", e),
+                source: Some(Box::new(e)),
+            })
+    }
+
+    /// Returns the number of pages in a document about to be redacted,
+    /// failing if it has none.
+    ///
+    /// A document without pages, whether empty or with a damaged page
+    /// tree, has nothing to redact; saying so beats returning a result in
+    /// which nothing was processed.
+    fn redactable_page_count(pdf_doc: &PdfDocument) -> RedactorResult<i32> {
+        match Self::page_count(pdf_doc)? {
+            0 => Err(RedactorError::PdfProcessing {
+                message: "Document has no pages; its page tree may be empty or damaged".to_string(),
+                page: None,
+                source: None,
+            }),
+            count => Ok(count),
+        }
+    }
+
+    /// Extracts the text of every page with MuPDF.
+    fn document_text(pdf_doc: &PdfDocument, input: &Path) -> RedactorResult<String> {
+        let mut text = String::new();
+        for page_idx in 0..Self::page_count(pdf_doc)? {
+            let page = Self::load_page(pdf_doc, page_idx)?;
+            text.push_str(&Self::page_text(&page, page_idx, input)?);
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    /// Extracts the text of one page with MuPDF.
+    fn page_text(page: &Page, page_idx: i32, input: &Path) -> RedactorResult<String> {
+        page.to_text().map_err(|e| RedactorError::TextExtraction {
+            path: input.to_path_buf(),
+            reason: format!("MuPDF failed on page {}: {}", page_idx + 1, e),
+        })
+    }
+
+    /// Extracts text with MuPDF, the same text model that
+    /// [`search_page`](Self::search_page) matches against, so resolved patterns are found.
+    fn extract_text_mupdf(&self, input: &Path) -> RedactorResult<String> {
+        let (pdf_doc, _) = self.open_searchable(input, None)?;
+        Self::document_text(&pdf_doc, input)
+    }
+
+    /// Extracts text with `pdf_extract`, for documents MuPDF cannot read.
+    ///
+    /// The input is memory-mapped rather than read, so a large document is
+    /// not copied into memory before parsing.
+    fn extract_text_fallback(input: &Path) -> RedactorResult<String> {
+        let bytes = read_input(input)?;
+
+        pdf_extract::extract_text_from_mem(&bytes).map_err(|e| RedactorError::TextExtraction {
+            path: input.to_path_buf(),
+            reason: format!("{}{}", e, damage_hint(input)),
+        })
+    }
+
+    /// Loads a page by zero-based index.
+    fn load_page(pdf_doc: &PdfDocument, page_idx: i32) -> RedactorResult<Page> {
+        pdf_doc
+            .load_page(page_idx)
+            .map_err(|e| RedactorError::PdfProcessing {
+                message: format!("Failed to load page {}", page_idx + 1),
+                page: Some(page_idx as usize + 1),
+                source: Some(Box::new(e)),
+            })
+    }
+
+    /// Searches a page for `pattern`.
+    ///
+    /// Returns the hits and whether the pattern's hit limit was reached, in
+    /// which case MuPDF may have stopped before the last match.
+    fn search_page(
+        page: &Page,
+        page_idx: i32,
+        pattern: &SearchPattern,
+    ) -> RedactorResult<(Vec<Quad>, bool)> {
+        let hits = page.search(&pattern.text, pattern.max_hits).map_err(|e| {
+            RedactorError::BackendError {
+                backend: "MuPDF".to_string(),
+                message: format!(
+                    "Search failed for pattern on page {}: {}",
+                    page_idx + 1,
+                    pattern.text
+                ),
+                source: Some(Box::new(e)),
+            }
+        })?;
+
+        // MuPDF treats a limit of 0 as its default
+        let limit = if pattern.max_hits == 0 {
+            MUPDF_DEFAULT_MAX_HITS
+        } else {
+            pattern.max_hits
+        };
+        let truncated = hits.len() as u32 >= limit;
+
+        log::debug!(
+            "Page {}: {} hit(s) for '{}'",
+            page_idx + 1,
+            hits.len(),
+            pattern.text
+        );
+        if truncated {
+            log::warn!(
+                "Page {}: hit limit of {} reached for '{}', more matches may remain",
+                page_idx + 1,
+                limit,
+                pattern.text
+            );
+        }
+
+        Ok((hits.into_iter().collect(), truncated))
+    }
+
+    /// Locates the regions to redact on a page.
+    fn locate_regions<'p>(
+        &self,
+        page: &Page,
+        page_idx: i32,
+        patterns: &'p [SearchPattern],
+    ) -> RedactorResult<LocatedRegions<'p>> {
+        // Check if this is a full-page redaction (single pattern matching everything: ".+")
+        if patterns.len() == 1 && patterns[0].text == ".+" {
+            let mut bounds = page.bounds().map_err(|e| RedactorError::BackendError {
+                backend: "MuPDF".to_string(),
+                message: format!("Failed to get bounds for page {}", page_idx + 1),
+                source: Some(Box::new(e)),
+            })?;
+
+            // Inset by the configured header/footer bands (y grows downward)
+            let (top, bottom) = self.alltext_margins;
+            bounds.y0 += top;
+            bounds.y1 = (bounds.y1 - bottom).max(bounds.y0);
+
+            return Ok(LocatedRegions {
+                regions: vec![(bounds, &patterns[0])],
+                truncated: Vec::new(),
+                covered: Vec::new(),
+            });
+        }
+
+        let mut located = LocatedRegions {
+            regions: Vec::new(),
+            truncated: Vec::new(),
+            covered: Vec::new(),
+        };
+
+        // Areas and styled text first, so matches inside them are covered
+        let page_number = page_idx as usize + 1;
+        for pattern in patterns {
+            if let Some(area) = &pattern.area {
+                if area.page.map_or(true, |page| page == page_number) {
+                    located.regions.push((area.rect, pattern));
+                }
+            }
+            if let Some(style) = &pattern.style {
+                for rect in styles::styled_regions(page, page_idx, style)? {
+                    located.regions.push((rect, pattern));
+                }
+            }
+        }
+
+        // Search for each pattern
+        let mut page_text = None;
+        for pattern in patterns.iter().filter(|p| p.searchable()) {
+            if !pattern.pieces.is_empty() {
+                let rects = Self::locate_split(page, page_idx, pattern)?;
+                self.add_regions(&mut located, pattern, rects);
+                continue;
+            }
+
+            let (mut hits, truncated) = Self::search_page(page, page_idx, pattern)?;
+            if truncated {
+                located.truncated.push(pattern);
+            }
+
+            if pattern.whole_word && !hits.is_empty() {
+                let text = match &page_text {
+                    Some(text) => text,
+                    None => page_text.insert(page.to_text().map_err(|e| {
+                        RedactorError::BackendError {
+                            backend: "MuPDF".to_string(),
+                            message: format!("Failed to extract text of page {}", page_idx + 1),
+                            source: Some(Box::new(e)),
+                        }
+                    })?),
+                };
+                hits = whole_word_hits(hits, text, &pattern.text);
+            }
+
+            self.add_regions(&mut located, pattern, hits.iter().map(quad_rect).collect());
+        }
+
+        Ok(located)
+    }
+
+    /// Adds the rectangles of a pattern's hits to `located`, skipping those
+    /// inside an earlier region.
+    fn add_regions<'p>(
+        &self,
+        located: &mut LocatedRegions<'p>,
+        pattern: &'p SearchPattern,
+        rects: Vec<MuRect>,
+    ) {
+        for mut rect in rects {
+            // Split the hit horizontally, assuming evenly spaced
+            // characters, to cover only the masked prefix
+            if let Some(masked) = self.masked_prefix_len(&pattern.text) {
+                let chars = pattern.text.chars().count() as f32;
+                rect.x1 = rect.x0 + (rect.x1 - rect.x0) * masked as f32 / chars;
+            }
+
+            // Patterns are searched longest first, so a hit inside an
+            // earlier region is a substring of a longer match
+            if located.regions.iter().any(|(r, _)| rect_contains(r, &rect)) {
+                located.covered.push(pattern);
+                continue;
+            }
+
+            located.regions.push((rect, pattern));
+        }
+    }
+
+    /// Locates a split pattern by searching for each of its pieces and
+    /// chaining hits that follow one another along a line.
+    fn locate_split(
+        page: &Page,
+        page_idx: i32,
+        pattern: &SearchPattern,
+    ) -> RedactorResult<Vec<MuRect>> {
+        let mut piece_hits = Vec::with_capacity(pattern.pieces.len());
+        for piece in &pattern.pieces {
+            let hits =
+                page.search(piece, pattern.max_hits)
+                    .map_err(|e| RedactorError::BackendError {
+                        backend: "MuPDF".to_string(),
+                        message: format!(
+                            "Search failed for pattern on page {}: {}",
+                            page_idx + 1,
+                            piece
+                        ),
+                        source: Some(Box::new(e)),
+                    })?;
+            piece_hits.push(hits.iter().map(quad_rect).collect::<Vec<_>>());
+        }
+
+        let rects = chain_hits(&piece_hits);
+        log::debug!(
+            "Page {}: {} hit(s) for '{}' split as {:?}",
+            page_idx + 1,
+            rects.len(),
+            pattern.text,
+            pattern.pieces
+        );
+        Ok(rects)
+    }
+
+    /// Creates a redaction annotation over each rectangle and applies them,
+    /// physically removing the covered content.
+    ///
+    /// Each rectangle comes with the label to draw over it, if any.
+    ///
+    /// Returns the number of annotations applied.
+    fn redact_regions(
+        &self,
+        ctx: &ffi::Context,
+        pdf_page: &mut PdfPage,
+        page_idx: i32,
+        rects: impl IntoIterator<Item = (MuRect, Option<String>)>,
+    ) -> RedactorResult<usize> {
+        let mut count = 0;
+
+        // The fill must be in the page's color space
+        let fill = match self.fill_color {
+            Some(rgb) => colors::page_fill(&pdf_page.object(), rgb).unwrap_or_else(|e| {
+                log::warn!(
+                    "Page {}: could not read color space ({}), filling with black",
+                    page_idx + 1,
+                    e
+                );
+                None
+            }),
+            None => None,
+        };
+
+        for (rect, label) in rects {
+            let overlay = label.map(|text| {
+                CString::new(text).expect("Labels are masks or NUL-stripped overlay text")
+            });
+
+            let annot = pdf_page
+                .create_annotation(PdfAnnotationType::Redact)
+                .map_err(|e| RedactorError::PdfProcessing {
+                    message: format!(
+                        "Failed to create redaction annotation on page {}",
+                        page_idx + 1
+                    ),
+                    page: Some(page_idx as usize + 1),
+                    source: Some(Box::new(e)),
+                })?;
+
+            // Set annotation rectangle and appearance
+            unsafe {
+                ffi::set_annotation_rect(ctx, &annot, rect);
+                if fill.is_some() || overlay.is_some() {
+                    ffi::configure_redact_annotation(
+                        ctx,
+                        &annot,
+                        fill.as_deref(),
+                        overlay.as_deref(),
+                    );
+                }
+            }
+
+            count += 1;
+        }
+
+        // Apply redactions if any were created
+        if count > 0 {
+            pdf_page
+                .redact()
+                .map_err(|e| RedactorError::PdfProcessing {
+                    message: format!("Failed to apply redactions on page {}", page_idx + 1),
+                    page: Some(page_idx as usize + 1),
+                    source: Some(Box::new(e)),
+                })?;
+        }
+
+        Ok(count)
+    }
+
+    /// Applies redactions to a PDF using MuPDF, reporting progress for each
+    /// selected page.
+    ///
+    /// With `page_patterns`, each page's patterns are resolved just before
+    /// it is searched and added to `patterns` for the pages after it. Pages
+    /// searched before a pattern was first resolved are searched again at
+    /// the end, for the patterns resolved after them only.
+    fn apply_mupdf_redactions(
+        &self,
+        pdf_doc: &PdfDocument,
+        mut patterns: Vec<SearchPattern>,
+        page_patterns: Option<&PagePatterns<'_>>,
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<RedactionResult> {
+        let page_count = Self::redactable_page_count(pdf_doc)?;
+        let pages = options.pages;
+        if let Some(pages) = pages {
+            pages.check_within(page_count as usize)?;
+        }
+
+        let mut result = RedactionResult {
+            secure: true,
+            ..Default::default()
+        };
+        let mut located = HashSet::new();
+
+        // One context for every annotation created below
+        let ctx = ffi::Context::new()?;
+
+        // Form field values are not page content, so MuPDF search never
+        // finds them; clear the fields that hold a pattern instead
+        self.clear_fields(pdf_doc, &patterns, pages, &mut located, &mut result)?;
+
+        // Process each selected page, noting when streaming which page each
+        // pattern was first searched on
+        let mut first_page: HashMap<String, i32> = HashMap::new();
+        for page_idx in 0..page_count {
+            if pages.is_some_and(|p| !p.contains(page_idx as usize + 1)) {
+                continue;
+            }
+            result.pages_processed += 1;
+
+            if let Some(resolve) = page_patterns {
+                let page = Self::load_page(pdf_doc, page_idx)?;
+                patterns.extend(resolve(&page, page_idx)?);
+                patterns = dedup_patterns(patterns);
+                for pattern in &patterns {
+                    first_page.entry(pattern.text.clone()).or_insert(page_idx);
+                }
+            }
+
+            located.extend(self.redact_page(
+                &ctx,
+                pdf_doc,
+                page_idx,
+                &patterns,
+                options,
+                &mut result,
+            )?);
+        }
+
+        // Values first resolved on a later page may be on earlier ones, and
+        // in form fields, too; search those pages again for just those
+        // values, and clear the fields now holding a pattern
+        if page_patterns.is_some() {
+            self.clear_fields(pdf_doc, &patterns, pages, &mut located, &mut result)?;
+        }
+        let quiet = RedactionOptions {
+            progress: None,
+            ..*options
+        };
+        for page_idx in 0..page_count {
+            if pages.is_some_and(|p| !p.contains(page_idx as usize + 1)) {
+                continue;
+            }
+            let later: Vec<SearchPattern> = patterns
+                .iter()
+                .filter(|p| {
+                    first_page
+                        .get(&p.text)
+                        .is_some_and(|&first| first > page_idx)
+                })
+                .cloned()
+                .collect();
+            if !later.is_empty() {
+                located.extend(self.redact_page(
+                    &ctx,
+                    pdf_doc,
+                    page_idx,
+                    &later,
+                    &quiet,
+                    &mut result,
+                )?);
+            }
+        }
+
+        // Bookmark titles are not page content either; scrub them once
+        // every pattern is known
+        if !self.keep_outline {
+            for found in outline::scrub_matching_titles(pdf_doc, &needles(&patterns))? {
+                result.instances_redacted += 1;
+                *result
+                    .by_category
+                    .entry(patterns[found[0]].category.clone())
+                    .or_default() += 1;
+                for pattern in found.into_iter().map(|idx| &patterns[idx]) {
+                    located.extend(pattern.detected.clone());
+                    if self.match_text {
+                        result.matched_text.push(pattern.text.clone());
+                    }
+                }
+            }
+        }
+
+        // Text inside a Form XObject can escape MuPDF search; cover the
+        // whole form holding any value that is still unlocated
+        let unlocated: Vec<&SearchPattern> = patterns
+            .iter()
+            .filter(|p| p.detected.as_deref().is_some_and(|d| !located.contains(d)))
+            .collect();
+        if !unlocated.is_empty() {
+            self.redact_forms(&ctx, pdf_doc, &unlocated, pages, &mut located, &mut result)?;
+        }
+
+        // Values found in the extracted text that MuPDF search never located
+        // are still in the output; report them rather than failing silently
+        for pattern in &patterns {
+            let Some(detected) = pattern.detected.as_deref() else {
+                continue;
+            };
+            if !located.contains(detected)
+                && !result.detected_not_redacted.iter().any(|d| d == detected)
+            {
+                result.detected_not_redacted.push(detected.to_string());
+                result
+                    .unredacted_categories
+                    .insert(pattern.category.clone());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Clears the form fields shown on the selected pages that hold any of
+    /// `patterns`, counting each field once in `result`.
+    ///
+    /// Cleared fields are empty, so clearing again only finds fields that
+    /// hold patterns added since.
+    fn clear_fields(
+        &self,
+        pdf_doc: &PdfDocument,
+        patterns: &[SearchPattern],
+        pages: Option<&PageSelection>,
+        located: &mut HashSet<String>,
+        result: &mut RedactionResult,
+    ) -> RedactorResult<()> {
+        for found in forms::clear_matching_fields(pdf_doc, &needles(patterns), pages)? {
+            result.instances_redacted += 1;
+            *result
+                .by_category
+                .entry(patterns[found[0]].category.clone())
+                .or_default() += 1;
+            for pattern in found.into_iter().map(|idx| &patterns[idx]) {
+                located.extend(pattern.detected.clone());
+                if self.match_text {
+                    result.matched_text.push(pattern.text.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Searches one page for `patterns` and redacts what is found, adding
+    /// to the page's statistics in `result`.
+    ///
+    /// Returns the detected values located on the page.
+    fn redact_page(
+        &self,
+        ctx: &ffi::Context,
+        pdf_doc: &PdfDocument,
+        page_idx: i32,
+        patterns: &[SearchPattern],
+        options: &RedactionOptions<'_>,
+        result: &mut RedactionResult,
+    ) -> RedactorResult<HashSet<String>> {
+        let page = Self::load_page(pdf_doc, page_idx)?;
+
+        // Convert to PDF page for annotation support
+        let mut pdf_page = match PdfPage::try_from(page.clone()) {
+            Ok(p) => p,
+            Err(_) => return Ok(HashSet::new()), // Skip non-PDF pages
+        };
+
+        let page_number = page_idx as usize + 1;
+        let total_pages = Self::page_count(pdf_doc)? as usize;
+        options.report(RedactionPhase::Searching, page_number, total_pages);
+        let LocatedRegions {
+            regions,
+            truncated,
+            covered,
+        } = self.locate_regions(&page, page_idx, patterns)?;
+        for pattern in truncated {
+            if !result.truncated_patterns.contains(&pattern.text) {
+                result.truncated_patterns.push(pattern.text.clone());
+            }
+            result.truncated_categories.insert(pattern.category.clone());
+        }
+        let mut located: HashSet<String> = regions
+            .iter()
+            .map(|(_, p)| *p)
+            .chain(covered)
+            .filter_map(|p| p.detected.clone())
+            .collect();
+
+        options.report(RedactionPhase::Applying, page_number, total_pages);
+        let mut page_redactions = self.redact_regions(
+            ctx,
+            &mut pdf_page,
+            page_idx,
+            regions.iter().map(|(rect, pattern)| {
+                if pattern.area.is_some() || pattern.style.is_some() {
+                    (*rect, self.overlay_for(&pattern.category))
+                } else {
+                    (*rect, self.label_for(&pattern.text, &pattern.category))
+                }
+            }),
+        )?;
+
+        // Comment text is not page content either; delete the comment
+        if !self.keep_annotations {
+            for found in annotations::delete_matching_comments(&pdf_page, &needles(patterns))? {
+                page_redactions += 1;
+                *result
+                    .by_category
+                    .entry(patterns[found[0]].category.clone())
+                    .or_default() += 1;
+                for pattern in found.into_iter().map(|idx| &patterns[idx]) {
+                    located.extend(pattern.detected.clone());
+                    if self.match_text {
+                        result.matched_text.push(pattern.text.clone());
+                    }
+                }
+            }
+        }
+
+        // So are link URIs; delete the link
+        let all_links = patterns.iter().find(|p| p.all_links);
+        if !self.keep_annotations || all_links.is_some() {
+            let link_needles = if self.keep_annotations {
+                Vec::new()
+            } else {
+                needles(patterns)
+            };
+            for found in
+                links::delete_matching_links(&pdf_page, &link_needles, all_links.is_some())?
+            {
+                page_redactions += 1;
+                let category = match found.first() {
+                    Some(&idx) => patterns[idx].category.as_str(),
+                    None => all_links.map_or("link", |p| p.category.as_str()),
+                };
+                *result.by_category.entry(category.to_string()).or_default() += 1;
+                for pattern in found.into_iter().map(|idx| &patterns[idx]) {
+                    located.extend(pattern.detected.clone());
+                    if self.match_text {
+                        result.matched_text.push(pattern.text.clone());
+                    }
+                }
+            }
+        }
+
+        let stats = match result
+            .per_page
+            .iter()
+            .position(|stats| stats.page_number == page_number)
+        {
+            Some(idx) => &mut result.per_page[idx],
+            None => {
+                result.per_page.push(PageRedactionStats {
+                    page_number,
+                    ..Default::default()
+                });
+                result.per_page.last_mut().expect("Stats were just pushed")
+            }
+        };
+        if page_redactions > 0 && stats.instances == 0 {
+            result.pages_modified += 1;
+        }
+        stats.instances += page_redactions;
+        stats.patterns_matched += regions
+            .iter()
+            .map(|(_, p)| p.text.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+        result.instances_redacted += page_redactions;
+
+        for (_, pattern) in &regions {
+            *result
+                .by_category
+                .entry(pattern.category.clone())
+                .or_default() += 1;
+            if self.match_text {
+                result.matched_text.push(pattern.text.clone());
+            }
+        }
+
+        Ok(located)
+    }
+
+    /// Redacts the area of each Form XObject whose text holds one of the
+    /// `unlocated` patterns, marking their values located.
+    ///
+    /// The form is found by reading the document with lopdf, so forms that
+    /// lopdf cannot decode are skipped with a warning; their values stay in
+    /// [`detected_not_redacted`](RedactionResult::detected_not_redacted).
+    fn redact_forms(
+        &self,
+        ctx: &ffi::Context,
+        pdf_doc: &PdfDocument,
+        unlocated: &[&SearchPattern],
+        pages: Option<&PageSelection>,
+        located: &mut HashSet<String>,
+        result: &mut RedactionResult,
+    ) -> RedactorResult<()> {
+        let mut bytes = Vec::new();
+        pdf_doc
+            .write_to(&mut bytes)
+            .map_err(|e| RedactorError::PdfProcessing {
+                message: "Failed to serialize PDF to search form XObjects".to_string(),
+                page: None,
+                source: Some(Box::new(e)),
+            })?;
+        let regions = match xobjects::form_regions(&bytes) {
+            Ok(regions) => regions,
+            Err(e) => {
+                log::warn!("Skipping form XObject search: {}", e);
+                return Ok(());
+            }
+        };
+
+        let mut by_page: BTreeMap<usize, Vec<(MuRect, &SearchPattern)>> = BTreeMap::new();
+        for region in &regions {
+            if pages.is_some_and(|p| !p.contains(region.page)) {
+                continue;
+            }
+            let Some(pattern) = unlocated.iter().find(|p| region.contains(&p.text)) else {
+                continue;
+            };
+            let (x0, y0, x1, y1) = region.rect;
+            by_page
+                .entry(region.page)
+                .or_default()
+                .push((MuRect { x0, y0, x1, y1 }, pattern));
+            located.extend(
+                unlocated
+                    .iter()
+                    .filter(|p| region.contains(&p.text))
+                    .filter_map(|p| p.detected.clone()),
+            );
+        }
+
+        for (page_number, regions) in by_page {
+            let page_idx = page_number as i32 - 1;
+            let page = Self::load_page(pdf_doc, page_idx)?;
+            let Ok(mut pdf_page) = PdfPage::try_from(page) else {
+                continue;
+            };
+
+            let count = self.redact_regions(
+                ctx,
+                &mut pdf_page,
+                page_idx,
+                regions
+                    .iter()
+                    .map(|(rect, pattern)| (*rect, self.overlay_for(&pattern.category))),
+            )?;
+            log::info!(
+                "Page {}: covered {} form XObject(s) holding unlocated text",
+                page_number,
+                count
+            );
+
+            result.instances_redacted += count;
+            if let Some(stats) = result
+                .per_page
+                .iter_mut()
+                .find(|stats| stats.page_number == page_number)
+            {
+                if stats.instances == 0 {
+                    result.pages_modified += 1;
+                }
+                stats.instances += count;
+            }
+            for (_, pattern) in &regions {
+                *result
+                    .by_category
+                    .entry(pattern.category.clone())
+                    .or_default() += 1;
+                if self.match_text {
+                    result.matched_text.push(pattern.text.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Hit limit MuPDF applies when asked for 0.
+const MUPDF_DEFAULT_MAX_HITS: u32 = 16;
+
+/// Stands in for the input path in errors about documents redacted in
+/// memory.
+const IN_MEMORY: &str = "<in-memory PDF>";
+
+/// Regions located on one page.
+struct LocatedRegions<'p> {
+    /// Each region paired with the pattern that produced it
+    regions: Vec<(MuRect, &'p SearchPattern)>,
+
+    /// Patterns that reached their hit limit, so may have more matches
+    truncated: Vec<&'p SearchPattern>,
+
+    /// Patterns with hits that fell inside another region, so were
+    /// redacted without a region of their own
+    covered: Vec<&'p SearchPattern>,
+}
+
+/// Resolves the patterns of one page from its text, for redacting a
+/// document page by page.
+type PagePatterns<'a> = dyn Fn(&Page, i32) -> RedactorResult<Vec<SearchPattern>> + 'a;
+
+/// Returns a pattern for each variant of every value `matcher` finds in
+/// `text`, recording the value it renders; values that do not normalize
+/// are skipped.
+///
+/// Every detector target resolves through this, except phone numbers,
+/// which are also searched for as written since groupings vary by country,
+/// and the Verizon account number, of which a statement has just one.
+fn detected_patterns(matcher: &dyn PatternMatcher, text: &str) -> Vec<SearchPattern> {
+    let mut patterns = Vec::new();
+    for value in matcher.extract_all(text) {
+        if let Some(normalized) = matcher.normalize(value) {
+            patterns.extend(
+                matcher
+                    .generate_variants(&normalized)
+                    .into_iter()
+                    .map(|variant| SearchPattern::detected(variant, value)),
+            );
+        }
+    }
+    patterns
+}
+
+/// Returns the text MuPDF searches for each pattern.
+///
+/// Areas, styles and links have no text to find, split patterns repeat the
+/// text of another, and empty needles never match.
+fn needles(patterns: &[SearchPattern]) -> Vec<&str> {
+    patterns
+        .iter()
+        .map(|p| {
+            if p.searchable() && p.pieces.is_empty() {
+                p.text.as_str()
+            } else {
+                ""
+            }
+        })
+        .collect()
+}
+
+/// Returns true if `inner` lies within `outer`, allowing for rounding.
+fn rect_contains(outer: &MuRect, inner: &MuRect) -> bool {
+    const TOLERANCE: f32 = 0.01;
+    inner.x0 >= outer.x0 - TOLERANCE
+        && inner.y0 >= outer.y0 - TOLERANCE
+        && inner.x1 <= outer.x1 + TOLERANCE
+        && inner.y1 <= outer.y1 + TOLERANCE
+}
+
+/// Returns the bounding rectangle of a search hit.
+fn quad_rect(quad: &Quad) -> MuRect {
+    MuRect {
+        x0: quad.ul.x.min(quad.ll.x).min(quad.ur.x).min(quad.lr.x),
+        y0: quad.ul.y.min(quad.ll.y).min(quad.ur.y).min(quad.lr.y),
+        x1: quad.ul.x.max(quad.ll.x).max(quad.ur.x).max(quad.lr.x),
+        y1: quad.ul.y.max(quad.ll.y).max(quad.ur.y).max(quad.lr.y),
+    }
+}
+
+/// Returns true if `next` continues the line of text that `prev` ends: it
+/// shares the line and starts no more than a line height to its right.
+fn continues_line(prev: &MuRect, next: &MuRect) -> bool {
+    const TOLERANCE: f32 = 0.5;
+    let height = prev.y1 - prev.y0;
+    next.y0 < prev.y1
+        && prev.y0 < next.y1
+        && next.x0 >= prev.x1 - TOLERANCE
+        && next.x0 - prev.x1 <= height
+}
+
+/// Chains hits of consecutive pieces of split text into the rectangles
+/// of the whole.
+///
+/// Each hit of the first piece is extended by the nearest hit of the next
+/// piece that continues its line, and so on; hits that cannot be extended
+/// to the last piece are dropped.
+fn chain_hits(piece_hits: &[Vec<MuRect>]) -> Vec<MuRect> {
+    let Some((first, rest)) = piece_hits.split_first() else {
+        return Vec::new();
+    };
+    first
+        .iter()
+        .filter_map(|start| {
+            let mut chain = *start;
+            let mut last = *start;
+            for hits in rest {
+                let next = hits
+                    .iter()
+                    .filter(|hit| continues_line(&last, hit))
+                    .min_by(|a, b| a.x0.total_cmp(&b.x0))?;
+                chain.x0 = chain.x0.min(next.x0);
+                chain.y0 = chain.y0.min(next.y0);
+                chain.x1 = chain.x1.max(next.x1);
+                chain.y1 = chain.y1.max(next.y1);
+                last = *next;
+            }
+            Some(chain)
+        })
+        .collect()
+}
+
+/// Adds, for each pattern found across the strings of a run, a pattern
+/// made of the parts each string shows.
+///
+/// The matched text need not be found by searching for it whole, but a
+/// split pattern whose parts are all found is.
+fn add_split_patterns(patterns: &mut Vec<SearchPattern>, runs: &[TjRun]) {
+    let mut seen = HashSet::new();
+    let mut split = Vec::new();
+    for pattern in patterns
+        .iter()
+        .filter(|p| p.searchable() && !p.text.is_empty())
+    {
+        for run in runs {
+            for (start, found) in run.text.match_indices(&pattern.text) {
+                let end = start + found.len();
+                if pattern.whole_word && !is_whole_word(&run.text, start, end) {
+                    continue;
+                }
+                let pieces: Vec<String> = run
+                    .pieces_of(start, end)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                if pieces.len() < 2 || !seen.insert((pattern.text.clone(), pieces.clone())) {
+                    continue;
+                }
+                split.push(SearchPattern {
+                    detected: pattern.detected.clone(),
+                    category: pattern.category.clone(),
+                    max_hits: pattern.max_hits,
+                    pieces,
+                    ..SearchPattern::given(pattern.text.clone())
+                });
+            }
+        }
+    }
+    patterns.extend(split);
+}
+
+/// Returns true if `c` can be part of a word: a letter, digit or `_`.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns true if `text[start..end]` is not part of a longer word.
+///
+/// A side is only checked when the span starts or ends with a word
+/// character there, so `(555)` is whole wherever it appears.
+fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+    let span = &text[start..end];
+    let joined = |inner: Option<char>, outer: Option<char>| {
+        inner.is_some_and(is_word_char) && outer.is_some_and(is_word_char)
+    };
+    !joined(span.chars().next(), text[..start].chars().next_back())
+        && !joined(span.chars().next_back(), text[end..].chars().next())
+}
+
+/// Returns the 1-based numbers of the pages with redactions in `result`.
+fn modified_pages(result: &RedactionResult) -> BTreeSet<usize> {
+    // A page searched twice has two entries
+    result
+        .per_page
+        .iter()
+        .filter(|stats| stats.instances > 0)
+        .map(|stats| stats.page_number)
+        .collect()
+}
+
+/// Returns true if `page` shows any non-blank text or an image.
+///
+/// Paths are not content here, since every redaction box is one.
+fn page_has_content(page: &Page, page_idx: i32) -> RedactorResult<bool> {
+    let text_page = page
+        .to_text_page(TextPageOptions::PRESERVE_IMAGES)
+        .map_err(|e| RedactorError::PdfProcessing {
+            message: format!("Failed to read the content of page {}", page_idx + 1),
+            page: Some(page_idx as usize + 1),
+            source: Some(Box::new(e)),
+        })?;
+    Ok(text_page.blocks().any(|block| match block.r#type() {
+        TextBlockType::Image => true,
+        TextBlockType::Text => block
+            .lines()
+            .flat_map(|line| line.chars())
+            .any(|c| c.char().is_some_and(|c| !c.is_whitespace())),
+    }))
+}
+
+/// Keeps the hits for `needle` that are whole words in `page_text`.
+///
+/// MuPDF searches for substrings, ignoring case and collapsing whitespace,
+/// and returns hits in text order, so the n-th hit is the n-th occurrence
+/// in the page text. If the counts differ the hits cannot be paired up and
+/// all are kept, redacting too much rather than too little.
+fn whole_word_hits<T>(hits: Vec<T>, page_text: &str, needle: &str) -> Vec<T> {
+    let Some(whole) = whole_word_occurrences(page_text, needle) else {
+        return hits;
+    };
+    if whole.len() != hits.len() {
+        log::warn!(
+            "Found {} hit(s) but {} occurrence(s) of '{}', redacting all as whole words",
+            hits.len(),
+            whole.len(),
+            needle
+        );
+        return hits;
+    }
+
+    hits.into_iter()
+        .zip(whole)
+        .filter_map(|(hit, whole)| whole.then_some(hit))
+        .collect()
+}
+
+/// Finds `needle` in `text` the way MuPDF searches for it, returning
+/// whether each occurrence, in text order, is a whole word; `None` if the
+/// needle cannot be searched for.
+pub(super) fn whole_word_occurrences(text: &str, needle: &str) -> Option<Vec<bool>> {
+    let pattern = needle
+        .split_whitespace()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+");
+    let re = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .ok()?;
+    Some(
+        re.find_iter(text)
+            .map(|m| is_whole_word(text, m.start(), m.end()))
+            .collect(),
+    )
+}
+
+/// Merges patterns with the same text and sorts them longest first, so
+/// each string is searched once and longer matches are located before
+/// their substrings.
+///
+/// Several targets can resolve the same string, such as `--verizon`'s
+/// phone number and call detail targets on a call detail table. A merged
+/// pattern keeps the category of the first target that contributed it, the
+/// highest hit cap, and a detected value if any duplicate has one, so its
+/// regions are redacted and counted once. It is restricted to whole words
+/// only if every duplicate is. Patterns searched for in different pieces,
+/// and areas, styles and links, whose text only describes them, are never
+/// merged with a pattern searched for whole.
+fn dedup_patterns(patterns: Vec<SearchPattern>) -> Vec<SearchPattern> {
+    let mut unique: Vec<SearchPattern> = Vec::with_capacity(patterns.len());
+    let mut index: HashMap<(bool, String, Vec<String>), usize> = HashMap::new();
+    for pattern in patterns {
+        let key = (
+            pattern.searchable(),
+            pattern.text.clone(),
+            pattern.pieces.clone(),
+        );
+        match index.get(&key) {
+            Some(&idx) => {
+                let existing = &mut unique[idx];
+                existing.max_hits = existing.max_hits.max(pattern.max_hits);
+                existing.whole_word &= pattern.whole_word;
+                if existing.detected.is_none() {
+                    existing.detected = pattern.detected;
+                }
+            }
+            None => {
+                index.insert(key, unique.len());
+                unique.push(pattern);
+            }
+        }
+    }
+    // Stable, so equally long patterns keep their target order
+    unique.sort_by_key(|p| std::cmp::Reverse(p.text.chars().count()));
+    unique
+}
+
+/// A literal search string and what it was resolved from.
+#[derive(Debug, Clone)]
+struct SearchPattern {
+    text: String,
+
+    /// Value detected in the extracted text that this pattern renders, if
+    /// the pattern came from detection rather than the caller
+    detected: Option<String>,
+
+    /// Category reported in `RedactionResult::by_category`
+    category: String,
+
+    /// Hit cap of the originating target
+    max_hits: u32,
+
+    /// Area redacted whatever its content, in which case `text` only
+    /// describes it
+    area: Option<Area>,
+
+    /// Whether hits inside a longer word are skipped
+    whole_word: bool,
+
+    /// Whether the pattern stands for every link, in which case `text`
+    /// only describes it
+    all_links: bool,
+
+    /// Style of the text redacted whatever it says, in which case `text`
+    /// only describes it
+    style: Option<TextStyle>,
+
+    /// Parts of `text` shown by consecutive strings of a `TJ` array, found
+    /// by searching for each in turn; empty unless `text` is split
+    pieces: Vec<String>,
+}
+
+/// A rectangle to redact, on one page or on every page.
+#[derive(Debug, Clone)]
+struct Area {
+    /// 1-based page number, or `None` for every page
+    page: Option<usize>,
+    rect: MuRect,
+}
+
+impl SearchPattern {
+    /// A pattern supplied by the caller.
+    fn given(text: String) -> Self {
+        Self {
+            text,
+            detected: None,
+            category: String::new(),
+            max_hits: 0,
+            area: None,
+            whole_word: false,
+            all_links: false,
+            style: None,
+            pieces: Vec::new(),
+        }
+    }
+
+    /// A rectangle redacted whatever its content.
+    fn area(page: Option<usize>, rect: MuRect) -> Self {
+        let bounds = format!("({}, {}, {}, {})", rect.x0, rect.y0, rect.x1, rect.y1);
+        let text = match page {
+            Some(page) => format!("page {} area {}", page, bounds),
+            None => format!("area {}", bounds),
+        };
+        Self {
+            area: Some(Area { page, rect }),
+            ..Self::given(text)
+        }
+    }
+
+    /// Every link, whatever its URI.
+    fn all_links() -> Self {
+        Self {
+            all_links: true,
+            ..Self::given("all links".to_string())
+        }
+    }
+
+    /// Text in `style`, whatever it says, described as `description`.
+    fn styled(style: TextStyle, description: &str) -> Self {
+        Self {
+            style: Some(style),
+            ..Self::given(description.to_string())
+        }
+    }
+
+    /// A rendering of `value`, which was detected in the document text.
+    fn detected(text: String, value: &str) -> Self {
+        Self {
+            detected: Some(value.to_string()),
+            ..Self::given(text)
+        }
+    }
+
+    /// Restricts the pattern to hits that are whole words.
+    fn whole_word(mut self) -> Self {
+        self.whole_word = true;
+        self
+    }
+
+    /// Overrides the originating target's category.
+    fn in_category(mut self, category: &str) -> Self {
+        self.category = category.to_string();
+        self
+    }
+
+    /// Returns true if the pattern is found by searching for its text,
+    /// rather than standing for an area, a style or every link.
+    fn searchable(&self) -> bool {
+        self.area.is_none() && self.style.is_none() && !self.all_links
+    }
+}
+
+impl RedactionStrategy for SecureRedactionStrategy {
+    fn redact(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        self.redact_with_options(input, output, targets, &RedactionOptions::default())
+    }
+
+    fn redact_with_options(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<RedactionResult> {
+        // On the worker, the worker strategy enforces the limit instead
+        if let Some(limit) = self.timeout.filter(|_| !worker::on_worker_thread()) {
+            return self.redact_with_timeout(input, output, targets, options, limit);
+        }
+
+        options.report(RedactionPhase::Resolving, 0, 0);
+
+        // Encrypted documents can only be read through MuPDF, once unlocked
+        let unlocked = match options.password {
+            Some(password) => Some(self.open_searchable(input, Some(password))?),
+            None => None,
+        };
+
+        // When streaming, targets are resolved against the empty text up
+        // front, which validates them, then those that need text against
+        // each page's text
+        let needs_text = targets.iter().any(RedactionTarget::requires_text);
+        let streaming = self.streaming && needs_text && options.text.is_none();
+        let text: Option<Cow<'_, str>> = match (options.text, &unlocked) {
+            (Some(text), _) => Some(Cow::Borrowed(text)),
+            _ if !needs_text => None,
+            _ if streaming => Some(Cow::Borrowed("")),
+            (None, Some((pdf_doc, _))) => Some(Cow::Owned(Self::document_text(pdf_doc, input)?)),
+            (None, None) => Some(Cow::Owned(self.extract_text(input)?)),
+        };
+
+        // Resolve patterns before opening an unencrypted document, so
+        // invalid targets fail without involving MuPDF
+        let resolve = |hidden_values: &[String]| match text.as_deref() {
+            Some(text) if streaming => self.resolve_streamed(input, targets, text, hidden_values),
+            text => self.resolve_patterns(input, targets, text, hidden_values),
+        };
+        let mut patterns = resolve(&[])?;
+
+        let (mut pdf_doc, layer_config) = match unlocked {
+            Some(unlocked) => unlocked,
+            None => self.open_searchable(input, None)?,
+        };
+
+        // Values typed into form fields and comment text are not part of
+        // the extracted text, and values split across the strings of a TJ
+        // array may not be whole in it
+        let runs = runs::split_runs(&pdf_doc)?;
+        let mut hidden_values = self.hidden_values(&pdf_doc)?;
+        hidden_values.extend(runs.iter().map(|run| run.text.clone()));
+        if !hidden_values.is_empty() {
+            patterns = resolve(&hidden_values)?;
+        }
+        add_split_patterns(&mut patterns, &runs);
+
+        // If no patterns found, just copy the file
+        if patterns.is_empty() && !self.strip_metadata && !streaming {
+            let total_pages = Self::page_count(&pdf_doc)? as usize;
+            options.report(RedactionPhase::Saving, total_pages, total_pages);
+            self.copy_document(input, output)?;
+            let mut result = RedactionResult::none();
+            self.describe_output(output, &mut result)?;
+            return Ok(result);
+        }
+
+        // Apply redactions
+        let page_targets: Vec<RedactionTarget> = targets
+            .iter()
+            .filter(|target| target.requires_text())
+            .cloned()
+            .collect();
+        let resolve_page = |page: &Page, page_idx: i32| {
+            let text = Self::page_text(page, page_idx, input)?;
+            self.resolve_streamed(input, &page_targets, &text, &[])
+        };
+        let page_patterns: Option<&PagePatterns<'_>> =
+            if streaming { Some(&resolve_page) } else { None };
+        let content = self.pages_with_content(&pdf_doc)?;
+        let mut result = self.apply_mupdf_redactions(&pdf_doc, patterns, page_patterns, options)?;
+        self.finish_modified_pages(&mut pdf_doc, &mut result, content.as_ref())?;
+
+        let total_pages = Self::page_count(&pdf_doc)? as usize;
+        options.report(RedactionPhase::Saving, total_pages, total_pages);
+        self.save_document(&pdf_doc, input, output, &result, layer_config.as_ref())?;
+        self.describe_output(output, &mut result)?;
+
+        Ok(result)
+    }
+
+    fn redact_bytes(&self, input: &[u8], targets: &[RedactionTarget]) -> RedactorResult<Vec<u8>> {
+        let (mut pdf_doc, layer_config) = self.open_bytes(input)?;
+        let source = Path::new(IN_MEMORY);
+
+        let text = if targets.iter().any(RedactionTarget::requires_text) {
+            Self::document_text(&pdf_doc, source)?
+        } else {
+            String::new()
+        };
+        let runs = runs::split_runs(&pdf_doc)?;
+        let mut hidden_values = self.hidden_values(&pdf_doc)?;
+        hidden_values.extend(runs.iter().map(|run| run.text.clone()));
+        let mut patterns = self.resolve_patterns(source, targets, Some(&text), &hidden_values)?;
+        add_split_patterns(&mut patterns, &runs);
+        if patterns.is_empty() && !self.strip_metadata {
+            return Ok(input.to_vec());
+        }
+
+        let content = self.pages_with_content(&pdf_doc)?;
+        let mut result =
+            self.apply_mupdf_redactions(&pdf_doc, patterns, None, &Default::default())?;
+        if !result.has_redactions() && !self.strip_metadata {
+            return Ok(input.to_vec());
+        }
+        self.finish_modified_pages(&mut pdf_doc, &mut result, content.as_ref())?;
+
+        // MuPDF only appends updates to files, so buffers are rewritten
+        let mut output = Vec::new();
+        pdf_doc
+            .write_to_with_options(&mut output, self.write_options(&pdf_doc, false)?)
+            .map_err(|e| RedactorError::PdfProcessing {
+                message: "Failed to save redacted PDF".to_string(),
+                page: None,
+                source: Some(Box::new(e)),
+            })?;
+        log::info!(
+            "Saved {} redaction(s) to {} byte(s)",
+            result.instances_redacted,
+            output.len()
+        );
+        match &layer_config {
+            Some(config) => Ok(layers::with_layer_config(&output, config)?.unwrap_or(output)),
+            None => Ok(output),
+        }
+    }
+
+    fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        let mut patterns = self.resolve_patterns(input, targets, None, &[])?;
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (pdf_doc, _) = self.open_searchable(input, None)?;
+        add_split_patterns(&mut patterns, &runs::split_runs(&pdf_doc)?);
+        let mut hits = Vec::new();
+
+        for page_idx in 0..Self::page_count(&pdf_doc)? {
+            let page = Self::load_page(&pdf_doc, page_idx)?;
+            for (rect, pattern) in self.locate_regions(&page, page_idx, &patterns)?.regions {
+                hits.push(RedactionHit {
+                    page: page_idx as usize + 1,
+                    rect: (rect.x0, rect.y0, rect.x1, rect.y1),
+                    text: pattern.text.clone(),
+                    category: pattern.category.clone(),
+                    whole_word: pattern.whole_word,
+                });
+            }
+        }
+
+        Ok(hits)
+    }
+
+    fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        let (mut pdf_doc, layer_config) = self.open_searchable(input, None)?;
+        let page_count = Self::redactable_page_count(&pdf_doc)?;
+
+        if let Some(hit) = hits
+            .iter()
+            .find(|hit| hit.page == 0 || hit.page > page_count as usize)
+        {
+            return Err(RedactorError::InvalidInput {
+                parameter: "hits".to_string(),
+                reason: format!(
+                    "Hit on page {} is outside the document's {} page(s)",
+                    hit.page, page_count
+                ),
+            });
+        }
+
+        let content = self.pages_with_content(&pdf_doc)?;
+        let mut result = RedactionResult {
+            pages_processed: page_count as usize,
+            secure: true,
+            ..Default::default()
+        };
+        let ctx = ffi::Context::new()?;
+
+        for page_idx in 0..page_count {
+            let page_hits: Vec<&RedactionHit> = hits
+                .iter()
+                .filter(|hit| hit.page == page_idx as usize + 1)
+                .collect();
+            let mut stats = PageRedactionStats {
+                page_number: page_idx as usize + 1,
+                patterns_matched: page_hits
+                    .iter()
+                    .map(|hit| hit.text.as_str())
+                    .collect::<HashSet<_>>()
+                    .len(),
+                ..Default::default()
+            };
+            if page_hits.is_empty() {
+                result.per_page.push(stats);
+                continue;
+            }
+
+            let rects = page_hits.iter().map(|hit| {
+                let (x0, y0, x1, y1) = hit.rect;
+                (
+                    MuRect { x0, y0, x1, y1 },
+                    self.label_for(&hit.text, &hit.category),
+                )
+            });
+
+            let page = Self::load_page(&pdf_doc, page_idx)?;
+            let mut pdf_page =
+                PdfPage::try_from(page).map_err(|e| RedactorError::PdfProcessing {
+                    message: format!("Page {} does not support annotations", page_idx + 1),
+                    page: Some(page_idx as usize + 1),
                     source: Some(Box::new(e)),
                 })?;
-        } else {
-            // No redactions - just copy the file
-            std::fs::copy(input, output).map_err(|e| RedactorError::Io {
-                path: output.to_path_buf(),
-                source: e,
-            })?;
+
+            let page_redactions = self.redact_regions(&ctx, &mut pdf_page, page_idx, rects)?;
+            result.instances_redacted += page_redactions;
+            result.pages_modified += 1;
+            stats.instances = page_redactions;
+            if self.match_text {
+                result
+                    .matched_text
+                    .extend(page_hits.iter().map(|hit| hit.text.clone()));
+            }
+            result.per_page.push(stats);
         }
 
+        self.finish_modified_pages(&mut pdf_doc, &mut result, content.as_ref())?;
+        self.save_document(&pdf_doc, input, output, &result, layer_config.as_ref())?;
+        self.describe_output(output, &mut result)?;
+
         Ok(result)
     }
 
     fn extract_text(&self, input: &Path) -> RedactorResult<String> {
-        let bytes = std::fs::read(input).map_err(|e| RedactorError::Io {
-            path: input.to_path_buf(),
-            source: e,
-        })?;
+        ensure_not_empty(input)?;
 
-        pdf_extract::extract_text_from_mem(&bytes).map_err(|e| RedactorError::TextExtraction {
-            path: input.to_path_buf(),
-            reason: e.to_string(),
-        })
+        // Fall back for documents MuPDF rejects but pdf_extract can read
+        match self.extract_text_mupdf(input) {
+            Ok(text) => Ok(text),
+            Err(
+                e @ (RedactorError::EncryptedPdf { .. } | RedactorError::UnsupportedFeature { .. }),
+            ) => Err(e),
+            Err(_) => Self::extract_text_fallback(input),
+        }
+    }
+
+    fn extract_text_bytes(&self, input: &[u8]) -> RedactorResult<String> {
+        let (pdf_doc, _) = self.open_bytes(input)?;
+        Self::document_text(&pdf_doc, Path::new(IN_MEMORY))
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
     }
 
     fn name(&self) -> &str {
@@ -313,35 +2755,186 @@ impl RedactionStrategy for SecureRedactionStrategy {
     }
 }
 
+/// Returns `text` as given, lowercase, UPPERCASE and Title Case.
+fn case_renderings(text: &str) -> [String; 4] {
+    let mut title = String::with_capacity(text.len());
+    let mut word_start = true;
+    for ch in text.chars() {
+        if word_start {
+            title.extend(ch.to_uppercase());
+        } else {
+            title.extend(ch.to_lowercase());
+        }
+        word_start = ch.is_whitespace();
+    }
+
+    [
+        text.to_string(),
+        text.to_lowercase(),
+        text.to_uppercase(),
+        title,
+    ]
+}
+
+/// Rejects 0-byte inputs, a common mistake that backends report obscurely.
+fn ensure_not_empty(input: &Path) -> RedactorResult<()> {
+    let len = std::fs::metadata(input)
+        .map_err(|e| RedactorError::Io {
+            path: input.to_path_buf(),
+            source: e,
+        })?
+        .len();
+
+    if len == 0 {
+        return Err(RedactorError::InvalidInput {
+            parameter: "input".to_string(),
+            reason: format!("'{}' is an empty file (0 bytes)", input.display()),
+        });
+    }
+    Ok(())
+}
+
+/// Describes MuPDF's failure to open `bytes`, naming the unsupported
+/// feature responsible when it is recognized and otherwise adding `hint`.
+fn open_error(bytes: &[u8], hint: &str, e: mupdf::Error) -> RedactorError {
+    match features::unsupported_feature(bytes) {
+        Some(feature) => RedactorError::UnsupportedFeature { feature },
+        None => RedactorError::PdfProcessing {
+            message: format!("Failed to open PDF with MuPDF{}", hint),
+            page: None,
+            source: Some(Box::new(e)),
+        },
+    }
+}
+
+/// Returns a hint to append to parse errors when the file looks truncated.
+///
+/// A complete PDF ends with an `%%EOF` marker; its absence from the tail
+/// usually means an interrupted download or copy.
+fn damage_hint(input: &Path) -> &'static str {
+    const TAIL: usize = 1024;
+
+    let Ok(bytes) = std::fs::read(input) else {
+        return "";
+    };
+    let tail = &bytes[bytes.len().saturating_sub(TAIL)..];
+    if tail.windows(5).any(|w| w == b"%%EOF") {
+        ""
+    } else {
+        " (file is possibly truncated: no %%EOF marker found)"
+    }
+}
+
 /// FFI helpers for MuPDF annotation operations.
 mod ffi {
+    use crate::error::{RedactorError, RedactorResult};
     use mupdf::pdf::PdfAnnotation;
     use mupdf::Rect;
+    use mupdf_sys::fz_context;
+    use std::ffi::CStr;
+
+    #[repr(C)]
+    struct PdfAnnotRaw {
+        inner: *mut mupdf_sys::pdf_annot,
+    }
+
+    /// A MuPDF base context for editing annotations, dropped with this
+    /// value.
+    ///
+    /// One context is created per redaction and shared by every annotation
+    /// it edits, rather than one per annotation. The raw pointer keeps it
+    /// `!Send` and `!Sync`, so it is only ever used from the thread that
+    /// created it.
+    pub(super) struct Context(*mut fz_context);
+
+    impl Context {
+        /// Creates a base context.
+        ///
+        /// Fails with a backend error if MuPDF cannot allocate one, instead
+        /// of leaving annotations without the rectangle or appearance they
+        /// were meant to get.
+        pub fn new() -> RedactorResult<Self> {
+            // SAFETY: a new context is owned by nothing else
+            unsafe { Self::from_raw(mupdf_sys::mupdf_new_base_context()) }
+        }
+
+        /// Takes ownership of `raw`, failing if it is null.
+        ///
+        /// # Safety
+        /// `raw` must be null or a context from `mupdf_new_base_context`
+        /// that nothing else uses or drops.
+        pub(super) unsafe fn from_raw(raw: *mut fz_context) -> RedactorResult<Self> {
+            if raw.is_null() {
+                return Err(RedactorError::BackendError {
+                    backend: "MuPDF".to_string(),
+                    message: "Failed to create a MuPDF context to edit redaction annotations"
+                        .to_string(),
+                    source: None,
+                });
+            }
+            Ok(Self(raw))
+        }
+    }
+
+    impl Drop for Context {
+        fn drop(&mut self) {
+            // SAFETY: the context is non-null and owned by this value
+            unsafe { mupdf_sys::mupdf_drop_base_context(self.0) }
+        }
+    }
 
     /// Sets the rectangle for a PDF annotation via FFI.
     ///
     /// # Safety
-    /// This function uses unsafe FFI calls to access MuPDF's C API.
-    /// The annotation must be valid and the context properly initialized.
-    pub unsafe fn set_annotation_rect(annot: &PdfAnnotation, rect: Rect) {
-        #[repr(C)]
-        struct PdfAnnotRaw {
-            inner: *mut mupdf_sys::pdf_annot,
-        }
+    /// `annot` must be a live annotation: it is reinterpreted as MuPDF's
+    /// raw `pdf_annot` pointer, which must not be dropped while the call
+    /// runs. `ctx` is only borrowed, so it stays valid for the call.
+    pub unsafe fn set_annotation_rect(ctx: &Context, annot: &PdfAnnotation, rect: Rect) {
+        let annot_raw = std::mem::transmute::<&PdfAnnotation, &PdfAnnotRaw>(annot);
+        let fz_rect = mupdf_sys::fz_rect {
+            x0: rect.x0,
+            y0: rect.y0,
+            x1: rect.x1,
+            y1: rect.y1,
+        };
+
+        mupdf_sys::pdf_set_annot_rect(ctx.0, annot_raw.inner, fz_rect);
+    }
 
+    /// Sets the fill color and overlay text MuPDF draws when applying a
+    /// redaction annotation.
+    ///
+    /// The fill is the annotation's interior color (`/IC`), with one, three
+    /// or four components for gray, RGB or CMYK; the label is stored as
+    /// both `/OverlayText` and `/Contents`.
+    ///
+    /// # Safety
+    /// Same requirements as [`set_annotation_rect`].
+    pub unsafe fn configure_redact_annotation(
+        ctx: &Context,
+        annot: &PdfAnnotation,
+        fill: Option<&[f32]>,
+        overlay: Option<&CStr>,
+    ) {
         let annot_raw = std::mem::transmute::<&PdfAnnotation, &PdfAnnotRaw>(annot);
-        let ctx = mupdf_sys::mupdf_new_base_context();
-
-        if !ctx.is_null() {
-            let fz_rect = mupdf_sys::fz_rect {
-                x0: rect.x0,
-                y0: rect.y0,
-                x1: rect.x1,
-                y1: rect.y1,
-            };
+        let ctx = ctx.0;
 
-            mupdf_sys::pdf_set_annot_rect(ctx, annot_raw.inner, fz_rect);
-            mupdf_sys::mupdf_drop_base_context(ctx);
+        if let Some(color) = fill {
+            mupdf_sys::pdf_set_annot_interior_color(
+                ctx,
+                annot_raw.inner,
+                color.len() as i32,
+                color.as_ptr(),
+            );
+        }
+
+        if let Some(text) = overlay {
+            let obj = mupdf_sys::pdf_annot_obj(ctx, annot_raw.inner);
+            let value = mupdf_sys::pdf_new_text_string(ctx, text.as_ptr());
+            let key = b"OverlayText\0".as_ptr().cast();
+            mupdf_sys::pdf_dict_puts(ctx, obj, key, value);
+            mupdf_sys::pdf_drop_obj(ctx, value);
+            mupdf_sys::pdf_set_annot_contents(ctx, annot_raw.inner, text.as_ptr());
         }
     }
 }
@@ -349,6 +2942,7 @@ mod ffi {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::extract_tj_runs;
 
     #[test]
     fn test_strategy_creation() {
@@ -362,4 +2956,410 @@ mod tests {
         let strategy = SecureRedactionStrategy::new().with_max_hits(50);
         assert_eq!(strategy.max_hits, 50);
     }
+
+    #[test]
+    fn test_target_max_hits_override() {
+        let phones = RedactionTarget::PhoneNumbers;
+        let literal = RedactionTarget::Literal("x".to_string());
+        let strategy = SecureRedactionStrategy::new()
+            .with_max_hits(10)
+            .with_target_max_hits(phones.clone(), 500)
+            .with_target_max_hits(phones.clone(), 1000);
+
+        assert_eq!(strategy.max_hits_for(&phones), 1000);
+        assert_eq!(strategy.max_hits_for(&literal), 10);
+    }
+
+    #[test]
+    fn test_masked_prefix_len() {
+        let strategy = SecureRedactionStrategy::new();
+        assert_eq!(strategy.masked_prefix_len("123456789-00001"), None);
+
+        let strategy = strategy.with_keep_trailing(5);
+        assert_eq!(strategy.masked_prefix_len("123456789-00001"), Some(10));
+        assert_eq!(strategy.masked_prefix_len("00001"), None);
+        assert_eq!(strategy.masked_prefix_len("Jane Roe"), None);
+        assert_eq!(
+            strategy.label_for("123456789-00001", "account"),
+            Some("**********".to_string())
+        );
+    }
+
+    #[test]
+    fn test_missing_mupdf_context_is_an_error() {
+        let result = unsafe { ffi::Context::from_raw(std::ptr::null_mut()) };
+
+        assert!(matches!(
+            result,
+            Err(RedactorError::BackendError { ref backend, .. }) if backend == "MuPDF"
+        ));
+    }
+
+    #[test]
+    fn test_category_labels() {
+        let strategy = SecureRedactionStrategy::new().with_overlay_text("REDACTED".to_string());
+        assert_eq!(
+            strategy.label_for("555-234-5678", "phone"),
+            Some("REDACTED".to_string())
+        );
+
+        let strategy = strategy.with_category_labels(true);
+        assert_eq!(
+            strategy.label_for("555-234-5678", "phone"),
+            Some("[PHONE]".to_string())
+        );
+        assert_eq!(strategy.overlay_for("ssn"), Some("[SSN]".to_string()));
+        assert_eq!(
+            strategy.overlay_for("call_detail"),
+            Some("[CALL_DETAIL]".to_string())
+        );
+        assert_eq!(strategy.overlay_for("na\0me"), Some("[NAME]".to_string()));
+
+        // Hits built by hand may have no category
+        assert_eq!(strategy.overlay_for(""), Some("REDACTED".to_string()));
+
+        let strategy = strategy.with_keep_trailing(4);
+        assert_eq!(
+            strategy.label_for("555-234-5678", "phone"),
+            Some("********".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dedup_patterns_merges_and_sorts_longest_first() {
+        let mut short = SearchPattern::given("123456".to_string());
+        short.max_hits = 10;
+        let mut duplicate = SearchPattern::detected("123456".to_string(), "123456");
+        duplicate.max_hits = 50;
+
+        let patterns = dedup_patterns(vec![
+            short,
+            SearchPattern::given("456789".to_string()),
+            duplicate,
+            SearchPattern::given("123456789".to_string()),
+        ]);
+
+        let texts: Vec<&str> = patterns.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, ["123456789", "123456", "456789"]);
+        assert_eq!(patterns[1].max_hits, 50);
+        assert_eq!(patterns[1].detected.as_deref(), Some("123456"));
+    }
+
+    #[test]
+    fn test_resolve_patterns_merges_targets_on_call_detail_table() {
+        let text = "Date Time Number Origination Destination\n\
+                    01/15 10:26 PM 555-234-5678 Boston, MA Incoming, CL\n\
+                    01/16 11:05 PM 555-234-5678 Boston, MA Boston, MA\n";
+        let targets = [
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::VerizonCallDetails,
+            RedactionTarget::Literal("555-234-5678".to_string()),
+        ];
+        let patterns = SecureRedactionStrategy::new()
+            .resolve_patterns(Path::new("bill.pdf"), &targets, Some(text), &[])
+            .unwrap();
+
+        let mut texts: Vec<&str> = patterns.iter().map(|p| p.text.as_str()).collect();
+        texts.sort_unstable();
+        let total = texts.len();
+        texts.dedup();
+        assert_eq!(texts.len(), total, "Each string should be searched once");
+
+        let category = |text: &str| {
+            patterns
+                .iter()
+                .find(|p| p.text == text)
+                .map(|p| p.category.as_str())
+        };
+        assert_eq!(category("555-234-5678"), Some("phone"));
+        assert_eq!(category("10:26 PM"), Some("call_detail"));
+        assert_eq!(category("Boston, MA"), Some("call_detail"));
+    }
+
+    #[test]
+    fn test_resolve_patterns_folds_full_width_digits() {
+        let text = "Call （５５５） ２３４-５６７８ today";
+        let patterns = SecureRedactionStrategy::new()
+            .resolve_patterns(
+                Path::new("bill.pdf"),
+                &[RedactionTarget::PhoneNumbers],
+                Some(text),
+                &[],
+            )
+            .unwrap();
+
+        let written = patterns
+            .iter()
+            .find(|p| p.text == "（５５５） ２３４-５６７８")
+            .expect("Phone should be searched for as written");
+        assert_eq!(written.category, "phone");
+        assert_eq!(written.detected.as_deref(), Some("(555) 234-5678"));
+        assert!(patterns.iter().any(|p| p.text == "555-234-5678"));
+    }
+
+    #[test]
+    fn test_resolve_patterns_whole_word_regex() {
+        let text = "SECRET_CODE, SECRET and TOPSECRET";
+        let patterns = SecureRedactionStrategy::new()
+            .resolve_patterns(
+                Path::new("memo.pdf"),
+                &[RedactionTarget::RegexWholeWord(r"SECRET\w*".to_string())],
+                Some(text),
+                &[],
+            )
+            .unwrap();
+
+        let texts: Vec<&str> = patterns.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, vec!["SECRET_CODE", "SECRET"]);
+        assert!(patterns.iter().all(|p| p.whole_word));
+    }
+
+    #[test]
+    fn test_is_whole_word() {
+        let text = "SECRET SECRET_CODE (555) x(555)";
+        assert!(is_whole_word(text, 0, 6));
+        assert!(!is_whole_word(text, 7, 13));
+        assert!(is_whole_word(text, 7, 18));
+        // Spans ending in punctuation do not need a boundary there
+        assert!(is_whole_word(text, 19, 24));
+        assert!(is_whole_word(text, 26, 31));
+        assert!(!is_whole_word("TOPSECRET", 3, 9));
+        assert!(!is_whole_word("café", 0, 3));
+    }
+
+    #[test]
+    fn test_whole_word_hits() {
+        let page_text = "The code is SECRET.\nNot a SECRET_CODE\nBut secret again";
+        assert_eq!(
+            whole_word_hits(vec![1, 2, 3], page_text, "SECRET"),
+            vec![1, 3]
+        );
+
+        // Whitespace is collapsed as by MuPDF
+        assert_eq!(
+            whole_word_hits(vec![1], "is\n SECRET", "is SECRET"),
+            vec![1]
+        );
+
+        // Hits that cannot be paired with occurrences are all kept
+        assert_eq!(whole_word_hits(vec![1, 2], page_text, "SECRET"), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_dedup_keeps_whole_word_only_if_all_are() {
+        let patterns = dedup_patterns(vec![
+            SearchPattern::given("SECRET".to_string()).whole_word(),
+            SearchPattern::given("SECRET".to_string()),
+            SearchPattern::given("CODE".to_string()).whole_word(),
+        ]);
+        assert!(!patterns[0].whole_word);
+        assert!(patterns[1].whole_word);
+    }
+
+    #[test]
+    fn test_dedup_keeps_split_and_placeholder_patterns_apart() {
+        let split = SearchPattern {
+            pieces: vec!["555".to_string(), "234".to_string()],
+            ..SearchPattern::given("555234".to_string())
+        };
+        let patterns = dedup_patterns(vec![
+            SearchPattern::given("555234".to_string()),
+            split,
+            SearchPattern::given("all links".to_string()),
+            SearchPattern::all_links(),
+        ]);
+        assert_eq!(patterns.len(), 4);
+        assert_eq!(patterns.iter().filter(|p| !p.pieces.is_empty()).count(), 1);
+        assert_eq!(patterns.iter().filter(|p| p.all_links).count(), 1);
+    }
+
+    #[test]
+    fn test_resolve_patterns_area() {
+        let strategy = SecureRedactionStrategy::new();
+        let target = RedactionTarget::AllTextInRect {
+            page: Some(2),
+            rect: (0.0, 770.0, 595.0, 842.0),
+        };
+        let patterns = strategy
+            .resolve_patterns(Path::new("in.pdf"), &[target], None, &[])
+            .unwrap();
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].text, "page 2 area (0, 770, 595, 842)");
+        assert_eq!(patterns[0].category, "area");
+        let area = patterns[0].area.as_ref().unwrap();
+        assert_eq!(area.page, Some(2));
+        assert_eq!(area.rect, MuRect::new(0.0, 770.0, 595.0, 842.0));
+
+        let page_zero = RedactionTarget::AllTextInRect {
+            page: Some(0),
+            rect: (0.0, 0.0, 10.0, 10.0),
+        };
+        assert!(strategy
+            .resolve_patterns(Path::new("in.pdf"), &[page_zero], None, &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_resolve_patterns_all_links() {
+        let strategy = SecureRedactionStrategy::new();
+        let targets = [
+            RedactionTarget::AllLinks,
+            RedactionTarget::Literal("intranet".to_string()),
+        ];
+        let patterns = strategy
+            .resolve_patterns(Path::new("in.pdf"), &targets, None, &[])
+            .unwrap();
+
+        assert_eq!(patterns.len(), 2);
+        let links = patterns.iter().find(|p| p.all_links).unwrap();
+        assert_eq!(links.category, "link");
+        // The links pattern has no text to search for
+        assert_eq!(
+            needles(&patterns)
+                .into_iter()
+                .filter(|n| !n.is_empty())
+                .collect::<Vec<_>>(),
+            vec!["intranet"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_patterns_style() {
+        let strategy = SecureRedactionStrategy::new();
+        let target = RedactionTarget::TextWithStyle {
+            color: Some((255, 0, 0)),
+            font_contains: None,
+        };
+        let patterns = strategy
+            .resolve_patterns(Path::new("in.pdf"), &[target], None, &[])
+            .unwrap();
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].text, "style:color=#ff0000");
+        assert_eq!(patterns[0].category, "style");
+        assert_eq!(
+            patterns[0].style,
+            Some(TextStyle::new(Some((255, 0, 0)), None))
+        );
+        assert_eq!(needles(&patterns), vec![""]);
+
+        let unstyled = RedactionTarget::TextWithStyle {
+            color: None,
+            font_contains: None,
+        };
+        assert!(strategy
+            .resolve_patterns(Path::new("in.pdf"), &[unstyled], None, &[])
+            .is_err());
+
+        let listed_fonts = RedactionTarget::TextWithStyle {
+            color: None,
+            font_contains: Some("Bold,Italic".to_string()),
+        };
+        assert!(strategy
+            .resolve_patterns(Path::new("in.pdf"), &[listed_fonts], None, &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let outer = MuRect::new(10.0, 10.0, 100.0, 20.0);
+        assert!(rect_contains(&outer, &MuRect::new(10.0, 10.0, 60.0, 20.0)));
+        assert!(rect_contains(
+            &outer,
+            &MuRect::new(40.0, 10.001, 100.0, 20.0)
+        ));
+        assert!(!rect_contains(
+            &outer,
+            &MuRect::new(90.0, 10.0, 110.0, 20.0)
+        ));
+    }
+
+    #[test]
+    fn test_chain_hits_follows_line() {
+        let piece_hits = vec![
+            // "555" in the number and in a line below it
+            vec![
+                MuRect::new(10.0, 10.0, 30.0, 22.0),
+                MuRect::new(10.0, 40.0, 30.0, 52.0),
+            ],
+            // "234" after the first, and far along the second line
+            vec![
+                MuRect::new(32.0, 10.0, 52.0, 22.0),
+                MuRect::new(200.0, 40.0, 220.0, 52.0),
+            ],
+            vec![MuRect::new(53.0, 10.0, 80.0, 22.0)],
+        ];
+        assert_eq!(
+            chain_hits(&piece_hits),
+            vec![MuRect::new(10.0, 10.0, 80.0, 22.0)]
+        );
+        assert!(chain_hits(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_add_split_patterns() {
+        let runs = extract_tj_runs("[(Call )-100(555)-100(234)-100(5678)] TJ");
+        let mut patterns = vec![
+            SearchPattern::detected("5552345678".to_string(), "5552345678"),
+            SearchPattern::given("Call".to_string()),
+            SearchPattern::given("234".to_string()).whole_word(),
+            SearchPattern::given("234-5678".to_string()),
+        ];
+        add_split_patterns(&mut patterns, &runs);
+
+        assert_eq!(patterns.len(), 5);
+        let split = &patterns[4];
+        assert_eq!(split.text, "5552345678");
+        assert_eq!(split.pieces, vec!["555", "234", "5678"]);
+        assert_eq!(split.detected.as_deref(), Some("5552345678"));
+
+        // Split patterns are found on the page, not in form or comment text
+        assert_eq!(needles(&patterns)[4], "");
+    }
+
+    #[test]
+    fn test_case_renderings() {
+        assert_eq!(
+            case_renderings("top SECRET"),
+            ["top SECRET", "top secret", "TOP SECRET", "Top Secret"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_strip_metadata_disabled_by_default() {
+        assert!(!SecureRedactionStrategy::new().match_text);
+        assert!(!SecureRedactionStrategy::new().strip_metadata);
+        assert!(
+            SecureRedactionStrategy::new()
+                .with_strip_metadata(true)
+                .strip_metadata
+        );
+    }
+
+    #[test]
+    fn test_fill_color_and_overlay_configuration() {
+        let strategy = SecureRedactionStrategy::new()
+            .with_fill_color(1.0, 1.5, -0.5)
+            .with_overlay_text("RED\0ACTED".to_string());
+        assert_eq!(strategy.fill_color, Some([1.0, 1.0, 0.0]));
+        assert_eq!(strategy.overlay_text.as_deref(), Some("REDACTED"));
+    }
+
+    #[test]
+    fn test_alltext_margins_configuration() {
+        let strategy = SecureRedactionStrategy::new().with_alltext_margins(36.0, -5.0);
+        assert_eq!(strategy.alltext_margins, (36.0, 0.0));
+    }
+
+    #[test]
+    fn test_hidden_layers_enabled_by_default() {
+        assert!(!SecureRedactionStrategy::new().skip_hidden_layers);
+        assert!(!SecureRedactionStrategy::default().skip_hidden_layers);
+        assert!(
+            SecureRedactionStrategy::new()
+                .with_include_hidden_layers(false)
+                .skip_hidden_layers
+        );
+    }
 }
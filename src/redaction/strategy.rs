@@ -3,18 +3,42 @@
 //! This module defines the core abstraction for redaction strategies,
 //! allowing for different implementations (secure, visual, etc.).
 
-use crate::error::RedactorResult;
-use std::path::Path;
+use super::PageSelection;
+use crate::domain::{ChecksumAlgorithm, ChecksumMatcher};
+use crate::error::{RedactorError, RedactorResult};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A pattern or text to be redacted from a document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RedactionTarget {
     /// Exact text match
     Literal(String),
 
+    /// Text match ignoring case
+    ///
+    /// Every casing of the text found in the document is redacted, as well
+    /// as the given, lowercase, UPPERCASE and Title Case renderings.
+    LiteralCaseInsensitive(String),
+
     /// Regular expression pattern
     Regex(String),
 
+    /// Exact text match that stands alone as a word
+    ///
+    /// Occurrences inside a longer word, such as `SECRET` in `SECRET_CODE`,
+    /// are left intact. Word characters are letters, digits and `_`.
+    LiteralWholeWord(String),
+
+    /// Regular expression pattern whose matches stand alone as words
+    ///
+    /// Matches are checked as for [`LiteralWholeWord`](Self::LiteralWholeWord).
+    RegexWholeWord(String),
+
     /// Phone numbers (using domain-specific logic)
     PhoneNumbers,
 
@@ -23,10 +47,602 @@ pub enum RedactionTarget {
 
     /// Verizon call detail columns (time, origination, destination)
     VerizonCallDetails,
+
+    /// Log timestamps with a time of day (ISO-8601 and syslog formats)
+    Timestamp,
+
+    /// Calendar dates in ISO, US or textual form that exist in the calendar
+    Dates,
+
+    /// US ZIP and ZIP+4 codes following a state abbreviation or ZIP label
+    PostalCode,
+
+    /// U.S. Social Security Numbers (dashed or spaced)
+    Ssn,
+
+    /// Email addresses
+    Email,
+
+    /// Payment card numbers that pass the Luhn checksum
+    CreditCard,
+
+    /// International Bank Account Numbers that pass the mod-97 checksum
+    Iban,
+
+    /// Vehicle Identification Numbers with a valid check digit
+    Vin,
+
+    /// MAC addresses in colon-, hyphen- or dot-separated form
+    MacAddress,
+
+    /// `@mentions` and usernames after a user or login label
+    Handle,
+
+    /// Passport numbers in a country's format, or labelled as passport
+    /// numbers when no country is given
+    ///
+    /// `country` is an ISO 3166 alpha-2 code; see
+    /// [`PassportMatcher::SUPPORTED_COUNTRIES`](crate::domain::PassportMatcher::SUPPORTED_COUNTRIES).
+    Passport { country: Option<String> },
+
+    /// US driver's license numbers in a state's format, or labelled as
+    /// license numbers when no state is given
+    ///
+    /// `state` is a USPS state code; see
+    /// [`DriversLicenseMatcher::SUPPORTED_STATES`](crate::domain::DriversLicenseMatcher::SUPPORTED_STATES).
+    DriversLicense { state: Option<String> },
+
+    /// ABA bank routing numbers that pass the checksum, after a routing
+    /// label or a check's MICR transit symbol
+    RoutingNumber,
+
+    /// Entities listed in a file produced by an external detector (e.g. NER)
+    ///
+    /// One entity per line, optionally followed by a tab and a category;
+    /// see [`Entity::parse_list`](crate::domain::Entity::parse_list).
+    EntityList { path: PathBuf },
+
+    /// Named people, each given as a full name
+    ///
+    /// Every rendering [`NameMatcher`](crate::domain::NameMatcher) knows is
+    /// redacted where it stands alone as words: the full name, the surname
+    /// alone, `J. Doe` and `Doe, John` for `John Doe`.
+    NamedEntity(Vec<String>),
+
+    /// Regular expression pattern whose matches an overlay covers with a
+    /// row of `replacement` characters instead of a box
+    ///
+    /// One character is drawn per matched character, stretched to the
+    /// match's width, which keeps the line's layout readable (the legacy
+    /// mask was [`MASK_CHAR`](super::diff::MASK_CHAR), `█`). Only
+    /// [`OverlayRedactionStrategy`](super::OverlayRedactionStrategy) draws
+    /// the replacement; other strategies redact matches as for
+    /// [`Regex`](Self::Regex).
+    RegexWithReplacement { pattern: String, replacement: char },
+
+    /// Regex whose named capture groups are each redacted
+    ///
+    /// Every group's span is reported under the group name in
+    /// [`RedactionResult::by_category`], e.g.
+    /// `(?P<ssn>\d{3}-\d{2}-\d{4}).*(?P<phone>\d{3}-\d{4})`.
+    NamedGroups { pattern: String },
+
+    /// Regex of which only one capture group is redacted
+    ///
+    /// The whole match gives context, e.g. `Account:\s*(\d+)` with group 1
+    /// redacts just the digits. Group 0 is the whole match.
+    RegexCaptureGroup { pattern: String, group: usize },
+
+    /// Whole numbers of exactly `length` digits that pass a checksum
+    ///
+    /// For ID formats without a built-in matcher, e.g. 10-digit member
+    /// numbers with a Luhn check digit.
+    Checksum {
+        length: usize,
+        algorithm: ChecksumAlgorithm,
+    },
+
+    /// Values found by a matcher registered with
+    /// [`RedactionService::with_custom_matcher`](super::RedactionService::with_custom_matcher)
+    /// under this name
+    ///
+    /// The service resolves these into [`Literal`](Self::Literal) targets,
+    /// so matches are reported under the `literal` category.
+    Custom(String),
+
+    /// Everything inside a rectangle, whatever its content
+    ///
+    /// `rect` is `(x0, y0, x1, y1)` in page coordinates, as in
+    /// [`RedactionHit::rect`], with y growing downward. `page` is 1-based;
+    /// `None` applies the rectangle to every page, e.g. to blank a header
+    /// or footer band.
+    AllTextInRect {
+        page: Option<usize>,
+        rect: (f32, f32, f32, f32),
+    },
+
+    /// Every hyperlink to a URI, whatever it points to
+    ///
+    /// Links are annotations rather than page content, so the link is
+    /// deleted and its visible text, if any, is left in place.
+    AllLinks,
+
+    /// Text drawn in a given color or font, whatever it says
+    ///
+    /// For watermarks and stamps, such as a red `CONFIDENTIAL` or a
+    /// `DRAFT` in a display font. `color` is RGB and matches within a small
+    /// tolerance, so text colored in gray or CMYK matches its RGB
+    /// equivalent. `font_contains` matches part of the font name, ignoring
+    /// case, e.g. `Bold`, and must not be empty or contain `,`. Text must
+    /// match every criterion given, and at least one must be.
+    TextWithStyle {
+        color: Option<(u8, u8, u8)>,
+        font_contains: Option<String>,
+    },
+}
+
+impl RedactionTarget {
+    /// Returns true if resolving this target requires the document text.
+    pub fn requires_text(&self) -> bool {
+        !matches!(
+            self,
+            Self::Literal(_)
+                | Self::LiteralWholeWord(_)
+                | Self::EntityList { .. }
+                | Self::NamedEntity(_)
+                | Self::AllTextInRect { .. }
+                | Self::AllLinks
+                | Self::TextWithStyle { .. }
+        )
+    }
+
+    /// Category under which this target's redactions are reported.
+    ///
+    /// Entity lists and named groups report finer-grained categories
+    /// where available, falling back to this one.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Literal(_) | Self::LiteralCaseInsensitive(_) | Self::LiteralWholeWord(_) => {
+                "literal"
+            }
+            Self::Regex(_)
+            | Self::RegexCaptureGroup { .. }
+            | Self::RegexWholeWord(_)
+            | Self::RegexWithReplacement { .. } => "regex",
+            Self::PhoneNumbers => "phone",
+            Self::VerizonAccount => "account",
+            Self::VerizonCallDetails => "call_detail",
+            Self::Timestamp => "timestamp",
+            Self::Dates => "date",
+            Self::PostalCode => "postal_code",
+            Self::Ssn => "ssn",
+            Self::Email => "email",
+            Self::CreditCard => "credit_card",
+            Self::Iban => "iban",
+            Self::Vin => "vin",
+            Self::MacAddress => "mac_address",
+            Self::Handle => "handle",
+            Self::Passport { .. } => "passport",
+            Self::DriversLicense { .. } => "drivers_license",
+            Self::RoutingNumber => "routing_number",
+            Self::EntityList { .. } => "entity",
+            Self::NamedEntity(_) => "name",
+            Self::NamedGroups { .. } => "named_group",
+            Self::Checksum { .. } => "checksum",
+            Self::Custom(_) => "custom",
+            Self::AllTextInRect { .. } => "area",
+            Self::AllLinks => "link",
+            Self::TextWithStyle { .. } => "style",
+        }
+    }
+}
+
+/// Formats a target as the spec [`FromStr`](RedactionTarget::from_str)
+/// parses back into it.
+impl fmt::Display for RedactionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(text) => write!(f, "literal:{}", text),
+            Self::LiteralCaseInsensitive(text) => write!(f, "literal-ci:{}", text),
+            Self::LiteralWholeWord(text) => write!(f, "literal-word:{}", text),
+            Self::Regex(pattern) => write!(f, "regex:{}", pattern),
+            Self::RegexWholeWord(pattern) => write!(f, "regex-word:{}", pattern),
+            Self::RegexCaptureGroup { pattern, group } => {
+                write!(f, "regex-group:{}:{}", group, pattern)
+            }
+            Self::RegexWithReplacement {
+                pattern,
+                replacement,
+            } => write!(f, "regex-replace:{}:{}", replacement, pattern),
+            Self::NamedGroups { pattern } => write!(f, "named-groups:{}", pattern),
+            Self::PhoneNumbers => f.write_str("phones"),
+            Self::VerizonAccount => f.write_str("verizon-account"),
+            Self::VerizonCallDetails => f.write_str("verizon-call-details"),
+            Self::Timestamp => f.write_str("timestamps"),
+            Self::Dates => f.write_str("dates"),
+            Self::PostalCode => f.write_str("postal-codes"),
+            Self::Ssn => f.write_str("ssn"),
+            Self::Email => f.write_str("emails"),
+            Self::CreditCard => f.write_str("credit-cards"),
+            Self::Iban => f.write_str("iban"),
+            Self::Vin => f.write_str("vin"),
+            Self::MacAddress => f.write_str("mac"),
+            Self::Handle => f.write_str("handles"),
+            Self::Passport { country: None } => f.write_str("passport"),
+            Self::Passport {
+                country: Some(country),
+            } => write!(f, "passport:{}", country),
+            Self::DriversLicense { state: None } => f.write_str("dl"),
+            Self::DriversLicense { state: Some(state) } => write!(f, "dl:{}", state),
+            Self::RoutingNumber => f.write_str("routing"),
+            Self::EntityList { path } => write!(f, "entities:{}", path.display()),
+            Self::NamedEntity(names) => write!(f, "names:{}", names.join(";")),
+            Self::Checksum { length, algorithm } => {
+                write!(f, "checksum:{}:{}", length, algorithm.name())
+            }
+            Self::Custom(name) => write!(f, "custom:{}", name),
+            Self::AllTextInRect { page, rect } => {
+                f.write_str("area:")?;
+                if let Some(page) = page {
+                    write!(f, "{}:", page)?;
+                }
+                write!(f, "{},{},{},{}", rect.0, rect.1, rect.2, rect.3)
+            }
+            Self::AllLinks => f.write_str("links"),
+            Self::TextWithStyle {
+                color,
+                font_contains,
+            } => {
+                f.write_str("style:")?;
+                if let Some((r, g, b)) = color {
+                    write!(f, "color=#{:02x}{:02x}{:02x}", r, g, b)?;
+                    if font_contains.is_some() {
+                        f.write_str(",")?;
+                    }
+                }
+                if let Some(font) = font_contains {
+                    write!(f, "font={}", font)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Parses a target spec, as used in config files.
+///
+/// Built-in detectors are named as their CLI flags: `phones`,
+/// `verizon-account`, `verizon-call-details`, `timestamps`, `dates`,
+/// `postal-codes`, `ssn`, `emails`, `credit-cards`, `iban`, `vin`, `mac`,
+/// `handles`, `routing` and `links`. `passport` and `dl` take an optional
+/// country or state, as in `passport:US`.
+///
+/// Other targets take a value after a colon, which runs to the end of the
+/// spec so it may contain colons itself:
+///
+/// - `literal:TEXT`, `literal-ci:TEXT`, `literal-word:TEXT`
+/// - `regex:PATTERN`, `regex-word:PATTERN`, `named-groups:PATTERN`
+/// - `regex-group:GROUP:PATTERN`, `regex-replace:CHAR:PATTERN`
+/// - `entities:PATH`, `custom:NAME`
+/// - `names:NAME;NAME`, full names separated by semicolons
+/// - `checksum:LENGTH:ALGORITHM`, with `luhn`, `mod97` or `none`
+/// - `area:X0,Y0,X1,Y1` or `area:PAGE:X0,Y0,X1,Y1`
+/// - `style:color=#RRGGBB`, `style:font=NAME` or both, separated by a
+///   comma, so `NAME` may not contain one
+impl FromStr for RedactionTarget {
+    type Err = RedactorError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| RedactorError::InvalidInput {
+            parameter: "target".to_string(),
+            reason,
+        };
+        let (name, value) = match spec.split_once(':') {
+            Some((name, value)) => (name, Some(value)),
+            None => (spec, None),
+        };
+        let required = |what: &str| match value {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(invalid(format!("'{}' needs a {}", name, what))),
+        };
+        let optional = |what: &str| match value {
+            Some("") => Err(invalid(format!("'{}' has an empty {}", name, what))),
+            value => Ok(value.map(str::to_string)),
+        };
+        let number = |what: &str, text: &str| {
+            text.trim()
+                .parse::<usize>()
+                .map_err(|_| invalid(format!("'{}' is not a valid {}", text, what)))
+        };
+
+        let target = match name {
+            "literal" => Self::Literal(required("text")?.to_string()),
+            "literal-ci" => Self::LiteralCaseInsensitive(required("text")?.to_string()),
+            "literal-word" => Self::LiteralWholeWord(required("text")?.to_string()),
+            "regex" => Self::Regex(required("pattern")?.to_string()),
+            "regex-word" => Self::RegexWholeWord(required("pattern")?.to_string()),
+            "regex-group" => {
+                let (group, pattern) = required("group and pattern")?
+                    .split_once(':')
+                    .filter(|(_, pattern)| !pattern.is_empty())
+                    .ok_or_else(|| invalid("'regex-group' needs GROUP:PATTERN".to_string()))?;
+                Self::RegexCaptureGroup {
+                    pattern: pattern.to_string(),
+                    group: number("group", group)?,
+                }
+            }
+            "regex-replace" => {
+                let value = required("character and pattern")?;
+                let mut chars = value.chars();
+                let replacement = chars.next().filter(|_| chars.next() == Some(':'));
+                match (replacement, chars.as_str()) {
+                    (Some(replacement), pattern) if !pattern.is_empty() => {
+                        Self::RegexWithReplacement {
+                            pattern: pattern.to_string(),
+                            replacement,
+                        }
+                    }
+                    _ => return Err(invalid("'regex-replace' needs CHAR:PATTERN".to_string())),
+                }
+            }
+            "named-groups" => Self::NamedGroups {
+                pattern: required("pattern")?.to_string(),
+            },
+            "passport" => Self::Passport {
+                country: optional("country")?,
+            },
+            "dl" => Self::DriversLicense {
+                state: optional("state")?,
+            },
+            "entities" => Self::EntityList {
+                path: PathBuf::from(required("path")?),
+            },
+            "names" => Self::NamedEntity(
+                required("name")?
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            "custom" => Self::Custom(required("name")?.to_string()),
+            "checksum" => {
+                let (length, algorithm) = required("length and algorithm")?
+                    .split_once(':')
+                    .ok_or_else(|| invalid("'checksum' needs LENGTH:ALGORITHM".to_string()))?;
+                let length = number("length", length)?;
+                let algorithm = ChecksumAlgorithm::from_name(algorithm).ok_or_else(|| {
+                    invalid(format!(
+                        "unknown checksum '{}' (expected luhn, mod97 or none)",
+                        algorithm
+                    ))
+                })?;
+                ChecksumMatcher::try_new(length, algorithm)
+                    .ok_or_else(|| invalid("checksum length must be at least 1".to_string()))?;
+                Self::Checksum { length, algorithm }
+            }
+            "area" => {
+                let value = required("rectangle")?;
+                let (page, rect) = match value.split_once(':') {
+                    Some((page, rect)) => (Some(number("page", page)?), rect),
+                    None => (None, value),
+                };
+                if page == Some(0) {
+                    return Err(invalid("page numbers start at 1".to_string()));
+                }
+                let coords = rect
+                    .split(',')
+                    .map(|c| c.trim().parse::<f32>().ok().filter(|c| c.is_finite()))
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|coords| coords.len() == 4)
+                    .ok_or_else(|| invalid(format!("'{}' is not a rectangle X0,Y0,X1,Y1", rect)))?;
+                Self::AllTextInRect {
+                    page,
+                    rect: (coords[0], coords[1], coords[2], coords[3]),
+                }
+            }
+            "style" => {
+                let mut color = None;
+                let mut font_contains = None;
+                for criterion in required("color or font")?.split(',') {
+                    match criterion.split_once('=') {
+                        Some(("color", hex)) => {
+                            color = Some(parse_hex_color(hex).ok_or_else(|| {
+                                invalid(format!("'{}' is not a color #RRGGBB", hex))
+                            })?);
+                        }
+                        Some(("font", font)) if !font.is_empty() => {
+                            font_contains = Some(font.to_string());
+                        }
+                        _ => {
+                            return Err(invalid(format!(
+                                "'{}' is not color=#RRGGBB or font=NAME",
+                                criterion
+                            )))
+                        }
+                    }
+                }
+                check_style(color, font_contains.as_deref()).map_err(invalid)?;
+                Self::TextWithStyle {
+                    color,
+                    font_contains,
+                }
+            }
+            _ => {
+                let target = match name {
+                    "phones" => Self::PhoneNumbers,
+                    "verizon-account" => Self::VerizonAccount,
+                    "verizon-call-details" => Self::VerizonCallDetails,
+                    "timestamps" => Self::Timestamp,
+                    "dates" => Self::Dates,
+                    "postal-codes" => Self::PostalCode,
+                    "ssn" => Self::Ssn,
+                    "emails" => Self::Email,
+                    "credit-cards" => Self::CreditCard,
+                    "iban" => Self::Iban,
+                    "vin" => Self::Vin,
+                    "mac" => Self::MacAddress,
+                    "handles" => Self::Handle,
+                    "routing" => Self::RoutingNumber,
+                    "links" => Self::AllLinks,
+                    _ => return Err(invalid(format!("unknown target '{}'", name))),
+                };
+                if value.is_some() {
+                    return Err(invalid(format!("'{}' takes no value", name)));
+                }
+                target
+            }
+        };
+
+        Ok(target)
+    }
+}
+
+/// Checks that a style target has at least one criterion and a font name,
+/// if given, that is not empty and has no `,`, so its spec parses back.
+pub(crate) fn check_style(
+    color: Option<(u8, u8, u8)>,
+    font_contains: Option<&str>,
+) -> Result<(), String> {
+    match font_contains {
+        None if color.is_none() => {
+            Err("Give a color, a font or both to match text by style".to_string())
+        }
+        Some("") => Err("Font name to match must not be empty".to_string()),
+        Some(font) if font.contains(',') => Err(format!(
+            "Font name '{}' must not contain ',', which separates style criteria",
+            font
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Parses a color written `#RRGGBB`, the `#` being optional.
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Per-call inputs passed to a strategy alongside its own configuration.
+#[derive(Clone, Copy, Default)]
+pub struct RedactionOptions<'a> {
+    /// Text already extracted from the input, used instead of re-extracting.
+    pub text: Option<&'a str>,
+
+    /// Pages to redact; all pages when `None`.
+    pub pages: Option<&'a PageSelection>,
+
+    /// Password to open an encrypted input with.
+    pub password: Option<&'a str>,
+
+    /// Called as the strategy moves through each phase and page.
+    pub progress: Option<&'a dyn Fn(RedactionProgress)>,
+}
+
+impl RedactionOptions<'_> {
+    /// Reports progress to the callback, if any.
+    pub fn report(&self, phase: RedactionPhase, current_page: usize, total_pages: usize) {
+        if let Some(progress) = self.progress {
+            progress(RedactionProgress {
+                phase,
+                current_page,
+                total_pages,
+            });
+        }
+    }
+}
+
+impl std::fmt::Debug for RedactionOptions<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedactionOptions")
+            .field("text", &self.text.map(str::len))
+            .field("pages", &self.pages)
+            .field("password", &self.password.map(|_| "***"))
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Stage of a redaction, in the order strategies go through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionPhase {
+    /// Extracting text and turning targets into search strings
+    Resolving,
+
+    /// Searching a page for the resolved strings
+    Searching,
+
+    /// Removing the located regions from a page
+    Applying,
+
+    /// Writing the output file
+    Saving,
+}
+
+/// A progress update passed to the callback of
+/// [`RedactionService::redact_with_progress`](super::RedactionService::redact_with_progress).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedactionProgress {
+    /// Phase the redaction is in.
+    pub phase: RedactionPhase,
+
+    /// 1-based page being searched or redacted; 0 outside the page phases
+    /// and `total_pages` once saving.
+    pub current_page: usize,
+
+    /// Pages in the document; 0 while resolving, before it is opened.
+    pub total_pages: usize,
+}
+
+/// A located region that will be (or was) redacted.
+///
+/// Produced by [`RedactionStrategy::preview`] and consumed by
+/// [`RedactionStrategy::apply_hits`], so hits can be reviewed and adjusted
+/// between detection and redaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionHit {
+    /// Page number (1-based)
+    pub page: usize,
+
+    /// Region in page coordinates as `(x0, y0, x1, y1)`
+    pub rect: (f32, f32, f32, f32),
+
+    /// Pattern that matched this region
+    pub text: String,
+
+    /// Category of the target that matched this region, as reported in
+    /// [`RedactionResult::by_category`]; empty if unknown
+    pub category: String,
+
+    /// Whether the pattern only matches whole words, so `text` may still
+    /// appear inside longer words elsewhere
+    pub whole_word: bool,
+}
+
+/// Redaction statistics for a single page.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct PageRedactionStats {
+    /// Page number (1-based)
+    pub page_number: usize,
+
+    /// Number of instances redacted on this page
+    pub instances: usize,
+
+    /// Number of distinct patterns that matched on this page
+    pub patterns_matched: usize,
 }
 
 /// Statistics about a redaction operation.
-#[derive(Debug, Clone, Default)]
+///
+/// Serializes without any document text unless match text was recorded
+/// (see [`SecureRedactionStrategy::with_match_text`]).
+///
+/// [`SecureRedactionStrategy::with_match_text`]: super::SecureRedactionStrategy::with_match_text
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RedactionResult {
     /// Number of instances redacted
     pub instances_redacted: usize,
@@ -37,8 +653,68 @@ pub struct RedactionResult {
     /// Pages with redactions
     pub pages_modified: usize,
 
+    /// Pages deleted because redaction left them empty, which are not
+    /// counted in `pages_modified`
+    pub pages_removed: usize,
+
     /// Whether text was physically removed (vs visually obscured)
     pub secure: bool,
+
+    /// Values detected in the document text that no redaction covered.
+    ///
+    /// A non-empty list means sensitive text may remain in the output, e.g.
+    /// because the backend could not locate text drawn with an unusual font.
+    /// Serialized as a count, since the values are sensitive.
+    #[serde(
+        rename = "detected_not_redacted_count",
+        serialize_with = "serialize_count"
+    )]
+    pub detected_not_redacted: Vec<String>,
+
+    /// Categories of the values in `detected_not_redacted`, which unlike
+    /// the values themselves can be shown in logs
+    pub unredacted_categories: BTreeSet<String>,
+
+    /// Instances redacted per category (see [`RedactionTarget::category`])
+    pub by_category: BTreeMap<String, usize>,
+
+    /// Statistics for each processed page, in page order
+    pub per_page: Vec<PageRedactionStats>,
+
+    /// Patterns whose search reached its hit limit on some page, so more
+    /// matches may remain in the output.
+    /// Serialized as a count, since the patterns are sensitive.
+    #[serde(rename = "truncated_pattern_count", serialize_with = "serialize_count")]
+    pub truncated_patterns: Vec<String>,
+
+    /// Categories of the patterns in `truncated_patterns`
+    pub truncated_categories: BTreeSet<String>,
+
+    /// Input path, set by [`RedactionService`](super::RedactionService)
+    pub input: Option<PathBuf>,
+
+    /// Output path, set by [`RedactionService`](super::RedactionService)
+    pub output: Option<PathBuf>,
+
+    /// Size of the written output in bytes
+    pub output_bytes: u64,
+
+    /// Hex-encoded SHA-256 digest of the written output, if the strategy
+    /// was configured to compute it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_sha256: Option<String>,
+
+    /// Targets requested, set by [`RedactionService`](super::RedactionService).
+    ///
+    /// Serialized as categories only, since literal and regex targets are
+    /// themselves sensitive.
+    #[serde(serialize_with = "serialize_categories")]
+    pub targets: Vec<RedactionTarget>,
+
+    /// Text of each redacted instance; only recorded when the strategy is
+    /// configured to keep it
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_text: Vec<String>,
 }
 
 impl RedactionResult {
@@ -51,6 +727,128 @@ impl RedactionResult {
     pub fn has_redactions(&self) -> bool {
         self.instances_redacted > 0
     }
+
+    /// Returns true if every detected value was redacted and no search
+    /// was cut off by its hit limit.
+    pub fn is_complete(&self) -> bool {
+        self.detected_not_redacted.is_empty() && self.truncated_patterns.is_empty()
+    }
+
+    /// Adds `other` to this result, e.g. to total a batch of documents.
+    ///
+    /// Counts are summed and lists concatenated, skipping values already
+    /// listed. `per_page` is concatenated as is, so page numbers restart
+    /// with each document's stats. The total is secure only if both results
+    /// are, except that a result that processed nothing (such as
+    /// [`none`](Self::none)) does not count. Paths and output digests are
+    /// kept only if both results have the same one.
+    pub fn merge(&mut self, other: &RedactionResult) {
+        let processed =
+            |result: &RedactionResult| result.pages_processed > 0 || result.has_redactions();
+        self.secure = match (processed(self), processed(other)) {
+            (true, true) => self.secure && other.secure,
+            (true, false) => self.secure,
+            (false, _) => other.secure,
+        };
+
+        self.instances_redacted += other.instances_redacted;
+        self.pages_processed += other.pages_processed;
+        self.pages_modified += other.pages_modified;
+        self.pages_removed += other.pages_removed;
+        for (category, count) in &other.by_category {
+            *self.by_category.entry(category.clone()).or_insert(0) += count;
+        }
+        self.per_page.extend(other.per_page.iter().cloned());
+        self.matched_text.extend(other.matched_text.iter().cloned());
+
+        for value in &other.detected_not_redacted {
+            if !self.detected_not_redacted.contains(value) {
+                self.detected_not_redacted.push(value.clone());
+            }
+        }
+        for pattern in &other.truncated_patterns {
+            if !self.truncated_patterns.contains(pattern) {
+                self.truncated_patterns.push(pattern.clone());
+            }
+        }
+        self.unredacted_categories
+            .extend(other.unredacted_categories.iter().cloned());
+        self.truncated_categories
+            .extend(other.truncated_categories.iter().cloned());
+        for target in &other.targets {
+            if !self.targets.contains(target) {
+                self.targets.push(target.clone());
+            }
+        }
+
+        if self.input != other.input {
+            self.input = None;
+        }
+        if self.output != other.output {
+            self.output = None;
+        }
+        self.output_bytes += other.output_bytes;
+        if self.output_sha256 != other.output_sha256 {
+            self.output_sha256 = None;
+        }
+    }
+
+    /// Serializes the result as a JSON audit report, stamped with the
+    /// current UTC time.
+    pub fn to_audit_json(&self) -> String {
+        #[derive(Serialize)]
+        struct AuditReport<'a> {
+            generated_at: String,
+            #[serde(flatten)]
+            result: &'a RedactionResult,
+        }
+
+        let report = AuditReport {
+            generated_at: rfc3339_utc(SystemTime::now()),
+            result: self,
+        };
+        serde_json::to_string_pretty(&report).expect("Audit report is always serializable")
+    }
+}
+
+fn serialize_count<S: Serializer>(values: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(values.len() as u64)
+}
+
+fn serialize_categories<S: Serializer>(
+    targets: &[RedactionTarget],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(targets.iter().map(RedactionTarget::category))
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp with second precision.
+fn rfc3339_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
 }
 
 /// Strategy for redacting sensitive information from PDFs.
@@ -74,11 +872,100 @@ pub trait RedactionStrategy: Send + Sync {
         targets: &[RedactionTarget],
     ) -> RedactorResult<RedactionResult>;
 
+    /// Redacts with additional per-call options.
+    ///
+    /// The default implementation ignores the options and delegates to
+    /// [`redact`](Self::redact).
+    fn redact_with_options(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<RedactionResult> {
+        let _ = options;
+        self.redact(input, output, targets)
+    }
+
+    /// Redacts a PDF held in memory, returning the redacted PDF.
+    ///
+    /// The default implementation writes `input` to a temporary directory
+    /// and delegates to [`redact`](Self::redact). Override it to keep
+    /// documents off disk.
+    fn redact_bytes(&self, input: &[u8], targets: &[RedactionTarget]) -> RedactorResult<Vec<u8>> {
+        let dir = tempfile::tempdir().map_err(|e| RedactorError::Io {
+            path: std::env::temp_dir(),
+            source: e,
+        })?;
+        let input_path = dir.path().join("input.pdf");
+        let output_path = dir.path().join("output.pdf");
+
+        std::fs::write(&input_path, input).map_err(|e| RedactorError::Io {
+            path: input_path.clone(),
+            source: e,
+        })?;
+        self.redact(&input_path, &output_path, targets)?;
+        std::fs::read(&output_path).map_err(|e| RedactorError::Io {
+            path: output_path,
+            source: e,
+        })
+    }
+
+    /// Locates the regions `targets` would redact, without modifying anything.
+    ///
+    /// The default implementation reports that previews are unsupported.
+    fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        let _ = (input, targets);
+        Err(unsupported(self.name(), "preview"))
+    }
+
+    /// Redacts exactly the supplied regions, bypassing pattern detection.
+    ///
+    /// The default implementation reports that applying hits is unsupported.
+    fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        let _ = (input, output, hits);
+        Err(unsupported(self.name(), "apply_hits"))
+    }
+
     /// Extracts text from a PDF for pattern matching.
     ///
     /// This method should handle complex text encodings (e.g., Type3 fonts).
     fn extract_text(&self, input: &Path) -> RedactorResult<String>;
 
+    /// Extracts text from a PDF held in memory.
+    ///
+    /// The default implementation writes `input` to a temporary directory
+    /// and delegates to [`extract_text`](Self::extract_text).
+    fn extract_text_bytes(&self, input: &[u8]) -> RedactorResult<String> {
+        let dir = tempfile::tempdir().map_err(|e| RedactorError::Io {
+            path: std::env::temp_dir(),
+            source: e,
+        })?;
+        let input_path = dir.path().join("input.pdf");
+
+        std::fs::write(&input_path, input).map_err(|e| RedactorError::Io {
+            path: input_path.clone(),
+            source: e,
+        })?;
+        self.extract_text(&input_path)
+    }
+
+    /// Returns the time limit on each redaction, if the strategy has one.
+    ///
+    /// The default implementation has none.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Returns a human-readable name for this strategy.
     fn name(&self) -> &str;
 
@@ -86,6 +973,13 @@ pub trait RedactionStrategy: Send + Sync {
     fn is_secure(&self) -> bool;
 }
 
+fn unsupported(strategy: &str, operation: &str) -> RedactorError {
+    RedactorError::InvalidInput {
+        parameter: "strategy".to_string(),
+        reason: format!("{} does not support {}", strategy, operation),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +995,304 @@ mod tests {
         };
         assert!(result.has_redactions());
     }
+
+    fn sample_result(instances: usize, page: usize) -> RedactionResult {
+        RedactionResult {
+            instances_redacted: instances,
+            pages_processed: 1,
+            pages_modified: 1,
+            secure: true,
+            detected_not_redacted: vec!["555-234-5678".to_string()],
+            by_category: BTreeMap::from([("phone".to_string(), instances)]),
+            per_page: vec![PageRedactionStats {
+                page_number: page,
+                instances,
+                patterns_matched: 1,
+            }],
+            input: Some(PathBuf::from(format!("{}.pdf", page))),
+            targets: vec![RedactionTarget::PhoneNumbers],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_two_results() {
+        let mut total = sample_result(2, 1);
+        let mut other = sample_result(3, 2);
+        other.by_category.insert("email".to_string(), 1);
+        other.targets.push(RedactionTarget::Email);
+        other.secure = false;
+        total.merge(&other);
+
+        assert_eq!(total.instances_redacted, 5);
+        assert_eq!(total.pages_processed, 2);
+        assert_eq!(total.pages_modified, 2);
+        assert!(!total.secure);
+        assert_eq!(total.by_category.get("phone"), Some(&5));
+        assert_eq!(total.by_category.get("email"), Some(&1));
+        assert_eq!(total.per_page.len(), 2);
+        assert_eq!(total.detected_not_redacted, vec!["555-234-5678"]);
+        assert_eq!(
+            total.targets,
+            vec![RedactionTarget::PhoneNumbers, RedactionTarget::Email]
+        );
+        assert_eq!(total.input, None);
+    }
+
+    #[test]
+    fn test_merge_output_size_and_digest() {
+        let mut total = sample_result(2, 1);
+        total.output_bytes = 1000;
+        total.output_sha256 = Some("ab".repeat(32));
+        let mut other = sample_result(3, 2);
+        other.output_bytes = 500;
+        other.output_sha256 = Some("cd".repeat(32));
+        total.merge(&other);
+
+        assert_eq!(total.output_bytes, 1500);
+        assert_eq!(total.output_sha256, None);
+    }
+
+    #[test]
+    fn test_merge_with_none() {
+        let result = sample_result(2, 1);
+
+        let mut total = RedactionResult::none();
+        total.merge(&result);
+        assert_eq!(total.instances_redacted, 2);
+        assert!(total.secure, "An empty total takes the result's security");
+        assert_eq!(total.input, None);
+
+        let mut total = result.clone();
+        total.merge(&RedactionResult::none());
+        assert_eq!(total.instances_redacted, 2);
+        assert_eq!(total.pages_processed, 1);
+        assert!(total.secure);
+        assert_eq!(total.per_page, result.per_page);
+        assert_eq!(total.input, None);
+    }
+
+    #[test]
+    fn test_rfc3339_utc() {
+        use std::time::Duration;
+
+        assert_eq!(rfc3339_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            rfc3339_utc(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29T12:34:56Z"
+        );
+    }
+
+    #[test]
+    fn test_audit_json_omits_target_text() {
+        let result = RedactionResult {
+            instances_redacted: 1,
+            detected_not_redacted: vec!["555-234-5678".to_string()],
+            targets: vec![
+                RedactionTarget::Literal("Jane Roe".to_string()),
+                RedactionTarget::PhoneNumbers,
+            ],
+            ..Default::default()
+        };
+
+        let json = result.to_audit_json();
+        assert!(json.contains(r#""instances_redacted": 1"#));
+        assert!(json.contains(r#""literal""#));
+        assert!(json.contains(r#""phone""#));
+        assert!(json.contains("generated_at"));
+        assert!(json.contains(r#""detected_not_redacted_count": 1"#));
+        assert!(!json.contains("Jane Roe"));
+        assert!(!json.contains("555-234-5678"));
+        assert!(!json.contains("matched_text"));
+    }
+
+    #[test]
+    fn test_requires_text() {
+        assert!(!RedactionTarget::Literal("x".to_string()).requires_text());
+        assert!(!RedactionTarget::LiteralWholeWord("x".to_string()).requires_text());
+        assert!(RedactionTarget::RegexWholeWord("x".to_string()).requires_text());
+        assert!(RedactionTarget::LiteralCaseInsensitive("x".to_string()).requires_text());
+        assert!(RedactionTarget::PhoneNumbers.requires_text());
+        assert!(RedactionTarget::Timestamp.requires_text());
+        assert!(RedactionTarget::NamedGroups {
+            pattern: "(?P<id>\\d+)".to_string()
+        }
+        .requires_text());
+        assert!(RedactionTarget::Regex(r"\d+".to_string()).requires_text());
+        assert!(!RedactionTarget::EntityList {
+            path: PathBuf::from("entities.txt")
+        }
+        .requires_text());
+        assert!(!RedactionTarget::NamedEntity(vec!["John Doe".to_string()]).requires_text());
+        assert!(!RedactionTarget::AllTextInRect {
+            page: None,
+            rect: (0.0, 0.0, 612.0, 72.0)
+        }
+        .requires_text());
+    }
+
+    #[test]
+    fn test_target_spec_round_trips() {
+        let targets = vec![
+            RedactionTarget::Literal("Jane Doe".to_string()),
+            RedactionTarget::LiteralCaseInsensitive("secret".to_string()),
+            RedactionTarget::LiteralWholeWord("SECRET".to_string()),
+            RedactionTarget::Regex(r"\d{3}:\d{4}".to_string()),
+            RedactionTarget::RegexWholeWord(r"[A-Z]{3}\d+".to_string()),
+            RedactionTarget::RegexCaptureGroup {
+                pattern: r"Account:\s*(\d+)".to_string(),
+                group: 1,
+            },
+            RedactionTarget::RegexWithReplacement {
+                pattern: r"\d{3}-\d{4}".to_string(),
+                replacement: '█',
+            },
+            RedactionTarget::RegexWithReplacement {
+                pattern: r"\d+".to_string(),
+                replacement: ':',
+            },
+            RedactionTarget::NamedGroups {
+                pattern: r"(?P<ssn>\d{3}-\d{2}-\d{4})".to_string(),
+            },
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::VerizonAccount,
+            RedactionTarget::VerizonCallDetails,
+            RedactionTarget::Timestamp,
+            RedactionTarget::Dates,
+            RedactionTarget::PostalCode,
+            RedactionTarget::Ssn,
+            RedactionTarget::Email,
+            RedactionTarget::CreditCard,
+            RedactionTarget::Iban,
+            RedactionTarget::Vin,
+            RedactionTarget::MacAddress,
+            RedactionTarget::Handle,
+            RedactionTarget::Passport { country: None },
+            RedactionTarget::Passport {
+                country: Some("US".to_string()),
+            },
+            RedactionTarget::DriversLicense { state: None },
+            RedactionTarget::DriversLicense {
+                state: Some("CA".to_string()),
+            },
+            RedactionTarget::RoutingNumber,
+            RedactionTarget::EntityList {
+                path: PathBuf::from("ner/entities.tsv"),
+            },
+            RedactionTarget::NamedEntity(vec!["John Doe".to_string(), "Doe, Jane".to_string()]),
+            RedactionTarget::Checksum {
+                length: 10,
+                algorithm: ChecksumAlgorithm::Luhn,
+            },
+            RedactionTarget::Custom("employee_id".to_string()),
+            RedactionTarget::AllTextInRect {
+                page: None,
+                rect: (0.0, 0.0, 612.0, 72.0),
+            },
+            RedactionTarget::AllTextInRect {
+                page: Some(3),
+                rect: (10.5, 20.25, 300.0, 400.75),
+            },
+            RedactionTarget::AllLinks,
+            RedactionTarget::TextWithStyle {
+                color: Some((255, 0, 0)),
+                font_contains: None,
+            },
+            RedactionTarget::TextWithStyle {
+                color: Some((0x1a, 0x2b, 0x3c)),
+                font_contains: Some("Bold".to_string()),
+            },
+            RedactionTarget::TextWithStyle {
+                color: None,
+                font_contains: Some("Courier".to_string()),
+            },
+        ];
+
+        for target in targets {
+            let spec = target.to_string();
+            assert_eq!(spec.parse::<RedactionTarget>().unwrap(), target, "{}", spec);
+        }
+
+        // Style targets whose spec could not be read back are rejected
+        for (color, font) in [
+            (None, None),
+            (None, Some("")),
+            (Some((255, 0, 0)), Some("")),
+            (None, Some("Bold,Italic")),
+            (Some((255, 0, 0)), Some("color=#000000,Bold")),
+        ] {
+            let target = RedactionTarget::TextWithStyle {
+                color,
+                font_contains: font.map(str::to_string),
+            };
+            assert!(check_style(color, font).is_err(), "{:?}", target);
+            assert!(
+                target.to_string().parse::<RedactionTarget>().is_err(),
+                "{}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_target_spec_parses_names() {
+        assert_eq!(
+            "phones".parse::<RedactionTarget>().unwrap(),
+            RedactionTarget::PhoneNumbers
+        );
+        assert_eq!(
+            "verizon-account".parse::<RedactionTarget>().unwrap(),
+            RedactionTarget::VerizonAccount
+        );
+        assert_eq!(
+            "literal:a:b".parse::<RedactionTarget>().unwrap(),
+            RedactionTarget::Literal("a:b".to_string())
+        );
+        assert_eq!(
+            "checksum:8:MOD97".parse::<RedactionTarget>().unwrap(),
+            RedactionTarget::Checksum {
+                length: 8,
+                algorithm: ChecksumAlgorithm::Mod97,
+            }
+        );
+    }
+
+    #[test]
+    fn test_target_spec_rejects_malformed() {
+        for spec in [
+            "",
+            "phone",
+            "Phones",
+            "phones:555",
+            "regex:",
+            "regex",
+            "literal:",
+            "regex-group:Account:(\\d+)",
+            "regex-group:1:",
+            "regex-replace:*",
+            "regex-replace:*:",
+            "regex-replace:**:\\d+",
+            "passport:",
+            "checksum:10",
+            "checksum:0:luhn",
+            "checksum:10:crc32",
+            "area:0,0,612",
+            "area:0:0,0,612,72",
+            "area:0,0,612,wide",
+            "area:0,0,612,inf",
+            "style:",
+            "style:color=red",
+            "style:color=#ff00",
+            "style:font=",
+            "style:size=12",
+        ] {
+            let err = spec.parse::<RedactionTarget>().unwrap_err();
+            assert!(
+                matches!(err, RedactorError::InvalidInput { ref parameter, .. } if parameter == "target"),
+                "{:?}: {}",
+                spec,
+                err
+            );
+        }
+    }
 }
@@ -0,0 +1,155 @@
+//! Text picked out by its color or font.
+//!
+//! Watermarks and stamps are usually drawn in a color or font the rest of
+//! the page does not use, so they can be redacted by how they look rather
+//! than what they say. MuPDF's structured text records the color and font
+//! of every character; these helpers walk it and return the areas covered
+//! by text of a given style.
+
+use crate::error::{RedactorError, RedactorResult};
+use mupdf::text_page::TextBlockType;
+use mupdf::{Page, Rect, TextChar, TextPageOptions};
+use std::ffi::CStr;
+
+/// How far each color channel may be from the one asked for and still
+/// match, out of 255.
+///
+/// MuPDF converts text colored in gray or CMYK to RGB, which can round a
+/// channel away from the value the color was chosen as.
+const COLOR_TOLERANCE: u8 = 8;
+
+/// Criteria that the style of a character must all meet.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TextStyle {
+    color: Option<(u8, u8, u8)>,
+    /// Part of the font name, lowercased
+    font: Option<String>,
+}
+
+impl TextStyle {
+    /// Matches text in `color` whose font name contains `font_contains`,
+    /// ignoring case.
+    pub(super) fn new(color: Option<(u8, u8, u8)>, font_contains: Option<&str>) -> Self {
+        Self {
+            color,
+            font: font_contains.map(str::to_lowercase),
+        }
+    }
+
+    /// Returns true if text in the sRGB color `argb` (alpha in the top
+    /// byte) drawn with the font `font_name` has this style.
+    fn matches(&self, argb: u32, font_name: &str) -> bool {
+        let color_matches = self.color.map_or(true, |(r, g, b)| {
+            let [_, text_r, text_g, text_b] = argb.to_be_bytes();
+            [(r, text_r), (g, text_g), (b, text_b)]
+                .iter()
+                .all(|&(want, got)| want.abs_diff(got) <= COLOR_TOLERANCE)
+        });
+        color_matches
+            && self
+                .font
+                .as_ref()
+                .map_or(true, |font| font_name.to_lowercase().contains(font))
+    }
+}
+
+/// Returns the areas of `page` covered by text in `style`, one for each
+/// run of consecutive matching characters on a line.
+pub(super) fn styled_regions(
+    page: &Page,
+    page_idx: i32,
+    style: &TextStyle,
+) -> RedactorResult<Vec<Rect>> {
+    let text_page =
+        page.to_text_page(TextPageOptions::empty())
+            .map_err(|e| RedactorError::PdfProcessing {
+                message: format!("Failed to read the text styles of page {}", page_idx + 1),
+                page: Some(page_idx as usize + 1),
+                source: Some(Box::new(e)),
+            })?;
+
+    let mut regions = Vec::new();
+    for block in text_page.blocks() {
+        if block.r#type() != TextBlockType::Text {
+            continue;
+        }
+        for line in block.lines() {
+            let mut run: Option<Rect> = None;
+            for ch in line.chars() {
+                let (argb, font_name) = char_style(&ch);
+                if style.matches(argb, &font_name) {
+                    let rect = Rect::from(ch.quad());
+                    match &mut run {
+                        Some(run) => {
+                            run.union(rect);
+                        }
+                        None => run = Some(rect),
+                    }
+                } else {
+                    regions.extend(run.take());
+                }
+            }
+            regions.extend(run);
+        }
+    }
+    Ok(regions)
+}
+
+#[repr(C)]
+struct TextCharRaw<'a> {
+    inner: &'a mupdf_sys::fz_stext_char,
+}
+
+/// Returns the color and font name of a character.
+///
+/// The safe API exposes neither, so both are read from MuPDF's
+/// `fz_stext_char`; the font name is the one MuPDF loaded the font under,
+/// which for embedded subsets can keep its `ABCDEF+` prefix.
+fn char_style(ch: &TextChar<'_>) -> (u32, String) {
+    // SAFETY: `TextChar` holds only a reference to the MuPDF character,
+    // which lives as long as its text page, and its font, if any, is kept
+    // alive by the character
+    unsafe {
+        let raw = std::mem::transmute::<&TextChar<'_>, &TextCharRaw<'_>>(ch);
+        let font_name = match raw.inner.font.as_ref() {
+            Some(font) => CStr::from_ptr(font.name.as_ptr())
+                .to_string_lossy()
+                .into_owned(),
+            None => String::new(),
+        };
+        (raw.inner.argb, font_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: u32 = 0xFFFF_0000;
+    const BLACK: u32 = 0xFF00_0000;
+
+    #[test]
+    fn test_color_matches_within_tolerance() {
+        let red = TextStyle::new(Some((255, 0, 0)), None);
+        assert!(red.matches(RED, "Helvetica"));
+        assert!(red.matches(0xFFFA_0405, "Helvetica"));
+        assert!(!red.matches(BLACK, "Helvetica"));
+        assert!(!red.matches(0xFFC0_0000, "Helvetica"));
+    }
+
+    #[test]
+    fn test_font_matches_part_of_name_ignoring_case() {
+        let bold = TextStyle::new(None, Some("BOLD"));
+        assert!(bold.matches(BLACK, "Helvetica-Bold"));
+        assert!(bold.matches(BLACK, "ABCDEF+Arial,Bold"));
+        assert!(!bold.matches(BLACK, "Helvetica"));
+    }
+
+    #[test]
+    fn test_every_criterion_must_match() {
+        let style = TextStyle::new(Some((255, 0, 0)), Some("Bold"));
+        assert!(style.matches(RED, "Helvetica-Bold"));
+        assert!(!style.matches(RED, "Helvetica"));
+        assert!(!style.matches(BLACK, "Helvetica-Bold"));
+    }
+}
@@ -0,0 +1,391 @@
+//! A dedicated thread for all MuPDF work.
+//!
+//! MuPDF font loading is not thread-safe, so documents cannot be opened
+//! from several threads at once. A [`MupdfWorker`] owns one thread that
+//! runs jobs sent to it over a channel, one at a time, and
+//! [`WorkerStrategy`] runs another strategy's every call as such a job.
+//! Callers on any number of threads can then share a strategy while MuPDF
+//! only ever runs on the worker.
+
+use super::strategy::{
+    RedactionHit, RedactionOptions, RedactionProgress, RedactionResult, RedactionStrategy,
+    RedactionTarget,
+};
+use crate::error::{RedactorError, RedactorResult};
+use once_cell::sync::OnceCell;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A job queued on the worker.
+type Job = Box<dyn FnOnce() + Send>;
+
+thread_local! {
+    static ON_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns true when called from a [`MupdfWorker`]'s thread.
+pub(crate) fn on_worker_thread() -> bool {
+    ON_WORKER.with(Cell::get)
+}
+
+/// A thread that runs queued jobs one at a time, in the order submitted.
+///
+/// The thread exits once the worker is dropped and its queue has drained.
+/// A job that panics fails with an error instead of taking the worker
+/// down.
+#[derive(Debug)]
+pub struct MupdfWorker {
+    jobs: Mutex<mpsc::Sender<Job>>,
+}
+
+impl MupdfWorker {
+    /// Starts a worker thread.
+    pub fn spawn() -> RedactorResult<Self> {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name("mupdf-worker".to_string())
+            .spawn(move || {
+                ON_WORKER.with(|on| on.set(true));
+                for job in queue {
+                    job();
+                }
+            })
+            .map_err(|e| RedactorError::BackendError {
+                backend: "MuPDF".to_string(),
+                message: "Failed to start the MuPDF worker thread".to_string(),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self {
+            jobs: Mutex::new(jobs),
+        })
+    }
+
+    /// Returns the worker shared by the whole process, started on first
+    /// use.
+    ///
+    /// Every caller that goes through this worker is serialized with every
+    /// other, which is what keeps MuPDF safe.
+    pub fn global() -> RedactorResult<Arc<Self>> {
+        static GLOBAL: OnceCell<Arc<MupdfWorker>> = OnceCell::new();
+        GLOBAL
+            .get_or_try_init(|| Self::spawn().map(Arc::new))
+            .cloned()
+    }
+
+    /// Queues `job`, returning a handle to wait for its result.
+    pub fn submit<T, F>(&self, job: F) -> RedactorResult<WorkerJob<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let job: Job = Box::new(move || {
+            // The submitter may have stopped waiting
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(job)).ok());
+        });
+
+        self.jobs
+            .lock()
+            .map_err(|_| worker_gone())?
+            .send(job)
+            .map_err(|_| worker_gone())?;
+        Ok(WorkerJob { receiver })
+    }
+
+    /// Runs `job` on the worker and waits for its result.
+    pub fn run<T, F>(&self, job: F) -> RedactorResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.submit(job)?.wait()
+    }
+}
+
+/// A job submitted to a [`MupdfWorker`].
+#[derive(Debug)]
+pub struct WorkerJob<T> {
+    receiver: mpsc::Receiver<Option<T>>,
+}
+
+impl<T> WorkerJob<T> {
+    /// Waits for the job to finish and returns its result.
+    ///
+    /// # Errors
+    /// Fails if the job panicked or the worker thread is gone.
+    pub fn wait(self) -> RedactorResult<T> {
+        match self.receiver.recv() {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(RedactorError::PdfProcessing {
+                message: "MuPDF worker job panicked".to_string(),
+                page: None,
+                source: None,
+            }),
+            Err(_) => Err(worker_gone()),
+        }
+    }
+}
+
+/// Progress of a timed job, shared between the job and its submitter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimedJob {
+    Running,
+    Finished,
+    /// The submitter stopped waiting after the timeout fired
+    Abandoned,
+}
+
+/// Returns a temporary path next to `output` for a job to write to.
+fn temp_path(output: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = output
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    output.with_file_name(format!(
+        ".{}.{}-{}.worker.tmp",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+fn worker_gone() -> RedactorError {
+    RedactorError::PdfProcessing {
+        message: "MuPDF worker thread is no longer running".to_string(),
+        page: None,
+        source: None,
+    }
+}
+
+/// A strategy that runs each call of another on a [`MupdfWorker`].
+///
+/// Arguments are copied into the job, and progress is reported on the
+/// caller's thread as the job runs.
+///
+/// If the inner strategy has a [timeout](RedactionStrategy::timeout), it
+/// is enforced here rather than by the inner strategy, so that MuPDF
+/// still only runs on the worker. The caller stops waiting once the
+/// timeout fires, but the job keeps the worker busy until MuPDF returns,
+/// delaying the jobs queued behind it. Its output is then discarded.
+pub struct WorkerStrategy {
+    inner: Arc<dyn RedactionStrategy>,
+    worker: Arc<MupdfWorker>,
+}
+
+impl WorkerStrategy {
+    /// Runs `inner` on `worker`.
+    pub fn new(inner: Box<dyn RedactionStrategy>, worker: Arc<MupdfWorker>) -> Self {
+        Self {
+            inner: Arc::from(inner),
+            worker,
+        }
+    }
+}
+
+impl RedactionStrategy for WorkerStrategy {
+    fn redact(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<RedactionResult> {
+        self.redact_with_options(input, output, targets, &RedactionOptions::default())
+    }
+
+    fn redact_with_options(
+        &self,
+        input: &Path,
+        output: &Path,
+        targets: &[RedactionTarget],
+        options: &RedactionOptions<'_>,
+    ) -> RedactorResult<RedactionResult> {
+        let inner = Arc::clone(&self.inner);
+        let input_path = input.to_path_buf();
+        let input = input.to_path_buf();
+        let output = output.to_path_buf();
+        let targets = targets.to_vec();
+        let text = options.text.map(str::to_string);
+        let pages = options.pages.cloned();
+        let password = options.password.map(str::to_string);
+
+        // A timed job writes to a temporary file that is only moved into
+        // place if it finishes in time
+        let limit = self.inner.timeout();
+        let temp = limit.map(|_| temp_path(&output));
+        let written = temp.clone().unwrap_or_else(|| output.clone());
+        let state = Arc::new(Mutex::new(TimedJob::Running));
+        let job_state = Arc::clone(&state);
+
+        let (updates, received) = mpsc::channel::<RedactionProgress>();
+        let job = self.worker.submit(move || {
+            let progress = |update: RedactionProgress| {
+                let _ = updates.send(update);
+            };
+            let options = RedactionOptions {
+                text: text.as_deref(),
+                pages: pages.as_ref(),
+                password: password.as_deref(),
+                progress: Some(&progress),
+            };
+            let redacted = inner.redact_with_options(&input, &written, &targets, &options);
+
+            // Nobody is waiting after a timeout, so drop the output
+            if let Ok(mut state) = job_state.lock() {
+                if *state == TimedJob::Abandoned {
+                    let _ = std::fs::remove_file(&written);
+                }
+                *state = TimedJob::Finished;
+            }
+            redacted
+        })?;
+
+        let deadline = limit.map(|limit| Instant::now() + limit);
+        loop {
+            let update = match deadline {
+                Some(deadline) => {
+                    received.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => received.recv().map_err(RecvTimeoutError::from),
+            };
+            match update {
+                Ok(update) => options.report(update.phase, update.current_page, update.total_pages),
+                // The job is done and has dropped its sender
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => {
+                    let mut state = state.lock().map_err(|_| worker_gone())?;
+                    // A job that finished as the timeout fired is kept
+                    if *state == TimedJob::Finished {
+                        break;
+                    }
+                    *state = TimedJob::Abandoned;
+                    return Err(RedactorError::Timeout {
+                        path: input_path,
+                        limit: limit.expect("Only timed jobs have a deadline"),
+                    });
+                }
+            }
+        }
+
+        let redacted = job.wait().and_then(|redacted| redacted);
+        if let Some(temp) = temp {
+            match &redacted {
+                Ok(_) => std::fs::rename(&temp, &output).map_err(|e| RedactorError::Io {
+                    path: output.clone(),
+                    source: e,
+                })?,
+                Err(_) => {
+                    let _ = std::fs::remove_file(&temp);
+                }
+            }
+        }
+        redacted
+    }
+
+    fn redact_bytes(&self, input: &[u8], targets: &[RedactionTarget]) -> RedactorResult<Vec<u8>> {
+        let inner = Arc::clone(&self.inner);
+        let input = input.to_vec();
+        let targets = targets.to_vec();
+        self.worker
+            .run(move || inner.redact_bytes(&input, &targets))?
+    }
+
+    fn preview(
+        &self,
+        input: &Path,
+        targets: &[RedactionTarget],
+    ) -> RedactorResult<Vec<RedactionHit>> {
+        let inner = Arc::clone(&self.inner);
+        let input = input.to_path_buf();
+        let targets = targets.to_vec();
+        self.worker.run(move || inner.preview(&input, &targets))?
+    }
+
+    fn apply_hits(
+        &self,
+        input: &Path,
+        output: &Path,
+        hits: &[RedactionHit],
+    ) -> RedactorResult<RedactionResult> {
+        let inner = Arc::clone(&self.inner);
+        let input = input.to_path_buf();
+        let output = output.to_path_buf();
+        let hits = hits.to_vec();
+        self.worker
+            .run(move || inner.apply_hits(&input, &output, &hits))?
+    }
+
+    fn extract_text(&self, input: &Path) -> RedactorResult<String> {
+        let inner = Arc::clone(&self.inner);
+        let input = input.to_path_buf();
+        self.worker.run(move || inner.extract_text(&input))?
+    }
+
+    fn extract_text_bytes(&self, input: &[u8]) -> RedactorResult<String> {
+        let inner = Arc::clone(&self.inner);
+        let input = input.to_vec();
+        self.worker.run(move || inner.extract_text_bytes(&input))?
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn is_secure(&self) -> bool {
+        self.inner.is_secure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_run_one_at_a_time_on_one_thread() {
+        let worker = Arc::new(MupdfWorker::spawn().unwrap());
+        let running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let worker = Arc::clone(&worker);
+                let running = Arc::clone(&running);
+                thread::spawn(move || {
+                    worker
+                        .run(move || {
+                            assert_eq!(running.fetch_add(1, Ordering::SeqCst), 0);
+                            thread::sleep(Duration::from_millis(5));
+                            running.fetch_sub(1, Ordering::SeqCst);
+                            (i, thread::current().name().map(str::to_string))
+                        })
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            let (job, thread_name) = handle.join().unwrap();
+            assert_eq!(job, i);
+            assert_eq!(thread_name.as_deref(), Some("mupdf-worker"));
+        }
+    }
+
+    #[test]
+    fn test_panicking_job_fails_without_stopping_the_worker() {
+        let worker = MupdfWorker::spawn().unwrap();
+        let result = worker.run(|| -> usize { panic!("job failed") });
+        assert!(matches!(result, Err(RedactorError::PdfProcessing { .. })));
+        assert_eq!(worker.run(|| 42).unwrap(), 42);
+    }
+}
@@ -0,0 +1,374 @@
+//! Form XObject text location.
+//!
+//! Text drawn inside a Form XObject is reached through a `Do` operator
+//! rather than the page's own content stream, and MuPDF search can miss it
+//! in heavily nested documents such as Verizon bills. These helpers walk
+//! each page's forms so a value search could not locate can be redacted by
+//! covering the whole form that holds it.
+
+use super::geometry::PageTransform;
+use crate::error::{RedactorError, RedactorResult};
+use lopdf::content::Content;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// Deepest form nesting followed, guarding against cyclic resources.
+const MAX_DEPTH: usize = 16;
+
+/// Affine matrix `[a b c d e f]` as used by the `cm` operator.
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// A Form XObject drawn on a page.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FormRegion {
+    /// Page number (1-based)
+    pub page: usize,
+
+    /// Area the form's bounding box covers, in MuPDF page coordinates
+    pub rect: (f32, f32, f32, f32),
+
+    /// Text the form draws itself, excluding nested forms
+    pub text: String,
+}
+
+impl FormRegion {
+    /// Returns true if the form's text holds `value`, ignoring whitespace,
+    /// which text operators rarely encode the way extraction reports it.
+    pub fn contains(&self, value: &str) -> bool {
+        let compact = |s: &str| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        let value = compact(value);
+        !value.is_empty() && compact(&self.text).contains(&value)
+    }
+}
+
+/// Returns every Form XObject drawn on each page of a PDF, innermost
+/// forms first.
+pub(crate) fn form_regions(bytes: &[u8]) -> RedactorResult<Vec<FormRegion>> {
+    let doc = Document::load_mem(bytes).map_err(|e| RedactorError::BackendError {
+        backend: "LoPDF".to_string(),
+        message: "Failed to read form XObjects".to_string(),
+        source: Some(Box::new(e)),
+    })?;
+
+    let mut regions = Vec::new();
+    for (page_idx, page_id) in doc.get_pages().values().enumerate() {
+        let Ok(content) = doc.get_page_content(*page_id) else {
+            continue;
+        };
+        let mut walker = Walker {
+            doc: &doc,
+            page: page_idx + 1,
+            transform: PageTransform::for_page(&doc, *page_id),
+            regions: &mut regions,
+        };
+        walker.visit(&content, &page_resources(&doc, *page_id), IDENTITY, 0);
+    }
+
+    Ok(regions)
+}
+
+/// Interprets content streams, recording each form they draw.
+struct Walker<'a> {
+    doc: &'a Document,
+    page: usize,
+    transform: PageTransform,
+    regions: &'a mut Vec<FormRegion>,
+}
+
+impl<'a> Walker<'a> {
+    /// Walks one content stream drawn with `ctm`, returning its text.
+    fn visit(
+        &mut self,
+        content: &[u8],
+        resources: &[&'a Dictionary],
+        ctm: Matrix,
+        depth: usize,
+    ) -> String {
+        let Ok(content) = Content::decode(content) else {
+            return String::new();
+        };
+
+        let mut text = String::new();
+        let mut ctm = ctm;
+        let mut saved = Vec::new();
+        let mut encoding = None;
+
+        for op in &content.operations {
+            match op.operator.as_str() {
+                "q" => saved.push(ctm),
+                "Q" => ctm = saved.pop().unwrap_or(ctm),
+                "cm" => {
+                    if let Some(m) = as_matrix(self.doc, &op.operands) {
+                        ctm = concat(m, ctm);
+                    }
+                }
+                "Tf" => {
+                    encoding = op
+                        .operands
+                        .first()
+                        .and_then(|name| name.as_name().ok())
+                        .and_then(|name| lookup(self.doc, resources, b"Font", name))
+                        .and_then(|font| font.as_dict().ok())
+                        .map(Dictionary::get_font_encoding);
+                }
+                "Tj" | "TJ" | "'" | "\"" => collect_text(&mut text, encoding, &op.operands),
+                "ET" => text.push('\n'),
+                "Do" if depth < MAX_DEPTH => {
+                    if let Some(name) = op.operands.first().and_then(|n| n.as_name().ok()) {
+                        self.visit_form(name, resources, ctm, depth);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        text
+    }
+
+    /// Records the form named `name`, if it is one, and the forms it draws.
+    fn visit_form(&mut self, name: &[u8], resources: &[&'a Dictionary], ctm: Matrix, depth: usize) {
+        let Some(Object::Stream(stream)) = lookup(self.doc, resources, b"XObject", name) else {
+            return;
+        };
+        let dict = &stream.dict;
+        let is_form = dict
+            .get(b"Subtype")
+            .and_then(Object::as_name)
+            .is_ok_and(|subtype| subtype == b"Form");
+        if !is_form {
+            return;
+        }
+        let Some(bbox) = dict.get(b"BBox").ok().and_then(|b| as_rect(self.doc, b)) else {
+            return;
+        };
+
+        let matrix = dict
+            .get(b"Matrix")
+            .ok()
+            .and_then(|m| deref(self.doc, m).as_array().ok())
+            .and_then(|m| as_matrix(self.doc, m))
+            .unwrap_or(IDENTITY);
+        let form_ctm = concat(matrix, ctm);
+
+        // Forms without their own resources use those of their parent
+        let own = dict
+            .get(b"Resources")
+            .ok()
+            .and_then(|r| deref(self.doc, r).as_dict().ok());
+        let form_resources: Vec<&Dictionary> = match own {
+            Some(own) => vec![own],
+            None => resources.to_vec(),
+        };
+
+        let content = stream
+            .decompressed_content()
+            .unwrap_or_else(|_| stream.content.clone());
+        let text = self.visit(&content, &form_resources, form_ctm, depth + 1);
+
+        let (x0, y0, x1, y1) = bbox;
+        let corners = [(x0, y0), (x0, y1), (x1, y0), (x1, y1)].map(|(x, y)| apply(form_ctm, x, y));
+        let xs = corners.map(|c| c.0);
+        let ys = corners.map(|c| c.1);
+        let user = (
+            xs.iter().copied().fold(f32::INFINITY, f32::min),
+            ys.iter().copied().fold(f32::INFINITY, f32::min),
+            xs.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            ys.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        );
+
+        self.regions.push(FormRegion {
+            page: self.page,
+            rect: self.transform.to_page_space(user),
+            text,
+        });
+    }
+}
+
+/// Returns a page's resource dictionaries, its own first.
+pub(super) fn page_resources(doc: &Document, page_id: ObjectId) -> Vec<&Dictionary> {
+    let (own, inherited) = doc.get_page_resources(page_id);
+    own.into_iter()
+        .chain(
+            inherited
+                .into_iter()
+                .filter_map(|id| doc.get_dictionary(id).ok()),
+        )
+        .collect()
+}
+
+/// Looks up `name` in the `category` subdictionary of the first resource
+/// dictionary that has it.
+pub(super) fn lookup<'a>(
+    doc: &'a Document,
+    resources: &[&'a Dictionary],
+    category: &[u8],
+    name: &[u8],
+) -> Option<&'a Object> {
+    resources.iter().find_map(|res| {
+        let entries = deref(doc, res.get(category).ok()?).as_dict().ok()?;
+        Some(deref(doc, entries.get(name).ok()?))
+    })
+}
+
+pub(super) fn deref<'a>(doc: &'a Document, obj: &'a Object) -> &'a Object {
+    doc.dereference(obj).map_or(obj, |(_, resolved)| resolved)
+}
+
+fn as_number(doc: &Document, obj: &Object) -> Option<f32> {
+    deref(doc, obj).as_float().ok()
+}
+
+fn as_matrix(doc: &Document, operands: &[Object]) -> Option<Matrix> {
+    if operands.len() != 6 {
+        return None;
+    }
+    let mut m = IDENTITY;
+    for (value, operand) in m.iter_mut().zip(operands) {
+        *value = as_number(doc, operand)?;
+    }
+    Some(m)
+}
+
+fn as_rect(doc: &Document, obj: &Object) -> Option<(f32, f32, f32, f32)> {
+    let values = deref(doc, obj).as_array().ok()?;
+    if values.len() != 4 {
+        return None;
+    }
+    let [x0, y0, x1, y1] = [0, 1, 2, 3].map(|i| as_number(doc, &values[i]));
+    Some((x0?, y0?, x1?, y1?))
+}
+
+/// Returns the matrix applying `first`, then `then`.
+fn concat(first: Matrix, then: Matrix) -> Matrix {
+    let [a, b, c, d, e, f] = first;
+    let [a2, b2, c2, d2, e2, f2] = then;
+    [
+        a * a2 + b * c2,
+        a * b2 + b * d2,
+        c * a2 + d * c2,
+        c * b2 + d * d2,
+        e * a2 + f * c2 + e2,
+        e * b2 + f * d2 + f2,
+    ]
+}
+
+fn apply(m: Matrix, x: f32, y: f32) -> (f32, f32) {
+    (x * m[0] + y * m[2] + m[4], x * m[1] + y * m[3] + m[5])
+}
+
+/// Appends the text shown by a text operator's operands.
+fn collect_text(text: &mut String, encoding: Option<&str>, operands: &[Object]) {
+    for operand in operands {
+        match operand {
+            Object::String(bytes, _) => text.push_str(&Document::decode_text(encoding, bytes)),
+            Object::Array(parts) => collect_text(text, encoding, parts),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Stream};
+
+    /// Builds a one-page PDF that draws `Outer` at (100, 500), which in
+    /// turn draws `Inner` scaled by half.
+    fn nested_forms_document() -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let fonts = dictionary! { "F1" => font };
+
+        let inner = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "BBox" => vec![0.into(), 0.into(), 200.into(), 40.into()],
+                "Matrix" => vec![0.5.into(), 0.into(), 0.into(), 0.5.into(), 0.into(), 0.into()],
+                "Resources" => dictionary! { "Font" => fonts.clone() },
+            },
+            b"BT /F1 12 Tf 10 10 Td (Account 123456789-00001) Tj ET".to_vec(),
+        ));
+        let outer = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "BBox" => vec![0.into(), 0.into(), 300.into(), 100.into()],
+                "Resources" => dictionary! {
+                    "Font" => fonts,
+                    "XObject" => dictionary! { "Inner" => inner },
+                },
+            },
+            b"BT /F1 12 Tf 10 80 Td (Statement) Tj ET q 1 0 0 1 0 20 cm /Inner Do Q".to_vec(),
+        ));
+
+        let pages_id = doc.new_object_id();
+        let content = doc.add_object(Stream::new(
+            Dictionary::new(),
+            b"q 1 0 0 1 100 500 cm /Outer Do Q".to_vec(),
+        ));
+        let page = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            "Contents" => content,
+            "Resources" => dictionary! { "XObject" => dictionary! { "Outer" => outer } },
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog);
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_nested_forms_are_mapped_to_page_space() {
+        let regions = form_regions(&nested_forms_document()).unwrap();
+        assert_eq!(regions.len(), 2);
+
+        // Inner: 200x40 halved, at (100, 520) in user space
+        let inner = &regions[0];
+        assert_eq!(inner.page, 1);
+        assert_eq!(inner.rect, (100.0, 252.0, 200.0, 272.0));
+        assert!(inner.contains("123456789-00001"));
+
+        // Outer: 300x100 at (100, 500) in user space
+        let outer = &regions[1];
+        assert_eq!(outer.rect, (100.0, 192.0, 400.0, 292.0));
+        assert!(outer.contains("Statement"));
+        assert!(!outer.contains("123456789-00001"));
+    }
+
+    #[test]
+    fn test_contains_ignores_whitespace() {
+        let region = FormRegion {
+            page: 1,
+            rect: (0.0, 0.0, 1.0, 1.0),
+            text: "(555) 234-\n5678".to_string(),
+        };
+        assert!(region.contains("(555) 234-5678"));
+        assert!(!region.contains(" "));
+    }
+
+    #[test]
+    fn test_concat_applies_first_matrix_first() {
+        let scale = [2.0, 0.0, 0.0, 2.0, 0.0, 0.0];
+        let shift = [1.0, 0.0, 0.0, 1.0, 10.0, 0.0];
+        assert_eq!(apply(concat(scale, shift), 1.0, 1.0), (12.0, 2.0));
+        assert_eq!(apply(concat(shift, scale), 1.0, 1.0), (22.0, 2.0));
+    }
+}
@@ -135,6 +135,200 @@ mod argument_parsing {
     }
 }
 
+/// Tests the confirmation prompt for full-page (`.+`) redaction.
+mod full_page_confirmation {
+    use super::*;
+
+    #[test]
+    fn test_full_page_with_yes_proceeds() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Everything goes")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern")
+            .arg(".+")
+            .arg("--yes")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("Continue?").not());
+
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn test_full_page_declined_aborts() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Everything stays")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern")
+            .arg(".+")
+            .write_stdin("n\n")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("remove ALL text from 1 page(s)"))
+            .stderr(predicate::str::contains("Aborted"));
+
+        assert!(!output.exists(), "Nothing should be written when declined");
+    }
+}
+
+/// Tests redacting a directory of PDFs.
+mod batch_redaction {
+    use super::*;
+
+    #[test]
+    fn test_directory_input_redacts_each_pdf() {
+        let temp_dir = TempDir::new().unwrap();
+        let in_dir = temp_dir.path().join("bills");
+        let out_dir = temp_dir.path().join("redacted");
+        fs::create_dir(&in_dir).unwrap();
+
+        for name in ["a.pdf", "b.PDF"] {
+            TestPdfBuilder::new()
+                .with_phone("(555) 234-5678")
+                .build(&in_dir.join(name))
+                .unwrap();
+        }
+        fs::write(in_dir.join("notes.txt"), "not a pdf").unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(in_dir.as_os_str())
+            .arg("-o")
+            .arg(out_dir.as_os_str())
+            .arg("--phones")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Redacted 2 of 2 file(s)"));
+
+        assert!(out_dir.join("a.pdf").exists());
+        assert!(out_dir.join("b.PDF").exists());
+        assert!(!out_dir.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_directory_input_prints_totals() {
+        let temp_dir = TempDir::new().unwrap();
+        let in_dir = temp_dir.path().join("bills");
+        let out_dir = temp_dir.path().join("redacted");
+        fs::create_dir(&in_dir).unwrap();
+
+        for name in ["a.pdf", "b.pdf"] {
+            TestPdfBuilder::new()
+                .with_phone("(555) 234-5678")
+                .build(&in_dir.join(name))
+                .unwrap();
+        }
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(in_dir.as_os_str())
+            .arg("-o")
+            .arg(out_dir.as_os_str())
+            .arg("--phones")
+            .arg("--verbose")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Total"))
+            .stdout(predicate::str::contains("2 of 2 page(s) modified"))
+            .stdout(predicate::str::contains("phone: "));
+    }
+
+    #[test]
+    fn test_failed_file_requires_continue_on_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let in_dir = temp_dir.path().join("bills");
+        let out_dir = temp_dir.path().join("redacted");
+        fs::create_dir(&in_dir).unwrap();
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&in_dir.join("good.pdf"))
+            .unwrap();
+        fs::write(in_dir.join("bad.pdf"), b"Not a PDF file").unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(in_dir.as_os_str())
+            .arg("-o")
+            .arg(out_dir.as_os_str())
+            .arg("--phones")
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("failed"))
+            .stderr(predicate::str::contains("--continue-on-error"));
+        assert!(out_dir.join("good.pdf").exists());
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(in_dir.as_os_str())
+            .arg("-o")
+            .arg(out_dir.as_os_str())
+            .arg("--phones")
+            .arg("--continue-on-error")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Redacted 1 of 2 file(s)"));
+    }
+}
+
+/// Tests the JSON audit report.
+mod audit_report {
+    use super::*;
+
+    #[test]
+    fn test_audit_report_written_without_redacted_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+        let report = temp_dir.path().join("audit.json");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--audit-report")
+            .arg(report.as_os_str())
+            .assert()
+            .success();
+
+        let json = fs::read_to_string(&report).unwrap();
+        assert!(json.contains("\"generated_at\""));
+        assert!(json.contains("\"per_page\""));
+        assert!(json.contains("\"phone\""));
+        assert!(json.contains("out.pdf"));
+        assert!(!json.contains("234-5678"), "Redacted text must not leak");
+    }
+}
+
 /// Tests phone number redaction via CLI.
 mod phone_redaction {
     use super::*;
@@ -340,6 +534,134 @@ mod pattern_redaction {
             .assert()
             .success();
     }
+
+    #[test]
+    fn test_pattern_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+        let patterns = temp_dir.path().join("names.txt");
+
+        TestPdfBuilder::new()
+            .with_title("Pattern File")
+            .with_content("Customer: Jane Doe")
+            .with_content("Agent: John Smith")
+            .with_content("Witness: Ada Lovelace")
+            .with_content("Reviewer: Alan Turing")
+            .build(&input)
+            .unwrap();
+        fs::write(
+            &patterns,
+            "# Names to redact\nJane Doe\nJohn Smith\n\nAda Lovelace\n",
+        )
+        .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern-file")
+            .arg(patterns.as_os_str())
+            .assert()
+            .success();
+
+        let text = extract_text(&output).unwrap();
+        assert!(!text.contains("Jane Doe"));
+        assert!(!text.contains("John Smith"));
+        assert!(!text.contains("Ada Lovelace"));
+        assert!(text.contains("Alan Turing"), "Unlisted name should be kept");
+    }
+}
+
+/// Tests --config job files.
+mod config_file {
+    use super::*;
+
+    #[test]
+    fn test_toml_config_redacts_phones_and_regex() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+        let config = temp_dir.path().join("job.toml");
+
+        TestPdfBuilder::new()
+            .with_title("Config")
+            .with_content("Phone: (555) 234-5678")
+            .with_content("Ticket: INC-004211")
+            .with_content("Agent: John Smith")
+            .build(&input)
+            .unwrap();
+        fs::write(
+            &config,
+            format!(
+                "input = {:?}\noutput = {:?}\ntargets = [\"phones\", 'regex:INC-\\d{{6}}']\n",
+                input.display().to_string(),
+                output.display().to_string()
+            ),
+        )
+        .unwrap();
+
+        redactor_cmd()
+            .arg("--config")
+            .arg(config.as_os_str())
+            .assert()
+            .success();
+
+        let text = extract_text(&output).unwrap();
+        assert!(!text.contains("234-5678"));
+        assert!(!text.contains("INC-004211"));
+        assert!(text.contains("John Smith"), "Unmatched text should be kept");
+    }
+
+    #[test]
+    fn test_flags_override_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("flag-out.pdf");
+        let config = temp_dir.path().join("job.json");
+
+        TestPdfBuilder::new()
+            .with_content("Customer: Jane Doe")
+            .with_content("Agent: John Smith")
+            .build(&input)
+            .unwrap();
+        fs::write(
+            &config,
+            r#"{"output": "never-written.pdf", "targets": ["literal:Jane Doe"]}"#,
+        )
+        .unwrap();
+
+        redactor_cmd()
+            .arg("--config")
+            .arg(config.as_os_str())
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern")
+            .arg("John Smith")
+            .assert()
+            .success();
+
+        let text = extract_text(&output).unwrap();
+        assert!(!text.contains("Jane Doe"));
+        assert!(!text.contains("John Smith"));
+    }
+
+    #[test]
+    fn test_invalid_config_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = temp_dir.path().join("job.toml");
+        fs::write(&config, "targets = [\"phone\"]\n").unwrap();
+
+        redactor_cmd()
+            .arg("--config")
+            .arg(config.as_os_str())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown target 'phone'"));
+    }
 }
 
 /// Tests extract subcommand.
@@ -418,10 +740,167 @@ mod extract_command {
     }
 }
 
+/// Tests diff subcommand.
+mod diff_command {
+    use super::*;
+
+    #[test]
+    fn test_diff_shows_redacted_phone_as_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_title("Diff Test")
+            .with_content("Contact: 555-234-5678")
+            .with_content("Thank you for your business")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("--input")
+            .arg(input.as_os_str())
+            .arg("--output")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .assert()
+            .success();
+
+        redactor_cmd()
+            .arg("diff")
+            .arg("--input")
+            .arg(input.as_os_str())
+            .arg("--output")
+            .arg(output.as_os_str())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("[-555-234-5678-]"))
+            .stdout(predicate::str::contains("1 span(s) removed"))
+            .stdout(predicate::str::contains("Thank you").not());
+    }
+
+    #[test]
+    fn test_diff_of_identical_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Nothing to redact")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("diff")
+            .arg("--input")
+            .arg(input.as_os_str())
+            .arg("--output")
+            .arg(input.as_os_str())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("No text removed"));
+    }
+
+    #[test]
+    fn test_diff_missing_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+
+        TestPdfBuilder::new().build(&input).unwrap();
+
+        redactor_cmd()
+            .arg("diff")
+            .arg("--input")
+            .arg(input.as_os_str())
+            .arg("--output")
+            .arg("/nonexistent/out.pdf")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("not exist").or(predicate::str::contains("Error")));
+    }
+}
+
+/// Tests list-matchers subcommand.
+mod list_matchers_command {
+    use super::*;
+
+    #[test]
+    fn test_list_matchers() {
+        redactor_cmd()
+            .arg("list-matchers")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("PhoneNumberMatcher"))
+            .stdout(predicate::str::contains("RedactionTarget::Ssn"))
+            .stdout(predicate::str::contains("Example: 123-45-6789"))
+            .stdout(predicate::str::contains("Pattern:").not());
+    }
+
+    #[test]
+    fn test_list_matchers_verbose_shows_patterns() {
+        redactor_cmd()
+            .arg("--verbose")
+            .arg("list-matchers")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Pattern:"));
+    }
+}
+
 /// Tests error handling and edge cases.
 mod error_handling {
     use super::*;
 
+    #[test]
+    fn test_require_matches_fails_on_absent_pattern() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern")
+            .arg("NOT-IN-DOCUMENT")
+            .arg("--require-matches")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("not found"));
+
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_pattern_file_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Jane Doe")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--pattern-file")
+            .arg(temp_dir.path().join("missing.txt").as_os_str())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Failed to read pattern file"));
+
+        assert!(!output.exists());
+    }
+
     #[test]
     fn test_input_file_not_found() {
         redactor_cmd()
@@ -597,6 +1076,32 @@ mod combined_operations {
 mod output_messages {
     use super::*;
 
+    #[test]
+    fn test_no_clobber_refuses_existing_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+        std::fs::write(&output, b"previous result").unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--no-clobber")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("already exists"));
+
+        assert_eq!(std::fs::read(&output).unwrap(), b"previous result");
+    }
+
     #[test]
     fn test_success_message_format() {
         let temp_dir = TempDir::new().unwrap();
@@ -642,6 +1147,206 @@ mod output_messages {
             .success()
             .stdout(predicate::str::contains("No instances").or(predicate::str::contains("0")));
     }
+
+    #[test]
+    fn test_warns_about_pages_without_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .with_outlined_page()
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--verbose")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("no extractable text on page(s) 2"))
+            .stderr(predicate::str::contains(
+                "Page(s) 2 appear to hold text drawn as outlines",
+            ));
+
+        // The analysis behind the warning only runs when verbose
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("extractable text").not());
+    }
+
+    #[test]
+    fn test_no_warning_when_every_page_has_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .with_page("Second page")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--verbose")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("extractable text").not());
+    }
+
+    #[test]
+    fn test_format_json_prints_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        let assert = redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--format")
+            .arg("json")
+            .assert()
+            .success();
+
+        let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&stdout).expect("Stdout is JSON");
+        assert!(json["instances_redacted"].as_u64().unwrap() > 0);
+        assert_eq!(json["secure"], true);
+        assert!(json["warnings"].is_array());
+        assert!(!stdout.contains("234-5678"), "Redacted text must not leak");
+    }
+
+    #[test]
+    fn test_quiet_prints_nothing_on_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--quiet")
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+
+        assert!(output.exists());
+    }
+
+    #[test]
+    fn test_hash_prints_output_digest() {
+        use sha2::{Digest, Sha256};
+
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let output = temp_dir.path().join("out.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        let assert = redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg(output.as_os_str())
+            .arg("--phones")
+            .arg("--hash")
+            .arg("--quiet")
+            .assert()
+            .success();
+
+        let digest = format!("{:x}", Sha256::digest(std::fs::read(&output).unwrap()));
+        let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        assert_eq!(stdout, format!("{}  {}\n", digest, output.display()));
+    }
+
+    #[test]
+    fn test_output_dash_writes_pdf_to_stdout() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+        let piped = temp_dir.path().join("piped.pdf");
+
+        TestPdfBuilder::new()
+            .with_title("Stdout")
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        let assert = redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg("-")
+            .arg("--phones")
+            .arg("--verbose")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("stdout"));
+
+        let stdout = &assert.get_output().stdout;
+        assert!(stdout.starts_with(b"%PDF"), "Stdout should be the PDF");
+
+        std::fs::write(&piped, stdout).unwrap();
+        let text = extract_text(&piped).unwrap();
+        assert!(!text.contains("234-5678"), "Phone should be redacted");
+    }
+
+    #[test]
+    fn test_output_dash_rejects_audit_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("test.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)
+            .unwrap();
+
+        redactor_cmd()
+            .arg("-i")
+            .arg(input.as_os_str())
+            .arg("-o")
+            .arg("-")
+            .arg("--phones")
+            .arg("--audit-report")
+            .arg(temp_dir.path().join("audit.json").as_os_str())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--audit-report"));
+    }
 }
 
 /// Performance and stress tests.
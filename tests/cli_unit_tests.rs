@@ -6,11 +6,15 @@
 use anyhow::Result;
 use redactor::{RedactionService, RedactionTarget};
 use std::path::PathBuf;
+use std::sync::Mutex;
 use tempfile::TempDir;
 
 mod common;
 use common::*;
 
+// Global mutex to serialize MuPDF operations
+static MUPDF_LOCK: Mutex<()> = Mutex::new(());
+
 /// Tests for build_targets function behavior (mirrors main.rs logic)
 mod build_targets_tests {
     use super::*;
@@ -186,6 +190,7 @@ mod output_formatting_tests {
             pages_processed: 3,
             pages_modified: 2,
             secure: true,
+            ..Default::default()
         };
 
         let message = format_success_message(&result, "/output.pdf");
@@ -206,6 +211,7 @@ mod output_formatting_tests {
             pages_processed: 5,
             pages_modified: 3,
             secure: true,
+            ..Default::default()
         };
 
         let message = format_verbose_message(&result);
@@ -283,6 +289,7 @@ mod extract_command_tests {
             .build(&input_pdf)?;
 
         let service = RedactionService::with_secure_strategy();
+        let _guard = MUPDF_LOCK.lock().unwrap();
         let text = service.extract_text(&input_pdf)?;
 
         assert!(text.contains("Extract Test"));
@@ -298,6 +305,7 @@ mod extract_command_tests {
         TestPdfBuilder::new().with_title("").build(&input_pdf)?;
 
         let service = RedactionService::with_secure_strategy();
+        let _guard = MUPDF_LOCK.lock().unwrap();
         let text = service.extract_text(&input_pdf)?;
 
         // Should return some text (at least empty or minimal)
@@ -308,9 +316,6 @@ mod extract_command_tests {
 
 mod integration_workflow_tests {
     use super::*;
-    use std::sync::Mutex;
-
-    static MUPDF_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
     fn test_complete_redaction_workflow() -> Result<()> {
@@ -39,9 +39,21 @@ pub fn extract_text(pdf_path: &Path) -> Result<String> {
 #[derive(Debug, Clone)]
 pub struct TestPdfBuilder {
     title: String,
+    author: Option<String>,
     account_numbers: Vec<String>,
     phone_numbers: Vec<String>,
     custom_content: Vec<String>,
+    positioned_text: Vec<(String, Mm, Mm)>,
+    styled_text: Vec<(String, (u8, u8, u8), BuiltinFont)>,
+    hidden_text: Vec<String>,
+    form_fields: Vec<(String, String)>,
+    comments: Vec<String>,
+    links: Vec<String>,
+    bookmarks: Vec<String>,
+    xobject_text: Vec<String>,
+    tj_arrays: Vec<String>,
+    extra_pages: Vec<String>,
+    outlined_pages: usize,
     page_width: Mm,
     page_height: Mm,
 }
@@ -51,9 +63,21 @@ impl TestPdfBuilder {
     pub fn new() -> Self {
         Self {
             title: "Test Document".to_string(),
+            author: None,
             account_numbers: Vec::new(),
             phone_numbers: Vec::new(),
             custom_content: Vec::new(),
+            positioned_text: Vec::new(),
+            styled_text: Vec::new(),
+            hidden_text: Vec::new(),
+            form_fields: Vec::new(),
+            comments: Vec::new(),
+            links: Vec::new(),
+            bookmarks: Vec::new(),
+            xobject_text: Vec::new(),
+            tj_arrays: Vec::new(),
+            extra_pages: Vec::new(),
+            outlined_pages: 0,
             page_width: Mm(210.0),  // A4 width
             page_height: Mm(297.0), // A4 height
         }
@@ -65,6 +89,13 @@ impl TestPdfBuilder {
         self
     }
 
+    /// Sets the document author metadata.
+    #[allow(dead_code)]
+    pub fn with_author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
     /// Adds a Verizon account number to the document.
     pub fn with_verizon_account(mut self, account: &str) -> Self {
         self.account_numbers.push(account.to_string());
@@ -83,6 +114,87 @@ impl TestPdfBuilder {
         self
     }
 
+    /// Adds text at a fixed position, measured in mm from the bottom-left.
+    #[allow(dead_code)]
+    pub fn with_text_at(mut self, text: &str, x: f32, y: f32) -> Self {
+        self.positioned_text.push((text.to_string(), Mm(x), Mm(y)));
+        self
+    }
+
+    /// Adds a line of text in an RGB `color` and a built-in `font`, below
+    /// the main content, as a stamp or watermark is drawn.
+    #[allow(dead_code)]
+    pub fn with_styled_text(mut self, text: &str, color: (u8, u8, u8), font: BuiltinFont) -> Self {
+        self.styled_text.push((text.to_string(), color, font));
+        self
+    }
+
+    /// Adds text on a separate layer that is hidden by default.
+    #[allow(dead_code)]
+    pub fn with_hidden_text(mut self, text: &str) -> Self {
+        self.hidden_text.push(text.to_string());
+        self
+    }
+
+    /// Adds a filled-in text form field to the first page.
+    #[allow(dead_code)]
+    pub fn with_form_field(mut self, name: &str, value: &str) -> Self {
+        self.form_fields.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Adds a sticky-note comment containing `contents` to the first page.
+    #[allow(dead_code)]
+    pub fn with_comment(mut self, contents: &str) -> Self {
+        self.comments.push(contents.to_string());
+        self
+    }
+
+    /// Adds a link to `uri` over the top of the first page.
+    #[allow(dead_code)]
+    pub fn with_link(mut self, uri: &str) -> Self {
+        self.links.push(uri.to_string());
+        self
+    }
+
+    /// Adds a top-level bookmark titled `title` that opens the first page.
+    #[allow(dead_code)]
+    pub fn with_bookmark(mut self, title: &str) -> Self {
+        self.bookmarks.push(title.to_string());
+        self
+    }
+
+    /// Adds text drawn by a Form XObject nested inside another on the
+    /// first page, rather than by the page's own content stream.
+    #[allow(dead_code)]
+    pub fn with_xobject_text(mut self, text: &str) -> Self {
+        self.xobject_text.push(text.to_string());
+        self
+    }
+
+    /// Adds a line to the first page shown by a single `TJ` operator with
+    /// `array` as its operand, e.g. `(555)-100(234)-100(5678)`.
+    #[allow(dead_code)]
+    pub fn with_tj_array(mut self, array: &str) -> Self {
+        self.tj_arrays.push(array.to_string());
+        self
+    }
+
+    /// Appends a page containing `content` after the first page.
+    #[allow(dead_code)]
+    pub fn with_page(mut self, content: &str) -> Self {
+        self.extra_pages.push(content.to_string());
+        self
+    }
+
+    /// Appends a page with no text that draws a line of glyph outlines, as
+    /// a PDF whose text was converted to paths does.
+    #[allow(dead_code)]
+    pub fn with_outlined_page(mut self) -> Self {
+        self.outlined_pages += 1;
+        self
+    }
+
     /// Sets custom page dimensions.
     #[allow(dead_code)]
     pub fn with_dimensions(mut self, width: f32, height: f32) -> Self {
@@ -95,6 +207,10 @@ impl TestPdfBuilder {
     pub fn build(self, output_path: &Path) -> Result<PathBuf> {
         let (doc, page1, layer1) =
             PdfDocument::new(&self.title, self.page_width, self.page_height, "Layer 1");
+        let doc = match &self.author {
+            Some(author) => doc.with_author(author),
+            None => doc,
+        };
         let current_layer = doc.get_page(page1).get_layer(layer1);
 
         // Build content string
@@ -128,10 +244,59 @@ impl TestPdfBuilder {
         // Add text to PDF
         let font = doc.add_builtin_font(BuiltinFont::Helvetica)?;
         current_layer.use_text(&content, 12.0, Mm(20.0), Mm(270.0), &font);
+        for (text, x, y) in &self.positioned_text {
+            current_layer.use_text(text, 12.0, *x, *y, &font);
+        }
+        for (i, (text, (r, g, b), styled_font)) in self.styled_text.iter().enumerate() {
+            let styled_font = doc.add_builtin_font(*styled_font)?;
+            let rgb = |c: u8| f32::from(c) / 255.0;
+            current_layer.set_fill_color(Color::Rgb(Rgb::new(rgb(*r), rgb(*g), rgb(*b), None)));
+            let y = Mm(120.0 - 15.0 * i as f32);
+            current_layer.use_text(text, 24.0, Mm(20.0), y, &styled_font);
+        }
+        if !self.styled_text.is_empty() {
+            current_layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        }
+        if !self.hidden_text.is_empty() {
+            let hidden_layer = doc.get_page(page1).add_layer(HIDDEN_LAYER);
+            hidden_layer.use_text(self.hidden_text.join("\n"), 12.0, Mm(20.0), Mm(60.0), &font);
+        }
+
+        for content in &self.extra_pages {
+            let (page, layer) = doc.add_page(self.page_width, self.page_height, "Layer 1");
+            doc.get_page(page)
+                .get_layer(layer)
+                .use_text(content, 12.0, Mm(20.0), Mm(270.0), &font);
+        }
 
         // Save PDF
         doc.save(&mut BufWriter::new(fs::File::create(output_path)?))?;
 
+        if !self.hidden_text.is_empty() {
+            hide_layer(output_path, HIDDEN_LAYER)?;
+        }
+        if !self.form_fields.is_empty() {
+            add_form_fields(output_path, &self.form_fields)?;
+        }
+        if !self.comments.is_empty() {
+            add_comments(output_path, &self.comments)?;
+        }
+        if !self.links.is_empty() {
+            add_links(output_path, &self.links)?;
+        }
+        if !self.bookmarks.is_empty() {
+            add_bookmarks(output_path, &self.bookmarks)?;
+        }
+        if !self.xobject_text.is_empty() {
+            add_xobject_text(output_path, &self.xobject_text)?;
+        }
+        if !self.tj_arrays.is_empty() {
+            add_tj_arrays(output_path, &self.tj_arrays)?;
+        }
+        if self.outlined_pages > 0 {
+            add_outlined_pages(output_path, self.outlined_pages)?;
+        }
+
         Ok(output_path.to_path_buf())
     }
 }
@@ -142,6 +307,373 @@ impl Default for TestPdfBuilder {
     }
 }
 
+const HIDDEN_LAYER: &str = "Hidden";
+
+/// Turns a layer off in the document's default layer configuration.
+///
+/// printpdf always emits every layer as visible.
+fn hide_layer(path: &Path, name: &str) -> Result<()> {
+    use printpdf::lopdf::{self, Object};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let id = doc
+        .objects
+        .iter()
+        .find(|(_, obj)| {
+            obj.as_dict()
+                .and_then(|d| d.get(b"Name"))
+                .and_then(Object::as_str)
+                .is_ok_and(|n| n == name.as_bytes())
+        })
+        .map(|(id, _)| *id)
+        .ok_or_else(|| anyhow::anyhow!("Layer '{}' not found", name))?;
+
+    let config = doc
+        .catalog_mut()?
+        .get_mut(b"OCProperties")?
+        .as_dict_mut()?
+        .get_mut(b"D")?
+        .as_dict_mut()?;
+    if let Ok(on) = config.get_mut(b"ON").and_then(Object::as_array_mut) {
+        on.retain(|o| o.as_reference().ok() != Some(id));
+    }
+    config.set("OFF", vec![Object::Reference(id)]);
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Adds an AcroForm with one filled text field per `(name, value)` to the
+/// first page, each with an appearance stream showing its value.
+///
+/// printpdf cannot create form fields.
+fn add_form_fields(path: &Path, fields: &[(String, String)]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object, Stream};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+    });
+
+    let mut field_ids = Vec::new();
+    for (i, (name, value)) in fields.iter().enumerate() {
+        let bottom = 100.0 + 30.0 * i as f32;
+        let appearance = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "BBox" => vec![0.into(), 0.into(), 200.into(), 20.into()],
+                "Resources" => dictionary! {
+                    "Font" => dictionary! { "Helv" => font_id },
+                },
+            },
+            format!("/Tx BMC BT /Helv 12 Tf 2 6 Td ({}) Tj ET EMC", value).into_bytes(),
+        );
+        let appearance_id = doc.add_object(appearance);
+        field_ids.push(Object::Reference(doc.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Widget",
+            "FT" => "Tx",
+            "T" => Object::string_literal(name.as_str()),
+            "V" => Object::string_literal(value.as_str()),
+            "Rect" => vec![72.into(), bottom.into(), 272.into(), (bottom + 20.0).into()],
+            "F" => 4,
+            "P" => page_id,
+            "DA" => Object::string_literal("/Helv 12 Tf 0 g"),
+            "AP" => dictionary! { "N" => appearance_id },
+        })));
+    }
+
+    doc.get_object_mut(page_id)?
+        .as_dict_mut()?
+        .set("Annots", field_ids.clone());
+    let form_id = doc.add_object(dictionary! {
+        "Fields" => field_ids,
+        "DA" => Object::string_literal("/Helv 12 Tf 0 g"),
+        "DR" => dictionary! {
+            "Font" => dictionary! { "Helv" => font_id },
+        },
+    });
+    doc.catalog_mut()?.set("AcroForm", form_id);
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Adds a sticky-note (`/Text`) annotation per entry of `comments` to the
+/// first page, after any annotations already there.
+///
+/// printpdf cannot create annotations.
+fn add_comments(path: &Path, comments: &[String]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+
+    let mut annots = doc
+        .get_object(page_id)?
+        .as_dict()?
+        .get(b"Annots")
+        .and_then(Object::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for (i, contents) in comments.iter().enumerate() {
+        let top = 800.0 - 30.0 * i as f32;
+        annots.push(Object::Reference(doc.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Text",
+            "Rect" => vec![500.into(), (top - 20.0).into(), 520.into(), top.into()],
+            "Contents" => Object::string_literal(contents.as_str()),
+            "P" => page_id,
+        })));
+    }
+    doc.get_object_mut(page_id)?
+        .as_dict_mut()?
+        .set("Annots", annots);
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Adds a link annotation with a URI action per entry of `uris` to the
+/// first page, after any annotations already there.
+///
+/// printpdf cannot create annotations.
+fn add_links(path: &Path, uris: &[String]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+
+    let mut annots = doc
+        .get_object(page_id)?
+        .as_dict()?
+        .get(b"Annots")
+        .and_then(Object::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for (i, uri) in uris.iter().enumerate() {
+        let top = 800.0 - 20.0 * i as f32;
+        annots.push(Object::Reference(doc.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Link",
+            "Rect" => vec![56.into(), (top - 14.0).into(), 300.into(), top.into()],
+            "Border" => vec![0.into(), 0.into(), 0.into()],
+            "A" => dictionary! {
+                "S" => "URI",
+                "URI" => Object::string_literal(uri.as_str()),
+            },
+            "P" => page_id,
+        })));
+    }
+    doc.get_object_mut(page_id)?
+        .as_dict_mut()?
+        .set("Annots", annots);
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Replaces the document outline with one top-level bookmark per entry of
+/// `titles`, each opening the first page.
+///
+/// printpdf cannot create bookmarks on an existing page.
+fn add_bookmarks(path: &Path, titles: &[String]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+
+    let outlines_id = doc.new_object_id();
+    let item_ids: Vec<_> = titles.iter().map(|_| doc.new_object_id()).collect();
+    for (i, (title, id)) in titles.iter().zip(&item_ids).enumerate() {
+        let mut item = dictionary! {
+            "Title" => Object::string_literal(title.as_str()),
+            "Parent" => outlines_id,
+            "Dest" => vec![page_id.into(), "Fit".into()],
+        };
+        if i > 0 {
+            item.set("Prev", item_ids[i - 1]);
+        }
+        if let Some(next) = item_ids.get(i + 1) {
+            item.set("Next", *next);
+        }
+        doc.objects.insert(*id, Object::Dictionary(item));
+    }
+    doc.objects.insert(
+        outlines_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Outlines",
+            "First" => item_ids[0],
+            "Last" => item_ids[item_ids.len() - 1],
+            "Count" => item_ids.len() as i64,
+        }),
+    );
+    doc.catalog_mut()?.set("Outlines", outlines_id);
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Draws each line of `lines` on the first page from a Form XObject that
+/// is itself drawn by an outer Form XObject, as statement generators do.
+///
+/// printpdf cannot create form XObjects.
+fn add_xobject_text(path: &Path, lines: &[String]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Stream};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+    });
+
+    let mut text = String::from("BT /F1 12 Tf 0 -14 TL 0 100 Td");
+    for line in lines {
+        text.push_str(&format!(" ({}) '", line));
+    }
+    text.push_str(" ET");
+    let inner_id = doc.add_object(Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), 400.into(), 120.into()],
+            "Resources" => dictionary! {
+                "Font" => dictionary! { "F1" => font_id },
+            },
+        },
+        text.into_bytes(),
+    ));
+    let outer_id = doc.add_object(Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), 400.into(), 120.into()],
+            "Resources" => dictionary! {
+                "XObject" => dictionary! { "Inner" => inner_id },
+            },
+        },
+        b"/Inner Do".to_vec(),
+    ));
+
+    doc.add_xobject(page_id, "RedactorOuter", outer_id)?;
+    doc.add_page_contents(page_id, b"q 1 0 0 1 72 300 cm /RedactorOuter Do Q".to_vec())?;
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Shows each of `arrays` on its own line of the first page with `TJ`.
+///
+/// printpdf writes each string it is given as a single `Tj`.
+fn add_tj_arrays(path: &Path, arrays: &[String]) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let page_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+    });
+
+    // printpdf keeps the page's fonts in a dictionary of their own
+    let fonts_id = doc
+        .get_or_create_resources(page_id)?
+        .as_dict()?
+        .get(b"Font")
+        .and_then(Object::as_reference)?;
+    doc.get_dictionary_mut(fonts_id)?.set("RedactorTJ", font_id);
+
+    let mut text = String::from("BT /RedactorTJ 12 Tf 14 TL 72 200 Td");
+    for array in arrays {
+        text.push_str(&format!(" [{}] TJ T*", array));
+    }
+    text.push_str(" ET");
+    doc.add_page_contents(page_id, text.into_bytes())?;
+
+    doc.save(path)?;
+    Ok(())
+}
+
+/// Appends `count` pages that fill a row of curved glyph-like shapes and
+/// show no text.
+///
+/// printpdf only draws outlines from a font.
+fn add_outlined_pages(path: &Path, count: usize) -> Result<()> {
+    use printpdf::lopdf::{self, dictionary, Object, Stream};
+
+    let mut doc = lopdf::Document::load(path)?;
+    let first_id = *doc
+        .get_pages()
+        .get(&1)
+        .ok_or_else(|| anyhow::anyhow!("Document has no pages"))?;
+    let first = doc.get_dictionary(first_id)?;
+    let pages_id = first.get(b"Parent")?.as_reference()?;
+    let media_box = first.get(b"MediaBox")?.clone();
+
+    // An "o" is four curves; draw a few hundred of them along one line
+    let mut content = String::new();
+    for i in 0..80 {
+        let x = 72 + 6 * i;
+        content.push_str(&format!(
+            "{x} 700 m {x} 703 {l} 705 {c} 705 c {r} 705 {e} 703 {e} 700 c \
+             {e} 697 {r} 695 {c} 695 c {l} 695 {x} 697 {x} 700 c f\n",
+            x = x,
+            l = x + 1,
+            c = x + 2,
+            r = x + 3,
+            e = x + 4,
+        ));
+    }
+
+    for _ in 0..count {
+        let content_id = doc.add_object(Stream::new(
+            lopdf::Dictionary::new(),
+            content.clone().into_bytes(),
+        ));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => media_box.clone(),
+            "Contents" => content_id,
+        });
+        let pages = doc.get_dictionary_mut(pages_id)?;
+        pages
+            .get_mut(b"Kids")
+            .and_then(Object::as_array_mut)?
+            .push(page_id.into());
+        let total = pages.get(b"Count").and_then(Object::as_i64)? + 1;
+        pages.set("Count", total);
+    }
+
+    doc.save(path)?;
+    Ok(())
+}
+
 /// Quick helper to create a Verizon bill PDF with standard content.
 pub fn create_verizon_bill(path: &Path, account: &str, phones: &[&str]) -> Result<PathBuf> {
     let mut builder = TestPdfBuilder::new()
@@ -176,6 +708,50 @@ pub fn create_contact_list(
     builder.build(path)
 }
 
+/// Re-saves a PDF encrypted with AES-128, opened with `user_password`.
+///
+/// printpdf cannot write encrypted documents.
+#[allow(dead_code)]
+pub fn encrypt_pdf(path: &Path, user_password: &str) -> Result<()> {
+    use mupdf::pdf::{Encryption, PdfDocument as MuPdfDocument, PdfWriteOptions};
+
+    // MuPDF cannot overwrite the file it is reading from
+    let encrypted = path.with_extension("encrypted.pdf");
+    {
+        let doc = MuPdfDocument::open(path.to_str().expect("Test paths are UTF-8"))?;
+        let mut options = PdfWriteOptions::default();
+        options
+            .set_encryption(Encryption::Aes128)
+            .set_user_password(user_password)
+            .set_owner_password("owner");
+        doc.save_with_options(encrypted.to_str().expect("Test paths are UTF-8"), options)?;
+    }
+    fs::rename(&encrypted, path)?;
+    Ok(())
+}
+
+/// Marks a PDF as encrypted with the certificate (`Adobe.PubSec`) security
+/// handler, which MuPDF cannot open.
+///
+/// Only the encryption dictionary is added; the content stays readable to
+/// tools that ignore it.
+#[allow(dead_code)]
+pub fn protect_with_certificate(path: &Path) -> Result<()> {
+    use lopdf::{dictionary, Document, Object};
+
+    let mut doc = Document::load(path)?;
+    let encrypt = doc.add_object(dictionary! {
+        "Filter" => "Adobe.PubSec",
+        "SubFilter" => "adbe.pkcs7.s5",
+        "V" => 4,
+        "R" => 4,
+        "Length" => 128,
+    });
+    doc.trailer.set("Encrypt", Object::Reference(encrypt));
+    doc.save(path)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
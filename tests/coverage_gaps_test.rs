@@ -268,6 +268,7 @@ fn test_handler_verbose_output() -> Result<()> {
         pages_processed: 3,
         pages_modified: 2,
         secure: true,
+        ..Default::default()
     };
 
     // Verify all fields are accessible and formatted correctly
@@ -3,7 +3,7 @@
 //! Comprehensive tests for PDF escape sequence handling and pattern matching
 //! to ensure correct parsing of PDF text content.
 
-use redactor::domain::{PdfEscapes, PdfPatterns};
+use redactor::domain::{extract_pdf_strings, extract_tj_runs, PdfEscapes, PdfPatterns};
 
 /// Tests PDF escape sequence unescaping.
 ///
@@ -112,6 +112,25 @@ mod pdf_escapes_tests {
         assert_eq!(PdfEscapes::unescape(input), expected);
     }
 
+    #[test]
+    fn test_unescape_arbitrary_octal() {
+        assert_eq!(PdfEscapes::unescape("\\101\\102\\103"), "ABC");
+        assert_eq!(PdfEscapes::unescape("caf\\351"), "café");
+        // One- and two-digit forms end at the first non-octal character
+        assert_eq!(PdfEscapes::unescape("\\61-\\0628"), "1-28");
+    }
+
+    #[test]
+    fn test_unescape_octal_phone_number() {
+        let input = "\\050\\06555\\051\\040\\062\\063\\064-\\065\\066\\067\\070\\n";
+        assert_eq!(PdfEscapes::unescape(input), "(555) 234-5678\n");
+    }
+
+    #[test]
+    fn test_unescape_escaped_parens() {
+        assert_eq!(PdfEscapes::unescape("\\(555\\)\\t1"), "(555)\t1");
+    }
+
     /// Property-based test: unescaping should never panic
     #[test]
     fn test_unescape_never_panics() {
@@ -356,6 +375,81 @@ mod pdf_patterns_tests {
     }
 }
 
+/// Tests the balanced-parenthesis string scanner.
+mod pdf_strings_tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_keeps_nested_parens() {
+        assert_eq!(extract_pdf_strings("(a (b) c)"), vec!["a (b) c"]);
+        assert_eq!(
+            extract_pdf_strings("(outer (inner)) Tj (next) Tj"),
+            vec!["outer (inner)", "next"]
+        );
+    }
+
+    #[test]
+    fn test_extract_respects_escaped_parens() {
+        let content = r"(Phone: \(555\) 234-5678) Tj (a \) b) Tj";
+        assert_eq!(
+            extract_pdf_strings(content),
+            vec![r"Phone: \(555\) 234-5678", r"a \) b"]
+        );
+    }
+
+    #[test]
+    fn test_extract_escaped_backslash_before_paren() {
+        // A doubled backslash is an escaped backslash, so the ")" after it
+        // closes the string
+        assert_eq!(extract_pdf_strings(r"(C:\\) (x)"), vec![r"C:\\", "x"]);
+    }
+
+    #[test]
+    fn test_extract_from_tj_array() {
+        let content = "[(Account:)-250(123456789)] TJ";
+        assert_eq!(extract_pdf_strings(content), vec!["Account:", "123456789"]);
+    }
+
+    #[test]
+    fn test_extract_drops_unterminated_string() {
+        assert_eq!(extract_pdf_strings("(done) (open (x)"), vec!["done"]);
+        assert!(extract_pdf_strings("no strings").is_empty());
+    }
+}
+
+/// Tests joining the strings of TJ arrays.
+mod tj_run_tests {
+    use super::*;
+
+    #[test]
+    fn test_run_joins_kerned_strings() {
+        let runs = extract_tj_runs("BT [(555)-100(234)-100(5678)] TJ ET");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].text, "5552345678");
+        assert_eq!(runs[0].pieces, vec![0..3, 3..6, 6..10]);
+    }
+
+    #[test]
+    fn test_run_unescapes_strings() {
+        let runs = extract_tj_runs(r"[(\(555\))-50( 234-5678)] TJ");
+        assert_eq!(runs[0].text, "(555) 234-5678");
+        assert_eq!(runs[0].pieces, vec![0..5, 5..14]);
+    }
+
+    #[test]
+    fn test_pieces_of_match() {
+        let run = &extract_tj_runs("[(Tel: 555)-100(234)-100(5678 ext)] TJ")[0];
+        let start = run.text.find("5552345678").unwrap();
+        assert_eq!(run.pieces_of(start, start + 10), vec!["555", "234", "5678"]);
+        assert_eq!(run.pieces_of(0, 3), vec!["Tel"]);
+    }
+
+    #[test]
+    fn test_no_runs_without_tj() {
+        assert!(extract_tj_runs("(555) Tj (234-5678) Tj").is_empty());
+    }
+}
+
 /// Performance and edge case tests
 mod edge_cases {
     use super::*;
@@ -115,6 +115,31 @@ fn test_backend_error_display() {
     assert!(display.contains("Failed to initialize"));
 }
 
+#[test]
+fn test_timeout_error_display() {
+    let err = RedactorError::Timeout {
+        path: PathBuf::from("/test/bill.pdf"),
+        limit: std::time::Duration::from_secs(30),
+    };
+
+    let display = err.to_string();
+    assert!(display.contains("/test/bill.pdf"));
+    assert!(display.contains("30s"));
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn test_output_exists_error_display() {
+    let err = RedactorError::OutputExists {
+        path: PathBuf::from("/test/redacted.pdf"),
+    };
+
+    let display = err.to_string();
+    assert!(display.contains("/test/redacted.pdf"));
+    assert!(display.contains("already exists"));
+    assert!(err.source().is_none());
+}
+
 /// Tests error source chaining to ensure proper error context propagation.
 #[test]
 fn test_io_error_source_chain() {
@@ -178,6 +178,53 @@ mod phone_properties {
         }
     }
 
+    /// Property: Every value normalize returns normalizes to itself, and
+    /// each of its variants normalizes back to it
+    #[test]
+    fn test_normalize_round_trips() {
+        let matchers = vec![
+            PhoneNumberMatcher::new(),
+            PhoneNumberMatcher::permissive(),
+            PhoneNumberMatcher::new().with_local_numbers(true),
+            PhoneNumberMatcher::permissive().with_local_numbers(true),
+        ];
+        let inputs = vec![
+            "(555) 234-5678",
+            "5552345678",
+            "+1-555-234-5678",
+            "1 (212) 555 1234",
+            "(155) 234-5678",
+            "055.134.5678",
+            "ext 345-6789",
+            "155-1234",
+        ];
+
+        for matcher in &matchers {
+            for input in &inputs {
+                let Some(normalized) = matcher.normalize(input) else {
+                    continue;
+                };
+                assert_eq!(
+                    matcher.normalize(&normalized).as_deref(),
+                    Some(normalized.as_str()),
+                    "Normalization of {} should be idempotent",
+                    input
+                );
+                assert!(matcher.is_normalized(&normalized));
+
+                for variant in matcher.generate_variants(&normalized) {
+                    assert_eq!(
+                        matcher.normalize(&variant).as_deref(),
+                        Some(normalized.as_str()),
+                        "Variant {} of {} should normalize back",
+                        variant,
+                        input
+                    );
+                }
+            }
+        }
+    }
+
     /// Property: Extract all should return substrings of input
     #[test]
     fn test_extract_all_returns_substrings() {
@@ -5,7 +5,10 @@
 //! testing corner cases explicitly and thoroughly.
 
 use anyhow::Result;
-use redactor::{RedactionService, RedactionStrategy, RedactionTarget, SecureRedactionStrategy};
+use redactor::{
+    RedactionService, RedactionStrategy, RedactionTarget, RedactorError, SecureRedactionStrategy,
+    TextCache,
+};
 use std::path::PathBuf;
 use std::sync::Mutex;
 use tempfile::TempDir;
@@ -93,6 +96,71 @@ fn test_extract_nonexistent_file_error() {
     );
 }
 
+/// Tests that a 0-byte file is rejected with an "empty file" error.
+#[test]
+fn test_redact_zero_byte_file_error() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("empty.pdf");
+    let output = temp_dir.path().join("output.pdf");
+    std::fs::write(&input, b"")?;
+
+    let service = RedactionService::with_secure_strategy();
+    let error = service
+        .redact(&input, &output, &[RedactionTarget::PhoneNumbers])
+        .unwrap_err();
+
+    assert!(
+        matches!(error, RedactorError::InvalidInput { .. }),
+        "Expected InvalidInput, got: {:?}",
+        error
+    );
+    assert!(error.to_string().contains("empty file"), "{}", error);
+
+    Ok(())
+}
+
+/// Tests that a truncated PDF produces a distinct "possibly truncated" error
+/// from both the MuPDF and the text extraction paths.
+#[test]
+fn test_redact_truncated_file_error() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let full = temp_dir.path().join("full.pdf");
+    let input = temp_dir.path().join("truncated.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Some content")
+        .build(&full)?;
+    std::fs::write(&input, &std::fs::read(&full)?[..100])?;
+
+    let service = RedactionService::with_secure_strategy();
+
+    let error = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Literal("content".to_string())]
+    ))
+    .unwrap_err();
+    assert!(
+        !matches!(error, RedactorError::InvalidInput { .. }),
+        "Truncation should not be reported as an empty file"
+    );
+    assert!(
+        error.to_string().contains("possibly truncated"),
+        "{}",
+        error
+    );
+
+    let error = with_mupdf_lock!(service.extract_text(&input)).unwrap_err();
+    assert!(
+        error.to_string().contains("possibly truncated"),
+        "{}",
+        error
+    );
+
+    Ok(())
+}
+
 // ============================================================================
 // Empty and Minimal Content Tests
 // ============================================================================
@@ -289,6 +357,11 @@ fn test_redact_duplicate_patterns() -> Result<()> {
         .build(&input)?;
 
     let service = RedactionService::with_secure_strategy();
+    let single = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Literal("CLASSIFIED".to_string())]
+    ))?;
     let result = with_mupdf_lock!(service.redact(
         &input,
         &output,
@@ -299,8 +372,12 @@ fn test_redact_duplicate_patterns() -> Result<()> {
         ]
     ))?;
 
-    // Should handle duplicates gracefully (might redact once or multiple times)
+    // Duplicates are applied once, so the count matches a single target
     assert!(result.instances_redacted > 0, "Should redact the pattern");
+    assert_eq!(
+        result.instances_redacted, single.instances_redacted,
+        "Repeated targets should not inflate the instance count"
+    );
 
     Ok(())
 }
@@ -327,8 +404,45 @@ fn test_redact_overlapping_patterns() -> Result<()> {
         ]
     ))?;
 
-    // Should handle overlapping patterns
-    assert!(result.instances_redacted > 0);
+    // Should handle overlapping patterns, redacting the shared region once
+    assert_eq!(result.instances_redacted, 1);
+    assert_eq!(result.by_category.values().sum::<usize>(), 1);
+
+    Ok(())
+}
+
+/// Tests that `--verizon`'s phone number and call detail targets redact a
+/// call detail table's Number column once per rendered number.
+#[test]
+fn test_redact_call_detail_table_with_phones_counts_regions_once() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Date Time Number Origination Destination")
+        .with_content("01/15 10:26 PM 555-234-5678 Boston, MA Incoming, CL")
+        .with_content("01/16 11:05 PM 555-234-5678 Boston, MA Incoming, CL")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::VerizonCallDetails,
+        ]
+    ))?;
+
+    // Per row: the number, the time, the origination and the destination
+    assert_eq!(result.instances_redacted, 8);
+    assert_eq!(result.by_category.get("phone"), Some(&2));
+    assert_eq!(result.by_category.get("call_detail"), Some(&6));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("234-5678"), "Numbers should be removed");
+    assert!(!text.contains("Boston"), "Locations should be removed");
 
     Ok(())
 }
@@ -407,7 +521,7 @@ fn test_extract_text_minimal_pdf() -> Result<()> {
         .build(&input)?;
 
     let service = RedactionService::with_secure_strategy();
-    let text = service.extract_text(&input)?;
+    let text = with_mupdf_lock!(service.extract_text(&input))?;
 
     assert!(
         text.contains("Hello World"),
@@ -426,7 +540,7 @@ fn test_extract_text_empty_pdf() -> Result<()> {
     TestPdfBuilder::new().with_title("Empty").build(&input)?;
 
     let service = RedactionService::with_secure_strategy();
-    let text = service.extract_text(&input)?;
+    let text = with_mupdf_lock!(service.extract_text(&input))?;
 
     // Empty PDF might return empty string or just title - no panic is success
     let _ = text.len();
@@ -434,6 +548,140 @@ fn test_extract_text_empty_pdf() -> Result<()> {
     Ok(())
 }
 
+/// Tests that the text diff masks a phone number only in the "after" text.
+#[test]
+fn test_diff_masks_phone_in_after_text() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Call the office")
+        .with_phone("(555) 234-5678")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let diff = with_mupdf_lock!(service.diff(&input, &[RedactionTarget::PhoneNumbers]))?;
+
+    assert!(diff.before.contains("234-5678"));
+    assert!(!diff.after.contains("234-5678"));
+    assert!(diff.after.contains("Call the office"));
+    assert!(diff.has_changes());
+    assert!(!output.exists(), "Diff must not write a PDF");
+
+    Ok(())
+}
+
+/// Tests that scanning then redacting the same file extracts text only once
+/// when the text cache is enabled.
+#[test]
+fn test_text_cache_scan_then_redact_extracts_once() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_phone("(555) 234-5678")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy().with_text_cache(TextCache::new(8));
+
+    let scanned = with_mupdf_lock!(service.extract_text(&input))?;
+    assert!(scanned.contains("234-5678"), "Scan should see the phone");
+
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::VerizonCallDetails
+        ]
+    ))?;
+    assert!(
+        result.has_redactions(),
+        "Redaction should still find the phone"
+    );
+
+    let cache = service.text_cache().expect("Cache should be enabled");
+    assert_eq!(cache.misses(), 1, "Text should be extracted exactly once");
+    assert_eq!(cache.hits(), 1, "Redaction should reuse the scanned text");
+
+    Ok(())
+}
+
+/// Tests the preview -> review -> apply_hits workflow: a hit dropped during
+/// review must survive while the kept hits are removed.
+#[test]
+fn test_apply_hits_redacts_only_kept_hits() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Keep: ALPHA-SECRET")
+        .with_content("Drop: BRAVO-SECRET")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let hits = with_mupdf_lock!(service.preview(
+        &input,
+        &[
+            RedactionTarget::Literal("ALPHA-SECRET".to_string()),
+            RedactionTarget::Literal("BRAVO-SECRET".to_string()),
+        ]
+    ))?;
+    assert_eq!(hits.len(), 2, "Preview should locate both literals");
+    assert!(!output.exists(), "Preview must not write output");
+
+    // Simulate a reviewer rejecting the BRAVO hit
+    let kept: Vec<_> = hits
+        .into_iter()
+        .filter(|hit| hit.text != "BRAVO-SECRET")
+        .collect();
+
+    let result = with_mupdf_lock!(service.apply_hits(&input, &output, &kept))?;
+    assert_eq!(result.instances_redacted, 1);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("ALPHA-SECRET"), "Kept hit should be removed");
+    assert!(text.contains("BRAVO-SECRET"), "Dropped hit should survive");
+
+    Ok(())
+}
+
+/// Tests that a batch records a failing file and still redacts the rest.
+#[test]
+fn test_redact_batch_continues_past_failures() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let good = temp_dir.path().join("good.pdf");
+    let bad = temp_dir.path().join("bad.pdf");
+    let out_dir = temp_dir.path().join("redacted");
+
+    TestPdfBuilder::new()
+        .with_phone("(555) 234-5678")
+        .build(&good)?;
+    std::fs::write(&bad, b"Not a PDF file")?;
+
+    let service = RedactionService::with_secure_strategy();
+    let results = with_mupdf_lock!(service.redact_batch(
+        &[bad.clone(), good.clone()],
+        &out_dir,
+        &[RedactionTarget::PhoneNumbers]
+    ))?;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, bad);
+    assert!(results[0].1.is_err(), "Invalid PDF should be recorded");
+    assert_eq!(results[1].0, good);
+    assert!(results[1].1.as_ref().is_ok_and(|r| r.has_redactions()));
+
+    let text = extract_text(&out_dir.join("good.pdf"))?;
+    assert!(!text.contains("234-5678"));
+    assert!(!out_dir.join("bad.pdf").exists());
+
+    Ok(())
+}
+
 // ============================================================================
 // Regression Tests
 // ============================================================================
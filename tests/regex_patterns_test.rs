@@ -5,7 +5,7 @@
 //! and error handling.
 
 use anyhow::Result;
-use redactor::{RedactionService, RedactionTarget};
+use redactor::{RedactionService, RedactionTarget, RedactorError};
 use std::sync::Mutex;
 use tempfile::TempDir;
 
@@ -185,7 +185,8 @@ fn test_regex_date_redaction() -> Result<()> {
         &[RedactionTarget::Regex(r"\d{4}-\d{2}-\d{2}".to_string())]
     ))?;
 
-    // Verify redaction occurred (note: catches invalid date too)
+    // Verify redaction occurred (note: catches invalid date too; use
+    // RedactionTarget::Dates to redact only real dates)
     assert!(result.has_redactions());
     assert!(result.instances_redacted >= 3); // At least the 3 valid dates
 
@@ -473,6 +474,11 @@ fn test_regex_invalid_pattern_error() -> Result<()> {
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Invalid regex pattern") || err.to_string().contains("regex"));
+    assert!(
+        matches!(&err, RedactorError::PatternError { pattern, .. } if pattern == "[invalid(regex"),
+        "Expected PatternError naming the pattern, got: {:?}",
+        err
+    );
 
     Ok(())
 }
@@ -612,6 +618,39 @@ fn test_regex_word_boundary_patterns() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_whole_word_leaves_longer_words_intact() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input_pdf = temp_dir.path().join("input.pdf");
+
+    TestPdfBuilder::new()
+        .with_title("Word Boundaries")
+        .with_content("The secret code is: SECRET")
+        .with_content("This is not a SECRET_CODE")
+        .with_content("But this is SECRET again")
+        .build(&input_pdf)?;
+
+    let service = RedactionService::with_secure_strategy();
+    for target in [
+        RedactionTarget::RegexWholeWord(r"SECRET".to_string()),
+        RedactionTarget::LiteralWholeWord("SECRET".to_string()),
+    ] {
+        let output_pdf = temp_dir.path().join("output.pdf");
+        let result = with_mupdf_lock!(service.redact(
+            &input_pdf,
+            &output_pdf,
+            std::slice::from_ref(&target)
+        ))?;
+
+        assert_eq!(result.instances_redacted, 2, "{:?}", target);
+        let output_text = extract_text(&output_pdf)?;
+        assert!(output_text.contains("SECRET_CODE"), "{:?}", target);
+        assert!(!output_text.contains("SECRET again"), "{:?}", target);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_regex_multiline_content() -> Result<()> {
     let temp_dir = TempDir::new()?;
@@ -4,7 +4,11 @@
 //! secure redaction implementation.
 
 use anyhow::Result;
-use redactor::{RedactionService, RedactionStrategy, RedactionTarget, SecureRedactionStrategy};
+use redactor::domain::PhoneLocale;
+use redactor::{
+    MaskingRedactionStrategy, MupdfWorker, PageSelection, RedactionOptions, RedactionService,
+    RedactionStrategy, RedactionTarget, RedactorError, SecureRedactionStrategy,
+};
 use std::path::PathBuf;
 use std::sync::Mutex;
 use tempfile::TempDir;
@@ -135,6 +139,162 @@ mod error_handling {
 
         Ok(())
     }
+
+    #[test]
+    fn test_encrypted_pdf_without_password() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        with_mupdf_lock!(encrypt_pdf(&input, "secret"))?;
+
+        let service = RedactionService::with_secure_strategy();
+
+        // Both the text-extraction path and the literal-only path must
+        // report the encryption rather than a generic processing error
+        for target in [
+            RedactionTarget::PhoneNumbers,
+            RedactionTarget::Literal("234-5678".to_string()),
+        ] {
+            let result =
+                with_mupdf_lock!(service.redact(&input, &output, std::slice::from_ref(&target)));
+            assert!(
+                matches!(result, Err(RedactorError::EncryptedPdf { .. })),
+                "Expected EncryptedPdf for {:?}, got {:?}",
+                target,
+                result
+            );
+        }
+        assert!(!output.exists(), "No output should be written");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_certificate_encrypted_pdf_is_unsupported() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        protect_with_certificate(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("234-5678".to_string())]
+        ));
+        match result {
+            Err(RedactorError::UnsupportedFeature { feature }) => {
+                assert!(feature.contains("Adobe.PubSec"), "{}", feature);
+            }
+            other => panic!("Expected UnsupportedFeature, got {:?}", other),
+        }
+        assert!(!output.exists(), "No output should be written");
+
+        // Text extraction must not fall back to reading the content
+        let result = with_mupdf_lock!(service.extract_text(&input));
+        assert!(matches!(
+            result,
+            Err(RedactorError::UnsupportedFeature { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_text_encrypted_pdf() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        with_mupdf_lock!(encrypt_pdf(&input, "secret"))?;
+
+        // The fallback extractor must not mask the encryption error
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.extract_text(&input));
+        assert!(matches!(result, Err(RedactorError::EncryptedPdf { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_redact_encrypted_with_password() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        with_mupdf_lock!(encrypt_pdf(&input, "secret"))?;
+
+        let service = RedactionService::with_secure_strategy();
+        let targets = [RedactionTarget::PhoneNumbers];
+
+        let wrong = with_mupdf_lock!(service.redact_encrypted(&input, &output, &targets, "guess"));
+        assert!(matches!(wrong, Err(RedactorError::InvalidInput { .. })));
+
+        let result =
+            with_mupdf_lock!(service.redact_encrypted(&input, &output, &targets, "secret"))?;
+        assert!(result.has_redactions());
+        assert!(output.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_redact_zero_page_document() -> Result<()> {
+        use lopdf::{dictionary, Document, Object};
+
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // A structurally valid document whose page tree is empty
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.add_object(dictionary! {
+            "Type" => "Pages",
+            "Kids" => Vec::<Object>::new(),
+            "Count" => 0,
+        });
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.save(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("secret".to_string())]
+        ));
+
+        match result {
+            Err(RedactorError::PdfProcessing { message, page, .. }) => {
+                assert!(
+                    message.contains("no pages"),
+                    "Unexpected message: {}",
+                    message
+                );
+                assert_eq!(page, None);
+            }
+            other => panic!("Expected PdfProcessing, got {:?}", other),
+        }
+        assert!(!output.exists(), "No output should be written");
+
+        Ok(())
+    }
 }
 
 /// Tests boundary conditions and edge cases in redaction logic.
@@ -272,6 +432,110 @@ mod boundary_conditions {
         Ok(())
     }
 
+    #[test]
+    fn test_full_width_digits_in_extracted_text_are_detected() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Call (555) 234-5678")
+            .build(&input)?;
+
+        // Extraction from a CJK font reports full-width digits
+        let extracted = "Call （５５５） ２３４-５６７８";
+        let options = RedactionOptions {
+            text: Some(extracted),
+            ..Default::default()
+        };
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new().redact_with_options(
+            &input,
+            &output,
+            &[RedactionTarget::PhoneNumbers],
+            &options
+        ))?;
+
+        assert!(
+            result.has_redactions(),
+            "Full-width phone should be detected"
+        );
+        assert_eq!(result.by_category.get("phone"), Some(&1));
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("234-5678"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_phone_wrapped_in_bidi_marks_is_detected() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Call 555-234-5678")
+            .build(&input)?;
+
+        // Extraction from a right-to-left bill embeds the number in
+        // directional marks
+        let extracted = "Call \u{202B}\u{200E}555\u{200E}-234-5678\u{202C}";
+        let options = RedactionOptions {
+            text: Some(extracted),
+            ..Default::default()
+        };
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new().redact_with_options(
+            &input,
+            &output,
+            &[RedactionTarget::PhoneNumbers],
+            &options
+        ))?;
+
+        assert!(
+            result.has_redactions(),
+            "Bidi-embedded phone should be detected"
+        );
+        assert_eq!(result.by_category.get("phone"), Some(&1));
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("234-5678"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_uk_phone_redacted_in_uk_locale() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let default_output = temp_dir.path().join("default.pdf");
+        let uk_output = temp_dir.path().join("uk.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("London office: +44 20 7946 0958")
+            .build(&input)?;
+
+        // The NANP default does not recognize the number
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new().redact(
+            &input,
+            &default_output,
+            &[RedactionTarget::PhoneNumbers]
+        ))?;
+        assert!(!result.has_redactions());
+        assert!(extract_text(&default_output)?.contains("7946 0958"));
+
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new()
+            .with_phone_locale(PhoneLocale::Uk)
+            .redact(&input, &uk_output, &[RedactionTarget::PhoneNumbers]))?;
+        assert!(result.has_redactions(), "UK number should be redacted");
+        assert_eq!(result.by_category.get("phone"), Some(&1));
+
+        let text = extract_text(&uk_output)?;
+        assert!(!text.contains("7946 0958"));
+        assert!(text.contains("London office"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_redact_special_regex_characters() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -353,6 +617,157 @@ mod strategy_configuration {
         assert_eq!(strategy1.name(), strategy2.name());
         assert_eq!(strategy1.is_secure(), strategy2.is_secure());
     }
+
+    #[test]
+    fn test_overlay_text_replaces_redacted_text() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Account holder: Jane Roe")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new()
+            .with_fill_color(1.0, 1.0, 1.0)
+            .with_overlay_text("REDACTED".to_string());
+        let service = RedactionService::new(Box::new(strategy));
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("Jane Roe".to_string())]
+        ))?;
+
+        assert!(result.has_redactions());
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("Jane Roe"));
+        assert!(
+            text.contains("REDACTED"),
+            "Label should be drawn in the box"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_category_labels_name_each_box() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Phone: 555-234-5678")
+            .with_content("SSN: 123-45-6789")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new()
+            .with_fill_color(1.0, 1.0, 1.0)
+            .with_overlay_text("REDACTED".to_string())
+            .with_category_labels(true);
+        let targets = [RedactionTarget::PhoneNumbers, RedactionTarget::Ssn];
+
+        let hits = with_mupdf_lock!(strategy.preview(&input, &targets))?;
+        let category_of = |text: &str| {
+            hits.iter()
+                .find(|hit| hit.text.contains(text))
+                .map(|hit| hit.category.as_str())
+        };
+        assert_eq!(category_of("234-5678"), Some("phone"));
+        assert_eq!(category_of("123-45-6789"), Some("ssn"));
+
+        let service = RedactionService::new(Box::new(strategy));
+        with_mupdf_lock!(service.redact(&input, &output, &targets))?;
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("555-234-5678"));
+        assert!(!text.contains("123-45-6789"));
+        assert!(
+            text.contains("[PHONE]"),
+            "Phone box should be labeled: {}",
+            text
+        );
+        assert!(
+            text.contains("[SSN]"),
+            "SSN box should be labeled: {}",
+            text
+        );
+        assert!(
+            !text.contains("REDACTED"),
+            "Category labels replace the overlay text"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_per_target_max_hits_are_independent() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        let mut builder = TestPdfBuilder::new();
+        for _ in 0..5 {
+            builder = builder.with_content("NOISY RARE");
+        }
+        builder.build(&input)?;
+
+        let noisy = RedactionTarget::Literal("NOISY".to_string());
+        let rare = RedactionTarget::Literal("RARE".to_string());
+        let strategy = SecureRedactionStrategy::new()
+            .with_max_hits(3)
+            .with_target_max_hits(noisy.clone(), 100)
+            .with_target_max_hits(rare.clone(), 1);
+        let service = RedactionService::new(Box::new(strategy));
+
+        let result = with_mupdf_lock!(service.redact(&input, &output, &[noisy, rare]))?;
+
+        // All 5 NOISY hits under its own cap, a single RARE hit under its cap
+        assert_eq!(result.instances_redacted, 6);
+
+        let text = extract_text(&output)?;
+        assert!(
+            !text.contains("NOISY"),
+            "High-cap target should be fully redacted"
+        );
+        assert_eq!(
+            text.matches("RARE").count(),
+            4,
+            "Low-cap target stops at one hit"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hit_limit_reports_truncated_patterns() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        let mut builder = TestPdfBuilder::new().with_content("ONCE");
+        for _ in 0..3 {
+            builder = builder.with_content("OFTEN");
+        }
+        builder.build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_max_hits(2);
+        let service = RedactionService::new(Box::new(strategy));
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::Literal("ONCE".to_string()),
+                RedactionTarget::Literal("OFTEN".to_string()),
+            ]
+        ))?;
+
+        assert_eq!(result.truncated_patterns, vec!["OFTEN".to_string()]);
+        assert!(result.truncated_categories.contains("literal"));
+        assert!(!result.is_complete(), "Truncated search is not complete");
+
+        Ok(())
+    }
 }
 
 /// Tests for pattern resolution and matching logic.
@@ -494,6 +909,95 @@ mod concurrency {
             handle.join().unwrap();
         }
     }
+
+    #[test]
+    fn test_concurrent_redaction_through_worker() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let phones = [
+            "(555) 234-5678",
+            "(555) 345-6789",
+            "(555) 456-7890",
+            "(555) 567-8901",
+            "(555) 678-9012",
+            "(555) 789-0123",
+        ];
+        for (i, phone) in phones.iter().enumerate() {
+            TestPdfBuilder::new()
+                .with_phone(phone)
+                .with_content(&format!("Document {}", i))
+                .build(&temp_dir.path().join(format!("input{}.pdf", i)))?;
+        }
+
+        let service =
+            RedactionService::with_secure_strategy().with_mupdf_worker(MupdfWorker::global()?);
+
+        // The worker serializes these calls; the lock only keeps other
+        // tests in this file off MuPDF meanwhile
+        let results = with_mupdf_lock!(thread::scope(|scope| {
+            let handles: Vec<_> = (0..phones.len())
+                .map(|i| {
+                    let service = &service;
+                    let dir = temp_dir.path();
+                    scope.spawn(move || {
+                        service.redact(
+                            &dir.join(format!("input{}.pdf", i)),
+                            &dir.join(format!("output{}.pdf", i)),
+                            &[RedactionTarget::PhoneNumbers],
+                        )
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Redaction thread panicked"))
+                .collect::<Vec<_>>()
+        }));
+
+        for (i, (result, phone)) in results.into_iter().zip(phones).enumerate() {
+            assert!(result?.has_redactions(), "Document {} not redacted", i);
+            let text = extract_text(&temp_dir.path().join(format!("output{}.pdf", i)))?;
+            assert!(!text.contains(&phone[6..]), "{} survived", phone);
+            assert!(
+                text.contains(&format!("Document {}", i)),
+                "Wrong output {}",
+                i
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_batch_keeps_input_order() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let out_dir = temp_dir.path().join("redacted");
+        let inputs: Vec<PathBuf> = (0..5)
+            .map(|i| temp_dir.path().join(format!("bill{}.pdf", i)))
+            .collect();
+        for input in &inputs {
+            TestPdfBuilder::new()
+                .with_phone("(555) 234-5678")
+                .build(input)?;
+        }
+
+        let service =
+            RedactionService::with_secure_strategy().with_mupdf_worker(MupdfWorker::global()?);
+        let results = with_mupdf_lock!(service.redact_batch(
+            &inputs,
+            &out_dir,
+            &[RedactionTarget::PhoneNumbers]
+        ))?;
+
+        assert_eq!(results.len(), inputs.len());
+        for ((path, result), input) in results.iter().zip(&inputs) {
+            assert_eq!(path, input);
+            assert!(result.as_ref().is_ok_and(|r| r.has_redactions()));
+            let text = extract_text(&out_dir.join(input.file_name().unwrap()))?;
+            assert!(!text.contains("234-5678"));
+        }
+
+        Ok(())
+    }
 }
 
 /// Tests for result statistics and metadata.
@@ -545,4 +1049,2197 @@ mod result_statistics {
 
         Ok(())
     }
+
+    #[test]
+    fn test_result_reports_output_size_and_hash() -> Result<()> {
+        use sha2::{Digest, Sha256};
+
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_hash_output(true);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        assert_eq!(result.output_bytes, std::fs::metadata(&output)?.len());
+        let digest = format!("{:x}", Sha256::digest(std::fs::read(&output)?));
+        assert_eq!(result.output_sha256, Some(digest));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_reports_size_of_unchanged_copy_without_hash() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Nothing sensitive")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(!result.has_redactions());
+        assert_eq!(result.output_bytes, std::fs::metadata(&output)?.len());
+        assert_eq!(result.output_sha256, None);
+
+        Ok(())
+    }
+}
+
+/// Tests for per-page statistics on multi-page documents.
+mod per_page_statistics {
+    use super::*;
+
+    #[test]
+    fn test_per_page_stats_report_where_hits_were() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("SECRET and SECRET")
+            .with_page("Nothing sensitive here")
+            .with_page("SECRET plus TOKEN")
+            .build(&input)?;
+
+        let service = RedactionService::new(Box::new(SecureRedactionStrategy::new()));
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::Literal("SECRET".to_string()),
+                RedactionTarget::Literal("TOKEN".to_string()),
+            ]
+        ))?;
+
+        let pages: Vec<(usize, usize, usize)> = result
+            .per_page
+            .iter()
+            .map(|p| (p.page_number, p.instances, p.patterns_matched))
+            .collect();
+        assert_eq!(pages, vec![(1, 2, 1), (2, 0, 0), (3, 2, 2)]);
+
+        // Aggregates stay consistent with the breakdown
+        assert_eq!(result.pages_processed, 3);
+        assert_eq!(result.pages_modified, 2);
+        assert_eq!(result.instances_redacted, 4);
+
+        Ok(())
+    }
+}
+
+/// Tests for masking that keeps trailing characters.
+mod masking {
+    use super::*;
+
+    #[test]
+    fn test_masking_keeps_account_suffix() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_verizon_account("123456789-00001")
+            .build(&input)?;
+
+        let strategy = MaskingRedactionStrategy::new().with_keep_trailing(5);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::VerizonAccount]))?;
+
+        assert!(result.has_redactions());
+
+        let text = extract_text(&output)?;
+        assert!(
+            !text.contains("123456789"),
+            "Masked prefix should be removed"
+        );
+        assert!(text.contains("00001"), "Trailing digits should remain");
+        assert!(text.contains("*****"), "Masked prefix should be starred");
+
+        Ok(())
+    }
+}
+
+/// Tests for the non-destructive overlay strategy.
+mod overlay {
+    use super::*;
+    use redactor::{OverlayRedactionStrategy, RedactionHit};
+
+    #[test]
+    fn test_overlaid_text_is_still_extractable() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let overlaid = temp_dir.path().join("overlaid.pdf");
+        let removed = temp_dir.path().join("removed.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Account holder: Jane Roe")
+            .build(&input)?;
+        let targets = [RedactionTarget::Literal("Jane Roe".to_string())];
+
+        let service = RedactionService::new(Box::new(OverlayRedactionStrategy::new()));
+        let result = with_mupdf_lock!(service.redact(&input, &overlaid, &targets))?;
+        assert!(result.has_redactions());
+        assert!(!result.secure);
+        assert_eq!(result.by_category.get("literal"), Some(&1));
+
+        let text = extract_text(&overlaid)?;
+        assert!(
+            text.contains("Jane Roe"),
+            "Overlay should leave the text in the file"
+        );
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(&input, &removed, &targets))?;
+        assert!(result.secure);
+
+        let text = extract_text(&removed)?;
+        assert!(
+            !text.contains("Jane Roe"),
+            "Secure strategy should remove the text"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_hits_appends_boxes_to_content() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Account holder: Jane Roe")
+            .build(&input)?;
+
+        let hit = RedactionHit {
+            page: 1,
+            rect: (72.0, 100.0, 172.0, 112.0),
+            text: "Jane Roe".to_string(),
+            category: "literal".to_string(),
+            whole_word: false,
+        };
+        let result = OverlayRedactionStrategy::new().apply_hits(&input, &output, &[hit])?;
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.pages_modified, 1);
+        assert!(!result.secure);
+
+        let doc = lopdf::Document::load(&output)?;
+        let page_id = doc.get_pages()[&1];
+        let content = String::from_utf8_lossy(&doc.get_page_content(page_id)?).into_owned();
+        assert!(content.starts_with("q\n"), "Page content should be wrapped");
+        assert!(
+            content.trim_end().ends_with("re\nf\nQ"),
+            "Boxes should be drawn last: {}",
+            content
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replacement_covers_match_with_mask_characters() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let overlaid = temp_dir.path().join("overlaid.pdf");
+        let removed = temp_dir.path().join("removed.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Card on file: 4111-1111")
+            .build(&input)?;
+        let targets = [RedactionTarget::RegexWithReplacement {
+            pattern: r"\d{4}-\d{4}".to_string(),
+            replacement: '█',
+        }];
+
+        let service = RedactionService::new(Box::new(OverlayRedactionStrategy::new()));
+        let result = with_mupdf_lock!(service.redact(&input, &overlaid, &targets))?;
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.by_category.get("regex"), Some(&1));
+
+        let doc = lopdf::Document::load(&overlaid)?;
+        let content =
+            String::from_utf8_lossy(&doc.get_page_content(doc.get_pages()[&1])?).into_owned();
+        let (_, overlay) = content
+            .rsplit_once("Q\nq\n")
+            .expect("Overlay should follow the page content");
+        assert!(
+            overlay.starts_with("1 1 1 rg\n") && overlay.contains(" re\nf\n0 0 0 rg\n"),
+            "Match should be blanked before the mask is drawn: {}",
+            overlay
+        );
+        assert!(overlay.contains("<010101010101010101> Tj"), "{}", overlay);
+
+        let text = extract_text(&overlaid)?;
+        assert!(
+            text.contains("█████████"),
+            "One mask character per matched character: {}",
+            text
+        );
+        assert!(
+            text.contains("4111-1111"),
+            "Overlay should leave the text in the file"
+        );
+
+        let service = RedactionService::with_secure_strategy();
+        with_mupdf_lock!(service.redact(&input, &removed, &targets))?;
+        let text = extract_text(&removed)?;
+        assert!(!text.contains("4111-1111"));
+        assert!(
+            !text.contains('█'),
+            "Secure strategy ignores the replacement"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replacement_must_be_drawable() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new().with_content("Text").build(&input)?;
+
+        let target = RedactionTarget::RegexWithReplacement {
+            pattern: "Text".to_string(),
+            replacement: 'é',
+        };
+        let result = OverlayRedactionStrategy::new().redact(&input, &output, &[target]);
+        assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+        assert!(!output.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_hits_rejects_missing_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new().with_content("Text").build(&input)?;
+
+        let hit = RedactionHit {
+            page: 2,
+            rect: (0.0, 0.0, 10.0, 10.0),
+            text: "Text".to_string(),
+            category: String::new(),
+            whole_word: false,
+        };
+        let result = OverlayRedactionStrategy::new().apply_hits(&input, &output, &[hit]);
+        assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+        assert!(!output.exists());
+
+        Ok(())
+    }
+}
+
+/// Tests for removing document metadata.
+mod metadata {
+    use super::*;
+
+    /// Returns the output's Info dictionary entry for `key`, if any.
+    fn info_entry(path: &std::path::Path, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let doc = lopdf::Document::load(path)?;
+        let info = match doc.trailer.get(b"Info") {
+            Ok(info) => doc.dereference(info)?.1.as_dict()?.clone(),
+            Err(_) => return Ok(None),
+        };
+        Ok(info
+            .get(key)
+            .and_then(|v| v.as_str())
+            .ok()
+            .map(<[u8]>::to_vec))
+    }
+
+    fn is_empty(value: Option<Vec<u8>>) -> bool {
+        value.map_or(true, |v| v.is_empty())
+    }
+
+    #[test]
+    fn test_strip_metadata_clears_author_and_title() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_title("Monthly Statement")
+            .with_author("Jane Roe")
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        assert_eq!(info_entry(&input, b"Author")?, Some(b"Jane Roe".to_vec()));
+
+        let strategy = SecureRedactionStrategy::new().with_strip_metadata(true);
+        let service = RedactionService::new(Box::new(strategy));
+        with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(is_empty(info_entry(&output, b"Author")?));
+        assert!(is_empty(info_entry(&output, b"Title")?));
+
+        let bytes = std::fs::read(&output)?;
+        assert!(
+            !bytes.windows(8).any(|w| w == b"Jane Roe"),
+            "Metadata objects should not survive in the file"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_metadata_kept_by_default() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_author("Jane Roe")
+            .with_content("Nothing to redact")
+            .build(&input)?;
+
+        let service = RedactionService::new(Box::new(SecureRedactionStrategy::new()));
+        with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert_eq!(info_entry(&output, b"Author")?, Some(b"Jane Roe".to_vec()));
+
+        Ok(())
+    }
+}
+
+/// Tests for redacting values typed into form fields.
+mod forms {
+    use super::*;
+    use lopdf::Object;
+
+    /// Returns true if `needle` appears in any string or stream of `obj`.
+    fn object_contains(obj: &Object, needle: &[u8]) -> bool {
+        let has = |bytes: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+        match obj {
+            Object::String(bytes, _) => has(bytes),
+            Object::Array(items) => items.iter().any(|o| object_contains(o, needle)),
+            Object::Dictionary(dict) => dict.iter().any(|(_, o)| object_contains(o, needle)),
+            Object::Stream(stream) => {
+                has(&stream
+                    .decompressed_content()
+                    .unwrap_or_else(|_| stream.content.clone()))
+                    || dict_contains(&stream.dict, needle)
+            }
+            _ => false,
+        }
+    }
+
+    fn dict_contains(dict: &lopdf::Dictionary, needle: &[u8]) -> bool {
+        dict.iter().any(|(_, o)| object_contains(o, needle))
+    }
+
+    fn file_contains(path: &std::path::Path, needle: &str) -> Result<bool> {
+        let doc = lopdf::Document::load(path)?;
+        Ok(doc
+            .objects
+            .values()
+            .any(|obj| object_contains(obj, needle.as_bytes())))
+    }
+
+    #[test]
+    fn test_form_field_phone_is_removed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Service application")
+            .with_form_field("contact_phone", "555-234-5678")
+            .build(&input)?;
+        assert!(file_contains(&input, "234-5678")?);
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        assert!(result.is_complete());
+        assert!(
+            !file_contains(&output, "234-5678")?,
+            "Field value and appearance should be cleared"
+        );
+        assert!(extract_text(&output)?.contains("Service application"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_clears_fields_holding_later_values() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // The account number is only labelled on page 2, so streaming first
+        // resolves it after the fields were cleared for page 1
+        TestPdfBuilder::new()
+            .with_content("Service application")
+            .with_form_field("contact_phone", "555-234-5678")
+            .with_form_field("account", "48213977")
+            .with_page("Call 555-234-5678 about Account: 48213977")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_streaming(true);
+        let result = with_mupdf_lock!(strategy.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::RegexCaptureGroup {
+                    pattern: r"Account:\s*(\d+)".to_string(),
+                    group: 1,
+                },
+            ]
+        ))?;
+
+        assert!(result.is_complete());
+        assert!(!file_contains(&output, "234-5678")?);
+        assert!(!file_contains(&output, "48213977")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_form_field_on_unselected_page_is_kept() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // The field's widget is on page 1
+        TestPdfBuilder::new()
+            .with_content("Service application")
+            .with_form_field("contact_phone", "555-234-5678")
+            .with_page("Callback: 555-234-5678")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact_pages(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("555-234-5678".to_string())],
+            &PageSelection::parse("2")?
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1, "Only page 2 is redacted");
+        assert!(
+            file_contains(&output, "555-234-5678")?,
+            "Field on page 1 should keep its value"
+        );
+
+        Ok(())
+    }
+}
+
+/// Tests for redacting comment annotations.
+mod annotations {
+    use super::*;
+    use lopdf::Object;
+
+    /// Returns the `/Contents` of every annotation on the first page.
+    fn comment_contents(path: &std::path::Path) -> Result<Vec<String>> {
+        let doc = lopdf::Document::load(path)?;
+        let page_id = doc.page_iter().next().expect("Document has pages");
+        let annots = match doc.get_dictionary(page_id)?.get(b"Annots") {
+            Ok(annots) => doc.dereference(annots)?.1.as_array()?.clone(),
+            Err(_) => return Ok(Vec::new()),
+        };
+        let mut contents = Vec::new();
+        for annot in &annots {
+            let annot = doc.dereference(annot)?.1.as_dict()?;
+            if let Ok(Object::String(bytes, _)) = annot.get(b"Contents") {
+                contents.push(String::from_utf8_lossy(bytes).into_owned());
+            }
+        }
+        Ok(contents)
+    }
+
+    #[test]
+    fn test_comment_with_ssn_is_deleted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Claim notes")
+            .with_comment("Verify SSN 123-45-6789")
+            .with_comment("Looks fine")
+            .build(&input)?;
+        assert_eq!(comment_contents(&input)?.len(), 2);
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Ssn]))?;
+
+        assert!(result.has_redactions());
+        assert!(result.is_complete());
+        assert_eq!(comment_contents(&output)?, vec!["Looks fine".to_string()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_comments_kept_when_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Claim notes")
+            .with_comment("Verify SSN 123-45-6789")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_redact_annotations(false);
+        let service = RedactionService::new(Box::new(strategy));
+        let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Ssn]))?;
+
+        assert!(!result.has_redactions());
+        assert_eq!(
+            comment_contents(&output)?,
+            vec!["Verify SSN 123-45-6789".to_string()]
+        );
+
+        Ok(())
+    }
+}
+
+/// Tests for limiting redaction to selected pages.
+mod page_selection {
+    use super::*;
+
+    #[test]
+    fn test_unselected_pages_are_left_intact() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("SECRET on page one")
+            .with_page("SECRET on page two")
+            .with_page("SECRET on page three")
+            .build(&input)?;
+
+        let service = RedactionService::new(Box::new(SecureRedactionStrategy::new()));
+        let result = with_mupdf_lock!(service.redact_pages(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("SECRET".to_string())],
+            &PageSelection::parse("2-3")?
+        ))?;
+
+        assert_eq!(result.pages_processed, 2);
+        assert_eq!(result.instances_redacted, 2);
+        assert!(result.per_page.iter().all(|p| p.page_number != 1));
+
+        let text = extract_text(&output)?;
+        assert_eq!(text.matches("SECRET").count(), 1, "Page 1 should keep it");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_range_page_is_rejected() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new().with_content("SECRET").build(&input)?;
+
+        let service = RedactionService::new(Box::new(SecureRedactionStrategy::new()));
+        let result = with_mupdf_lock!(service.redact_pages(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("SECRET".to_string())],
+            &PageSelection::parse("1,4")?
+        ));
+
+        let err = result.expect_err("Page 4 does not exist");
+        assert!(err.to_string().contains("out of range"));
+        assert!(!output.exists());
+
+        Ok(())
+    }
+}
+
+/// Tests for the redaction time limit that finish within it; see
+/// `timeout_test.rs` for one that fires.
+mod timeout {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_redaction_within_timeout_succeeds() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Keep this")
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_timeout(Duration::from_secs(60));
+        let service = RedactionService::new(Box::new(strategy));
+        let mut phases = Vec::new();
+        let result = with_mupdf_lock!(service.redact_with_progress(
+            &input,
+            &output,
+            &[RedactionTarget::PhoneNumbers],
+            &mut |progress| phases.push(progress.phase),
+        ))?;
+
+        assert!(result.has_redactions());
+        assert!(!phases.is_empty(), "Progress should reach the caller");
+        let text = extract_text(&output)?;
+        assert!(!text.contains("234-5678"));
+        assert!(text.contains("Keep this"));
+
+        // Only the input and output, no temporary files
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 2);
+
+        Ok(())
+    }
+}
+
+/// Tests for redacting PDFs held in memory.
+mod in_memory {
+    use super::*;
+    use redactor::{RedactionResult, RedactorResult};
+    use std::path::Path;
+
+    /// Writes the input with a prefix, to show which bytes were returned.
+    struct PrefixingStrategy;
+
+    impl RedactionStrategy for PrefixingStrategy {
+        fn redact(
+            &self,
+            input: &Path,
+            output: &Path,
+            _targets: &[RedactionTarget],
+        ) -> RedactorResult<RedactionResult> {
+            let mut bytes = b"redacted:".to_vec();
+            bytes.extend(std::fs::read(input)?);
+            std::fs::write(output, bytes)?;
+            Ok(RedactionResult::none())
+        }
+
+        fn extract_text(&self, _input: &Path) -> RedactorResult<String> {
+            Ok(String::new())
+        }
+
+        fn name(&self) -> &str {
+            "Prefixing"
+        }
+
+        fn is_secure(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_redact_bytes_removes_phone() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Keep this")
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        let bytes = std::fs::read(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let redacted =
+            with_mupdf_lock!(service.redact_bytes(&bytes, &[RedactionTarget::PhoneNumbers]))?;
+        assert_ne!(redacted, bytes);
+
+        // Written out only to inspect the result
+        std::fs::write(&output, &redacted)?;
+        let text = extract_text(&output)?;
+        assert!(!text.contains("234-5678"));
+        assert!(text.contains("Keep this"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_redact_bytes_without_matches_returns_input() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Nothing sensitive")
+            .build(&input)?;
+        let bytes = std::fs::read(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let redacted =
+            with_mupdf_lock!(service.redact_bytes(&bytes, &[RedactionTarget::PhoneNumbers]))?;
+        assert_eq!(redacted, bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_redact_bytes_rejects_empty_input() {
+        let service = RedactionService::with_secure_strategy();
+        let result = service.redact_bytes(&[], &[RedactionTarget::PhoneNumbers]);
+        assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_default_redact_bytes_uses_temp_file() -> Result<()> {
+        let service = RedactionService::new(Box::new(PrefixingStrategy));
+        let redacted = service.redact_bytes(b"%PDF-1.7", &[RedactionTarget::PhoneNumbers])?;
+        assert_eq!(redacted, b"redacted:%PDF-1.7");
+        Ok(())
+    }
+}
+
+/// Tests for verifying redacted output.
+mod verification {
+    use super::*;
+    use redactor::{RedactionHit, RedactionResult, RedactorResult};
+    use std::path::Path;
+
+    /// Locates like the secure strategy but writes the input unchanged.
+    struct CopyingStrategy(SecureRedactionStrategy);
+
+    impl RedactionStrategy for CopyingStrategy {
+        fn redact(
+            &self,
+            input: &Path,
+            output: &Path,
+            _targets: &[RedactionTarget],
+        ) -> RedactorResult<RedactionResult> {
+            std::fs::copy(input, output)?;
+            Ok(RedactionResult::none())
+        }
+
+        fn preview(
+            &self,
+            input: &Path,
+            targets: &[RedactionTarget],
+        ) -> RedactorResult<Vec<RedactionHit>> {
+            self.0.preview(input, targets)
+        }
+
+        fn extract_text(&self, input: &Path) -> RedactorResult<String> {
+            self.0.extract_text(input)
+        }
+
+        fn name(&self) -> &str {
+            "Copying"
+        }
+
+        fn is_secure(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_verify_passes_when_text_is_removed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Call (555) 234-5678 today")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact_and_verify(
+            &input,
+            &output,
+            &[RedactionTarget::PhoneNumbers]
+        ))?;
+
+        assert!(result.has_redactions());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_fails_when_text_survives() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("SECRET plans")
+            .build(&input)?;
+
+        let service =
+            RedactionService::new(Box::new(CopyingStrategy(SecureRedactionStrategy::new())));
+        let result = with_mupdf_lock!(service.redact_and_verify(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("SECRET".to_string())]
+        ));
+
+        match result {
+            Err(RedactorError::VerificationFailed { pattern }) => assert_eq!(pattern, "SECRET"),
+            other => panic!("Expected VerificationFailed, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_ignores_whole_word_text_inside_longer_words() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("SECRET plans, TOPSECRET files")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact_and_verify(
+            &input,
+            &output,
+            &[RedactionTarget::LiteralWholeWord("SECRET".to_string())]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1);
+        let text = with_mupdf_lock!(service.extract_text(&output))?;
+        assert!(text.contains("TOPSECRET"));
+
+        Ok(())
+    }
+}
+
+/// Tests for progress reporting.
+mod progress {
+    use super::*;
+    use redactor::{RedactionPhase, RedactionProgress};
+
+    #[test]
+    fn test_progress_reports_each_phase_and_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("SECRET on page one")
+            .with_page("SECRET on page two")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let mut updates: Vec<RedactionProgress> = Vec::new();
+        let result = with_mupdf_lock!(service.redact_with_progress(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("SECRET".to_string())],
+            &mut |progress| updates.push(progress)
+        ))?;
+        assert_eq!(result.instances_redacted, 2);
+
+        let phases: Vec<(RedactionPhase, usize)> =
+            updates.iter().map(|p| (p.phase, p.current_page)).collect();
+        assert_eq!(
+            phases,
+            vec![
+                (RedactionPhase::Resolving, 0),
+                (RedactionPhase::Searching, 1),
+                (RedactionPhase::Applying, 1),
+                (RedactionPhase::Searching, 2),
+                (RedactionPhase::Applying, 2),
+                (RedactionPhase::Saving, 2),
+            ]
+        );
+        assert!(updates[1..].iter().all(|p| p.total_pages == 2));
+
+        Ok(())
+    }
+}
+
+/// Tests for full-page (`.+`) redaction.
+mod full_page_redaction {
+    use super::*;
+
+    #[test]
+    fn test_alltext_bottom_margin_preserves_footer() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_title("Confidential Body")
+            .with_content("Sensitive paragraph")
+            .with_text_at("BATES-000123", 20.0, 10.0)
+            .build(&input)?;
+
+        // 1 inch footer band; the footer text sits ~10mm above the bottom edge
+        let strategy = SecureRedactionStrategy::new().with_alltext_margins(0.0, 72.0);
+        let service = RedactionService::new(Box::new(strategy));
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal(".+".to_string())]
+        ))?;
+
+        assert!(result.has_redactions());
+
+        let text = extract_text(&output)?;
+        assert!(text.contains("BATES-000123"), "Footer should survive");
+        assert!(
+            !text.contains("Sensitive paragraph"),
+            "Body should be removed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alltext_in_rect_removes_only_the_band() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Body paragraph")
+            .with_text_at("BATES-000123", 20.0, 10.0)
+            .build(&input)?;
+
+        // Bottom inch of an A4 page (842pt tall), y growing downward
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::AllTextInRect {
+                page: None,
+                rect: (0.0, 770.0, 595.0, 842.0),
+            }]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.by_category.get("area"), Some(&1));
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("BATES-000123"), "Footer should be removed");
+        assert!(text.contains("Body paragraph"), "Body should survive");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alltext_in_rect_on_one_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("First page body")
+            .with_page("Second page body")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::AllTextInRect {
+                page: Some(2),
+                rect: (0.0, 0.0, 595.0, 842.0),
+            }]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.per_page[0].instances, 0);
+        assert_eq!(result.per_page[1].instances, 1);
+
+        let text = extract_text(&output)?;
+        assert!(text.contains("First page body"));
+        assert!(!text.contains("Second page body"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alltext_in_rect_rejects_empty_rect() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Body paragraph")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::AllTextInRect {
+                page: None,
+                rect: (100.0, 50.0, 100.0, 80.0),
+            }]
+        ));
+
+        assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+        assert!(!output.exists());
+
+        Ok(())
+    }
+}
+
+/// Tests for redacting text by its color or font.
+mod styled_text {
+    use super::*;
+    use printpdf::BuiltinFont;
+
+    #[test]
+    fn test_red_stamp_is_removed_and_black_text_kept() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Body paragraph")
+            .with_styled_text("CONFIDENTIAL", (255, 0, 0), BuiltinFont::Helvetica)
+            .with_styled_text("Signed copy", (0, 0, 0), BuiltinFont::Helvetica)
+            .build(&input)?;
+
+        let text = extract_text(&input)?;
+        assert!(
+            text.contains("CONFIDENTIAL"),
+            "Fixture should draw the stamp"
+        );
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::TextWithStyle {
+                color: Some((255, 0, 0)),
+                font_contains: None,
+            }]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.by_category.get("style"), Some(&1));
+        let text = extract_text(&output)?;
+        assert!(!text.contains("CONFIDENTIAL"), "Red text should be removed");
+        assert!(text.contains("Signed copy"), "Black text should survive");
+        assert!(text.contains("Body paragraph"), "Body should survive");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_in_matching_font_is_removed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Body paragraph")
+            .with_styled_text("DRAFT", (128, 128, 128), BuiltinFont::HelveticaBold)
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::TextWithStyle {
+                color: None,
+                font_contains: Some("bold".to_string()),
+            }]
+        ))?;
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("DRAFT"), "Bold text should be removed");
+        assert!(
+            text.contains("Body paragraph"),
+            "Regular text should survive"
+        );
+
+        Ok(())
+    }
+}
+
+/// Tests for text drawn inside Form XObjects.
+mod form_xobjects {
+    use super::*;
+
+    #[test]
+    fn test_text_in_nested_xobject_is_removed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Statement summary")
+            .with_xobject_text("Account: 123456789-00001")
+            .with_xobject_text("Call to (555) 234-5678")
+            .build(&input)?;
+
+        let text = extract_text(&input)?;
+        assert!(text.contains("123456789-00001"), "Fixture should draw text");
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::VerizonAccount,
+                RedactionTarget::PhoneNumbers
+            ]
+        ))?;
+
+        assert!(result.has_redactions());
+        assert!(
+            result.detected_not_redacted.is_empty(),
+            "Every value should be located: {:?}",
+            result.detected_not_redacted
+        );
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("123456789-00001"));
+        assert!(!text.contains("234-5678"));
+        assert!(text.contains("Statement summary"));
+
+        Ok(())
+    }
+}
+
+/// Tests for how output files are written.
+mod output_writing {
+    use super::*;
+
+    #[test]
+    fn test_in_place_redaction_outside_system_temp_dir() -> Result<()> {
+        // A directory that is not under the system temp dir, which may be
+        // a different mount than the output
+        let temp_dir = TempDir::new_in(env!("CARGO_TARGET_TMPDIR"))?;
+        assert_ne!(
+            temp_dir.path().parent(),
+            Some(std::env::temp_dir().as_path())
+        );
+        let path = temp_dir.path().join("statement.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Keep this")
+            .with_phone("(555) 234-5678")
+            .build(&path)?;
+
+        let service = RedactionService::new(Box::new(SecureRedactionStrategy::new()));
+        let result =
+            with_mupdf_lock!(service.redact(&path, &path, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        let text = extract_text(&path)?;
+        assert!(!text.contains("234-5678"));
+        assert!(text.contains("Keep this"));
+
+        // Only the output is left behind, no temporary files
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_temp_dir_override_is_used() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+        let missing = temp_dir.path().join("no-such-dir");
+
+        TestPdfBuilder::new()
+            .with_content("Content")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_temp_dir(missing);
+        let result = strategy.redact(&input, &output, &[RedactionTarget::PhoneNumbers]);
+
+        assert!(result.is_err(), "Writing into a missing temp dir must fail");
+        assert!(!output.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_clobber_keeps_existing_output() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        std::fs::write(&output, b"previous result")?;
+
+        let service = RedactionService::with_secure_strategy().with_no_clobber(true);
+        let result = service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]);
+
+        assert!(matches!(
+            result,
+            Err(RedactorError::OutputExists { ref path }) if path == &output
+        ));
+        assert_eq!(std::fs::read(&output)?, b"previous result");
+
+        // In-place redaction would overwrite the input
+        let result = service.redact(&input, &input, &[RedactionTarget::PhoneNumbers]);
+        assert!(matches!(result, Err(RedactorError::OutputExists { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_existing_output_is_overwritten_by_default() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        std::fs::write(&output, b"previous result")?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        assert_ne!(std::fs::read(&output)?, b"previous result");
+        assert!(!extract_text(&output)?.contains("234-5678"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_preserve_mtime_keeps_input_modification_time() -> Result<()> {
+        use filetime::FileTime;
+
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        let archived = FileTime::from_unix_time(1_262_304_000, 0);
+        filetime::set_file_mtime(&input, archived)?;
+        let mtime = |path: &std::path::Path| -> Result<i64> {
+            let metadata = std::fs::metadata(path)?;
+            Ok(FileTime::from_last_modification_time(&metadata).unix_seconds())
+        };
+
+        let service = RedactionService::with_secure_strategy().with_preserve_mtime(true);
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+        assert!(result.has_redactions());
+        assert!((mtime(&output)? - archived.unix_seconds()).abs() <= 1);
+
+        // Redacting in place keeps the time the input had beforehand
+        with_mupdf_lock!(service.redact(&input, &input, &[RedactionTarget::PhoneNumbers]))?;
+        assert!((mtime(&input)? - archived.unix_seconds()).abs() <= 1);
+
+        // Without the option the output is as new as any other write
+        let fresh = temp_dir.path().join("fresh.pdf");
+        with_mupdf_lock!(RedactionService::with_secure_strategy().redact(
+            &output,
+            &fresh,
+            &[RedactionTarget::PhoneNumbers]
+        ))?;
+        assert!(mtime(&fresh)? > archived.unix_seconds() + 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_incremental_save_appends_a_revision() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let rewritten = temp_dir.path().join("rewritten.pdf");
+        let appended = temp_dir.path().join("appended.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Keep this")
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        let original = std::fs::read(&input)?;
+        let revisions = |bytes: &[u8]| bytes.windows(5).filter(|w| w == b"%%EOF").count();
+
+        // Secure mode rewrites the whole file by default
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new().redact(
+            &input,
+            &rewritten,
+            &[RedactionTarget::PhoneNumbers]
+        ))?;
+        assert!(result.has_redactions());
+        let bytes = std::fs::read(&rewritten)?;
+        assert!(!bytes.starts_with(&original));
+        assert_eq!(revisions(&bytes), 1);
+
+        let strategy = SecureRedactionStrategy::new().with_incremental_save(true);
+        let result =
+            with_mupdf_lock!(strategy.redact(&input, &appended, &[RedactionTarget::PhoneNumbers]))?;
+        assert!(result.has_redactions());
+        let bytes = std::fs::read(&appended)?;
+        assert!(bytes.len() > original.len());
+        assert!(bytes.starts_with(&original));
+        assert_eq!(revisions(&bytes), revisions(&original) + 1);
+        // The current revision is redacted, the original still in the file
+        let text = extract_text(&appended)?;
+        assert!(!text.contains("234-5678"));
+        assert!(text.contains("Keep this"));
+
+        Ok(())
+    }
+}
+
+/// Tests for optional content groups (layers).
+mod hidden_layers {
+    use super::*;
+
+    #[test]
+    fn test_hidden_layer_phone_redacted_when_enabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Visible content")
+            .with_hidden_text("Hidden line: (555) 234-5678")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let layers = service.list_layers(&input)?;
+        assert!(
+            layers.iter().any(|l| l.name == "Hidden" && !l.visible),
+            "Fixture should contain a hidden layer: {:?}",
+            layers
+        );
+
+        let strategy = SecureRedactionStrategy::new().with_include_hidden_layers(true);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+
+        let text = extract_text(&output)?;
+        assert!(!text.contains("234-5678"), "Hidden phone should be removed");
+        assert!(text.contains("Visible content"));
+
+        let layers = service.list_layers(&output)?;
+        assert!(
+            layers.iter().any(|l| l.name == "Hidden" && !l.visible),
+            "Original layer visibility should be kept"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hidden_layer_phone_redacted_by_default() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Visible content")
+            .with_hidden_text("Hidden line: (555) 234-5678")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        assert!(result.is_complete());
+        assert!(!extract_text(&output)?.contains("234-5678"));
+
+        Ok(())
+    }
+
+    /// Without hidden-layer processing, text extraction still sees the
+    /// hidden phone but MuPDF search does not, so it must be reported.
+    #[test]
+    fn test_hidden_layer_phone_reported_when_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 111-2222")
+            .with_hidden_text("Hidden line: (555) 234-5678")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_include_hidden_layers(false);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions(), "Visible phone should be redacted");
+        assert!(!result.is_complete());
+        assert_eq!(result.detected_not_redacted, vec!["(555) 234-5678"]);
+
+        Ok(())
+    }
+}
+
+/// Tests for resolving targets one page at a time.
+mod streaming {
+    use super::*;
+
+    /// Builds a document with a distinct phone number on each of `pages`
+    /// pages.
+    fn many_pages(path: &std::path::Path, pages: usize) -> Result<()> {
+        let mut builder = TestPdfBuilder::new().with_content("Call (555) 200-0001");
+        for page in 2..=pages {
+            builder = builder.with_page(&format!("Call (555) 200-{:04}", page));
+        }
+        builder.build(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_matches_whole_document_resolution() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        many_pages(&input, 60)?;
+
+        let targets = [RedactionTarget::PhoneNumbers];
+        let mut results = Vec::new();
+        for streaming in [false, true] {
+            let output = temp_dir.path().join(format!("streaming-{}.pdf", streaming));
+            let strategy = SecureRedactionStrategy::new().with_streaming(streaming);
+            let result = with_mupdf_lock!(strategy.redact(&input, &output, &targets))?;
+
+            let text = extract_text(&output)?;
+            for page in 1..=60 {
+                assert!(
+                    !text.contains(&format!("200-{:04}", page)),
+                    "Page {} kept its phone number (streaming: {})",
+                    page,
+                    streaming
+                );
+            }
+            results.push(result);
+        }
+
+        let (whole, streamed) = (&results[0], &results[1]);
+        assert_eq!(streamed.instances_redacted, whole.instances_redacted);
+        assert_eq!(streamed.pages_processed, 60);
+        assert_eq!(streamed.pages_modified, 60);
+        assert_eq!(streamed.per_page.len(), 60);
+        assert!(streamed.per_page.iter().all(|page| page.instances >= 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_searches_earlier_pages_for_later_values() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // The number only has its label on the last page
+        TestPdfBuilder::new()
+            .with_content("Reference 48213977")
+            .with_page("Nothing sensitive here")
+            .with_page("Account: 48213977")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_streaming(true);
+        let result = with_mupdf_lock!(strategy.redact(
+            &input,
+            &output,
+            &[RedactionTarget::RegexCaptureGroup {
+                pattern: r"Account:\s*(\d+)".to_string(),
+                group: 1,
+            }]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 2);
+        assert_eq!(result.pages_modified, 2);
+        let pages: Vec<(usize, usize)> = result
+            .per_page
+            .iter()
+            .map(|p| (p.page_number, p.instances))
+            .collect();
+        assert_eq!(pages, vec![(1, 1), (2, 0), (3, 1)]);
+        assert!(!extract_text(&output)?.contains("48213977"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_research_leaves_areas_alone() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Reference 48213977")
+            .with_page("Account: 48213977")
+            .build(&input)?;
+
+        // Page 1 is searched again for the number found on page 2, which
+        // must not redact or count its area a second time
+        let targets = [
+            RedactionTarget::AllTextInRect {
+                page: Some(1),
+                rect: (0.0, 0.0, 612.0, 36.0),
+            },
+            RedactionTarget::RegexCaptureGroup {
+                pattern: r"Account:\s*(\d+)".to_string(),
+                group: 1,
+            },
+        ];
+        let mut results = Vec::new();
+        for streaming in [false, true] {
+            let output = temp_dir.path().join(format!("streaming-{}.pdf", streaming));
+            let strategy = SecureRedactionStrategy::new().with_streaming(streaming);
+            results.push(with_mupdf_lock!(strategy.redact(&input, &output, &targets))?);
+        }
+
+        let (whole, streamed) = (&results[0], &results[1]);
+        assert_eq!(streamed.instances_redacted, whole.instances_redacted);
+        assert_eq!(streamed.by_category, whole.by_category);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_redacts_phone_split_across_tj_strings() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Statement")
+            .with_tj_array("(Call )-100(555)-100(234)-100(5678)")
+            .with_page("Nothing sensitive here")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_streaming(true);
+        let result =
+            with_mupdf_lock!(strategy.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        let text = extract_text(&output)?;
+        for piece in ["234", "5678"] {
+            assert!(!text.contains(piece), "'{}' survived in: {}", piece, text);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_account_on_one_page_only() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // The second page has no account number to resolve
+        TestPdfBuilder::new()
+            .with_verizon_account("123456789-00001")
+            .with_page("Nothing sensitive here")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_streaming(true);
+        let result =
+            with_mupdf_lock!(strategy.redact(&input, &output, &[RedactionTarget::VerizonAccount]))?;
+
+        assert!(result.instances_redacted >= 1);
+        assert_eq!(result.pages_modified, 1);
+        assert!(!extract_text(&output)?.contains("123456789"));
+
+        Ok(())
+    }
+}
+
+/// Tests for failing when a target matches nothing.
+mod require_matches {
+    use super::*;
+
+    #[test]
+    fn test_present_pattern_is_redacted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .with_content("Reference SECRET")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy().with_require_matches(true);
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::Literal("SECRET".to_string()),
+            ]
+        ))?;
+
+        assert!(result.instances_redacted >= 2);
+        assert!(!extract_text(&output)?.contains("SECRET"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_absent_pattern_fails_without_writing() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy().with_require_matches(true);
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[
+                RedactionTarget::PhoneNumbers,
+                RedactionTarget::Literal("SECRET".to_string()),
+            ]
+        ));
+
+        match result {
+            Err(RedactorError::PatternNotFound { pattern, .. }) => {
+                let target = RedactionTarget::Literal("SECRET".to_string());
+                assert_eq!(pattern, target.to_string());
+            }
+            other => panic!("Expected PatternNotFound, got {:?}", other),
+        }
+        assert!(!output.exists(), "No pass-through copy should be written");
+
+        // Without the option the same run copies the document
+        let result = with_mupdf_lock!(RedactionService::with_secure_strategy().redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("SECRET".to_string())]
+        ))?;
+        assert!(!result.has_redactions());
+        assert!(output.exists());
+
+        Ok(())
+    }
+}
+
+/// Tests for fill colors on pages that are not composited in RGB.
+mod color_spaces {
+    use super::*;
+    use lopdf::{dictionary, Document, Object};
+
+    /// Gives every page of `path` a transparency group in `space`, as
+    /// print-ready gray and CMYK documents have.
+    fn set_group_color_space(path: &std::path::Path, space: Object) -> Result<()> {
+        let mut doc = Document::load(path)?;
+        for page_id in doc.get_pages().into_values() {
+            doc.get_dictionary_mut(page_id)?.set(
+                "Group",
+                dictionary! {
+                    "Type" => "Group",
+                    "S" => "Transparency",
+                    "CS" => space.clone(),
+                },
+            );
+        }
+        doc.save(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_color_on_grayscale_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("(555) 234-5678")
+            .build(&input)?;
+        set_group_color_space(&input, Object::Name(b"DeviceGray".to_vec()))?;
+
+        let strategy = SecureRedactionStrategy::new().with_fill_color(1.0, 0.0, 0.0);
+        let result =
+            with_mupdf_lock!(strategy.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        assert!(!extract_text(&output)?.contains("234-5678"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_color_on_cmyk_and_icc_pages() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let strategy = SecureRedactionStrategy::new()
+            .with_fill_color(0.5, 0.5, 0.5)
+            .with_overlay_text("REDACTED".to_string());
+
+        let icc = Object::Array(vec![
+            Object::Name(b"ICCBased".to_vec()),
+            Object::Reference((9999, 0)),
+        ]);
+        for (name, space) in [("cmyk", Object::Name(b"DeviceCMYK".to_vec())), ("icc", icc)] {
+            let input = temp_dir.path().join(format!("{}.pdf", name));
+            let output = temp_dir.path().join(format!("{}-out.pdf", name));
+            TestPdfBuilder::new()
+                .with_phone("(555) 234-5678")
+                .build(&input)?;
+            set_group_color_space(&input, space)?;
+
+            let result = with_mupdf_lock!(strategy.redact(
+                &input,
+                &output,
+                &[RedactionTarget::PhoneNumbers]
+            ))?;
+
+            assert!(result.has_redactions(), "{}", name);
+            assert!(!extract_text(&output)?.contains("234-5678"), "{}", name);
+        }
+
+        Ok(())
+    }
+}
+
+/// Tests for deleting hyperlinks, whose URIs are not page content.
+mod links {
+    use super::*;
+    use lopdf::{Document, Object};
+
+    /// Returns the URI of every link on the first page.
+    fn link_uris(path: &std::path::Path) -> Result<Vec<String>> {
+        let doc = Document::load(path)?;
+        let page_id = doc.page_iter().next().expect("Document has pages");
+        let annots = match doc.get_dictionary(page_id)?.get(b"Annots") {
+            Ok(annots) => doc.dereference(annots)?.1.as_array()?.clone(),
+            Err(_) => return Ok(Vec::new()),
+        };
+        let mut uris = Vec::new();
+        for annot in &annots {
+            let annot = doc.dereference(annot)?.1.as_dict()?;
+            let Ok(action) = annot.get(b"A") else {
+                continue;
+            };
+            if let Ok(Object::String(bytes, _)) = doc.dereference(action)?.1.as_dict()?.get(b"URI")
+            {
+                uris.push(String::from_utf8_lossy(bytes).into_owned());
+            }
+        }
+        Ok(uris)
+    }
+
+    /// Returns true if any string object in the file contains `needle`,
+    /// whether or not it is still reachable from a page.
+    fn file_contains_string(path: &std::path::Path, needle: &str) -> Result<bool> {
+        fn contains(obj: &Object, needle: &[u8]) -> bool {
+            match obj {
+                Object::String(bytes, _) => bytes.windows(needle.len()).any(|w| w == needle),
+                Object::Array(items) => items.iter().any(|item| contains(item, needle)),
+                Object::Dictionary(dict) => dict.iter().any(|(_, value)| contains(value, needle)),
+                Object::Stream(stream) => {
+                    stream.dict.iter().any(|(_, value)| contains(value, needle))
+                }
+                _ => false,
+            }
+        }
+
+        let doc = Document::load(path)?;
+        Ok(doc
+            .objects
+            .values()
+            .any(|obj| contains(obj, needle.as_bytes())))
+    }
+
+    #[test]
+    fn test_link_with_matching_uri_is_deleted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("See the team wiki")
+            .with_link("https://internal.example.com/wiki")
+            .with_link("https://example.org/help")
+            .build(&input)?;
+        assert_eq!(link_uris(&input)?.len(), 2);
+
+        let service = RedactionService::with_secure_strategy();
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("internal.example.com".to_string())]
+        ))?;
+
+        assert_eq!(result.instances_redacted, 1);
+        assert_eq!(result.by_category.get("literal"), Some(&1));
+        assert_eq!(
+            link_uris(&output)?,
+            vec!["https://example.org/help".to_string()]
+        );
+        assert!(!file_contains_string(&output, "internal.example.com")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_links_deletes_every_link() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("See the team wiki")
+            .with_link("https://internal.example.com")
+            .with_link("https://example.org/help")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::AllLinks]))?;
+
+        assert_eq!(result.instances_redacted, 2);
+        assert_eq!(result.by_category.get("link"), Some(&2));
+        assert!(link_uris(&output)?.is_empty());
+        assert!(!file_contains_string(&output, "internal.example.com")?);
+        assert!(
+            extract_text(&output)?.contains("See the team wiki"),
+            "Page text should be kept"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_links_kept_when_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("See the team wiki")
+            .with_link("https://internal.example.com")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_redact_annotations(false);
+        let service = RedactionService::new(Box::new(strategy));
+        let result = with_mupdf_lock!(service.redact(
+            &input,
+            &output,
+            &[RedactionTarget::Literal("internal.example.com".to_string())]
+        ))?;
+
+        assert!(!result.has_redactions());
+        assert_eq!(
+            link_uris(&output)?,
+            vec!["https://internal.example.com".to_string()]
+        );
+
+        Ok(())
+    }
+}
+
+/// Tests for scrubbing bookmark titles, which are not page content.
+mod outline {
+    use super::*;
+    use lopdf::{Document, Object};
+
+    /// Returns the title of every top-level bookmark, decoding UTF-16.
+    fn bookmark_titles(path: &std::path::Path) -> Result<Vec<String>> {
+        let doc = Document::load(path)?;
+        let outlines = doc.catalog()?.get(b"Outlines")?;
+        let mut next = doc.dereference(outlines)?.1.as_dict()?.get(b"First").ok();
+        let mut titles = Vec::new();
+        while let Some(item) = next {
+            let item = doc.dereference(item)?.1.as_dict()?;
+            if let Ok(Object::String(bytes, _)) = item.get(b"Title") {
+                titles.push(match bytes.strip_prefix(&[0xFE, 0xFF]) {
+                    Some(utf16) => String::from_utf16_lossy(
+                        &utf16
+                            .chunks_exact(2)
+                            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                            .collect::<Vec<_>>(),
+                    ),
+                    None => String::from_utf8_lossy(bytes).into_owned(),
+                });
+            }
+            next = item.get(b"Next").ok();
+        }
+        Ok(titles)
+    }
+
+    #[test]
+    fn test_bookmark_title_is_scrubbed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Statement summary")
+            .with_bookmark("Account 123456789-00001")
+            .with_bookmark("Charges")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::VerizonAccount]))?;
+
+        assert!(result.has_redactions());
+        assert!(result.is_complete());
+        let titles = bookmark_titles(&output)?;
+        assert_eq!(titles, vec!["Account [REDACTED]", "Charges"]);
+        assert!(titles.iter().all(|title| !title.contains("123456789")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bookmarks_kept_when_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Account 123456789-00001")
+            .with_bookmark("Account 123456789-00001")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_redact_outline(false);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::VerizonAccount]))?;
+
+        assert!(result.has_redactions());
+        assert!(!extract_text(&output)?.contains("123456789"));
+        assert_eq!(bookmark_titles(&output)?, vec!["Account 123456789-00001"]);
+
+        Ok(())
+    }
+}
+
+/// Tests for flattening redacted pages to images.
+mod rasterize {
+    use super::*;
+
+    #[test]
+    fn test_rasterized_page_has_no_text() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Quarterly statement")
+            .with_phone("555-234-5678")
+            .with_page("Appendix notes")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_rasterize(72);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        let text = extract_text(&output)?;
+        assert!(!text.contains("555-234-5678"));
+        assert!(
+            !text.contains("Quarterly"),
+            "Rasterized page should have no text: {}",
+            text
+        );
+        // Pages without redactions are left as they were
+        assert!(text.contains("Appendix notes"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_dpi_is_rejected() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_rasterize(0);
+        let service = RedactionService::new(Box::new(strategy));
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]));
+
+        assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+
+        Ok(())
+    }
+}
+
+/// Tests removal of pages that redaction leaves empty.
+mod empty_pages {
+    use super::*;
+
+    fn page_count(path: &std::path::Path) -> Result<usize> {
+        Ok(lopdf::Document::load(path)?.get_pages().len())
+    }
+
+    #[test]
+    fn test_full_page_redaction_drops_emptied_pages() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let kept = temp_dir.path().join("kept.pdf");
+        let dropped = temp_dir.path().join("dropped.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Confidential memo")
+            .with_page("Appendix")
+            .build(&input)?;
+        let targets = [RedactionTarget::Literal(".+".to_string())];
+
+        // Pages are kept by default
+        let result =
+            with_mupdf_lock!(SecureRedactionStrategy::new().redact(&input, &kept, &targets))?;
+        assert_eq!(result.pages_modified, 2);
+        assert_eq!(result.pages_removed, 0);
+        assert_eq!(page_count(&kept)?, 2);
+
+        // Both pages are emptied, and the first is kept so the PDF still
+        // has a page
+        let strategy = SecureRedactionStrategy::new().with_remove_emptied_pages(true);
+        let result = with_mupdf_lock!(strategy.redact(&input, &dropped, &targets))?;
+        assert_eq!(result.pages_modified, 1);
+        assert_eq!(result.pages_removed, 1);
+        assert_eq!(page_count(&dropped)?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_already_blank_pages_are_kept() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Keep this")
+            .with_page("555-234-5678")
+            .with_page("")
+            .build(&input)?;
+
+        let strategy = SecureRedactionStrategy::new().with_remove_emptied_pages(true);
+        let result =
+            with_mupdf_lock!(strategy.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        // Only the page that held nothing but the phone number goes
+        assert_eq!(result.pages_removed, 1);
+        assert_eq!(page_count(&output)?, 2);
+        let text = extract_text(&output)?;
+        assert!(text.contains("Keep this"));
+        assert!(!text.contains("234-5678"));
+
+        Ok(())
+    }
+}
+
+/// Tests text split across the strings of a TJ array.
+mod split_text {
+    use super::*;
+
+    #[test]
+    fn test_phone_split_across_tj_strings_is_redacted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        TestPdfBuilder::new()
+            .with_content("Statement")
+            .with_tj_array("(Call )-100(555)-100(234)-100(5678)")
+            .with_tj_array("(Fax )-400(555)-400(876)-400(5432)")
+            .build(&input)?;
+
+        let service = RedactionService::with_secure_strategy();
+        let result =
+            with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]))?;
+
+        assert!(result.has_redactions());
+        let text = extract_text(&output)?;
+        for piece in ["234", "5678", "876", "5432"] {
+            assert!(!text.contains(piece), "'{}' survived in: {}", piece, text);
+        }
+        assert!(text.contains("Statement"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_account_hyphenated_across_lines_is_redacted() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+        let output = temp_dir.path().join("output.pdf");
+
+        // A narrow column breaks the number with a hyphen
+        TestPdfBuilder::new()
+            .with_content("Statement for account 123456-")
+            .with_content("789-00001 due today")
+            .build(&input)?;
+
+        let result = with_mupdf_lock!(SecureRedactionStrategy::new().redact(
+            &input,
+            &output,
+            &[RedactionTarget::VerizonAccount]
+        ))?;
+
+        assert!(
+            result.has_redactions(),
+            "Account broken across lines should be detected"
+        );
+        let text = extract_text(&output)?;
+        assert!(!text.contains("123456"), "survived in: {}", text);
+        assert!(!text.contains("00001"), "survived in: {}", text);
+        assert!(text.contains("due today"));
+
+        Ok(())
+    }
+}
+
+mod analysis {
+    use super::*;
+
+    #[test]
+    fn test_analyze_flags_outlined_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input.pdf");
+
+        TestPdfBuilder::new()
+            .with_phone("555-234-5678")
+            .with_outlined_page()
+            .build(&input)?;
+
+        let analysis = RedactionService::with_secure_strategy().analyze(&input)?;
+
+        assert_eq!(analysis.page_count, 2);
+        assert_eq!(analysis.text_pages, vec![1]);
+        assert!(analysis.image_pages.is_empty());
+        assert_eq!(analysis.vector_text_suspected, vec![2]);
+        assert_eq!(analysis.pages_without_text(), vec![2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_missing_input_fails() {
+        let result =
+            RedactionService::with_secure_strategy().analyze(&PathBuf::from("missing.pdf"));
+        assert!(matches!(result, Err(RedactorError::Io { .. })));
+    }
 }
@@ -0,0 +1,981 @@
+//! End-to-end tests for redaction target variants.
+//!
+//! Each test builds a PDF containing the sensitive value, redacts it with the
+//! target under test, and verifies the value can no longer be extracted.
+
+use anyhow::Result;
+use redactor::domain::{ChecksumAlgorithm, PatternMatcher};
+use redactor::{RedactionService, RedactionTarget, RedactorError};
+use std::sync::Mutex;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+// Global mutex to serialize MuPDF operations
+static MUPDF_LOCK: Mutex<()> = Mutex::new(());
+
+macro_rules! with_mupdf_lock {
+    ($body:expr) => {{
+        let _guard = MUPDF_LOCK.lock().expect("MuPDF lock poisoned");
+        $body
+    }};
+}
+
+// ============================================================================
+// Entity List Tests
+// ============================================================================
+
+/// Tests that an entity file redacts a name verbatim and a phone number in
+/// a different rendering than the one listed.
+#[test]
+fn test_entity_list_redacts_name_and_phone_variants() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+    let entities = temp_dir.path().join("entities.tsv");
+
+    TestPdfBuilder::new()
+        .with_content("Customer: Jane Roe")
+        .with_phone("(555) 234-5678")
+        .build(&input)?;
+    std::fs::write(&entities, "Jane Roe\tPERSON\n555-234-5678\tPHONE\n")?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::EntityList { path: entities }]
+    ))?;
+
+    assert!(
+        result.instances_redacted >= 2,
+        "Both entities should be redacted"
+    );
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("Jane Roe"), "Name should be removed");
+    assert!(!text.contains("234-5678"), "Phone should be removed");
+    assert!(text.contains("Customer"), "Surrounding text should remain");
+
+    Ok(())
+}
+
+/// Tests that a missing entity file is reported with its path.
+#[test]
+fn test_entity_list_missing_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Content")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::EntityList {
+            path: temp_dir.path().join("missing.tsv"),
+        }],
+    );
+
+    let err = result.expect_err("Missing entity file should fail");
+    assert!(err.to_string().contains("missing.tsv"));
+
+    Ok(())
+}
+
+// ============================================================================
+// Named Entity Tests
+// ============================================================================
+
+/// Tests that a person's full name also redacts the surname-first and
+/// initial renderings, without touching words that contain the surname.
+#[test]
+fn test_named_entity_redacts_name_variants() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Plaintiff: John Doe")
+        .with_content("Deponent: Doe, John")
+        .with_content("Signed: J. Doe")
+        .with_content("Counsel: Doerr LLP")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::NamedEntity(vec!["John Doe".to_string()])]
+    ))?;
+
+    assert!(result.instances_redacted >= 3);
+    assert!(result.by_category.contains_key("name"));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("John Doe"), "Full name should be removed");
+    assert!(
+        !text.contains("Doe, John"),
+        "Inverted name should be removed"
+    );
+    assert!(!text.contains("J. Doe"), "Initial form should be removed");
+    assert!(text.contains("Doerr"), "Longer words should remain");
+    assert!(text.contains("Plaintiff"), "Surrounding text should remain");
+
+    Ok(())
+}
+
+// ============================================================================
+// Case-Insensitive Literal Tests
+// ============================================================================
+
+/// Tests that every casing of a literal is removed.
+#[test]
+fn test_literal_case_insensitive_redacts_all_casings() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Confidential memo")
+        .with_content("CONFIDENTIAL appendix")
+        .with_content("marked confidential")
+        .with_content("cOnFiDeNtIaL draft")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::LiteralCaseInsensitive(
+            "confidential".to_string()
+        )]
+    ))?;
+
+    assert!(result.instances_redacted >= 4);
+
+    let text = extract_text(&output)?.to_lowercase();
+    assert!(
+        !text.contains("confidential"),
+        "Every casing should be removed"
+    );
+    assert!(text.contains("memo") && text.contains("draft"));
+
+    Ok(())
+}
+
+// ============================================================================
+// Timestamp Tests
+// ============================================================================
+
+/// Tests that ISO-8601 and syslog timestamps are redacted while the log
+/// message around them is kept.
+#[test]
+fn test_timestamp_redacts_iso_and_syslog() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("2025-01-15T14:30:00Z login accepted")
+        .with_content("Jan 15 14:31:07 host sshd: session opened")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Timestamp]))?;
+
+    assert!(result.instances_redacted >= 2);
+
+    let text = extract_text(&output)?;
+    assert!(
+        !text.contains("14:30:00"),
+        "ISO timestamp should be removed"
+    );
+    assert!(
+        !text.contains("14:31:07"),
+        "Syslog timestamp should be removed"
+    );
+    assert!(text.contains("login accepted"));
+
+    Ok(())
+}
+
+// ============================================================================
+// Date Tests
+// ============================================================================
+
+/// Tests that ISO, US and textual dates are redacted while an impossible
+/// date that a plain digit regex would catch is kept.
+#[test]
+fn test_dates_redacts_valid_dates_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Meeting: 2026-01-15")
+        .with_content("Deadline: 02/28/2026")
+        .with_content("Signed: January 6, 2025")
+        .with_content("Invalid: 2026-13-40")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Dates]))?;
+
+    assert_eq!(result.instances_redacted, 3);
+    assert_eq!(result.by_category.get("date"), Some(&3));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("2026-01-15"));
+    assert!(!text.contains("02/28/2026"));
+    assert!(!text.contains("January 6, 2025"));
+    assert!(text.contains("2026-13-40"), "Invalid date should be kept");
+    assert!(text.contains("Deadline:"));
+
+    Ok(())
+}
+
+// ============================================================================
+// Named Group Tests
+// ============================================================================
+
+/// Tests that each named group's span is redacted and counted under the
+/// group name.
+#[test]
+fn test_named_groups_redacted_by_category() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("SSN 123-45-6789 ext 234-5678")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::NamedGroups {
+            pattern: r"(?P<ssn>\d{3}-\d{2}-\d{4}).*(?P<phone>\d{3}-\d{4})".to_string(),
+        }]
+    ))?;
+
+    assert!(result.by_category.get("ssn").is_some_and(|n| *n >= 1));
+    assert!(result.by_category.get("phone").is_some_and(|n| *n >= 1));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("123-45-6789"), "SSN group should be removed");
+    assert!(!text.contains("234-5678"), "Phone group should be removed");
+    assert!(text.contains("SSN"), "Text between groups should remain");
+
+    Ok(())
+}
+
+/// Tests that a pattern without named groups is rejected.
+#[test]
+fn test_named_groups_requires_named_group() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new().with_content("123").build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::NamedGroups {
+            pattern: r"(\d+)".to_string(),
+        }],
+    );
+
+    assert!(matches!(result, Err(RedactorError::PatternError { .. })));
+
+    Ok(())
+}
+
+// ============================================================================
+// Regex Capture Group Tests
+// ============================================================================
+
+/// Tests that only the selected group is redacted and the context matched
+/// around it is kept.
+#[test]
+fn test_regex_capture_group_redacts_group_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Account: 48213377")
+        .with_content("Invoice 99887766")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::RegexCaptureGroup {
+            pattern: r"Account:\s*(\d+)".to_string(),
+            group: 1,
+        }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 1);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("48213377"), "Group should be removed");
+    assert!(text.contains("Account:"), "Context should remain");
+    assert!(text.contains("99887766"), "Unmatched digits should remain");
+
+    Ok(())
+}
+
+/// Tests that a group index beyond the pattern's groups is rejected.
+#[test]
+fn test_regex_capture_group_out_of_range() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Account: 1")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::RegexCaptureGroup {
+            pattern: r"Account:\s*(\d+)".to_string(),
+            group: 2,
+        }],
+    );
+
+    assert!(matches!(result, Err(RedactorError::PatternError { .. })));
+
+    Ok(())
+}
+
+// ============================================================================
+// SSN Tests
+// ============================================================================
+
+/// Tests that dashed and spaced SSNs are redacted but invalid ones are kept.
+#[test]
+fn test_ssn_redacts_valid_numbers_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Primary: 123-45-6789")
+        .with_content("Spouse: 234 56 7890")
+        .with_content("Order: 666-12-3456")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Ssn]))?;
+
+    assert!(result.instances_redacted >= 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("123-45-6789"));
+    assert!(!text.contains("234 56 7890"));
+    assert!(text.contains("666-12-3456"), "Invalid SSN should be kept");
+
+    Ok(())
+}
+
+// ============================================================================
+// Email Tests
+// ============================================================================
+
+/// Tests that email addresses are redacted while an incomplete address and
+/// surrounding punctuation are kept.
+#[test]
+fn test_email_redacts_addresses() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Billing: jane+billing@mail.example.com.")
+        .with_content("Not an email: test@")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Email]))?;
+
+    assert_eq!(result.instances_redacted, 1);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("jane+billing@mail.example.com"));
+    assert!(text.contains("test@"), "Incomplete address should be kept");
+
+    Ok(())
+}
+
+// ============================================================================
+// Credit Card Tests
+// ============================================================================
+
+/// Tests that Luhn-valid card numbers are redacted and other long digit
+/// runs are kept.
+#[test]
+fn test_credit_card_redacts_luhn_valid_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Card: 4111 1111 1111 1111")
+        .with_content("Tracking: 1234567890123")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::CreditCard]))?;
+
+    assert!(result.has_redactions());
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("4111 1111 1111 1111"));
+    assert!(
+        text.contains("1234567890123"),
+        "Non-card digits should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// IBAN Tests
+// ============================================================================
+
+/// Tests that grouped and continuous IBANs are redacted and a corrupted
+/// one is kept.
+#[test]
+fn test_iban_redacts_valid_numbers_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Payee: GB82 WEST 1234 5698 7654 32")
+        .with_content("Refund: DE89370400440532013000")
+        .with_content("Typo: GB82WEST12345698765433")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Iban]))?;
+
+    assert!(result.instances_redacted >= 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("GB82 WEST 1234 5698 7654 32"));
+    assert!(!text.contains("DE89370400440532013000"));
+    assert!(
+        text.contains("GB82WEST12345698765433"),
+        "Corrupted IBAN should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// VIN Tests
+// ============================================================================
+
+/// Tests that a VIN is redacted and one with a wrong check digit is kept.
+#[test]
+fn test_vin_redacts_valid_numbers_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Insured vehicle: 1HGCM82633A004352")
+        .with_content("Quote reference: 1HGCM82643A004352")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Vin]))?;
+
+    assert_eq!(result.instances_redacted, 1);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("1HGCM82633A004352"));
+    assert!(
+        text.contains("1HGCM82643A004352"),
+        "VIN with a wrong check digit should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Postal Code Tests
+// ============================================================================
+
+/// Tests that a ZIP code in an address is redacted while an account number
+/// fragment of the same shape is kept.
+#[test]
+fn test_postal_code_redacts_address_zip_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Account: 48213-00001")
+        .with_content("Springfield, IL 62704-1234")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::PostalCode]))?;
+
+    assert_eq!(result.by_category.get("postal_code"), Some(&1));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("62704"));
+    assert!(text.contains("Springfield, IL"));
+    assert!(
+        text.contains("48213-00001"),
+        "Account fragment should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// MAC Address Tests
+// ============================================================================
+
+/// Tests that MAC addresses are redacted in colon and dot notation while a
+/// 5-octet string is kept.
+#[test]
+fn test_mac_address_redacts_six_octets_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Router WAN: 00:1A:2B:3C:4D:5E")
+        .with_content("Switch port: 001a.2b3c.4d5f")
+        .with_content("Partial: 00:1A:2B:3C:4D")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::MacAddress]))?;
+
+    assert!(result.instances_redacted >= 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("00:1A:2B:3C:4D:5E"));
+    assert!(!text.contains("001a.2b3c.4d5f"));
+    assert!(
+        text.contains("00:1A:2B:3C:4D"),
+        "5-octet string should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Handle Tests
+// ============================================================================
+
+/// Tests that mentions and labelled usernames are redacted while an email
+/// address is left to the email matcher.
+#[test]
+fn test_handle_redacts_mentions_but_not_emails() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Assigned to @alice")
+        .with_content("User: jdoe")
+        .with_content("Reply to carol@example.com")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Handle]))?;
+
+    assert!(result.instances_redacted >= 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("@alice"));
+    assert!(!text.contains("jdoe"));
+    assert!(text.contains("User:"), "the label should be kept");
+    assert!(
+        text.contains("carol@example.com"),
+        "email addresses are not handles"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Passport Tests
+// ============================================================================
+
+/// Tests that without a country only labelled passport numbers are redacted.
+#[test]
+fn test_passport_without_country_requires_keyword() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Passport No: 925076473")
+        .with_content("Invoice 812345670")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Passport { country: None }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 1);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("925076473"));
+    assert!(
+        text.contains("812345670"),
+        "Unlabelled number should be kept"
+    );
+
+    Ok(())
+}
+
+/// Tests that a US hint redacts both US formats without a keyword.
+#[test]
+fn test_passport_us_formats() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Traveler document: 488839667")
+        .with_content("Renewed: A12345678")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Passport {
+            country: Some("US".to_string())
+        }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("488839667"));
+    assert!(!text.contains("A12345678"));
+    assert!(text.contains("Traveler document"));
+
+    Ok(())
+}
+
+/// Tests that an unsupported country is rejected.
+#[test]
+fn test_passport_unsupported_country() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("123456789")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Passport {
+            country: Some("ZZ".to_string())
+        }]
+    ));
+
+    assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+
+    Ok(())
+}
+
+// ============================================================================
+// Driver's License Tests
+// ============================================================================
+
+/// Tests that without a state only labelled license numbers are redacted.
+#[test]
+fn test_drivers_license_without_state_requires_keyword() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Driver's License No: D1234567")
+        .with_content("Invoice D7654321")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::DriversLicense { state: None }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 1);
+    assert_eq!(result.by_category.get("drivers_license"), Some(&1));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("D1234567"));
+    assert!(
+        text.contains("D7654321"),
+        "Unlabelled number should be kept"
+    );
+
+    Ok(())
+}
+
+/// Tests that a FL hint redacts hyphenated and unbroken numbers without a
+/// keyword.
+#[test]
+fn test_drivers_license_fl_format() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Operator: S530-123-45-678-0")
+        .with_content("Renewed: S530123456781")
+        .with_content("Ticket: S530-123-45-678")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::DriversLicense {
+            state: Some("FL".to_string())
+        }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 2);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("S530-123-45-678-0"));
+    assert!(!text.contains("S530123456781"));
+    assert!(text.contains("Ticket: S530-123-45-678"));
+
+    Ok(())
+}
+
+/// Tests that an unsupported state is rejected.
+#[test]
+fn test_drivers_license_unsupported_state() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("D1234567")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::DriversLicense {
+            state: Some("ZZ".to_string())
+        }]
+    ));
+
+    assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+
+    Ok(())
+}
+
+// ============================================================================
+// Routing Number Tests
+// ============================================================================
+
+/// Tests that labelled routing numbers are redacted only when the ABA
+/// checksum passes, and unlabelled 9-digit numbers are kept.
+#[test]
+fn test_routing_number_redacts_valid_labelled_numbers_only() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Routing Number: 021000021")
+        .with_content("ABA: 021000022")
+        .with_content("Order 011000015")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result =
+        with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::RoutingNumber]))?;
+
+    assert_eq!(result.instances_redacted, 1);
+    assert_eq!(result.by_category.get("routing_number"), Some(&1));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("021000021"));
+    assert!(
+        text.contains("021000022"),
+        "Checksum-failing number should be kept"
+    );
+    assert!(
+        text.contains("011000015"),
+        "Unlabelled number should be kept"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Checksum Tests
+// ============================================================================
+
+/// Tests that only fixed-length numbers passing the checksum are redacted.
+#[test]
+fn test_checksum_luhn_member_numbers() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Member ID: 79927398713")
+        .with_content("Claim: 79927398710")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Checksum {
+            length: 11,
+            algorithm: ChecksumAlgorithm::Luhn,
+        }]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 1);
+    assert_eq!(result.by_category.get("checksum"), Some(&1));
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("79927398713"));
+    assert!(text.contains("79927398710"), "Failing checksum is kept");
+
+    Ok(())
+}
+
+/// Tests that a zero length is rejected.
+#[test]
+fn test_checksum_zero_length() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("123456789")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Checksum {
+            length: 0,
+            algorithm: ChecksumAlgorithm::None,
+        }]
+    ));
+
+    assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+
+    Ok(())
+}
+
+// ============================================================================
+// Custom Matcher Tests
+// ============================================================================
+
+/// Matches the word "FOO".
+struct FooMatcher(regex::Regex);
+
+impl PatternMatcher for FooMatcher {
+    fn pattern(&self) -> &regex::Regex {
+        &self.0
+    }
+
+    fn extract_all<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        self.0.find_iter(text).map(|m| m.as_str()).collect()
+    }
+
+    fn normalize(&self, text: &str) -> Option<String> {
+        Some(text.to_string())
+    }
+
+    fn generate_variants(&self, normalized: &str) -> Vec<String> {
+        vec![normalized.to_string()]
+    }
+}
+
+/// Tests redacting with a matcher registered on the service.
+#[test]
+fn test_custom_matcher() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Project FOO is confidential")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy()
+        .with_custom_matcher("foo", Box::new(FooMatcher(regex::Regex::new(r"\bFOO\b")?)));
+    let result = with_mupdf_lock!(service.redact(
+        &input,
+        &output,
+        &[RedactionTarget::Custom("foo".into())]
+    ))?;
+
+    assert_eq!(result.instances_redacted, 1);
+    assert_eq!(result.targets, vec![RedactionTarget::Custom("foo".into())]);
+
+    let text = extract_text(&output)?;
+    assert!(!text.contains("FOO"));
+    assert!(text.contains("confidential"));
+
+    Ok(())
+}
+
+/// Tests that an unregistered custom matcher is rejected.
+#[test]
+fn test_custom_matcher_not_registered() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("input.pdf");
+    let output = temp_dir.path().join("output.pdf");
+
+    TestPdfBuilder::new()
+        .with_content("Project FOO")
+        .build(&input)?;
+
+    let service = RedactionService::with_secure_strategy();
+    let result =
+        with_mupdf_lock!(service.redact(&input, &output, &[RedactionTarget::Custom("foo".into())]));
+
+    assert!(matches!(result, Err(RedactorError::InvalidInput { .. })));
+    assert!(!output.exists());
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Tests for the secure strategy's redaction time limit.
+//!
+//! A timed-out redaction keeps running MuPDF on the global worker until
+//! MuPDF returns. Every test here runs MuPDF only on that worker, waits for
+//! it to drain before finishing, and holds [`SERIAL`] throughout, so no two
+//! redactions overlap even though libtest runs tests in parallel.
+
+use anyhow::Result;
+use redactor::{
+    MupdfWorker, RedactionService, RedactionTarget, RedactorError, SecureRedactionStrategy,
+};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tempfile::TempDir;
+
+mod common;
+use common::*;
+
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Builds a document too large to redact within a millisecond.
+fn large_document(path: &Path) -> Result<()> {
+    let mut builder = TestPdfBuilder::new().with_title("Large Document");
+    for i in 0..300 {
+        builder = builder.with_page(&format!("Page {} call (555) 234-5678", i));
+    }
+    builder.build(path)?;
+    Ok(())
+}
+
+/// Waits for the abandoned redaction to finish on the global worker,
+/// returning the name of the thread it ran on.
+fn drain_global_worker() -> Result<Option<String>> {
+    Ok(MupdfWorker::global()?.run(|| std::thread::current().name().map(str::to_string))?)
+}
+
+#[test]
+fn test_timeout_fires_on_large_document() -> Result<()> {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("large.pdf");
+    let output = temp_dir.path().join("output.pdf");
+    large_document(&input)?;
+
+    let limit = Duration::from_millis(1);
+    let strategy = SecureRedactionStrategy::new().with_timeout(limit);
+    let service = RedactionService::new(Box::new(strategy));
+    let result = service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]);
+
+    match result {
+        Err(RedactorError::Timeout { path, limit: l }) => {
+            assert_eq!(path, input);
+            assert_eq!(l, limit);
+        }
+        other => panic!("Expected Timeout, got {:?}", other),
+    }
+
+    assert_eq!(drain_global_worker()?.as_deref(), Some("mupdf-worker"));
+    assert!(
+        !output.exists(),
+        "Nothing should be written after a timeout"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_timeout_on_worker_keeps_mupdf_on_the_worker() -> Result<()> {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let temp_dir = TempDir::new()?;
+    let input = temp_dir.path().join("large.pdf");
+    let output = temp_dir.path().join("output.pdf");
+    large_document(&input)?;
+
+    let limit = Duration::from_millis(1);
+    let strategy = SecureRedactionStrategy::new().with_timeout(limit);
+    let service =
+        RedactionService::new(Box::new(strategy)).with_mupdf_worker(MupdfWorker::global()?);
+    let result = service.redact(&input, &output, &[RedactionTarget::PhoneNumbers]);
+    assert!(
+        matches!(result, Err(RedactorError::Timeout { .. })),
+        "Expected Timeout, got {:?}",
+        result
+    );
+
+    // The abandoned redaction still holds the worker, so this job only
+    // runs once MuPDF has returned, and then nothing is left behind
+    assert_eq!(drain_global_worker()?.as_deref(), Some("mupdf-worker"));
+    assert!(
+        !output.exists(),
+        "Nothing should be written after a timeout"
+    );
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "Temporary output should be removed");
+
+    Ok(())
+}